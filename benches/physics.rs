@@ -0,0 +1,256 @@
+//! City-scale throughput of the two hot paths, each against the sequential
+//! code it replaced, at 10k and 100k nodes:
+//!
+//! * `physics_tick`: `resource_physics_system` (nodes stepped with
+//!   `par_iter_mut`) against a baseline stepping the same nodes one after
+//!   another on the calling thread
+//! * `placement`: the batched `Placement` of a new generation against one
+//!   despawn per old entity and one spawn per new node
+//!
+//! `cargo bench --bench physics`
+
+use std::hint::black_box;
+use std::time::Duration;
+
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use rand::prelude::*;
+use rand::rngs::{SmallRng, StdRng};
+
+use edgegenesis::aging::BatteryHealth;
+use edgegenesis::catastrophe::SolarExposure;
+use edgegenesis::components::{
+    Battery, Dead, EdgeNodeBundle, EnergyLedger, EnvRng, EpochCount, Gene, Lineage,
+    LoadedSolarProfiles, ModelCache, PowerOverrides, SimulationMetrics, Status, SurvivalScore,
+    Telemetry, TunedPolicy,
+};
+use edgegenesis::engine::{
+    HEADLESS_DT, NodeMut, StepEnv, random_gene, random_hardware_type, step_node,
+};
+use edgegenesis::hardware::{HardwareSpec, SolarPanel};
+use edgegenesis::milestones::NodeDied;
+use edgegenesis::policies::PolicyState;
+use edgegenesis::sensor::Sensor;
+use edgegenesis::speed::SimTime;
+use edgegenesis::systems::{Placement, resource_physics_system, solar_output_w};
+use edgegenesis::thermal::Temperature;
+use edgegenesis::units::WattHours;
+use edgegenesis::{EdgeGenesisPlugin, SimConfig};
+
+const POPULATIONS: [usize; 2] = [10_000, 100_000];
+/// Ticks before timing starts (every node loads its model first)
+const WARMUP_TICKS: u32 = 10;
+
+/// `population` random nodes with lineage ids from `first_id`
+fn random_nodes(rng: &mut StdRng, first_id: u64, population: usize) -> Vec<EdgeNodeBundle> {
+    (first_id..first_id + population as u64)
+        .map(|id| {
+            EdgeNodeBundle::new(
+                random_gene(rng),
+                HardwareSpec::new(random_hardware_type(rng)),
+                Lineage { id, parent: None },
+                Transform::default(),
+            )
+        })
+        .collect()
+}
+
+/// App with the simulation resources and `population` random nodes
+fn ecs_app(population: usize) -> App {
+    let mut app = App::new();
+    EdgeGenesisPlugin::new(SimConfig::default()).insert_resources(&mut app);
+    app.init_resource::<Time>()
+        .init_resource::<SimulationMetrics>()
+        .insert_resource(EpochCount(0));
+
+    let nodes = random_nodes(&mut StdRng::seed_from_u64(1), 0, population);
+    app.world_mut().spawn_batch(nodes);
+    app
+}
+
+type BaselineQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        (&'static mut Battery, &'static mut BatteryHealth),
+        &'static mut SurvivalScore,
+        &'static mut Status,
+        &'static mut Telemetry,
+        &'static mut TunedPolicy,
+        &'static mut PolicyState,
+        &'static mut ModelCache,
+        &'static mut EnergyLedger,
+        &'static mut Sensor,
+        &'static SolarExposure,
+        &'static Temperature,
+        &'static Gene,
+        (&'static HardwareSpec, &'static SolarPanel),
+        &'static Lineage,
+    ),
+    Without<Dead>,
+>;
+
+/// The physics tick before `par_iter_mut`: the same per-node step, node
+/// after node, with the totals summed as it goes
+fn sequential_physics_system(
+    time: SimTime,
+    power_overrides: Res<PowerOverrides>,
+    solar_profiles: Res<LoadedSolarProfiles>,
+    config: Res<SimConfig>,
+    mut env_rng: ResMut<EnvRng>,
+    mut metrics: ResMut<SimulationMetrics>,
+    mut query: BaselineQuery,
+) {
+    let dt = time.delta_secs();
+    metrics.current_hour = (metrics.current_hour + time.window_h()) % 24.0;
+    let hour = metrics.current_hour as usize % 24;
+    let expected_solar_w = |hour: usize| solar_output_w(&solar_profiles.0, hour);
+    let env = StepEnv {
+        window_s: time.window_s(),
+        hour,
+        solar_w: expected_solar_w(hour),
+        expected_solar_w: &expected_solar_w,
+        power_overrides: power_overrides.0.as_ref(),
+        battery_aging: config.battery_aging,
+        compatibility: config.compatibility,
+    };
+    let tick_seed: u64 = env_rng.0.random();
+
+    let mut drained = WattHours::ZERO;
+    for (
+        (mut battery, mut health),
+        mut score,
+        mut status,
+        mut telemetry,
+        mut tuned,
+        mut policy_state,
+        mut model_cache,
+        mut ledger,
+        mut sensor,
+        exposure,
+        temperature,
+        gene,
+        (hardware, panel),
+        lineage,
+    ) in query.iter_mut()
+    {
+        if *status == Status::Dead {
+            continue;
+        }
+        let mut rng = SmallRng::seed_from_u64(tick_seed ^ lineage.id);
+        let outcome = step_node(
+            NodeMut {
+                battery: &mut battery,
+                health: &mut health,
+                status: &mut status,
+                telemetry: &mut telemetry,
+                tuned: &mut tuned,
+                policy_state: &mut policy_state,
+                model_cache: &mut model_cache,
+                ledger: &mut ledger,
+                sensor: &mut sensor,
+                gene,
+                hardware,
+                panel,
+                exposure: exposure.0,
+                temperature_c: temperature.celsius,
+            },
+            &env,
+            &mut rng,
+        );
+        if outcome.alive {
+            score.0 += dt;
+        }
+        drained += outcome.drain_wh;
+    }
+    black_box(drained);
+}
+
+/// One physics tick per iteration on a warmed-up population
+fn physics_tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("physics_tick");
+    group.sample_size(10);
+    for population in POPULATIONS {
+        let mut app = ecs_app(population);
+        let world = app.world_mut();
+        let parallel = world.register_system(resource_physics_system);
+        let sequential = world.register_system(sequential_physics_system);
+        let tick = |world: &mut World, physics| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(HEADLESS_DT));
+            world.run_system(physics).unwrap();
+            world.resource_mut::<Messages<NodeDied>>().update();
+        };
+        for _ in 0..WARMUP_TICKS {
+            tick(world, parallel);
+        }
+
+        group.bench_function(BenchmarkId::new("sequential", population), |b| {
+            b.iter(|| tick(world, sequential))
+        });
+        group.bench_function(BenchmarkId::new("parallel", population), |b| {
+            b.iter(|| tick(world, parallel))
+        });
+    }
+    group.finish();
+}
+
+/// A world holding a finished generation, and the next generation's nodes
+fn next_generation(population: usize) -> (World, Vec<Entity>, Vec<EdgeNodeBundle>) {
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut world = World::new();
+    let old = world
+        .spawn_batch(random_nodes(&mut rng, 0, population))
+        .collect();
+    let new = random_nodes(&mut rng, population as u64, population);
+    (world, old, new)
+}
+
+/// Replacing a whole generation, world setup untimed
+fn placement(c: &mut Criterion) {
+    let mut group = c.benchmark_group("placement");
+    group.sample_size(10);
+    for population in POPULATIONS {
+        group.bench_function(BenchmarkId::new("despawn_respawn", population), |b| {
+            b.iter_batched(
+                || next_generation(population),
+                |(mut world, old, new)| {
+                    let mut queue = CommandQueue::default();
+                    let mut commands = Commands::new(&mut queue, &world);
+                    for entity in old {
+                        commands.entity(entity).despawn();
+                    }
+                    for node in new {
+                        commands.spawn(node);
+                    }
+                    queue.apply(&mut world);
+                    world
+                },
+                BatchSize::PerIteration,
+            )
+        });
+        group.bench_function(BenchmarkId::new("batched", population), |b| {
+            b.iter_batched(
+                || next_generation(population),
+                |(mut world, old, new)| {
+                    let mut queue = CommandQueue::default();
+                    let mut commands = Commands::new(&mut queue, &world);
+                    let mut placement = Placement::new(old);
+                    for node in new {
+                        placement.place(node);
+                    }
+                    placement.apply(&mut commands);
+                    queue.apply(&mut world);
+                    world
+                },
+                BatchSize::PerIteration,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, physics_tick, placement);
+criterion_main!(benches);
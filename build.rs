@@ -0,0 +1,44 @@
+//! Generates the gRPC control service (src/grpc.rs) without protoc: message
+//! types are hand-written prost structs, proto/edgegenesis.proto documents
+//! the wire contract for clients.
+use tonic_build::manual::{Builder, Method, Service};
+
+fn main() {
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::grpc::{}", input))
+            .output_type(format!("crate::grpc::{}", output))
+            .codec_path("tonic_prost::ProstCodec")
+            .build()
+    };
+
+    let control = Service::builder()
+        .name("Control")
+        .package("edgegenesis")
+        .method(method(
+            "start_run",
+            "StartRun",
+            "StartRunRequest",
+            "RunStatus",
+        ))
+        .method(method("step", "Step", "StepRequest", "RunStatus"))
+        .method(method(
+            "get_population",
+            "GetPopulation",
+            "GetPopulationRequest",
+            "Population",
+        ))
+        .method(method(
+            "inject_genome",
+            "InjectGenome",
+            "InjectGenomeRequest",
+            "InjectGenomeReply",
+        ))
+        .method(method("stop", "Stop", "StopRequest", "RunStatus"))
+        .build();
+
+    Builder::new().build_client(false).compile(&[control]);
+    println!("cargo:rerun-if-changed=build.rs");
+}
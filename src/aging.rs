@@ -0,0 +1,89 @@
+/// Battery cycle aging
+/// Each node's battery loses capacity as it cycles. The state-of-charge
+/// trace is split into half-cycles at its turning points (reversals smaller
+/// than `REVERSAL_HYSTERESIS` are noise, not cycles), and a half-cycle of
+/// depth d costs `0.5 × d^DOD_STRESS_EXPONENT / cycle_life` of the battery's
+/// life, the Wöhler-curve form of rainflow counting: at full depth the
+/// chemistry's rated `cycle_life` cycles reach end of life, while
+/// shallow cycles cost disproportionately less. Damage accrues as a
+/// half-cycle deepens, so a long discharge ages the battery before it ends.
+/// End of life (damage 1) means `EOL_FADE` of the nominal capacity is gone;
+/// fade continues past it. Node lifetimes are short next to a battery's,
+/// so `--battery-aging` scales the damage (like `--failure-acceleration`
+/// does for MTBF faults) to make deep duty cycles costly within a run.
+use crate::hardware::HardwareSpec;
+
+/// Capacity lost at end of life (fraction of nominal)
+pub const EOL_FADE: f32 = 0.2;
+
+/// Wöhler exponent: cycles to end of life scale as depth^-k
+pub const DOD_STRESS_EXPONENT: f32 = 1.5;
+
+/// State-of-charge reversal that ends a half-cycle
+pub const REVERSAL_HYSTERESIS: f32 = 0.02;
+
+/// Usable capacity and cycle history of a node's battery
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct BatteryHealth {
+    /// Usable capacity when new (Wh)
+    pub nominal_wh: f32,
+    /// Usable capacity now (Wh)
+    pub capacity_wh: f32,
+    /// Life consumed: 1 = end of life
+    pub damage: f32,
+    /// Equivalent full cycles (depth-weighted half-cycles / 2)
+    pub cycles: f32,
+    /// State of charge where the current half-cycle started
+    turn_soc: f32,
+    /// Furthest state of charge reached since
+    extreme_soc: f32,
+}
+
+impl BatteryHealth {
+    /// New battery of `hardware` at `state_of_charge`
+    pub fn new(hardware: &HardwareSpec, state_of_charge: f32) -> Self {
+        Self {
+            nominal_wh: hardware.usable_capacity_wh(),
+            capacity_wh: hardware.usable_capacity_wh(),
+            damage: 0.0,
+            cycles: 0.0,
+            turn_soc: state_of_charge,
+            extreme_soc: state_of_charge,
+        }
+    }
+
+    /// Remaining capacity as a fraction of nominal
+    pub fn state_of_health(&self) -> f32 {
+        self.capacity_wh / self.nominal_wh
+    }
+
+    /// Follow the charge to `battery_wh`, aging the battery by the cycle
+    /// depth gained (`acceleration` × the rated damage)
+    pub fn record(&mut self, battery_wh: f32, cycle_life: f32, acceleration: f32) {
+        let soc = (battery_wh / self.capacity_wh.max(f32::EPSILON)).clamp(0.0, 1.0);
+        let rising = self.extreme_soc >= self.turn_soc;
+        let back = if rising {
+            self.extreme_soc - soc
+        } else {
+            soc - self.extreme_soc
+        };
+        if back > REVERSAL_HYSTERESIS {
+            // Turning point: a new half-cycle starts at the extreme
+            self.turn_soc = self.extreme_soc;
+        } else if back >= 0.0 {
+            return;
+        }
+
+        let old_depth = (self.extreme_soc - self.turn_soc).abs();
+        self.extreme_soc = soc;
+        let new_depth = (soc - self.turn_soc).abs();
+        if new_depth <= old_depth {
+            return;
+        }
+        let stress = new_depth.powf(DOD_STRESS_EXPONENT) - old_depth.powf(DOD_STRESS_EXPONENT);
+        self.damage += 0.5 * stress * acceleration / cycle_life;
+        self.cycles += 0.5 * (new_depth - old_depth);
+        self.capacity_wh = self.nominal_wh * (1.0 - EOL_FADE * self.damage).max(0.0);
+    }
+}
@@ -0,0 +1,260 @@
+/// Post-run analysis: `EdgeGenesis analyze [--out <dir>] [--format svg|png] <inputs>...`
+/// Each input is either a metrics CSV (`--metrics-out`) or a snapshot
+/// directory (`--snapshot-dir`). All metrics runs are overlaid on one fitness
+/// curve (population mean per epoch) and one survival curve (share of the
+/// population alive at epoch end), so runs can be compared directly; each
+/// snapshot directory gets its own model-share plot (share of survivors
+/// running each model).
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Output image format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlotFormat {
+    Svg,
+    Png,
+}
+
+impl PlotFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            PlotFormat::Svg => "svg",
+            PlotFormat::Png => "png",
+        }
+    }
+}
+
+/// Columns of the metrics CSV the plots need (others are ignored)
+#[derive(Debug, Deserialize)]
+struct MetricsPoint {
+    epoch: u32,
+    population: usize,
+    survivors: usize,
+    fitness_mean: f32,
+}
+
+/// Columns of a snapshot CSV the plots need (others are ignored)
+#[derive(Debug, Deserialize)]
+struct SnapshotModel {
+    epoch: u32,
+    model: String,
+}
+
+/// Chart size in pixels
+const CHART_SIZE: (u32, u32) = (960, 540);
+
+/// One labelled line: (epoch, value) points
+pub type Series = (String, Vec<(f32, f32)>);
+
+/// Run the `analyze` command on the arguments following it
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut out_dir = PathBuf::from("results/plots");
+    let mut format = PlotFormat::Svg;
+    let mut inputs = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out_dir = args.next().ok_or("--out needs a directory")?.into(),
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("svg") => PlotFormat::Svg,
+                    Some("png") => PlotFormat::Png,
+                    other => return Err(format!("unknown plot format {:?}", other).into()),
+                }
+            }
+            _ => inputs.push(PathBuf::from(arg)),
+        }
+    }
+    if inputs.is_empty() {
+        return Err(
+            "usage: analyze [--out <dir>] [--format svg|png] <metrics.csv | snapshot dir>..."
+                .into(),
+        );
+    }
+    std::fs::create_dir_all(&out_dir)?;
+
+    let mut fitness = Vec::new();
+    let mut survival = Vec::new();
+    for input in &inputs {
+        let label = run_label(input);
+        if input.is_dir() {
+            let shares = model_shares(input)?;
+            let path = out_dir.join(format!("model_share_{}.{}", label, format.extension()));
+            plot(
+                &path,
+                format,
+                &format!("Model share - {}", label),
+                "% of survivors",
+                &shares,
+            )?;
+            println!("📈 {}", path.display());
+        } else {
+            let points = read_csv::<MetricsPoint>(input)?;
+            let curve = |value: fn(&MetricsPoint) -> f32| -> Vec<(f32, f32)> {
+                points.iter().map(|p| (p.epoch as f32, value(p))).collect()
+            };
+            fitness.push((label.clone(), curve(|p| p.fitness_mean)));
+            survival.push((
+                label,
+                curve(|p| 100.0 * p.survivors as f32 / p.population.max(1) as f32),
+            ));
+        }
+    }
+
+    if !fitness.is_empty() {
+        for (name, title, y_label, series) in [
+            ("fitness", "Mean fitness", "fitness", &fitness),
+            ("survival", "Survival", "% of population alive", &survival),
+        ] {
+            let path = out_dir.join(format!("{}.{}", name, format.extension()));
+            plot(&path, format, title, y_label, series)?;
+            println!("📈 {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Legend label of an input: file stem or directory name
+fn run_label(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn read_csv<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Vec<T>, Box<dyn Error>> {
+    let mut reader =
+        csv::Reader::from_path(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let rows = reader
+        .deserialize()
+        .collect::<Result<Vec<T>, _>>()
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(rows)
+}
+
+/// Share of survivors per model and epoch, one series per model
+fn model_shares(dir: &Path) -> Result<Vec<Series>, Box<dyn Error>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+        .collect();
+    files.sort();
+
+    // epoch -> model -> survivors
+    let mut counts: BTreeMap<u32, BTreeMap<String, usize>> = BTreeMap::new();
+    for file in &files {
+        for row in read_csv::<SnapshotModel>(file)? {
+            *counts
+                .entry(row.epoch)
+                .or_default()
+                .entry(row.model)
+                .or_default() += 1;
+        }
+    }
+    if counts.is_empty() {
+        return Err(format!("{}: no snapshot rows", dir.display()).into());
+    }
+
+    let mut models: BTreeMap<String, Vec<(f32, f32)>> = BTreeMap::new();
+    for by_model in counts.values() {
+        for model in by_model.keys() {
+            models.entry(model.clone()).or_default();
+        }
+    }
+    for (epoch, by_model) in &counts {
+        let total = by_model.values().sum::<usize>() as f32;
+        for (model, points) in models.iter_mut() {
+            let count = by_model.get(model).copied().unwrap_or(0) as f32;
+            points.push((*epoch as f32, 100.0 * count / total));
+        }
+    }
+    Ok(models.into_iter().collect())
+}
+
+/// Draw a line chart with one line per series
+fn plot(
+    path: &Path,
+    format: PlotFormat,
+    title: &str,
+    y_label: &str,
+    series: &[Series],
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        PlotFormat::Svg => draw(
+            SVGBackend::new(path, CHART_SIZE).into_drawing_area(),
+            title,
+            y_label,
+            series,
+        ),
+        PlotFormat::Png => draw(
+            BitMapBackend::new(path, CHART_SIZE).into_drawing_area(),
+            title,
+            y_label,
+            series,
+        ),
+    }
+}
+
+/// Render a line chart to an SVG string, for embedding in HTML
+pub fn svg_chart(title: &str, y_label: &str, series: &[Series]) -> Result<String, Box<dyn Error>> {
+    let mut svg = String::new();
+    draw(
+        SVGBackend::with_string(&mut svg, CHART_SIZE).into_drawing_area(),
+        title,
+        y_label,
+        series,
+    )?;
+    Ok(svg)
+}
+
+fn draw<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    title: &str,
+    y_label: &str,
+    series: &[Series],
+) -> Result<(), Box<dyn Error>>
+where
+    DB::ErrorType: 'static,
+{
+    let points = || series.iter().flat_map(|(_, points)| points.iter());
+    let x_max = points().map(|p| p.0).fold(1.0, f32::max);
+    let (y_min, y_max) = points().fold((0.0f32, 0.0f32), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+    let y_pad = ((y_max - y_min) * 0.05).max(1.0);
+
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 24))
+        .margin(10)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0.0f32..x_max, (y_min - y_pad)..(y_max + y_pad))?;
+    chart
+        .configure_mesh()
+        .x_desc("epoch")
+        .y_desc(y_label)
+        .draw()?;
+
+    for (i, (label, points)) in series.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        chart
+            .draw_series(LineSeries::new(
+                points.iter().copied(),
+                color.stroke_width(2),
+            ))?
+            .label(label)
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+            });
+    }
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+/// Node annotations and bookmarks (flag: `--annotations <path>`)
+/// Nodes are labeled from the inspector; labels live in the lineage log by
+/// lineage id, so they outlast the node, appear in the phylogeny and elite
+/// exports, and the Bookmarks window lists every labeled node (alive ones
+/// can be selected from it). With `--annotations` the labels are kept in a
+/// JSON-lines file, one labeled node per line with its parent, epoch of
+/// birth, model and fitness: it is loaded at startup and rewritten whenever
+/// a label or a labeled node's fitness changes. Lineage ids are sequential,
+/// so a file shared by a collaborator labels the same individuals in a
+/// re-run of the same seed and configuration.
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::io::Write;
+
+use crate::components::{Lineage, Status};
+use crate::config::SimConfig;
+use crate::inspector::SelectedNode;
+use crate::phylogeny::LineageLog;
+
+/// One line of the annotation file; only `id` and `labels` are read back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: u64,
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub parent: Option<u64>,
+    #[serde(default)]
+    pub born_epoch: Option<u32>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub fitness: Option<f32>,
+}
+
+/// Read a JSON-lines annotation file (blank lines are skipped)
+pub fn load_annotations(path: &str) -> Result<Vec<Annotation>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let mut annotations = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let annotation =
+            serde_json::from_str(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        annotations.push(annotation);
+    }
+    Ok(annotations)
+}
+
+/// Every labeled node of the lineage log, with its birth record
+pub fn annotations(lineage_log: &LineageLog) -> Vec<Annotation> {
+    lineage_log
+        .labeled()
+        .map(|(id, labels)| {
+            let record = lineage_log.record(id);
+            Annotation {
+                id,
+                labels: labels.to_vec(),
+                parent: record.and_then(|r| r.parent),
+                born_epoch: record.map(|r| r.born_epoch),
+                model: record.map(|r| r.model_type.name().to_string()),
+                fitness: record.and_then(|r| r.fitness),
+            }
+        })
+        .collect()
+}
+
+fn write_annotations(path: &str, annotations: &[Annotation]) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    for annotation in annotations {
+        serde_json::to_writer(&mut file, annotation)?;
+        file.write_all(b"\n")?;
+    }
+    Ok(file.flush()?)
+}
+
+/// Run condition: true when annotations are kept in a file
+pub fn annotations_enabled(config: Res<SimConfig>) -> bool {
+    config.annotations.is_some()
+}
+
+/// Rewrite the annotation file after labels change
+pub fn write_annotations_system(config: Res<SimConfig>, mut lineage_log: ResMut<LineageLog>) {
+    let Some(path) = &config.annotations else {
+        return;
+    };
+    if !lineage_log.take_labels_changed() {
+        return;
+    }
+    if let Err(e) = write_annotations(path, &annotations(&lineage_log)) {
+        warn!(target: "edgegenesis::annotations", "⚠️ Annotation export failed ({}): {}", path, e);
+    }
+}
+
+/// Bookmarks window: every labeled node, alive ones selectable
+pub fn bookmarks_ui_system(
+    mut contexts: EguiContexts,
+    lineage_log: Res<LineageLog>,
+    mut selected: ResMut<SelectedNode>,
+    nodes: Query<(Entity, &Lineage, &Status)>,
+) -> Result {
+    if lineage_log.labeled().next().is_none() {
+        return Ok(());
+    }
+    egui::Window::new("Bookmarks")
+        .default_open(false)
+        .show(contexts.ctx_mut()?, |ui| {
+            for (id, labels) in lineage_log.labeled() {
+                let alive = nodes
+                    .iter()
+                    .find(|(_, lineage, status)| lineage.id == id && **status == Status::Alive)
+                    .map(|(entity, ..)| entity);
+                let state = match lineage_log.record(id) {
+                    None => "not born yet".to_string(),
+                    Some(_) if alive.is_some() => "alive".to_string(),
+                    Some(record) => record
+                        .fitness
+                        .map_or("dead".to_string(), |f| format!("fitness {:.1}", f)),
+                };
+                ui.horizontal(|ui| {
+                    ui.label(format!("#{} {} ({})", id, labels.join(", "), state));
+                    if let Some(entity) = alive
+                        && ui.small_button("Select").clicked()
+                    {
+                        selected.select(entity, id);
+                    }
+                });
+            }
+        });
+    Ok(())
+}
+
+/// Register the annotation systems
+pub fn register_annotations(app: &mut App) {
+    app.add_systems(EguiPrimaryContextPass, bookmarks_ui_system)
+        .add_systems(Last, write_annotations_system.run_if(annotations_enabled));
+}
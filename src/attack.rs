@@ -0,0 +1,208 @@
+/// Battery-drain (denial-of-sleep) attack scenario
+/// Attackers (flag: `--attackers <n>`) sit at random points of the field and
+/// flood every node within `ATTACK_RANGE` with bogus requests. The sink also
+/// sends legitimate queries, and the two are indistinguishable on arrival:
+/// serving any request keeps the node awake for `SERVICE_TIME_S` at
+/// inference power plus a reply packet, while legitimate ones earn fitness
+/// credit. The only defense is the policy's request rate limit (a token
+/// bucket), so evolution has to find a limit that serves the sink without
+/// letting attackers drain the battery.
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::{
+    Battery, EnvRng, Gene, PowerOverrides, SimulationMetrics, Status, SurvivalScore, Telemetry,
+    TunedPolicy,
+};
+use crate::config::SimConfig;
+use crate::radio::Radio;
+use crate::speed::SimTime;
+use crate::topology::Topology;
+use crate::units::{Power, WattHours};
+
+/// Bogus requests each attacker sends to each node in range (per simulated hour)
+pub const ATTACK_RATE_PER_HOUR: f32 = 120.0;
+/// Distance within which an attacker reaches nodes (meters)
+pub const ATTACK_RANGE: f32 = 100.0;
+/// Legitimate sink queries per node (per simulated hour)
+pub const LEGIT_REQUEST_RATE_PER_HOUR: f32 = 2.0;
+/// Wake-up, capture, inference and reply time for one request (seconds)
+pub const SERVICE_TIME_S: f32 = 5.0;
+/// Request / reply packet size (bytes)
+pub const REQUEST_BYTES: f32 = 32.0;
+/// Token bucket depth, in hours of the node's rate limit
+const BUCKET_DEPTH_H: f32 = 0.1;
+
+/// Adversarial transmitter
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Attacker;
+
+/// Per-node token bucket enforcing the policy's request rate limit
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RequestLimiter {
+    pub tokens: f32,
+}
+
+impl RequestLimiter {
+    /// Bucket depth for a limit in requests per hour (at least one request)
+    pub fn depth(rate_limit: f32) -> f32 {
+        (rate_limit * BUCKET_DEPTH_H).max(1.0)
+    }
+
+    /// Full bucket for a newborn node
+    pub fn full(rate_limit: f32) -> Self {
+        Self {
+            tokens: Self::depth(rate_limit),
+        }
+    }
+}
+
+/// Per-epoch request statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct AttackMetrics {
+    pub legit_requests: u64,
+    pub legit_served: u64,
+    pub bogus_requests: u64,
+    pub bogus_served: u64,
+    pub energy_wh: WattHours,
+}
+
+/// Run condition: true when the attack scenario is active
+pub fn attack_enabled(config: Res<SimConfig>) -> bool {
+    config.attackers > 0
+}
+
+/// Place attackers uniformly over the field (persist across epochs)
+pub fn spawn_attackers_system(
+    mut commands: Commands,
+    config: Res<SimConfig>,
+    topology: Res<Topology>,
+    mut env_rng: ResMut<EnvRng>,
+) {
+    let min = topology.world_position(IVec2::ZERO);
+    let max = topology.world_position(IVec2::splat(topology.size - 1));
+
+    for _ in 0..config.attackers {
+        let x = env_rng.0.random_range(min.x..=max.x);
+        let y = env_rng.0.random_range(min.y..=max.y);
+        commands.spawn((Attacker, Transform::from_xyz(x, y, 0.0)));
+    }
+    println!(
+        "🏴 {} attackers placed (range {} m, {} req/h each)",
+        config.attackers, ATTACK_RANGE, ATTACK_RATE_PER_HOUR
+    );
+}
+
+/// Draw attackers as red crosses with their reach
+pub fn render_attackers_system(mut gizmos: Gizmos, attackers: Query<&Transform, With<Attacker>>) {
+    let color = Color::srgb(1.0, 0.1, 0.3);
+    for transform in attackers.iter() {
+        let p = transform.translation.truncate();
+        gizmos.line_2d(p + Vec2::new(-6.0, -6.0), p + Vec2::new(6.0, 6.0), color);
+        gizmos.line_2d(p + Vec2::new(-6.0, 6.0), p + Vec2::new(6.0, -6.0), color);
+        gizmos.circle_2d(p, ATTACK_RANGE, color.with_alpha(0.25));
+    }
+}
+
+/// Everything the request handler reads or updates on a node
+type RequestQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static mut RequestLimiter,
+        &'static mut Battery,
+        &'static mut SurvivalScore,
+        &'static TunedPolicy,
+        &'static Gene,
+        &'static Radio,
+        &'static Telemetry,
+        &'static Status,
+    ),
+>;
+
+/// Request system - legitimate and bogus arrivals, rate limiting, service cost
+#[allow(clippy::too_many_arguments)]
+pub fn request_system(
+    time: SimTime,
+    config: Res<SimConfig>,
+    topology: Res<Topology>,
+    power_overrides: Res<PowerOverrides>,
+    mut env_rng: ResMut<EnvRng>,
+    mut attack_metrics: ResMut<AttackMetrics>,
+    mut metrics: ResMut<SimulationMetrics>,
+    attackers: Query<&Transform, With<Attacker>>,
+    mut nodes: RequestQuery,
+) {
+    let hours = time.window_h();
+    let legit_p = (LEGIT_REQUEST_RATE_PER_HOUR * hours).clamp(0.0, 1.0) as f64;
+    let attack_p = (ATTACK_RATE_PER_HOUR * hours).clamp(0.0, 1.0) as f64;
+    let attacker_positions: Vec<Vec2> =
+        attackers.iter().map(|t| t.translation.truncate()).collect();
+    let rng = &mut env_rng.0;
+
+    for (transform, mut limiter, mut battery, mut score, tuned, gene, radio, telemetry, status) in
+        nodes.iter_mut()
+    {
+        if *status == Status::Dead {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        // 1. Token bucket refill
+        let limit = tuned.0.request_rate_limit;
+        limiter.tokens = (limiter.tokens + limit * hours).min(RequestLimiter::depth(limit));
+
+        // 2. Arrivals: at most one per source per tick
+        let legit = usize::from(rng.random_bool(legit_p));
+        let bogus = attacker_positions
+            .iter()
+            .filter(|a| topology.distance(position, **a) <= ATTACK_RANGE)
+            .filter(|_| rng.random_bool(attack_p))
+            .count();
+        if legit + bogus == 0 {
+            continue;
+        }
+        attack_metrics.legit_requests += legit as u64;
+        attack_metrics.bogus_requests += bogus as u64;
+        if telemetry.asleep {
+            continue; // Radio off: nothing received, nothing served
+        }
+
+        // 3. Every arrival costs a receive; served ones cost a wake-up + reply
+        let (_, inference_power) =
+            crate::data_loader::get_model_power(gene.model_type, power_overrides.0.as_ref());
+        let airtime_s = radio.radio_type.airtime_s(REQUEST_BYTES);
+        let rx = radio.radio_type.rx_power().over_s(airtime_s);
+        let serve = Power::from_watts(inference_power).over_s(SERVICE_TIME_S)
+            + radio
+                .radio_type
+                .tx_power(radio.tx_power_dbm)
+                .over_s(airtime_s);
+
+        let mut energy = rx * (legit + bogus) as f32;
+        // Arrival order within a tick is random: shuffle legit among bogus
+        let mut legit_left = legit;
+        for i in 0..legit + bogus {
+            let is_legit = legit_left > 0 && rng.random_range(0..legit + bogus - i) < legit_left;
+            if is_legit {
+                legit_left -= 1;
+            }
+            if limiter.tokens < 1.0 {
+                continue; // Rate limited: dropped after the receive
+            }
+            limiter.tokens -= 1.0;
+            energy += serve;
+            if is_legit {
+                attack_metrics.legit_served += 1;
+                score.0 += config.detection_credit;
+            } else {
+                attack_metrics.bogus_served += 1;
+            }
+        }
+
+        battery.drain(energy.wh());
+        attack_metrics.energy_wh += energy;
+        metrics.total_energy_consumed += energy;
+    }
+}
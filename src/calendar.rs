@@ -0,0 +1,135 @@
+/// Simulation calendar
+/// `SimulationMetrics::current_hour` is only the hour of day; the calendar
+/// counts the midnights it wraps through, starting on `--day-of-year`, and
+/// names the date and the (meteorological) season, flipped for southern
+/// `--location` sites. When the solar source varies through the year (a
+/// CSV with a `month` column, or the astronomical model) every midnight
+/// swaps in the new day's profile, so a long run walks through winter
+/// instead of repeating one day forever.
+use std::fmt;
+
+pub const DAYS_PER_YEAR: u16 = 365;
+
+/// Day of the year each month starts on, minus one (non-leap year)
+const MONTH_STARTS: [u16; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Day of the year (1-365) of `day` of `month` (1-12)
+pub fn day_of(month: u8, day: u8) -> u16 {
+    MONTH_STARTS[month as usize - 1] + day as u16
+}
+
+/// Month (1-12) of a day of the year (1-365)
+pub fn month_of(day_of_year: u16) -> u8 {
+    MONTH_STARTS
+        .iter()
+        .rposition(|&start| start < day_of_year)
+        .map_or(1, |month| month as u8 + 1)
+}
+
+/// Meteorological season (whole months)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Winter,
+    Spring,
+    Summer,
+    Autumn,
+}
+
+impl Season {
+    /// Season of `month` (1-12) in either hemisphere
+    pub fn of(month: u8, southern: bool) -> Self {
+        let northern = match month {
+            12 | 1 | 2 => Season::Winter,
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            _ => Season::Autumn,
+        };
+        if !southern {
+            return northern;
+        }
+        match northern {
+            Season::Winter => Season::Summer,
+            Season::Spring => Season::Autumn,
+            Season::Summer => Season::Winter,
+            Season::Autumn => Season::Spring,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Season::Winter => "winter",
+            Season::Spring => "spring",
+            Season::Summer => "summer",
+            Season::Autumn => "autumn",
+        }
+    }
+}
+
+/// Date of the run
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Resource))]
+pub struct Calendar {
+    /// Day of the year the run started on
+    pub start_day: u16,
+    /// Midnights passed since the start
+    pub days_elapsed: u32,
+    /// Southern-hemisphere site: seasons are flipped
+    pub southern: bool,
+    /// Hour of day last observed, to catch midnight
+    last_hour: f32,
+}
+
+impl Calendar {
+    /// Run starting on `start_day` (1-365), in the southern hemisphere
+    /// when `southern`
+    pub fn new(start_day: u16, southern: bool) -> Self {
+        Self {
+            start_day,
+            days_elapsed: 0,
+            southern,
+            last_hour: 0.0,
+        }
+    }
+
+    pub fn day_of_year(&self) -> u16 {
+        ((self.start_day as u32 - 1 + self.days_elapsed) % DAYS_PER_YEAR as u32) as u16 + 1
+    }
+
+    pub fn month(&self) -> u8 {
+        month_of(self.day_of_year())
+    }
+
+    pub fn season(&self) -> Season {
+        Season::of(self.month(), self.southern)
+    }
+
+    /// Follow the hour of day; true when it just passed midnight
+    pub fn observe_hour(&mut self, hour: f32) -> bool {
+        let midnight = hour < self.last_hour;
+        self.last_hour = hour;
+        if midnight {
+            self.days_elapsed += 1;
+        }
+        midnight
+    }
+}
+
+impl fmt::Display for Calendar {
+    /// `day 355 (21 Dec, winter)`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let day = self.day_of_year();
+        let month = self.month() as usize;
+        write!(
+            f,
+            "day {} ({} {}, {})",
+            day,
+            day - MONTH_STARTS[month - 1],
+            MONTH_NAMES[month - 1],
+            self.season().name()
+        )
+    }
+}
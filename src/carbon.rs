@@ -0,0 +1,92 @@
+/// Carbon accounting
+/// Solar nodes emit nothing while running, so their footprint is embodied:
+/// board, panel and battery manufacturing (`HardwareSpec::embodied_kgco2`),
+/// amortized linearly over `SERVICE_LIFE_H`. Each epoch charges every
+/// deployed node its share - dead ones included, the hardware still exists -
+/// and divides by detections, per hardware + model configuration. Operational
+/// energy is reported alongside, with the emissions it would cause on a
+/// fossil-heavy grid for comparison.
+use std::collections::HashMap;
+
+use crate::components::{Battery, EnergyLedger, Gene};
+use crate::hardware::HardwareSpec;
+use crate::sensing::Sensing;
+use crate::units::WattHours;
+
+/// Deployment lifetime embodied carbon is spread over (5 years, hours)
+pub const SERVICE_LIFE_H: f32 = 5.0 * 365.0 * 24.0;
+/// Grid carbon intensity for the grid-powered comparison (gCO2e/kWh,
+/// gas-dominated grid such as Algeria's)
+pub const GRID_GCO2_PER_KWH: f32 = 500.0;
+
+/// Footprint and useful work of a group of nodes over one epoch
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CarbonAccount {
+    /// Amortized embodied carbon (gCO2e)
+    pub embodied_g: f32,
+    /// Energy drawn from batteries
+    pub operational_wh: WattHours,
+    pub detections: u64,
+}
+
+impl CarbonAccount {
+    /// Embodied carbon per detection (None without detections)
+    pub fn g_per_detection(&self) -> Option<f32> {
+        (self.detections > 0).then(|| self.embodied_g / self.detections as f32)
+    }
+
+    /// Operational energy per detection (None without detections)
+    pub fn wh_per_detection(&self) -> Option<f32> {
+        (self.detections > 0).then(|| self.operational_wh.wh() / self.detections as f32)
+    }
+
+    /// Emissions the operational energy would cause if grid-powered (gCO2e)
+    pub fn grid_equivalent_g(&self) -> f32 {
+        self.operational_wh.wh() / 1000.0 * GRID_GCO2_PER_KWH
+    }
+
+    fn add(&mut self, other: &CarbonAccount) {
+        self.embodied_g += other.embodied_g;
+        self.operational_wh += other.operational_wh;
+        self.detections += other.detections;
+    }
+}
+
+/// Fleet total and per-configuration ("<hardware> + <model>") accounts,
+/// configurations sorted from lowest to highest carbon per detection
+pub fn carbon_accounts<'a>(
+    nodes: impl Iterator<
+        Item = (
+            &'a HardwareSpec,
+            &'a Gene,
+            &'a Sensing,
+            &'a Battery,
+            &'a EnergyLedger,
+        ),
+    >,
+    epoch_h: f32,
+) -> (CarbonAccount, Vec<(String, CarbonAccount)>) {
+    let mut fleet = CarbonAccount::default();
+    let mut by_config: HashMap<String, CarbonAccount> = HashMap::new();
+
+    for (hardware, gene, sensing, battery, ledger) in nodes {
+        let node = CarbonAccount {
+            embodied_g: hardware.embodied_kgco2() * 1000.0 * epoch_h / SERVICE_LIFE_H,
+            operational_wh: ledger.consumed_wh(battery),
+            detections: sensing.detections as u64,
+        };
+        fleet.add(&node);
+        by_config
+            .entry(format!("{} + {}", hardware.name(), gene.model_type.name()))
+            .or_default()
+            .add(&node);
+    }
+
+    let mut configs: Vec<(String, CarbonAccount)> = by_config.into_iter().collect();
+    configs.sort_by(|(a_name, a), (b_name, b)| {
+        let a_g = a.g_per_detection().unwrap_or(f32::INFINITY);
+        let b_g = b.g_per_detection().unwrap_or(f32::INFINITY);
+        a_g.total_cmp(&b_g).then_with(|| a_name.cmp(b_name))
+    });
+    (fleet, configs)
+}
@@ -0,0 +1,287 @@
+/// Catastrophic environmental events
+/// Multi-day storms (no solar anywhere), region-wide panel failures (no
+/// solar inside a radius) and localized shading (reduced solar in a small
+/// radius) are drawn at random (flag: `--catastrophe-rate <per day>`) or
+/// scripted (flag: `--catastrophe <kind>:<start_h>:<duration_h>`). Effects
+/// are tied to places, not nodes, so offspring born on a broken site
+/// inherit the damage. Start and end of every event go to the event log.
+/// A node's exposure is its sector's microclimate factor (from the
+/// scenario) times the worst catastrophe covering it, less panel dust.
+use bevy::prelude::*;
+use rand::Rng;
+use std::str::FromStr;
+
+use crate::components::{EnvRng, EpochCount, Status};
+use crate::config::SimConfig;
+use crate::curriculum;
+use crate::event_log::EventLog;
+use crate::extinction::Easing;
+use crate::maintenance::PanelSoiling;
+use crate::scenario::Scenario;
+use crate::speed::SimTime;
+use crate::topology::Topology;
+
+/// Kind of catastrophe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatastropheKind {
+    /// Whole field overcast: solar output zero
+    Storm,
+    /// Hail / dust damage: panels in a region produce nothing
+    PanelFailure,
+    /// Vegetation or construction: panels in a small area get 30%
+    Shading,
+}
+
+impl CatastropheKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CatastropheKind::Storm => "Storm",
+            CatastropheKind::PanelFailure => "Panel failure",
+            CatastropheKind::Shading => "Shading",
+        }
+    }
+
+    /// Solar multiplier inside the affected area
+    pub fn solar_factor(&self) -> f32 {
+        match self {
+            CatastropheKind::Storm => 0.0,
+            CatastropheKind::PanelFailure => 0.0,
+            CatastropheKind::Shading => 0.3,
+        }
+    }
+
+    /// Affected radius in meters (None = whole field)
+    pub fn radius(&self) -> Option<f32> {
+        match self {
+            CatastropheKind::Storm => None,
+            CatastropheKind::PanelFailure => Some(150.0),
+            CatastropheKind::Shading => Some(60.0),
+        }
+    }
+
+    /// Range of random durations (simulated hours)
+    fn duration_range_h(&self) -> std::ops::Range<f32> {
+        match self {
+            CatastropheKind::Storm => 24.0..72.0,
+            CatastropheKind::PanelFailure => 24.0..96.0,
+            CatastropheKind::Shading => 2.0..8.0,
+        }
+    }
+
+    /// Relative frequency among random events
+    fn weight(&self) -> f32 {
+        match self {
+            CatastropheKind::Storm => 0.2,
+            CatastropheKind::PanelFailure => 0.3,
+            CatastropheKind::Shading => 0.5,
+        }
+    }
+
+    pub const ALL: [CatastropheKind; 3] = [
+        CatastropheKind::Storm,
+        CatastropheKind::PanelFailure,
+        CatastropheKind::Shading,
+    ];
+}
+
+impl FromStr for CatastropheKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "storm" => Ok(CatastropheKind::Storm),
+            "panel-failure" | "failure" => Ok(CatastropheKind::PanelFailure),
+            "shading" | "shade" => Ok(CatastropheKind::Shading),
+            other => Err(format!("unknown catastrophe kind {:?}", other)),
+        }
+    }
+}
+
+/// Scripted catastrophe (flag: `--catastrophe <kind>:<start_h>:<duration_h>`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScriptedCatastrophe {
+    pub kind: CatastropheKind,
+    /// Simulated hours since the run started
+    pub start_h: f32,
+    pub duration_h: f32,
+}
+
+impl FromStr for ScriptedCatastrophe {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let [kind, start, duration] = parts[..] else {
+            return Err(format!(
+                "expected <kind>:<start_h>:<duration_h>, got {:?}",
+                s
+            ));
+        };
+        Ok(Self {
+            kind: kind.parse()?,
+            start_h: start
+                .parse()
+                .map_err(|_| format!("invalid start in {:?}", s))?,
+            duration_h: duration
+                .parse()
+                .map_err(|_| format!("invalid duration in {:?}", s))?,
+        })
+    }
+}
+
+/// Catastrophe in progress
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveCatastrophe {
+    pub kind: CatastropheKind,
+    pub center: Vec2,
+    pub remaining_h: f32,
+}
+
+impl ActiveCatastrophe {
+    /// Whether a position lies in the affected area
+    pub fn affects(&self, topology: &Topology, position: Vec2) -> bool {
+        self.kind
+            .radius()
+            .is_none_or(|radius| topology.distance(self.center, position) <= radius)
+    }
+}
+
+/// Catastrophe schedule and state
+#[derive(Resource, Debug, Default)]
+pub struct Catastrophes {
+    /// Simulated hours since the run started
+    pub elapsed_h: f32,
+    pub active: Vec<ActiveCatastrophe>,
+    /// Index of the next scripted event not yet started
+    next_scripted: usize,
+}
+
+/// Per-node solar multiplier from the microclimate, catastrophes and dust
+/// (1.0 = unaffected)
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SolarExposure(pub f32);
+
+impl Default for SolarExposure {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Advance the catastrophe clock, start/end events, update node exposure
+#[allow(clippy::too_many_arguments)]
+pub fn catastrophe_system(
+    time: SimTime,
+    config: Res<SimConfig>,
+    topology: Res<Topology>,
+    scenario: Res<Scenario>,
+    epoch_count: Res<EpochCount>,
+    easing: Res<Easing>,
+    mut catastrophes: ResMut<Catastrophes>,
+    mut env_rng: ResMut<EnvRng>,
+    mut event_log: ResMut<EventLog>,
+    mut nodes: Query<(&Transform, &mut SolarExposure, &PanelSoiling, &Status)>,
+) {
+    let hours = time.window_h();
+    catastrophes.elapsed_h += hours;
+    let now_h = catastrophes.elapsed_h;
+    let epoch = epoch_count.0;
+
+    // 1. Expire finished events
+    let mut ended = Vec::new();
+    catastrophes.active.retain_mut(|event| {
+        event.remaining_h -= hours;
+        if event.remaining_h <= 0.0 {
+            ended.push(event.kind);
+        }
+        event.remaining_h > 0.0
+    });
+    for kind in ended {
+        event_log.push(epoch, now_h, format!("{} over", kind.name()));
+    }
+
+    // 2. Start scripted and random events
+    let mut started = Vec::new();
+    while let Some(scripted) = config.catastrophes.get(catastrophes.next_scripted) {
+        if scripted.start_h > now_h {
+            break;
+        }
+        started.push((scripted.kind, scripted.duration_h));
+        catastrophes.next_scripted += 1;
+    }
+
+    let rng = &mut env_rng.0;
+    let stage = easing.apply(curriculum::stage(&config, epoch));
+    let rate = config.catastrophe_rate * stage.catastrophe_scale;
+    let start_p = (rate * hours / 24.0).clamp(0.0, 1.0);
+    if start_p > 0.0 && rng.random_bool(start_p as f64) {
+        let total: f32 = CatastropheKind::ALL.iter().map(|k| k.weight()).sum();
+        let mut pick = rng.random_range(0.0..total);
+        let kind = CatastropheKind::ALL
+            .into_iter()
+            .find(|k| {
+                pick -= k.weight();
+                pick < 0.0
+            })
+            .unwrap_or(CatastropheKind::Shading);
+        started.push((kind, rng.random_range(kind.duration_range_h())));
+    }
+
+    let min = topology.world_position(IVec2::ZERO);
+    let max = topology.world_position(IVec2::splat(topology.size - 1));
+    for (kind, duration_h) in started {
+        let center = Vec2::new(
+            rng.random_range(min.x..=max.x),
+            rng.random_range(min.y..=max.y),
+        );
+        let area = kind.radius().map_or("whole field".to_string(), |r| {
+            format!("{:.0} m around ({:.0}, {:.0})", r, center.x, center.y)
+        });
+        event_log.push(
+            epoch,
+            now_h,
+            format!(
+                "{} for {:.0} h: solar ×{:.1} over {}",
+                kind.name(),
+                duration_h,
+                kind.solar_factor(),
+                area
+            ),
+        );
+        catastrophes.active.push(ActiveCatastrophe {
+            kind,
+            center,
+            remaining_h: duration_h,
+        });
+    }
+
+    // 3. Exposure of every node: its microclimate, then the worst
+    // affecting event
+    for (transform, mut exposure, soiling, status) in nodes.iter_mut() {
+        if *status == Status::Dead {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let catastrophe = catastrophes
+            .active
+            .iter()
+            .filter(|event| event.affects(&topology, position))
+            .map(|event| event.kind.solar_factor())
+            .fold(1.0, f32::min);
+        exposure.0 = scenario.solar_factor(topology.cell_of(position))
+            * catastrophe
+            * soiling.clean_fraction();
+    }
+}
+
+/// Outline regional events (storms tint nothing: they cover everything)
+pub fn render_catastrophes_system(mut gizmos: Gizmos, catastrophes: Res<Catastrophes>) {
+    for event in &catastrophes.active {
+        if let Some(radius) = event.kind.radius() {
+            let color = match event.kind {
+                CatastropheKind::PanelFailure => Color::srgb(1.0, 0.5, 0.0),
+                _ => Color::srgb(0.6, 0.6, 0.8),
+            };
+            gizmos.circle_2d(event.center, radius, color);
+        }
+    }
+}
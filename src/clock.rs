@@ -0,0 +1,232 @@
+/// External clock co-simulation (flag: `--clock <wall|ntp|-|path>`)
+/// Simulated time is slaved to an outside clock instead of running free, so
+/// network simulators or digital twins can stay in lockstep with the run.
+/// The fixed loop only runs the ticks needed to catch up with the clock and
+/// idles while it is ahead. Sources:
+/// - `wall` (alias `ntp`): the system clock, NTP-disciplined on synced
+///   hosts, at the configured `--time-scale` from `--clock-origin` (Unix
+///   seconds; launch time by default). Processes sharing an origin agree on
+///   the simulated time wherever they run.
+/// - `-` (stdin) or a path, e.g. a named pipe: a stream of timestamps in
+///   simulated seconds since the start, one per line; each line grants the
+///   simulation up to that time and is acknowledged on the
+///   `edgegenesis::clock` log target once reached.
+///
+/// Catching up is soft real-time: at most `MAX_CATCHUP_TICKS` ticks run per
+/// frame and the lag is logged, rather than stalling the window.
+use bevy::prelude::*;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::SimConfig;
+use crate::speed::SimSpeed;
+
+/// Most fixed ticks run in one frame while behind the clock
+pub const MAX_CATCHUP_TICKS: u32 = 600;
+
+/// Where the external time comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClockSource {
+    /// System clock, scaled by `--time-scale`
+    Wall,
+    /// Timestamp lines from stdin (`-`) or a file / named pipe
+    Stream(String),
+}
+
+impl FromStr for ClockSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("empty clock source".to_string()),
+            "wall" | "ntp" => Ok(ClockSource::Wall),
+            path => Ok(ClockSource::Stream(path.to_string())),
+        }
+    }
+}
+
+/// Latest grant of a timestamp stream
+#[derive(Debug, Default)]
+struct Grant {
+    simulated_s: f64,
+    closed: bool,
+}
+
+enum Reading {
+    Wall { origin: SystemTime, time_scale: f64 },
+    Stream(Arc<Mutex<Grant>>),
+}
+
+/// External clock handle and the simulated time run under it (inert when
+/// disabled)
+#[derive(Resource, Default)]
+pub struct ExternalClock {
+    reading: Option<Reading>,
+    /// Fixed ticks run under the clock so far
+    pub ticks: u64,
+    /// Latest stream grant acknowledged
+    acknowledged_s: f64,
+    /// Whether the last frame had to leave ticks for later
+    behind: bool,
+}
+
+impl ExternalClock {
+    /// Start reading the configured source; a stream that cannot be opened
+    /// leaves the clock at zero (with a warning), so the run waits
+    pub fn new(config: &SimConfig) -> Self {
+        let reading = match &config.clock {
+            None => None,
+            Some(ClockSource::Wall) => {
+                let origin = config.clock_origin.map_or_else(SystemTime::now, |unix_s| {
+                    UNIX_EPOCH + Duration::from_secs_f64(unix_s.max(0.0))
+                });
+                Some(Reading::Wall {
+                    origin,
+                    time_scale: config.time_scale as f64,
+                })
+            }
+            Some(ClockSource::Stream(path)) => {
+                let grant = Arc::new(Mutex::new(Grant::default()));
+                let shared = Arc::clone(&grant);
+                let path = path.clone();
+                std::thread::spawn(move || read_grants(&path, &shared));
+                Some(Reading::Stream(grant))
+            }
+        };
+        Self {
+            reading,
+            ..default()
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.reading.is_some()
+    }
+
+    /// External time now, in simulated seconds since the start
+    pub fn now_s(&self) -> Option<f64> {
+        match self.reading.as_ref()? {
+            Reading::Wall { origin, time_scale } => {
+                let elapsed = SystemTime::now()
+                    .duration_since(*origin)
+                    .unwrap_or_default();
+                Some(elapsed.as_secs_f64() * time_scale)
+            }
+            Reading::Stream(grant) => grant.lock().ok().map(|grant| grant.simulated_s),
+        }
+    }
+
+    /// Ticks of `tick_s` simulated seconds due to catch up with the clock
+    pub fn ticks_due(&self, tick_s: f64) -> u64 {
+        let Some(now_s) = self.now_s() else {
+            return 0;
+        };
+        let due = (now_s / tick_s).floor() as u64;
+        due.saturating_sub(self.ticks)
+    }
+
+    /// Block until the clock allows `simulated_s` (headless runs)
+    pub fn wait_until(&self, simulated_s: f64) {
+        while self.now_s().is_some_and(|now_s| now_s < simulated_s) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+/// Background reader: keep the largest timestamp seen
+fn read_grants(path: &str, grant: &Mutex<Grant>) {
+    let reader: Box<dyn BufRead> = if path == "-" {
+        Box::new(BufReader::new(std::io::stdin()))
+    } else {
+        match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                warn!(target: "edgegenesis::clock",
+                    "⚠️ Cannot open clock stream {}: {}", path, e
+                );
+                return;
+            }
+        }
+    };
+    for line in reader.lines().map_while(Result::ok) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match line.parse::<f64>() {
+            Ok(simulated_s) if simulated_s.is_finite() => {
+                if let Ok(mut grant) = grant.lock() {
+                    grant.simulated_s = grant.simulated_s.max(simulated_s);
+                }
+            }
+            _ => warn!(target: "edgegenesis::clock", "⚠️ Ignoring clock timestamp {:?}", line),
+        }
+    }
+    if let Ok(mut grant) = grant.lock() {
+        grant.closed = true;
+    }
+}
+
+/// Run condition: true when simulated time follows an external clock
+pub fn clock_enabled(clock: Res<ExternalClock>) -> bool {
+    clock.enabled()
+}
+
+/// Virtual time stays paused; only the clock feeds the fixed loop
+pub fn pause_for_clock_system(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// Feed the fixed loop the ticks the clock allows this frame
+pub fn external_clock_system(
+    speed: Res<SimSpeed>,
+    mut clock: ResMut<ExternalClock>,
+    mut fixed: ResMut<Time<Fixed>>,
+) {
+    let timestep = fixed.timestep();
+    let tick_s = timestep.as_secs_f64() * speed.time_scale as f64;
+    let due = clock.ticks_due(tick_s);
+    let run = due.min(MAX_CATCHUP_TICKS as u64);
+    if run > 0 {
+        fixed.accumulate_overstep(timestep * run as u32);
+        clock.ticks += run;
+    }
+
+    let behind = due > run;
+    if behind != clock.behind {
+        clock.behind = behind;
+        if behind {
+            warn!(target: "edgegenesis::clock",
+                "⏱️ {} ticks behind the external clock, catching up", due - run
+            );
+        } else {
+            info!(target: "edgegenesis::clock", "⏱️ In step with the external clock");
+        }
+    }
+
+    // Acknowledge stream grants once the scheduled ticks cover them
+    let simulated_s = clock.ticks as f64 * tick_s;
+    let Some(Reading::Stream(grant)) = &clock.reading else {
+        return;
+    };
+    let Ok(grant) = grant.lock() else {
+        return;
+    };
+    let (granted_s, closed) = (grant.simulated_s, grant.closed);
+    drop(grant);
+    if granted_s > clock.acknowledged_s && simulated_s + tick_s > granted_s {
+        clock.acknowledged_s = granted_s;
+        info!(target: "edgegenesis::clock",
+            "⏱️ Reached t = {:.1} s{}", granted_s, if closed { " (stream closed)" } else { "" }
+        );
+    }
+}
+
+/// Register the external clock systems
+pub fn register_clock(app: &mut App) {
+    app.add_systems(Startup, pause_for_clock_system.run_if(clock_enabled))
+        .add_systems(PreUpdate, external_clock_system.run_if(clock_enabled));
+}
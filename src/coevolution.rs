@@ -0,0 +1,172 @@
+/// Co-evolving adversarial environment
+/// A second, optional evolutionary loop (flag: `--coevolve <n>`) over
+/// environment genomes: random catastrophe rate, ground-truth event rate and
+/// workload burstiness. Each genome sets the environment for one epoch and
+/// is scored by the node population's mean fitness under it; once all have
+/// played, the half that hurt the population most survives and mutated
+/// copies replace the rest. Parameters are bounded so the adversary has to
+/// find hard-but-survivable conditions rather than ending all life, and
+/// scores come from successive node generations, so the two populations
+/// chase each other in an open-ended arms race.
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::GeneticRng;
+use crate::config::SimConfig;
+use crate::milestones::EpochCompleted;
+
+/// Upper bound on random catastrophes per simulated day
+pub const MAX_CATASTROPHE_RATE: f32 = 1.0;
+
+/// Bounds on ground-truth objects per node per simulated hour
+pub const MIN_EVENT_RATE: f32 = 0.5;
+pub const MAX_EVENT_RATE: f32 = 6.0;
+
+/// Upper bound on workload burstiness
+pub const MAX_BURSTINESS: f32 = 0.9;
+
+/// Environment parameters under evolutionary control
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnvGenome {
+    pub catastrophe_rate: f32,
+    pub event_rate: f32,
+    pub event_burstiness: f32,
+}
+
+impl EnvGenome {
+    /// The environment currently configured
+    pub fn from_config(config: &SimConfig) -> Self {
+        Self {
+            catastrophe_rate: config.catastrophe_rate,
+            event_rate: config.event_rate,
+            event_burstiness: config.event_burstiness,
+        }
+    }
+
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self {
+            catastrophe_rate: rng.random_range(0.0..=MAX_CATASTROPHE_RATE),
+            event_rate: rng.random_range(MIN_EVENT_RATE..=MAX_EVENT_RATE),
+            event_burstiness: rng.random_range(0.0..=MAX_BURSTINESS),
+        }
+    }
+
+    /// Small random step on every parameter, clamped to its bounds
+    pub fn mutate(&self, rng: &mut impl Rng) -> Self {
+        Self {
+            catastrophe_rate: (self.catastrophe_rate + rng.random_range(-0.15..0.15))
+                .clamp(0.0, MAX_CATASTROPHE_RATE),
+            event_rate: (self.event_rate * rng.random_range(0.8..1.25))
+                .clamp(MIN_EVENT_RATE, MAX_EVENT_RATE),
+            event_burstiness: (self.event_burstiness + rng.random_range(-0.1..0.1))
+                .clamp(0.0, MAX_BURSTINESS),
+        }
+    }
+
+    fn apply(&self, config: &mut SimConfig) {
+        config.catastrophe_rate = self.catastrophe_rate;
+        config.event_rate = self.event_rate;
+        config.event_burstiness = self.event_burstiness;
+    }
+}
+
+/// Environment population and evaluation state
+#[derive(Resource, Debug, Default)]
+pub struct Adversary {
+    pub genomes: Vec<EnvGenome>,
+    /// Mean node fitness observed under each genome (None = not played yet)
+    pub scores: Vec<Option<f32>>,
+    /// Genome setting the environment of the current epoch
+    pub active: usize,
+    pub generation: u32,
+}
+
+impl Adversary {
+    /// The configured environment plays first; the rest start random
+    pub fn new(config: &SimConfig, rng: &mut impl Rng) -> Self {
+        let mut genomes = vec![EnvGenome::from_config(config)];
+        genomes.extend((1..config.coevolve).map(|_| EnvGenome::random(rng)));
+        Self {
+            scores: vec![None; genomes.len()],
+            genomes,
+            active: 0,
+            generation: 1,
+        }
+    }
+
+    /// Truncation selection: keep the genomes with the lowest node fitness,
+    /// refill with their mutants
+    fn breed(&mut self, rng: &mut impl Rng) {
+        let mut ranked: Vec<(f32, EnvGenome)> = self
+            .scores
+            .iter()
+            .zip(&self.genomes)
+            .map(|(score, genome)| (score.unwrap_or(f32::INFINITY), *genome))
+            .collect();
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let keep = self.genomes.len().div_ceil(2);
+        let parents: Vec<EnvGenome> = ranked[..keep].iter().map(|(_, g)| *g).collect();
+        self.genomes = parents.clone();
+        while self.genomes.len() < ranked.len() {
+            let parent = parents[rng.random_range(0..parents.len())];
+            self.genomes.push(parent.mutate(rng));
+        }
+        self.scores = vec![None; self.genomes.len()];
+        self.generation += 1;
+    }
+}
+
+/// Run condition: true when the environment co-evolves
+pub fn coevolution_enabled(config: Res<SimConfig>) -> bool {
+    config.coevolve > 0
+}
+
+/// Create the environment population (its first genome is the configured
+/// environment, already in place)
+pub fn setup_adversary_system(
+    mut commands: Commands,
+    config: Res<SimConfig>,
+    mut genetic_rng: ResMut<GeneticRng>,
+) {
+    let adversary = Adversary::new(&config, &mut genetic_rng.0);
+    info!(target: "edgegenesis::coevolution",
+        "🌪️ Co-evolving {} environment genomes against the population",
+        adversary.genomes.len()
+    );
+    commands.insert_resource(adversary);
+}
+
+/// Score the environment that just played, breed after a full round and
+/// switch the config to the next genome
+pub fn coevolution_system(
+    mut completed: MessageReader<EpochCompleted>,
+    mut adversary: ResMut<Adversary>,
+    mut config: ResMut<SimConfig>,
+    mut genetic_rng: ResMut<GeneticRng>,
+) {
+    for epoch in completed.read() {
+        let played = adversary.active;
+        adversary.scores[played] = Some(epoch.fitness_mean);
+        let genome = adversary.genomes[played];
+        info!(target: "edgegenesis::coevolution",
+            "🌪️ Environment {}.{} (storms {:.2}/day, events {:.1}/h, burstiness {:.2}) held mean fitness to {:.2}",
+            adversary.generation,
+            played,
+            genome.catastrophe_rate,
+            genome.event_rate,
+            genome.event_burstiness,
+            epoch.fitness_mean
+        );
+
+        adversary.active += 1;
+        if adversary.active == adversary.genomes.len() {
+            adversary.breed(&mut genetic_rng.0);
+            adversary.active = 0;
+            info!(target: "edgegenesis::coevolution",
+                "🧫 Environment generation {} bred", adversary.generation
+            );
+        }
+        adversary.genomes[adversary.active].apply(&mut config);
+    }
+}
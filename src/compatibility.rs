@@ -0,0 +1,78 @@
+/// Hardware/model compatibility (flag: `--compatibility <off|forbid|crash>`)
+/// A board can only run a model it can hold in memory (`size_mb` against
+/// `HardwareSpec::ram_mb`) and infer with inside `INFERENCE_DEADLINE_S`
+/// (`RealModelType::gflops` against `compute_gflops`); an ESP32 runs none
+/// of the full-precision models. `off` ignores this, as runs always did.
+/// `forbid` repairs every founder and offspring before it is born (ECS and
+/// headless): the gene gets the largest model its board runs, or, when the
+/// board runs none, the cheapest platform running the gene's model replaces
+/// it (unless `--hardware` locks the platform); OTA campaigns skip boards
+/// that cannot run the pushed model. `crash` lets such nodes be born, but
+/// loading the model fails and the node dies (`DeathCause::IncompatibleModel`),
+/// so selection learns the constraint. Editor and gRPC spawns are taken as
+/// given.
+use std::str::FromStr;
+
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::models::RealModelType;
+use crate::node::Gene;
+
+/// What happens to a gene whose model its hardware cannot run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compatibility {
+    /// Any model runs on any board
+    #[default]
+    Off,
+    /// Invalid combinations are repaired at birth
+    Forbid,
+    /// Invalid combinations die when the model load fails
+    Crash,
+}
+
+impl FromStr for Compatibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" => Ok(Compatibility::Off),
+            "forbid" => Ok(Compatibility::Forbid),
+            "crash" => Ok(Compatibility::Crash),
+            other => Err(format!("unknown compatibility mode {:?}", other)),
+        }
+    }
+}
+
+impl Compatibility {
+    /// The pair to give birth to: unchanged unless `forbid` rejects it
+    pub fn repair(
+        &self,
+        gene: Gene,
+        hardware: HardwareSpec,
+        locked: Option<HardwareType>,
+    ) -> (Gene, HardwareSpec) {
+        if *self != Compatibility::Forbid || hardware.runs(gene.model_type) {
+            return (gene, hardware);
+        }
+        let largest = RealModelType::ALL
+            .into_iter()
+            .filter(|model| hardware.runs(*model))
+            .max_by(|a, b| a.size_mb().total_cmp(&b.size_mb()));
+        if let Some(model_type) = largest {
+            return (Gene { model_type, ..gene }, hardware);
+        }
+        if locked.is_some() {
+            return (gene, hardware);
+        }
+        let cheapest = HardwareType::ALL
+            .into_iter()
+            .map(HardwareSpec::new)
+            .filter(|spec| spec.runs(gene.model_type))
+            .min_by(|a, b| a.cost_usd().total_cmp(&b.cost_usd()));
+        (gene, cheapest.unwrap_or(hardware))
+    }
+
+    /// True when the model load of this pair fails
+    pub fn crashes(&self, model: RealModelType, hardware: &HardwareSpec) -> bool {
+        *self == Compatibility::Crash && !hardware.runs(model)
+    }
+}
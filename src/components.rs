@@ -1,60 +1,126 @@
-use crate::hardware::HardwareSpec;
-use crate::models::RealModelType;
-use crate::policies::PowerPolicy;
+use crate::aging::BatteryHealth;
+use crate::attack::RequestLimiter;
+use crate::catastrophe::SolarExposure;
+use crate::hardware::{HardwareSpec, SolarPanel};
+use crate::maintenance::PanelSoiling;
+use crate::policies::PolicyState;
+use crate::radio::{Radio, RadioType};
+use crate::sensing::Sensing;
+use crate::sensor::Sensor;
+use crate::storage::StorageBuffer;
+use crate::thermal::Temperature;
+use crate::trust::Reputation;
+use crate::units::WattHours;
 use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use std::collections::HashMap;
 
-/// Battery component - stores energy level in Wh (Watt-hours)
-#[derive(Component)]
-pub struct Battery(pub f32);
+// The node state the Bevy-free engine steps
+pub use crate::node::{
+    Battery, Energy, EnergyLedger, Gene, ModelCache, Status, Telemetry, TunedPolicy,
+};
 
-/// Gene component - evolutionary configuration
+/// Lineage component - identity and ancestry of a node
+/// The id is the node's identity in every export (snapshots, elites,
+/// decision log, lineage log, extinction log, gRPC population) and in the
+/// console; entities are reused across epochs, so `Entity` values never
+/// leave the ECS.
 #[derive(Component, Clone, Copy, Debug)]
-pub struct Gene {
-    /// Model type from models.rs (type-safe, documented)
-    pub model_type: RealModelType,
-
-    /// Inference frequency ratio (0.0 - 1.0)
-    pub inference_frequency: f32,
-
-    /// Solar panel efficiency multiplier (0.8 - 1.2)
-    pub solar_efficiency_factor: f32,
-
-    /// Power management strategy
-    pub policy: PowerPolicy,
+pub struct Lineage {
+    /// Unique, sequential id (never reused within a run)
+    pub id: u64,
+    /// Elite this node was bred from (None for founders and manual spawns)
+    pub parent: Option<u64>,
 }
 
 /// Survival score - fitness metric
 #[derive(Component, Clone, Copy)]
 pub struct SurvivalScore(pub f32);
 
-/// Node status
-#[derive(Component, PartialEq, Eq, Debug)]
-pub enum Status {
-    Alive,
-    Dead,
-}
+/// Marker added once a node's death is announced (`NodeDied`); per-frame
+/// systems filter corpses out with `Without<Dead>` instead of visiting
+/// them. `Status` stays the source of truth within the frame of death.
+#[derive(Component, Debug)]
+pub struct Dead;
 
 /// Bundle for edge node entity
 #[derive(Bundle)]
 pub struct EdgeNodeBundle {
     pub battery: Battery,
+    pub battery_health: BatteryHealth,
+    pub energy_ledger: EnergyLedger,
     pub gene: Gene,
     pub hardware: HardwareSpec,
+    pub lineage: Lineage,
+    pub model_cache: ModelCache,
+    pub panel: SolarPanel,
+    pub panel_soiling: PanelSoiling,
+    pub policy_state: PolicyState,
+    pub radio: Radio,
+    pub reputation: Reputation,
+    pub request_limiter: RequestLimiter,
+    pub sensing: Sensing,
+    pub sensor: Sensor,
+    pub solar_exposure: SolarExposure,
+    pub storage: StorageBuffer,
     pub survival_score: SurvivalScore,
     pub status: Status,
+    pub telemetry: Telemetry,
+    pub temperature: Temperature,
+    pub tuned_policy: TunedPolicy,
     pub transform: Transform,
 }
 
+impl EdgeNodeBundle {
+    /// Fresh node with the given genome and hardware, battery at 80%
+    pub fn new(gene: Gene, hardware: HardwareSpec, lineage: Lineage, transform: Transform) -> Self {
+        let initial_wh = hardware.usable_capacity_wh() * 0.8;
+        let hardware_type = hardware.hardware_type;
+        Self {
+            battery: Battery::new(initial_wh),
+            battery_health: BatteryHealth::new(&hardware, 0.8),
+            energy_ledger: EnergyLedger {
+                initial_wh: WattHours::from_wh(initial_wh),
+                ..default()
+            },
+            gene,
+            hardware,
+            lineage,
+            model_cache: ModelCache::default(),
+            panel: SolarPanel::default(),
+            panel_soiling: PanelSoiling::default(),
+            policy_state: PolicyState::default(),
+            radio: Radio::new(RadioType::for_hardware(hardware_type), gene.tx_power_dbm),
+            reputation: Reputation::default(),
+            request_limiter: RequestLimiter::full(gene.policy_params.request_rate_limit),
+            sensing: Sensing::default(),
+            sensor: Sensor::for_node(gene.model_type, hardware_type),
+            solar_exposure: SolarExposure::default(),
+            storage: StorageBuffer::for_hardware(hardware_type),
+            survival_score: SurvivalScore(0.0),
+            status: Status::Alive,
+            telemetry: Telemetry::default(),
+            temperature: Temperature::default(),
+            tuned_policy: TunedPolicy(gene.policy_params),
+            transform,
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct EpochCount(pub u32);
 
 /// Global simulation metrics
 #[derive(Resource)]
 pub struct SimulationMetrics {
-    pub total_energy_consumed: f32,
-    pub total_energy_harvested: f32,
+    pub total_energy_consumed: WattHours,
+    pub total_energy_harvested: WattHours,
     pub total_inferences: u64,
+    pub model_loads: u64,
+    pub model_load_energy_wh: WattHours,
+    pub sensor_wakeups: u64,
+    pub sensor_energy_wh: WattHours,
 
     #[allow(dead_code)]
     pub avg_node_lifetime: f32,
@@ -66,9 +132,13 @@ pub struct SimulationMetrics {
 impl Default for SimulationMetrics {
     fn default() -> Self {
         Self {
-            total_energy_consumed: 0.0,
-            total_energy_harvested: 0.0,
+            total_energy_consumed: WattHours::ZERO,
+            total_energy_harvested: WattHours::ZERO,
             total_inferences: 0,
+            model_loads: 0,
+            model_load_energy_wh: WattHours::ZERO,
+            sensor_wakeups: 0,
+            sensor_energy_wh: WattHours::ZERO,
             avg_node_lifetime: 0.0,
             current_hour: 6.0,
             generation: 0,
@@ -83,3 +153,30 @@ pub struct PowerOverrides(pub Option<HashMap<String, crate::data_loader::PowerPr
 /// Solar profile data
 #[derive(Resource)]
 pub struct LoadedSolarProfiles(pub Vec<crate::data_loader::SolarProfile>);
+
+/// Ambient temperature profile data
+#[derive(Resource)]
+pub struct LoadedAmbientProfiles(pub Vec<crate::data_loader::AmbientProfile>);
+
+/// Environmental randomness stream: weather, workload and inference draws.
+/// Independent of `GeneticRng` so variance decomposition experiments can hold
+/// the environment fixed while varying evolution (or vice versa)
+#[derive(Resource)]
+pub struct EnvRng(pub StdRng);
+
+impl EnvRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+/// Genetic randomness stream: founding genomes, hardware lottery,
+/// parent selection and mutation
+#[derive(Resource)]
+pub struct GeneticRng(pub StdRng);
+
+impl GeneticRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
@@ -0,0 +1,68 @@
+/// Compression-vs-transmission tradeoff
+/// A gene decides whether result reports are compressed before they are
+/// queued. Compression costs CPU energy once per report - a fixed codec
+/// start-up plus a per-byte cost, both platform-specific - and shrinks the
+/// payload by `COMPRESSION_RATIO`, which saves TX energy (and LoRa duty-cycle
+/// silence) on every attempt. The breakeven payload is
+/// `setup / (saved TX energy per byte - CPU energy per byte)`: a fraction of a
+/// byte on LoRa, kilobytes on WiFi, where per-packet overhead dominates.
+use bevy::prelude::*;
+
+use crate::components::Gene;
+use crate::hardware::HardwareType;
+use crate::radio::RadioType;
+use crate::units::WattHours;
+
+/// Report header: node id, timestamp, sequence number (bytes)
+pub const REPORT_HEADER_BYTES: f32 = 16.0;
+/// Compressed / raw size for structured inference results (LZ-family codec)
+pub const COMPRESSION_RATIO: f32 = 0.45;
+
+/// Application payload of one report as queued by this genome (bytes)
+pub fn payload_bytes(gene: &Gene) -> f32 {
+    let raw = raw_payload_bytes(gene);
+    if gene.compress_reports {
+        raw * COMPRESSION_RATIO
+    } else {
+        raw
+    }
+}
+
+/// Uncompressed report payload (bytes)
+pub fn raw_payload_bytes(gene: &Gene) -> f32 {
+    REPORT_HEADER_BYTES + gene.model_type.result_bytes()
+}
+
+/// CPU energy to compress `raw_bytes` on a platform
+pub fn compression_energy(hardware_type: HardwareType, raw_bytes: f32) -> WattHours {
+    let (setup_j, j_per_byte) = match hardware_type {
+        HardwareType::ESP32 => (20e-6, 0.3e-6), // Bare-metal codec, 240 MHz core
+        HardwareType::RaspberryPi4 => (2e-3, 0.1e-6), // Process wake-up + cache warm-up
+        HardwareType::JetsonNano => (3e-3, 0.1e-6),
+    };
+    WattHours::from_joules(setup_j + raw_bytes * j_per_byte)
+}
+
+/// Payload size above which compression saves energy (bytes), if any
+pub fn breakeven_payload_bytes(
+    hardware_type: HardwareType,
+    radio_type: RadioType,
+    tx_power_dbm: f32,
+) -> Option<f32> {
+    let tx_per_byte = radio_type
+        .tx_power(tx_power_dbm)
+        .over_s(8.0 / radio_type.data_rate_bps());
+    let setup = compression_energy(hardware_type, 0.0);
+    let cpu_per_byte = compression_energy(hardware_type, 1.0) - setup;
+    let net_per_byte = tx_per_byte * (1.0 - COMPRESSION_RATIO) - cpu_per_byte;
+    (net_per_byte > WattHours::ZERO).then(|| setup / net_per_byte)
+}
+
+/// Per-epoch compression statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct CompressionMetrics {
+    pub compressed_reports: u64,
+    pub bytes_saved: f32,
+    /// CPU energy spent compressing
+    pub cpu_energy_wh: WattHours,
+}
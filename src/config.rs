@@ -0,0 +1,659 @@
+/// Runtime configuration for experiments
+/// Parsed from command-line flags so presets can change without recompilation
+use bevy::prelude::*;
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+
+use crate::calendar::Calendar;
+use crate::catastrophe::ScriptedCatastrophe;
+use crate::clock::ClockSource;
+use crate::compatibility::Compatibility;
+use crate::data_loader::{
+    SolarData, SolarProfile, load_hardware_profiles, load_solar_data, load_solar_profiles,
+};
+use crate::engine::{Breeding, DEFAULT_TIME_SCALE};
+use crate::fitness::{FitnessMode, FitnessNormalization};
+use crate::hardware::{ChargeController, HardwareCatalog, HardwareType, SolarPanel};
+use crate::ota::OtaCampaign;
+use crate::solar::{DEFAULT_DAY_OF_YEAR, DEFAULT_SOLAR_CSV, Location, SolarSeasons};
+use crate::topology::TopologyKind;
+use crate::weather::WeatherModel;
+
+/// Global simulation configuration
+#[derive(Resource, Debug, Clone)]
+pub struct SimConfig {
+    /// Single-node debug mode: spawn exactly one node, draw large gauges
+    /// and log every policy decision (flag: `--debug-node`)
+    pub debug_single_node: bool,
+
+    /// Master seed both RNG streams derive from (flag: `--seed <u64>`)
+    /// Drawn from entropy and printed at startup when not given
+    pub seed: u64,
+
+    /// Override for the environment stream (flag: `--env-seed <u64>`)
+    pub env_seed: Option<u64>,
+
+    /// Override for the genetics stream (flag: `--genetic-seed <u64>`)
+    pub genetic_seed: Option<u64>,
+
+    /// Per-epoch metrics CSV path (flag: `--metrics-out <path>`)
+    pub metrics_out: Option<String>,
+
+    /// Fleet metrics CSV, one row per simulated hour
+    /// (flag: `--metrics-hourly-out <path>`)
+    pub metrics_hourly_out: Option<String>,
+
+    /// Fleet metrics CSV, one row per tick; heavy, meant for debugging
+    /// single runs (flag: `--metrics-tick-out <path>`)
+    pub metrics_tick_out: Option<String>,
+
+    /// Directory for per-epoch survivor snapshots (flag: `--snapshot-dir <dir>`)
+    pub snapshot_dir: Option<String>,
+
+    /// Directory for the radio trace replayable in ns-3 / OMNeT++
+    /// (flag: `--net-trace <dir>`)
+    pub net_trace: Option<String>,
+
+    /// Survivor phylogeny SVG, rewritten every epoch (flag: `--phylogeny-out <path>`)
+    pub phylogeny_out: Option<String>,
+
+    /// JSON-lines file of node labels, loaded at startup and rewritten on
+    /// every change (flag: `--annotations <path>`)
+    pub annotations: Option<String>,
+
+    /// Horizontal gene transfer: exchanges per node per simulated hour
+    /// (flag: `--gossip-rate <f32>`, 0 = vertical inheritance only)
+    pub gossip_rate: f32,
+
+    /// Ground-truth objects appearing per node per simulated hour
+    /// (flag: `--event-rate <f32>`)
+    pub event_rate: f32,
+
+    /// Share of ground-truth objects arriving in short bursts instead of
+    /// evenly; the mean rate is unchanged (flag: `--event-burstiness <0..1>`)
+    pub event_burstiness: f32,
+
+    /// Fitness credit per detected event, in survival-seconds
+    /// (flag: `--detection-credit <f32>`, 0 = pure survival fitness)
+    pub detection_credit: f32,
+
+    /// Fitness penalty per event missed while asleep or dead, in
+    /// survival-seconds (flag: `--miss-penalty <f32>`)
+    pub miss_penalty: f32,
+
+    /// Fitness penalty per false alarm, in survival-seconds
+    /// (flag: `--false-alarm-penalty <f32>`)
+    pub false_alarm_penalty: f32,
+
+    /// Scripted over-the-air model updates
+    /// (flag: `--ota <epoch>:<model>[:<fraction>]`, repeatable)
+    pub ota_campaigns: Vec<OtaCampaign>,
+
+    /// Denial-of-sleep attackers flooding nearby nodes (flag: `--attackers <n>`)
+    pub attackers: usize,
+
+    /// Probability a newborn node is Byzantine (flag: `--byzantine <fraction>`)
+    pub byzantine_fraction: f32,
+
+    /// Random catastrophes started per simulated day
+    /// (flag: `--catastrophe-rate <f32>`)
+    pub catastrophe_rate: f32,
+
+    /// Scripted catastrophes, sorted by start time
+    /// (flag: `--catastrophe <kind>:<start_h>:<duration_h>`, repeatable)
+    pub catastrophes: Vec<ScriptedCatastrophe>,
+
+    /// Co-evolve this many environment genomes (catastrophe rate, event
+    /// rate, burstiness) against the population (flag: `--coevolve <n>`,
+    /// 0 = fixed environment)
+    pub coevolve: usize,
+
+    /// Ramp from generous solar, long days, no random catastrophes and a
+    /// light workload to the configured scenario over this many epochs
+    /// (flag: `--curriculum <epochs>`, 0 = configured scenario throughout)
+    pub curriculum_epochs: u32,
+
+    /// JSON-lines extinction reports (flag: `--extinction-log <path>`)
+    pub extinction_log: Option<String>,
+
+    /// Ease the environment after each extinction, tightening it again
+    /// after extinction-free epochs (flag: `--auto-ease`)
+    pub auto_ease: bool,
+
+    /// Plain-text event log path (flag: `--event-log <path>`)
+    pub event_log: Option<String>,
+
+    /// Divides every hardware MTBF, as in accelerated life testing; 0
+    /// disables hardware faults (flag: `--failure-acceleration <f32>`)
+    pub failure_acceleration: f32,
+
+    /// Scales battery cycle aging; 0 keeps batteries new
+    /// (flag: `--battery-aging <f32>`)
+    pub battery_aging: f32,
+
+    /// Fitness preset used for selection
+    /// (flag: `--fitness <survival|inferences|accuracy|energy|cost>`)
+    pub fitness: FitnessMode,
+
+    /// Hardware normalization of fitness
+    /// (flag: `--normalize-fitness <none|capacity|class>`)
+    pub fitness_normalization: FitnessNormalization,
+
+    /// Fair-comparison mode: every node gets this hardware instead of a
+    /// random platform (flag: `--hardware <esp32|rpi4|jetson>`)
+    pub locked_hardware: Option<HardwareType>,
+
+    /// Hardware catalog CSV the lottery draws platforms from instead of the
+    /// built-in three (flag: `--hardware-csv <path>`)
+    pub hardware_csv: Option<String>,
+
+    /// Total hardware spend allowed for each generation's fleet; unfilled
+    /// grid cells stay empty (flag: `--budget <usd>`)
+    pub budget_usd: Option<f32>,
+
+    /// Carrying-capacity mode: offspring are funded from the survivors'
+    /// remaining battery energy, each birth costing this fraction of the
+    /// newborn's battery capacity, so the population grows and shrinks with
+    /// the energy surplus; the grid is the carrying capacity
+    /// (flag: `--birth-cost <fraction>`)
+    pub birth_cost: Option<f32>,
+
+    /// TOML scenario file with the deployment layout (flag: `--scenario <path>`)
+    pub scenario: Option<String>,
+
+    /// Site whose solar profile is modeled instead of read from a CSV
+    /// (flag: `--location <lat,lon>`)
+    pub location: Option<Location>,
+
+    /// Day of the year (1-365) the run's calendar starts on; seasonal solar
+    /// sources begin with this day's profile (flag: `--day-of-year <n>`)
+    pub day_of_year: u16,
+
+    /// Measured hourly solar profile (native CSV, PVGIS or TMY export),
+    /// overriding the model (flag: `--solar-csv <path>`)
+    pub solar_csv: Option<String>,
+
+    /// Source of the hourly cloud cover
+    /// (flag: `--weather <clear|markov|trace:<path>>`)
+    pub weather: WeatherModel,
+
+    /// Panel fitted to every node instead of the reference ~20 W panel
+    /// (flag: `--panel <rated_w>[,area_m2[,efficiency[,orientation]]]`)
+    pub panel: Option<SolarPanel>,
+
+    /// Charge controller between every node's panel and battery (flag:
+    /// `--charge-controller <ideal|pwm|mppt>`, default MPPT)
+    pub charge_controller: Option<ChargeController>,
+
+    /// Simulated hours run before the first epoch starts, with no fitness
+    /// or metrics accumulating (flag: `--warm-up <hours>`)
+    pub warm_up_h: f32,
+
+    /// JSON-lines genome file the first generation is seeded from
+    /// (flag: `--seed-population <path>`)
+    pub seed_population: Option<String>,
+
+    /// JSON-lines file the best genomes of every epoch are appended to
+    /// (flag: `--elite-out <path>`)
+    pub elite_out: Option<String>,
+
+    /// Genomes saved per epoch with `--elite-out` (flag: `--elite-top <n>`)
+    pub elite_top: usize,
+
+    /// Gzip CSV trace of policy decisions (flag: `--decision-log <path>`)
+    pub decision_log: Option<String>,
+
+    /// Trace every decision instead of hourly node aggregates
+    /// (flag: `--decision-log-raw`)
+    pub decision_log_raw: bool,
+
+    /// Per-policy scores CSV, one row per policy per epoch
+    /// (flag: `--policy-stats-out <path>`)
+    pub policy_stats_out: Option<String>,
+
+    /// Per-model response latency CSV, one row per model per epoch
+    /// (flag: `--latency-out <path>`)
+    pub latency_out: Option<String>,
+
+    /// End-of-run battery/panel sizing report as CSV
+    /// (flag: `--sizing-out <path>`)
+    pub sizing_out: Option<String>,
+
+    /// Self-contained HTML run report, rewritten every epoch
+    /// (flag: `--report <path>`)
+    pub report: Option<String>,
+
+    /// Serve a live web dashboard on this port (flag: `--dashboard-port <port>`)
+    pub dashboard_port: Option<u16>,
+
+    /// Interface the dashboard listens on; loopback only unless another
+    /// address (e.g. `0.0.0.0`) is opted into (flag: `--dashboard-bind <addr>`)
+    pub dashboard_bind: IpAddr,
+
+    /// Serve the gRPC control API on this port; the run starts paused
+    /// (flag: `--grpc-port <port>`)
+    pub grpc_port: Option<u16>,
+
+    /// Fixed simulation ticks per rendered frame (at 60 fps): accelerates
+    /// visual runs without coarser physics steps; the starting `SimSpeed`,
+    /// changed at runtime with `+` / `-` (flag: `--ticks-per-frame <n>`)
+    pub ticks_per_frame: u32,
+
+    /// Simulated seconds per real second of fixed ticks, carried by
+    /// `SimSpeed` (flag: `--time-scale <x>`)
+    pub time_scale: f32,
+
+    /// Exit after this many genetic epochs (flag: `--max-epochs <n>`)
+    pub max_epochs: Option<u32>,
+
+    /// Most verbose log level shown (flag: `--log-level <error|warn|info|debug|trace>`)
+    pub log_level: bevy::log::Level,
+
+    /// Extra per-target log directives, e.g. `edgegenesis::radio=warn`
+    /// (flag: `--log-filter <directives>`)
+    pub log_filter: Option<String>,
+
+    /// One JSON object per log event instead of text lines (flag: `--log-json`)
+    pub log_json: bool,
+
+    /// Offspring inherit lifetime-tuned policy parameters instead of the
+    /// parent's genome values (flag: `--lamarckian`)
+    pub lamarckian: bool,
+
+    /// What happens to genes whose model their board cannot hold or run in
+    /// time (flag: `--compatibility <off|forbid|crash>`)
+    pub compatibility: Compatibility,
+
+    /// Grid boundary condition for spatial effects (flag: `--torus`)
+    pub topology: TopologyKind,
+
+    /// Start with the camera following the fittest alive node; `C` toggles
+    /// it at runtime (flag: `--spectator`)
+    pub spectator: bool,
+
+    /// Crossfade generation transitions instead of swapping the field at
+    /// once (disable with `--no-crossfade`)
+    pub crossfade: bool,
+
+    /// Draw parent → child arrows during generation transitions; `L`
+    /// toggles them at runtime (flag: `--lineage-arrows`)
+    pub lineage_arrows: bool,
+
+    /// Slave simulated time to an external clock: the system clock or a
+    /// timestamp stream (flag: `--clock <wall|ntp|-|path>`)
+    pub clock: Option<ClockSource>,
+
+    /// Unix time the wall clock counts simulated time from; launch time
+    /// when unset (flag: `--clock-origin <unix seconds>`)
+    pub clock_origin: Option<f64>,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            debug_single_node: false,
+            seed: rand::random(),
+            env_seed: None,
+            genetic_seed: None,
+            metrics_out: None,
+            metrics_hourly_out: None,
+            metrics_tick_out: None,
+            snapshot_dir: None,
+            net_trace: None,
+            phylogeny_out: None,
+            annotations: None,
+            gossip_rate: 0.0,
+            lamarckian: false,
+            event_rate: 2.0,
+            event_burstiness: 0.0,
+            detection_credit: 5.0,
+            miss_penalty: 2.5,
+            false_alarm_penalty: 1.0,
+            ota_campaigns: Vec::new(),
+            attackers: 0,
+            byzantine_fraction: 0.0,
+            catastrophe_rate: 0.0,
+            catastrophes: Vec::new(),
+            coevolve: 0,
+            curriculum_epochs: 0,
+            extinction_log: None,
+            auto_ease: false,
+            event_log: None,
+            failure_acceleration: 1.0,
+            battery_aging: 1.0,
+            fitness: FitnessMode::default(),
+            fitness_normalization: FitnessNormalization::default(),
+            locked_hardware: None,
+            hardware_csv: None,
+            budget_usd: None,
+            birth_cost: None,
+            scenario: None,
+            location: None,
+            day_of_year: DEFAULT_DAY_OF_YEAR,
+            solar_csv: None,
+            weather: WeatherModel::default(),
+            panel: None,
+            charge_controller: None,
+            warm_up_h: 0.0,
+            seed_population: None,
+            elite_out: None,
+            elite_top: 5,
+            decision_log: None,
+            decision_log_raw: false,
+            policy_stats_out: None,
+            latency_out: None,
+            sizing_out: None,
+            report: None,
+            dashboard_port: None,
+            dashboard_bind: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            grpc_port: None,
+            ticks_per_frame: 1,
+            time_scale: DEFAULT_TIME_SCALE,
+            max_epochs: None,
+            log_level: bevy::log::Level::INFO,
+            log_filter: None,
+            log_json: false,
+            topology: TopologyKind::Bounded,
+            spectator: false,
+            compatibility: Compatibility::default(),
+            crossfade: true,
+            lineage_arrows: false,
+            clock: None,
+            clock_origin: None,
+        }
+    }
+}
+
+impl SimConfig {
+    /// Build configuration from process arguments
+    pub fn from_args() -> Self {
+        let mut config = Self::default();
+        let mut args = std::env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--debug-node" => config.debug_single_node = true,
+                "--torus" => config.topology = TopologyKind::Torus,
+                "--lamarckian" => config.lamarckian = true,
+                "--spectator" => config.spectator = true,
+                "--no-crossfade" => config.crossfade = false,
+                "--compatibility" => {
+                    if let Some(compatibility) = parse_value(&arg, args.next()) {
+                        config.compatibility = compatibility;
+                    }
+                }
+                "--lineage-arrows" => config.lineage_arrows = true,
+                "--clock" => config.clock = parse_value(&arg, args.next()),
+                "--clock-origin" => config.clock_origin = parse_value(&arg, args.next()),
+                "--seed" => {
+                    if let Some(seed) = parse_value(&arg, args.next()) {
+                        config.seed = seed;
+                    }
+                }
+                "--env-seed" => config.env_seed = parse_value(&arg, args.next()),
+                "--genetic-seed" => config.genetic_seed = parse_value(&arg, args.next()),
+                "--metrics-out" => config.metrics_out = parse_value(&arg, args.next()),
+                "--metrics-hourly-out" => {
+                    config.metrics_hourly_out = parse_value(&arg, args.next())
+                }
+                "--metrics-tick-out" => config.metrics_tick_out = parse_value(&arg, args.next()),
+                "--snapshot-dir" => config.snapshot_dir = parse_value(&arg, args.next()),
+                "--net-trace" => config.net_trace = parse_value(&arg, args.next()),
+                "--phylogeny-out" => config.phylogeny_out = parse_value(&arg, args.next()),
+                "--annotations" => config.annotations = parse_value(&arg, args.next()),
+                "--gossip-rate" => {
+                    if let Some(rate) = parse_value(&arg, args.next()) {
+                        config.gossip_rate = rate;
+                    }
+                }
+                "--event-rate" => {
+                    if let Some(rate) = parse_value(&arg, args.next()) {
+                        config.event_rate = rate;
+                    }
+                }
+                "--event-burstiness" => {
+                    if let Some(burstiness) = parse_value(&arg, args.next()) {
+                        config.event_burstiness = burstiness;
+                    }
+                }
+                "--detection-credit" => {
+                    if let Some(credit) = parse_value(&arg, args.next()) {
+                        config.detection_credit = credit;
+                    }
+                }
+                "--miss-penalty" => {
+                    if let Some(penalty) = parse_value(&arg, args.next()) {
+                        config.miss_penalty = penalty;
+                    }
+                }
+                "--false-alarm-penalty" => {
+                    if let Some(penalty) = parse_value(&arg, args.next()) {
+                        config.false_alarm_penalty = penalty;
+                    }
+                }
+                "--ota" => {
+                    if let Some(campaign) = parse_value(&arg, args.next()) {
+                        config.ota_campaigns.push(campaign);
+                    }
+                }
+                "--attackers" => {
+                    if let Some(count) = parse_value(&arg, args.next()) {
+                        config.attackers = count;
+                    }
+                }
+                "--byzantine" => {
+                    if let Some(fraction) = parse_value(&arg, args.next()) {
+                        config.byzantine_fraction = fraction;
+                    }
+                }
+                "--catastrophe-rate" => {
+                    if let Some(rate) = parse_value(&arg, args.next()) {
+                        config.catastrophe_rate = rate;
+                    }
+                }
+                "--catastrophe" => {
+                    if let Some(catastrophe) = parse_value(&arg, args.next()) {
+                        config.catastrophes.push(catastrophe);
+                    }
+                }
+                "--coevolve" => {
+                    if let Some(count) = parse_value(&arg, args.next()) {
+                        config.coevolve = count;
+                    }
+                }
+                "--curriculum" => {
+                    if let Some(epochs) = parse_value(&arg, args.next()) {
+                        config.curriculum_epochs = epochs;
+                    }
+                }
+                "--extinction-log" => config.extinction_log = parse_value(&arg, args.next()),
+                "--auto-ease" => config.auto_ease = true,
+                "--event-log" => config.event_log = parse_value(&arg, args.next()),
+                "--failure-acceleration" => {
+                    if let Some(factor) = parse_value(&arg, args.next()) {
+                        config.failure_acceleration = factor;
+                    }
+                }
+                "--battery-aging" => {
+                    if let Some(factor) = parse_value(&arg, args.next()) {
+                        config.battery_aging = factor;
+                    }
+                }
+                "--fitness" => {
+                    if let Some(mode) = parse_value(&arg, args.next()) {
+                        config.fitness = mode;
+                    }
+                }
+                "--hardware" => config.locked_hardware = parse_value(&arg, args.next()),
+                "--hardware-csv" => config.hardware_csv = parse_value(&arg, args.next()),
+                "--normalize-fitness" => {
+                    if let Some(normalization) = parse_value(&arg, args.next()) {
+                        config.fitness_normalization = normalization;
+                    }
+                }
+                "--budget" => config.budget_usd = parse_value(&arg, args.next()),
+                "--birth-cost" => config.birth_cost = parse_value(&arg, args.next()),
+                "--scenario" => config.scenario = parse_value(&arg, args.next()),
+                "--location" => config.location = parse_value(&arg, args.next()),
+                "--day-of-year" => {
+                    if let Some(day) = parse_value::<u16>(&arg, args.next()) {
+                        config.day_of_year = day.clamp(1, 365);
+                    }
+                }
+                "--solar-csv" => config.solar_csv = parse_value(&arg, args.next()),
+                "--weather" => {
+                    if let Some(weather) = parse_value(&arg, args.next()) {
+                        config.weather = weather;
+                    }
+                }
+                "--panel" => config.panel = parse_value(&arg, args.next()),
+                "--charge-controller" => config.charge_controller = parse_value(&arg, args.next()),
+                "--warm-up" => {
+                    if let Some(hours) = parse_value::<f32>(&arg, args.next()) {
+                        config.warm_up_h = hours.max(0.0);
+                    }
+                }
+                "--seed-population" => config.seed_population = parse_value(&arg, args.next()),
+                "--elite-out" => config.elite_out = parse_value(&arg, args.next()),
+                "--elite-top" => {
+                    if let Some(top) = parse_value(&arg, args.next()) {
+                        config.elite_top = top;
+                    }
+                }
+                "--decision-log" => config.decision_log = parse_value(&arg, args.next()),
+                "--decision-log-raw" => config.decision_log_raw = true,
+                "--policy-stats-out" => config.policy_stats_out = parse_value(&arg, args.next()),
+                "--latency-out" => config.latency_out = parse_value(&arg, args.next()),
+                "--sizing-out" => config.sizing_out = parse_value(&arg, args.next()),
+                "--report" => config.report = parse_value(&arg, args.next()),
+                "--dashboard-port" => config.dashboard_port = parse_value(&arg, args.next()),
+                "--dashboard-bind" => {
+                    if let Some(address) = parse_value(&arg, args.next()) {
+                        config.dashboard_bind = address;
+                    }
+                }
+                "--grpc-port" => config.grpc_port = parse_value(&arg, args.next()),
+                "--ticks-per-frame" => {
+                    if let Some(ticks) = parse_value(&arg, args.next()) {
+                        config.ticks_per_frame = ticks;
+                    }
+                }
+                "--time-scale" => match parse_value::<f32>(&arg, args.next()) {
+                    Some(scale) if scale > 0.0 => config.time_scale = scale,
+                    Some(scale) => eprintln!("⚠️ --time-scale must be positive: {}", scale),
+                    None => {}
+                },
+                "--max-epochs" => config.max_epochs = parse_value(&arg, args.next()),
+                "--log-level" => {
+                    if let Some(level) = parse_value(&arg, args.next()) {
+                        config.log_level = level;
+                    }
+                }
+                "--log-filter" => config.log_filter = parse_value(&arg, args.next()),
+                "--log-json" => config.log_json = true,
+                other => eprintln!("⚠️ Unknown argument ignored: {}", other),
+            }
+        }
+
+        config
+            .catastrophes
+            .sort_by(|a, b| a.start_h.total_cmp(&b.start_h));
+        config
+    }
+
+    /// Seed for environmental stochasticity (weather, workload, inference draws)
+    pub fn env_seed(&self) -> u64 {
+        self.env_seed.unwrap_or(self.seed)
+    }
+
+    /// Panel fitted to every node when `--panel` or `--charge-controller`
+    /// replace the reference panel and its default controller
+    pub fn fitted_panel(&self) -> Option<SolarPanel> {
+        if self.panel.is_none() && self.charge_controller.is_none() {
+            return None;
+        }
+        let mut panel = self.panel.unwrap_or_default();
+        if let Some(controller) = self.charge_controller {
+            panel.controller = controller;
+        }
+        Some(panel)
+    }
+
+    /// Breeding rules of the run, platforms drawn from `catalog`; cost
+    /// efficiency makes hardware a heritable trait
+    pub fn breeding<'a>(&self, catalog: &'a HardwareCatalog) -> Breeding<'a> {
+        Breeding {
+            catalog,
+            locked_hardware: self.locked_hardware,
+            compatibility: self.compatibility,
+            heritable_hardware: self.fitness == FitnessMode::CostEfficiency,
+            budget_usd: self.budget_usd,
+            birth_cost: self.birth_cost,
+        }
+    }
+
+    /// Seed for genetic operators (founding genomes, selection, mutation)
+    /// Offset from the master seed so the two streams never coincide
+    pub fn genetic_seed(&self) -> u64 {
+        self.genetic_seed
+            .unwrap_or(self.seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Date of the run's first day (`--day-of-year`), seasons flipped for a
+    /// southern `--location`
+    pub fn calendar(&self) -> Calendar {
+        Calendar::new(
+            self.day_of_year,
+            self.location
+                .is_some_and(|location| location.latitude_deg < 0.0),
+        )
+    }
+
+    /// Solar source of the run: the `--solar-csv` file, else the modeled
+    /// `--location`, else the bundled default CSV
+    pub fn solar_seasons(&self) -> Result<SolarSeasons, Box<dyn Error>> {
+        match (&self.solar_csv, self.location) {
+            (Some(path), _) => match load_solar_data(path)? {
+                SolarData::Profiles(rows) => SolarSeasons::from_rows(rows),
+                SolarData::Year(days) => Ok(SolarSeasons::Daily(days)),
+            },
+            (None, Some(location)) => Ok(SolarSeasons::Modeled(location)),
+            (None, None) => SolarSeasons::from_rows(load_solar_profiles(DEFAULT_SOLAR_CSV)?),
+        }
+    }
+
+    /// Hourly solar profile of the run's first day
+    pub fn solar_profiles(&self) -> Result<Vec<SolarProfile>, Box<dyn Error>> {
+        Ok(self.solar_seasons()?.profiles_on(self.day_of_year))
+    }
+
+    /// Catalog of the run: the `--hardware-csv` rows, else empty
+    pub fn hardware_catalog(&self) -> Result<HardwareCatalog, Box<dyn Error>> {
+        match &self.hardware_csv {
+            Some(path) => Ok(HardwareCatalog::from_profiles(&load_hardware_profiles(
+                path,
+            )?)?),
+            None => Ok(HardwareCatalog::default()),
+        }
+    }
+}
+
+/// Parse the value following a flag, warning when it is missing or malformed
+fn parse_value<T: FromStr>(flag: &str, value: Option<String>) -> Option<T> {
+    let parsed = value.as_deref().and_then(|v| v.parse().ok());
+    if parsed.is_none() {
+        eprintln!("⚠️ Invalid or missing value for {}: {:?}", flag, value);
+    }
+    parsed
+}
+
+/// Run condition: true when the run has an epoch limit
+pub fn max_epochs_set(config: Res<SimConfig>) -> bool {
+    config.max_epochs.is_some()
+}
+
+/// Run condition: true when single-node debug mode is active
+pub fn debug_node_enabled(config: Res<SimConfig>) -> bool {
+    config.debug_single_node
+}
@@ -0,0 +1,168 @@
+/// Crossfaded generation transitions (flags: `--no-crossfade`,
+/// `--lineage-arrows`)
+/// At an epoch the offspring take over the previous generation's entities
+/// in place, so on screen the field pops from one generation to the next.
+/// Instead, just before selection every ring of the old generation (corpses
+/// included) is copied into a ghost sprite that fades out over
+/// `CROSSFADE_SECONDS` of real time while the offspring fade in at their
+/// positions. With lineage arrows on (`L` toggles them) each child gets an
+/// arrow from its parent's last position, fading with the transition. This
+/// is rendering only: the simulation does not wait for the fade.
+use bevy::prelude::*;
+use bevy_egui::input::EguiWantsInput;
+use std::collections::HashMap;
+
+use crate::components::{Gene, Lineage};
+use crate::config::SimConfig;
+use crate::sprites::render_nodes_system;
+use crate::systems::EpochSet;
+
+/// Length of a generation transition (real seconds)
+pub const CROSSFADE_SECONDS: f32 = 1.5;
+
+/// Lineage arrow color (faded with the transition)
+const ARROW_COLOR: Color = Color::srgb(0.9, 0.9, 1.0);
+
+/// Transition in progress, if any
+#[derive(Resource, Debug, Default)]
+pub struct Crossfade {
+    pub enabled: bool,
+    /// Draw parent → child arrows during transitions
+    pub arrows: bool,
+    /// Real seconds left in the current transition (0 = none)
+    pub remaining_s: f32,
+    /// Position of every node of the previous generation, by lineage id
+    parents: HashMap<u64, Vec2>,
+}
+
+impl Crossfade {
+    pub fn new(config: &SimConfig) -> Self {
+        Self {
+            enabled: config.crossfade,
+            arrows: config.lineage_arrows,
+            ..Default::default()
+        }
+    }
+
+    /// Opacity of the new generation: 0 as the transition starts, 1 outside
+    /// transitions
+    pub fn fade_in(&self) -> f32 {
+        1.0 - self.fade_out()
+    }
+
+    /// Opacity of the old generation's ghosts
+    pub fn fade_out(&self) -> f32 {
+        (self.remaining_s / CROSSFADE_SECONDS).clamp(0.0, 1.0)
+    }
+}
+
+/// A copy of an old-generation ring, with its opacity when copied
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Ghost {
+    pub alpha: f32,
+}
+
+/// Run condition: true when generation transitions are crossfaded
+pub fn crossfade_enabled(crossfade: Res<Crossfade>) -> bool {
+    crossfade.enabled
+}
+
+/// Copy the outgoing generation into ghosts before it is overwritten
+pub fn snapshot_generation_system(
+    mut commands: Commands,
+    mut crossfade: ResMut<Crossfade>,
+    nodes: Query<(&Sprite, &Transform, &Lineage), With<Gene>>,
+    ghosts: Query<Entity, With<Ghost>>,
+) {
+    // Epochs closer than a transition cut the previous one short
+    for entity in ghosts.iter() {
+        commands.entity(entity).despawn();
+    }
+    crossfade.parents.clear();
+    for (sprite, transform, lineage) in nodes.iter() {
+        commands.spawn((
+            sprite.clone(),
+            // Over the incoming node, so the old ring dissolves into the new
+            transform.with_translation(transform.translation + Vec3::Z * 0.5),
+            Ghost {
+                alpha: sprite.color.alpha(),
+            },
+        ));
+        crossfade
+            .parents
+            .insert(lineage.id, transform.translation.truncate());
+    }
+    crossfade.remaining_s = CROSSFADE_SECONDS;
+}
+
+/// `L` toggles the lineage arrows
+pub fn lineage_arrows_toggle_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    mut crossfade: ResMut<Crossfade>,
+) {
+    if egui_input.wants_any_keyboard_input() || !keys.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+    crossfade.arrows = !crossfade.arrows;
+    info!(target: "edgegenesis::epoch",
+        "🧬 Lineage arrows {}", if crossfade.arrows { "on" } else { "off" }
+    );
+}
+
+/// Fade the ghosts out and draw the lineage arrows; despawn the ghosts
+/// when the transition ends
+pub fn crossfade_system(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut crossfade: ResMut<Crossfade>,
+    mut gizmos: Gizmos,
+    mut ghosts: Query<(Entity, &mut Sprite, &Ghost)>,
+    nodes: Query<(&Transform, &Lineage), With<Gene>>,
+) {
+    if crossfade.remaining_s <= 0.0 {
+        return;
+    }
+    crossfade.remaining_s = (crossfade.remaining_s - time.delta_secs()).max(0.0);
+    let fade_out = crossfade.fade_out();
+
+    for (entity, mut sprite, ghost) in ghosts.iter_mut() {
+        if fade_out <= 0.0 {
+            commands.entity(entity).despawn();
+        } else {
+            sprite.color.set_alpha(ghost.alpha * fade_out);
+        }
+    }
+
+    if crossfade.arrows {
+        let color = ARROW_COLOR.with_alpha(fade_out);
+        for (transform, lineage) in nodes.iter() {
+            let child = transform.translation.truncate();
+            if let Some(&parent) = lineage.parent.and_then(|id| crossfade.parents.get(&id))
+                && parent != child
+            {
+                gizmos.arrow_2d(parent, child, color);
+            }
+        }
+    }
+
+    if fade_out <= 0.0 {
+        crossfade.parents.clear();
+    }
+}
+
+/// Register the crossfade systems
+pub fn register_crossfade(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        snapshot_generation_system
+            .in_set(EpochSet::BeforeSelection)
+            .run_if(crossfade_enabled),
+    )
+    .add_systems(
+        Update,
+        (lineage_arrows_toggle_system, crossfade_system)
+            .chain()
+            .before(render_nodes_system),
+    );
+}
@@ -0,0 +1,89 @@
+/// Curriculum schedule: progressively harsher conditions
+/// Populations dropped straight into a harsh scenario often go extinct
+/// before selection has anything to work with. With a curriculum
+/// (flag: `--curriculum <epochs>`) the first generation sees generous
+/// solar, long days, no random catastrophes and a light workload; over the
+/// given number of epochs every knob ramps linearly to the configured
+/// scenario, which then holds for the rest of the run.
+use bevy::prelude::*;
+
+use crate::config::SimConfig;
+use crate::milestones::EpochCompleted;
+
+/// Panel output multiplier at the start of the curriculum
+pub const START_SOLAR_SCALE: f32 = 1.5;
+
+/// Day-length multiplier at the start (daylight stretched around noon)
+pub const START_DAY_SCALE: f32 = 1.3;
+
+/// Ground-truth event rate multiplier at the start
+pub const START_WORKLOAD_SCALE: f32 = 0.25;
+
+/// Multipliers applied on top of the configured environment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stage {
+    /// Curriculum progress, 0 (easiest) to 1 (configured scenario)
+    pub progress: f32,
+    pub solar_scale: f32,
+    /// > 1 lengthens days (shortens nights)
+    pub day_scale: f32,
+    pub workload_scale: f32,
+    pub catastrophe_scale: f32,
+}
+
+impl Stage {
+    /// The configured scenario, unchanged
+    pub const FULL: Stage = Stage {
+        progress: 1.0,
+        solar_scale: 1.0,
+        day_scale: 1.0,
+        workload_scale: 1.0,
+        catastrophe_scale: 1.0,
+    };
+
+    /// Profile hour sampled for a clock hour: the day is stretched around
+    /// solar noon (12:00) by `day_scale`
+    pub fn solar_hour(&self, hour: usize) -> usize {
+        if self.day_scale == 1.0 {
+            return hour;
+        }
+        let offset = (hour as f32 + 0.5 - 12.0) / self.day_scale;
+        (12.0 + offset).floor().clamp(0.0, 23.0) as usize
+    }
+}
+
+/// Curriculum stage of an epoch (1-based)
+pub fn stage(config: &SimConfig, epoch: u32) -> Stage {
+    if config.curriculum_epochs == 0 {
+        return Stage::FULL;
+    }
+    let progress = (epoch.saturating_sub(1) as f32 / config.curriculum_epochs as f32).min(1.0);
+    let lerp = |start: f32| start + (1.0 - start) * progress;
+    Stage {
+        progress,
+        solar_scale: lerp(START_SOLAR_SCALE),
+        day_scale: lerp(START_DAY_SCALE),
+        workload_scale: lerp(START_WORKLOAD_SCALE),
+        catastrophe_scale: progress,
+    }
+}
+
+/// Run condition: true when a curriculum is configured
+pub fn curriculum_enabled(config: Res<SimConfig>) -> bool {
+    config.curriculum_epochs > 0
+}
+
+/// Announce the conditions of the coming epoch
+pub fn curriculum_log_system(config: Res<SimConfig>, mut completed: MessageReader<EpochCompleted>) {
+    for epoch in completed.read() {
+        let next = stage(&config, epoch.epoch + 1);
+        info!(target: "edgegenesis::curriculum",
+            "🎓 Curriculum {:.0}%: solar ×{:.2}, day ×{:.2}, workload ×{:.2}, catastrophes ×{:.2}",
+            next.progress * 100.0,
+            next.solar_scale,
+            next.day_scale,
+            next.workload_scale,
+            next.catastrophe_scale
+        );
+    }
+}
@@ -5,6 +5,9 @@ use serde::Deserialize;
 use std::error::Error;
 use std::fs::File;
 
+use crate::calendar::{DAYS_PER_YEAR, day_of, month_of};
+use crate::solar::PANEL_EFFICIENCY;
+
 /// Power profile loaded from CSV - optional override for models.rs defaults
 #[derive(Debug, Deserialize, Clone)]
 #[allow(dead_code)]
@@ -18,33 +21,71 @@ pub struct PowerProfileCSV {
     pub parameters_millions: f32,
 }
 
+/// One platform of a hardware catalog (see `HardwareCatalog`)
+#[derive(Debug, Deserialize, Clone)]
+pub struct HardwareProfileCSV {
+    pub name: String,
+    pub battery_wh: f32,
+    pub idle_w: f32,
+    pub peak_w: f32,
+    pub ram_mb: f32,
+    pub gflops: f32,
+    pub max_solar_w: f32,
+    /// Built-in platform (`esp32`, `rpi4`, `jetson`) whose radio, storage,
+    /// thermal and cost figures the row borrows; guessed from the RAM and
+    /// compute when absent
+    #[serde(default)]
+    pub class: Option<String>,
+}
+
 /// Solar irradiance profile for a specific hour
 #[derive(Debug, Deserialize, Clone)]
 #[allow(dead_code)]
 pub struct SolarProfile {
+    /// Month (1-12) of profiles that vary through the year; absent when
+    /// one profile serves every day
+    #[serde(default)]
+    pub month: Option<u8>,
     pub hour: u8,
     pub avg_irradiance_w_m2: f32,
     pub panel_efficiency: f32,
 }
 
+/// Area of the reference panel the profiles are expressed for (m², a small
+/// ~20 W IoT panel); node panels scale from it (see `SolarPanel`)
+pub const REFERENCE_PANEL_AREA_M2: f32 = 0.12;
+
 impl SolarProfile {
     /// Calculate power output from a 20W rated panel (IoT Scale)
     pub fn power_output_100w_panel(&self) -> f32 {
-        let panel_area_m2 = 0.12; // Small IoT Panel (~20W)
-        self.avg_irradiance_w_m2 * panel_area_m2 * self.panel_efficiency
+        self.avg_irradiance_w_m2 * REFERENCE_PANEL_AREA_M2 * self.panel_efficiency
     }
 }
 
+/// Ambient air temperature for a specific hour
+#[derive(Debug, Deserialize, Clone)]
+pub struct AmbientProfile {
+    pub hour: u8,
+    pub ambient_c: f32,
+}
+
+/// Cloud cover of one hour of a recorded weather trace
+#[derive(Debug, Deserialize, Clone)]
+pub struct CloudObservation {
+    /// Hours since the start of the trace
+    pub hour: u32,
+    /// Fraction of the sky covered (0-1)
+    pub cloud_cover: f32,
+}
+
 /// Helper to get power data with CSV override capability
 pub fn get_model_power(
     model: RealModelType,
     csv_overrides: Option<&std::collections::HashMap<String, PowerProfileCSV>>,
 ) -> (f32, f32) {
     // Try CSV first
-    if let Some(overrides) = csv_overrides {
-        if let Some(csv_data) = overrides.get(model.name()) {
-            return (csv_data.idle_power_w, csv_data.inference_power_w);
-        }
+    if let Some(csv_data) = csv_overrides.and_then(|overrides| overrides.get(model.name())) {
+        return (csv_data.idle_power_w, csv_data.inference_power_w);
     }
 
     // Fallback to models.rs (always reliable)
@@ -79,3 +120,240 @@ pub fn load_solar_profiles(path: &str) -> Result<Vec<SolarProfile>, Box<dyn Erro
 
     Ok(profiles)
 }
+
+/// Contents of a solar data file
+pub enum SolarData {
+    /// Native rows: one 24-hour profile, or one per month
+    Profiles(Vec<SolarProfile>),
+    /// Imported full-year timeline: 365 daily profiles, January 1st first
+    Year(Vec<Vec<SolarProfile>>),
+}
+
+/// Load a solar data file, recognizing its format from the first lines:
+/// - native CSV: `[month,]hour,avg_irradiance_w_m2,panel_efficiency`;
+/// - PVGIS hourly radiation or TMY CSV: `Latitude ...` header block, then
+///   a `time` column (`YYYYMMDD:HHMM`, UTC) and the global irradiance,
+///   `G(i)` on the inclined plane or `G(h)` on the horizontal;
+/// - NREL TMY3 CSV: a site line, then `Date (MM/DD/YYYY)`, `Time (HH:MM)`
+///   (local standard time, hour ending) and `GHI (W/m^2)` columns.
+pub fn load_solar_data(path: &str) -> Result<SolarData, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let mut lines = text.trim_start_matches('\u{feff}').lines();
+    let first = lines.next().unwrap_or_default();
+    let second = lines.next().unwrap_or_default();
+    if first.starts_with("Latitude") {
+        Ok(SolarData::Year(parse_pvgis(&text)?))
+    } else if second.starts_with("Date (MM/DD/YYYY)") {
+        Ok(SolarData::Year(parse_tmy3(&text)?))
+    } else {
+        Ok(SolarData::Profiles(load_solar_profiles(path)?))
+    }
+}
+
+const HOURS_PER_YEAR: i32 = DAYS_PER_YEAR as i32 * 24;
+
+/// Irradiance summed per hour of a non-leap year; series spanning several
+/// years average into one typical year
+struct YearAccumulator {
+    sums: Vec<f32>,
+    counts: Vec<u32>,
+}
+
+impl YearAccumulator {
+    fn new() -> Self {
+        Self {
+            sums: vec![0.0; HOURS_PER_YEAR as usize],
+            counts: vec![0; HOURS_PER_YEAR as usize],
+        }
+    }
+
+    /// Add the irradiance of `hour` (local clock, may spill into the
+    /// neighboring days) on `day` of `month`; February 29th is dropped
+    fn add(&mut self, month: u8, day: u8, hour: i32, irradiance_w_m2: f32) -> Result<(), String> {
+        if month == 2 && day == 29 {
+            return Ok(());
+        }
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return Err(format!("invalid date: month {}, day {}", month, day));
+        }
+        let index = ((day_of(month, day) as i32 - 1) * 24 + hour).rem_euclid(HOURS_PER_YEAR);
+        self.sums[index as usize] += irradiance_w_m2;
+        self.counts[index as usize] += 1;
+        Ok(())
+    }
+
+    /// Daily 24-hour profiles; every hour of the year must have data
+    fn finish(self) -> Result<Vec<Vec<SolarProfile>>, Box<dyn Error>> {
+        if let Some(missing) = self.counts.iter().position(|&count| count == 0) {
+            return Err(format!(
+                "solar timeline has no data for day {} hour {}",
+                missing / 24 + 1,
+                missing % 24
+            )
+            .into());
+        }
+        let rows: Vec<SolarProfile> = self
+            .sums
+            .iter()
+            .zip(&self.counts)
+            .enumerate()
+            .map(|(index, (&sum, &count))| {
+                let avg_irradiance_w_m2 = (sum / count as f32).max(0.0);
+                SolarProfile {
+                    month: Some(month_of(index as u16 / 24 + 1)),
+                    hour: (index % 24) as u8,
+                    avg_irradiance_w_m2,
+                    panel_efficiency: if avg_irradiance_w_m2 > 0.0 {
+                        PANEL_EFFICIENCY
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect();
+        Ok(rows.chunks(24).map(<[SolarProfile]>::to_vec).collect())
+    }
+}
+
+/// Parse a PVGIS hourly radiation or TMY CSV export; UTC times move to
+/// the clock of the site's whole-hour time zone
+pub fn parse_pvgis(text: &str) -> Result<Vec<Vec<SolarProfile>>, Box<dyn Error>> {
+    let mut lines = text.lines();
+    let mut offset_h = 0;
+    // Header block with the site, up to the column names
+    let columns: Vec<&str> = loop {
+        let line = lines.next().ok_or("PVGIS file has no time column")?;
+        if let Some(longitude) = line.strip_prefix("Longitude (decimal degrees):") {
+            offset_h = (longitude.trim().parse::<f32>()? / 15.0).round() as i32;
+        } else if line.starts_with("time") {
+            break line.split(',').map(str::trim).collect();
+        }
+    };
+    let column = |name: &str| columns.iter().position(|c| *c == name);
+    // Global irradiance, or its beam, diffuse and reflected components
+    let irradiance: Vec<usize> = match column("G(i)").or_else(|| column("G(h)")) {
+        Some(index) => vec![index],
+        None => ["Gb(i)", "Gd(i)", "Gr(i)"]
+            .into_iter()
+            .filter_map(column)
+            .collect(),
+    };
+    if irradiance.is_empty() {
+        return Err("PVGIS file has no G(i) or G(h) column".into());
+    }
+
+    let mut year = YearAccumulator::new();
+    // Data rows end where the legend block starts
+    for line in lines.take_while(|line| line.starts_with(|c: char| c.is_ascii_digit())) {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let (date, time) = fields[0]
+            .split_once(':')
+            .filter(|(date, time)| date.len() == 8 && time.len() == 4)
+            .ok_or_else(|| format!("invalid PVGIS time {:?}", fields[0]))?;
+        let irradiance_w_m2 = irradiance
+            .iter()
+            .map(|&index| -> Result<f32, Box<dyn Error>> {
+                let field = fields
+                    .get(index)
+                    .ok_or_else(|| format!("short PVGIS row {:?}", line))?;
+                Ok(field.parse()?)
+            })
+            .sum::<Result<f32, _>>()?;
+        year.add(
+            date[4..6].parse()?,
+            date[6..8].parse()?,
+            time[..2].parse::<i32>()? + offset_h,
+            irradiance_w_m2,
+        )?;
+    }
+    year.finish()
+}
+
+/// Parse an NREL TMY3 CSV file
+pub fn parse_tmy3(text: &str) -> Result<Vec<Vec<SolarProfile>>, Box<dyn Error>> {
+    // The first line describes the site
+    let mut lines = text.lines().skip(1);
+    let columns: Vec<&str> = lines
+        .next()
+        .ok_or("TMY3 file has no header")?
+        .split(',')
+        .map(str::trim)
+        .collect();
+    let column = |name: &str| {
+        columns
+            .iter()
+            .position(|c| *c == name)
+            .ok_or_else(|| format!("TMY3 file has no {:?} column", name))
+    };
+    let (date, time, ghi) = (
+        column("Date (MM/DD/YYYY)")?,
+        column("Time (HH:MM)")?,
+        column("GHI (W/m^2)")?,
+    );
+
+    let mut year = YearAccumulator::new();
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |index: usize| {
+            fields
+                .get(index)
+                .copied()
+                .ok_or_else(|| format!("short TMY3 row {:?}", line))
+        };
+        let mut date_parts = field(date)?.split('/');
+        let (month, day) = (date_parts.next(), date_parts.next());
+        let (Some(month), Some(day)) = (month, day) else {
+            return Err(format!("invalid TMY3 date {:?}", field(date)?).into());
+        };
+        let hour_ending: i32 = field(time)?.split(':').next().unwrap_or_default().parse()?;
+        year.add(
+            month.parse()?,
+            day.parse()?,
+            hour_ending - 1,
+            field(ghi)?.parse()?,
+        )?;
+    }
+    year.finish()
+}
+
+/// Load an hourly cloud-cover trace from CSV
+pub fn load_cloud_trace(path: &str) -> Result<Vec<CloudObservation>, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let mut reader = csv::Reader::from_reader(file);
+    let mut observations = Vec::new();
+
+    for result in reader.deserialize() {
+        let observation: CloudObservation = result?;
+        observations.push(observation);
+    }
+
+    Ok(observations)
+}
+
+/// Load a hardware catalog from CSV
+pub fn load_hardware_profiles(path: &str) -> Result<Vec<HardwareProfileCSV>, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let mut reader = csv::Reader::from_reader(file);
+    let mut profiles = Vec::new();
+
+    for result in reader.deserialize() {
+        let profile: HardwareProfileCSV = result?;
+        profiles.push(profile);
+    }
+
+    Ok(profiles)
+}
+
+/// Load ambient temperature profiles from CSV
+pub fn load_ambient_profiles(path: &str) -> Result<Vec<AmbientProfile>, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let mut reader = csv::Reader::from_reader(file);
+    let mut profiles = Vec::new();
+
+    for result in reader.deserialize() {
+        let profile: AmbientProfile = result?;
+        profiles.push(profile);
+    }
+
+    Ok(profiles)
+}
@@ -0,0 +1,146 @@
+/// Policy decision trace
+/// With `--decision-log <path>` every inference decision is traced to a
+/// gzip-compressed CSV for offline analysis of decision boundaries. By
+/// default decisions are aggregated per node and simulated hour (mean state
+/// of charge and solar input, share of ticks inferring), which keeps files
+/// small; `--decision-log-raw` writes one row per decision instead.
+use bevy::prelude::*;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+
+/// One trace row: a single decision (raw mode) or one node-hour
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionRow {
+    /// Simulated hours since the run started (start of the node-hour when
+    /// aggregated)
+    pub elapsed_h: f32,
+    pub epoch: u32,
+    pub node_id: u64,
+    pub policy: &'static str,
+    pub hardware: String,
+    /// Decisions folded into this row (1 in raw mode)
+    pub decisions: u32,
+    pub state_of_charge: f32,
+    pub solar_input_w: f32,
+    /// Policy threshold at decision time (lifetime-tuned)
+    pub battery_threshold: f32,
+    /// Share of decisions that ran inference
+    pub infer_fraction: f32,
+    /// Share of decisions taken while asleep per the wake schedule
+    pub asleep_fraction: f32,
+}
+
+impl DecisionRow {
+    /// Fold another decision of the same node-hour into a running mean
+    fn merge(&mut self, other: &DecisionRow) {
+        let n = self.decisions as f32;
+        let mean = |a: f32, b: f32| (a * n + b) / (n + 1.0);
+        self.state_of_charge = mean(self.state_of_charge, other.state_of_charge);
+        self.solar_input_w = mean(self.solar_input_w, other.solar_input_w);
+        self.battery_threshold = mean(self.battery_threshold, other.battery_threshold);
+        self.infer_fraction = mean(self.infer_fraction, other.infer_fraction);
+        self.asleep_fraction = mean(self.asleep_fraction, other.asleep_fraction);
+        self.decisions += 1;
+    }
+}
+
+/// Optional decision trace sink
+#[derive(Resource, Default)]
+pub struct DecisionLog {
+    writer: Option<csv::Writer<GzEncoder<File>>>,
+    raw: bool,
+    /// Simulated hours since the run started
+    pub elapsed_h: f32,
+    /// Whole simulated hour being aggregated
+    open_hour: Option<u32>,
+    /// Open node-hours, keyed by node id
+    pending: BTreeMap<u64, DecisionRow>,
+}
+
+impl DecisionLog {
+    /// Open the trace file; tracing is disabled (with a warning) on failure
+    pub fn create(path: Option<&str>, raw: bool) -> Self {
+        let writer = path.and_then(|path| match open_gz_csv(path) {
+            Ok(writer) => {
+                let mode = if raw { "every decision" } else { "hourly" };
+                println!("🧾 Tracing policy decisions ({}) to {}", mode, path);
+                Some(writer)
+            }
+            Err(e) => {
+                eprintln!("⚠️ Cannot create decision log {}: {}", path, e);
+                None
+            }
+        });
+
+        Self {
+            writer,
+            raw,
+            ..default()
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Advance the trace clock; crossing an hour boundary writes the
+    /// aggregated node-hours of the hour that ended and flushes the file
+    pub fn advance(&mut self, hours: f32) {
+        self.elapsed_h += hours;
+        let hour = self.elapsed_h as u32;
+        if self.open_hour != Some(hour) {
+            let rows: Vec<DecisionRow> = std::mem::take(&mut self.pending).into_values().collect();
+            self.write(&rows);
+            if let Some(writer) = self.writer.as_mut()
+                && let Err(e) = writer.flush()
+            {
+                eprintln!("⚠️ Decision log flush failed: {}", e);
+            }
+            self.open_hour = Some(hour);
+        }
+    }
+
+    /// Trace one decision (no-op when tracing is disabled)
+    pub fn record(&mut self, row: DecisionRow) {
+        if self.writer.is_none() {
+            return;
+        }
+        if self.raw {
+            self.write(&[row]);
+            return;
+        }
+
+        match self.pending.get_mut(&row.node_id) {
+            Some(open) => open.merge(&row),
+            None => {
+                let start = DecisionRow {
+                    elapsed_h: self.open_hour.unwrap_or(0) as f32,
+                    ..row
+                };
+                self.pending.insert(start.node_id, start);
+            }
+        }
+    }
+
+    fn write(&mut self, rows: &[DecisionRow]) {
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+        if let Err(e) = rows.iter().try_for_each(|row| writer.serialize(row)) {
+            eprintln!("⚠️ Decision log write failed: {}", e);
+            self.writer = None;
+        }
+    }
+}
+
+/// Create a gzip-compressed CSV writer, creating missing parent directories
+fn open_gz_csv(path: &str) -> Result<csv::Writer<GzEncoder<File>>, Box<dyn std::error::Error>> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let encoder = GzEncoder::new(File::create(path)?, Compression::default());
+    Ok(csv::Writer::from_writer(encoder))
+}
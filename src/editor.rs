@@ -0,0 +1,321 @@
+/// Interactive gene editor - construct a custom genome + hardware and inject
+/// it into the running population at a clicked position.
+/// Used for "invasive species" experiments: does the evolved population hold
+/// its ground against a hand-designed configuration?
+///
+/// Also hosts the manual perturbation tools (right-click kill, shift-click
+/// spawn) for stress-testing population resilience interactively.
+/// Injected and spawned nodes are founders in the lineage log (no parent).
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::input::EguiWantsInput;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+use crate::components::{EdgeNodeBundle, Gene, GeneticRng, Lineage, Status};
+use crate::config::SimConfig;
+use crate::engine::random_gene;
+use crate::hardware::{HardwareCatalog, HardwareSpec, HardwareType};
+use crate::milestones::{DeathCause, NodeDied};
+use crate::models::RealModelType;
+use crate::phylogeny::LineageLog;
+use crate::policies::{
+    MAX_BATTERY_THRESHOLD, MAX_PID_GAIN, MAX_REQUEST_RATE_LIMIT, MIN_BATTERY_THRESHOLD,
+    PowerPolicy, WakeSchedule,
+};
+use crate::radio::{MAX_TX_POWER_DBM, MIN_TX_POWER_DBM};
+
+/// Pick radius for manual kills and selection (world units, matches the
+/// largest node circle)
+pub const PICK_RADIUS: f32 = 20.0;
+
+/// Editor state: the genome being designed and whether clicks inject it
+#[derive(Resource)]
+pub struct GeneEditor {
+    pub gene: Gene,
+    pub hardware_type: HardwareType,
+    /// When armed, each left click in the world spawns the designed node
+    pub armed: bool,
+    pub injected: u32,
+}
+
+impl Default for GeneEditor {
+    fn default() -> Self {
+        Self {
+            gene: Gene {
+                model_type: RealModelType::MobileNetV3Small,
+                inference_frequency: 0.5,
+                solar_efficiency_factor: 1.0,
+                policy: PowerPolicy::SmartAdaptive,
+                policy_params: PowerPolicy::SmartAdaptive.default_params(),
+                tx_power_dbm: 14.0,
+                compress_reports: false,
+                wake_schedule: WakeSchedule::ALWAYS,
+            },
+            hardware_type: HardwareType::RaspberryPi4,
+            armed: false,
+            injected: 0,
+        }
+    }
+}
+
+/// Editor window - egui form for every gene field and the hardware tier
+pub fn gene_editor_ui_system(mut contexts: EguiContexts, mut editor: ResMut<GeneEditor>) -> Result {
+    let editor = editor.as_mut();
+
+    egui::Window::new("Gene Editor").show(contexts.ctx_mut()?, |ui| {
+        egui::ComboBox::from_label("Model")
+            .selected_text(editor.gene.model_type.name())
+            .show_ui(ui, |ui| {
+                for model in RealModelType::ALL {
+                    ui.selectable_value(&mut editor.gene.model_type, model, model.name());
+                }
+            });
+
+        egui::ComboBox::from_label("Policy")
+            .selected_text(editor.gene.policy.name())
+            .show_ui(ui, |ui| {
+                for policy in PowerPolicy::ALL {
+                    ui.selectable_value(&mut editor.gene.policy, policy, policy.name());
+                }
+            });
+
+        egui::ComboBox::from_label("Hardware")
+            .selected_text(HardwareSpec::new(editor.hardware_type).name())
+            .show_ui(ui, |ui| {
+                for hw_type in HardwareType::ALL {
+                    let spec = HardwareSpec::new(hw_type);
+                    ui.selectable_value(&mut editor.hardware_type, hw_type, spec.name());
+                }
+            });
+
+        // Ranges match the clamps applied by the mutation operators
+        ui.add(
+            egui::Slider::new(&mut editor.gene.inference_frequency, 0.1..=1.0)
+                .text("Inference frequency"),
+        );
+        ui.add(
+            egui::Slider::new(&mut editor.gene.solar_efficiency_factor, 0.7..=1.3)
+                .text("Solar efficiency"),
+        );
+        ui.add(
+            egui::Slider::new(
+                &mut editor.gene.policy_params.battery_threshold,
+                MIN_BATTERY_THRESHOLD..=MAX_BATTERY_THRESHOLD,
+            )
+            .text("Battery threshold"),
+        );
+        ui.add(
+            egui::Slider::new(
+                &mut editor.gene.policy_params.request_rate_limit,
+                0.0..=MAX_REQUEST_RATE_LIMIT,
+            )
+            .text("Request limit (/h)"),
+        );
+        ui.add(
+            egui::Slider::new(
+                &mut editor.gene.policy_params.tx_battery_threshold,
+                0.0..=MAX_BATTERY_THRESHOLD,
+            )
+            .text("Store below SOC"),
+        );
+        if editor.gene.policy == PowerPolicy::Pid {
+            let params = &mut editor.gene.policy_params;
+            ui.add(egui::Slider::new(&mut params.pid_kp, 0.0..=MAX_PID_GAIN).text("PID Kp"));
+            ui.add(egui::Slider::new(&mut params.pid_ki, 0.0..=MAX_PID_GAIN).text("PID Ki"));
+            ui.add(egui::Slider::new(&mut params.pid_kd, 0.0..=MAX_PID_GAIN).text("PID Kd"));
+        }
+        ui.add(
+            egui::Slider::new(
+                &mut editor.gene.tx_power_dbm,
+                MIN_TX_POWER_DBM..=MAX_TX_POWER_DBM,
+            )
+            .text("TX power (dBm)"),
+        );
+        ui.checkbox(&mut editor.gene.compress_reports, "Compress reports");
+        ui.label("Awake hours");
+        ui.horizontal_wrapped(|ui| {
+            for hour in 0..24 {
+                let mut awake = editor.gene.wake_schedule.is_awake(hour);
+                if ui
+                    .toggle_value(&mut awake, format!("{:02}", hour))
+                    .changed()
+                {
+                    editor.gene.wake_schedule.set_awake(hour, awake);
+                }
+            }
+        });
+
+        ui.separator();
+        ui.checkbox(&mut editor.armed, "Inject on left click");
+        ui.label(format!("Injected this run: {}", editor.injected));
+    });
+
+    Ok(())
+}
+
+/// World-space position under the cursor (2D camera projection)
+#[derive(SystemParam)]
+pub struct WorldCursor<'w, 's> {
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+}
+
+impl WorldCursor<'_, '_> {
+    /// Cursor position in world units, if the cursor is inside the window
+    pub fn position(&self) -> Option<Vec2> {
+        let window = self.windows.single().ok()?;
+        let (camera, camera_transform) = self.cameras.single().ok()?;
+        let cursor = window.cursor_position()?;
+        camera.viewport_to_world_2d(camera_transform, cursor).ok()
+    }
+}
+
+/// Spawn the designed node at the clicked world position
+/// The injected node is an ordinary population member: it competes in the
+/// next genetic epoch exactly like the evolved nodes
+pub fn inject_on_click_system(
+    mut commands: Commands,
+    mut editor: ResMut<GeneEditor>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    cursor: WorldCursor,
+    mut lineage_log: ResMut<LineageLog>,
+) {
+    // Shift-click belongs to the random spawn tool
+    if !editor.armed
+        || !mouse.just_pressed(MouseButton::Left)
+        || keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+        || egui_input.wants_any_pointer_input()
+    {
+        return;
+    }
+
+    let Some(world_pos) = cursor.position() else {
+        return;
+    };
+
+    let hardware = HardwareSpec::new(editor.hardware_type);
+    let lineage = lineage_log.register(None, &editor.gene);
+    commands.spawn(EdgeNodeBundle::new(
+        editor.gene,
+        hardware.clone(),
+        lineage,
+        Transform::from_xyz(world_pos.x, world_pos.y, 0.0),
+    ));
+
+    editor.injected += 1;
+    println!(
+        "🧪 Injected node {} {} / {} on {} at ({:.0}, {:.0})",
+        lineage.id,
+        editor.gene.model_type.name(),
+        editor.gene.policy.name(),
+        hardware.name(),
+        world_pos.x,
+        world_pos.y
+    );
+}
+
+/// Manual kill: right-click marks the nearest alive node as dead.
+/// An ordinary death as far as selection is concerned (`Status::Dead`)
+pub fn manual_kill_system(
+    mouse: Res<ButtonInput<MouseButton>>,
+    egui_input: Res<EguiWantsInput>,
+    cursor: WorldCursor,
+    mut deaths: MessageWriter<NodeDied>,
+    mut nodes: Query<(Entity, &Transform, &mut Status, &Lineage)>,
+) {
+    if !mouse.just_pressed(MouseButton::Right) || egui_input.wants_any_pointer_input() {
+        return;
+    }
+
+    let Some(world_pos) = cursor.position() else {
+        return;
+    };
+
+    let target = nodes
+        .iter_mut()
+        .filter(|(_, _, status, _)| **status == Status::Alive)
+        .map(|(entity, transform, status, lineage)| {
+            let distance = transform.translation.truncate().distance(world_pos);
+            (distance, entity, status, lineage)
+        })
+        .filter(|(distance, _, _, _)| *distance <= PICK_RADIUS)
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+
+    if let Some((_, entity, mut status, lineage)) = target {
+        *status = Status::Dead;
+        deaths.write(NodeDied {
+            entity,
+            node_id: lineage.id,
+            cause: DeathCause::ManualKill,
+        });
+        println!(
+            "💀 Manual kill of node {} at ({:.0}, {:.0})",
+            lineage.id, world_pos.x, world_pos.y
+        );
+    }
+}
+
+/// Manual spawn: shift + left-click adds a random founder-style node,
+/// an ordinary population member for the next epoch
+#[allow(clippy::too_many_arguments)]
+pub fn manual_spawn_system(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    cursor: WorldCursor,
+    config: Res<SimConfig>,
+    catalog: Res<HardwareCatalog>,
+    mut genetic_rng: ResMut<GeneticRng>,
+    mut lineage_log: ResMut<LineageLog>,
+) {
+    if !mouse.just_pressed(MouseButton::Left)
+        || !keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+        || egui_input.wants_any_pointer_input()
+    {
+        return;
+    }
+
+    let Some(world_pos) = cursor.position() else {
+        return;
+    };
+
+    let gene = random_gene(&mut genetic_rng.0);
+    let hardware = config
+        .breeding(&catalog)
+        .random_hardware(&mut genetic_rng.0);
+    let lineage = lineage_log.register(None, &gene);
+    println!(
+        "🐣 Manual spawn: node {} {} / {} on {} at ({:.0}, {:.0})",
+        lineage.id,
+        gene.model_type.name(),
+        gene.policy.name(),
+        hardware.name(),
+        world_pos.x,
+        world_pos.y
+    );
+
+    commands.spawn(EdgeNodeBundle::new(
+        gene,
+        hardware,
+        lineage,
+        Transform::from_xyz(world_pos.x, world_pos.y, 0.0),
+    ));
+}
+
+/// Register editor resources and systems
+pub fn register_editor(app: &mut App) {
+    app.init_resource::<GeneEditor>()
+        .add_systems(EguiPrimaryContextPass, gene_editor_ui_system)
+        .add_systems(
+            Update,
+            (
+                inject_on_click_system,
+                manual_kill_system,
+                manual_spawn_system,
+            ),
+        );
+}
@@ -0,0 +1,1175 @@
+/// Bevy-free simulation core
+/// The per-node energy physics (harvest, policy decision, model loads,
+/// sensor, battery and death) and the genetic operators (founders,
+/// selection, mutation, repopulation) as plain functions over plain data.
+/// The ECS systems in systems.rs call these and only add scheduling,
+/// spawning, metrics and tracing.
+/// `Engine` runs the same physics and survival-time evolution without any
+/// scheduler through `step(dt)`, for headless sweeps and tests; it covers
+/// energy and policies only, the subsystem modules (radio, gossip, sensing,
+/// attacks, ...) remain ECS systems. Neither this module nor the data
+/// modules it steps (node, hardware, policies, sensor, aging, calendar,
+/// solar, ...) import Bevy; their `Component` / `Resource` derives only
+/// exist with the `ecs` feature, so `--no-default-features` builds the
+/// engine alone (headless.rs drives it from the command line).
+/// With night fast-forward (`with_fast_night`, `headless --fast-night`) a
+/// node whose draw cannot change before sunrise (asleep, or a threshold
+/// policy already below its threshold, as the battery only falls in the
+/// dark) is drained analytically to the end of the dark stretch in one
+/// step, and the clock jumps ahead once every alive node is parked. Parked
+/// nodes skip their RNG draws, so such runs match tick-by-tick runs
+/// statistically, not bit for bit.
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::aging::BatteryHealth;
+use crate::calendar::Calendar;
+use crate::compatibility::Compatibility;
+use crate::data_loader::{PowerProfileCSV, SolarProfile, get_model_power};
+use crate::hardware::{DEFAULT_AMBIENT_C, HardwareCatalog, HardwareSpec, HardwareType, SolarPanel};
+use crate::models::RealModelType;
+use crate::node::{
+    Battery, EnergyLedger, Gene, MAX_TX_POWER_DBM, MIN_TX_POWER_DBM, ModelCache, Status, Telemetry,
+    TunedPolicy,
+};
+use crate::policies::{
+    MAX_BATTERY_THRESHOLD, MAX_PID_GAIN, MAX_REQUEST_RATE_LIMIT, MIN_BATTERY_THRESHOLD,
+    MpcController, MpcProblem, PolicyParams, PolicyState, PowerPolicy, WakeSchedule,
+};
+use crate::sensor::{Sensor, SensorKind};
+use crate::solar::SolarSeasons;
+use crate::units::{Power, SECONDS_PER_HOUR, WattHours};
+
+pub const SOLAR_EFFICIENCY_PENALTY: f32 = 1.0; // Real efficiency
+pub const EPOCH_SECONDS: u64 = 30; // Real seconds between genetic epochs
+
+/// Share of the ranked survivors that breed
+pub const ELITE_FRACTION: f32 = 0.15;
+
+/// Real seconds per headless step (one 60 fps frame)
+pub const HEADLESS_DT: f32 = 1.0 / 60.0;
+
+/// Simulated seconds per real second unless `--time-scale` says otherwise
+/// (1 real second = 3 simulated minutes)
+pub const DEFAULT_TIME_SCALE: f32 = 180.0;
+
+/// Fleet-wide inputs of one physics step
+pub struct StepEnv<'a> {
+    /// Simulated seconds covered by the step
+    pub window_s: f32,
+    /// Hour of the day (0-23)
+    pub hour: usize,
+    /// Reference panel output now (W)
+    pub solar_w: f32,
+    /// Expected reference panel output for an hour of the day (W), used by
+    /// MPC planning
+    pub expected_solar_w: &'a (dyn Fn(usize) -> f32 + Sync),
+    pub power_overrides: Option<&'a HashMap<String, PowerProfileCSV>>,
+    /// Battery cycle-aging acceleration (`--battery-aging`)
+    pub battery_aging: f32,
+    /// Whether incompatible model loads crash the node (`--compatibility`)
+    pub compatibility: Compatibility,
+}
+
+/// One node's state, borrowed from wherever it is stored
+pub struct NodeMut<'a> {
+    pub battery: &'a mut Battery,
+    pub health: &'a mut BatteryHealth,
+    pub status: &'a mut Status,
+    pub telemetry: &'a mut Telemetry,
+    pub tuned: &'a mut TunedPolicy,
+    pub policy_state: &'a mut PolicyState,
+    pub model_cache: &'a mut ModelCache,
+    pub ledger: &'a mut EnergyLedger,
+    pub sensor: &'a mut Sensor,
+    pub gene: &'a Gene,
+    pub hardware: &'a HardwareSpec,
+    pub panel: &'a SolarPanel,
+    /// Share of the panel output reaching this node (1 = unshaded)
+    pub exposure: f32,
+    /// Battery temperature (°C), which drives self-discharge
+    pub temperature_c: f32,
+}
+
+/// What one node's step did, for metrics and traces
+#[derive(Debug, Clone, Copy)]
+pub struct StepOutcome {
+    /// State of charge the policy decided on
+    pub state_of_charge: f32,
+    /// Solar power into the battery (W)
+    pub recharge_w: f32,
+    pub recharge_wh: WattHours,
+    /// Total draw (W) and energy drained
+    pub power_w: f32,
+    pub drain_wh: WattHours,
+    pub wants_infer: bool,
+    /// Inference actually ran (sensor warm)
+    pub inferred: bool,
+    pub asleep: bool,
+    /// A model load started this step
+    pub model_loaded: bool,
+    pub load_energy_wh: WattHours,
+    pub sensor_woke: bool,
+    pub sensor_energy_wh: WattHours,
+    /// The board could not load the model and crashed (see
+    /// compatibility.rs)
+    pub crashed: bool,
+    /// Battery above zero and no crash after the step
+    pub alive: bool,
+}
+
+/// Advance one alive node by `env.window_s` simulated seconds
+pub fn step_node(node: NodeMut, env: &StepEnv, rng: &mut impl Rng) -> StepOutcome {
+    let NodeMut {
+        battery,
+        health,
+        status,
+        telemetry,
+        tuned,
+        policy_state,
+        model_cache,
+        ledger,
+        sensor,
+        gene,
+        hardware,
+        panel,
+        exposure,
+        temperature_c,
+    } = node;
+    let window_s = env.window_s;
+    let hours = window_s / SECONDS_PER_HOUR;
+
+    // MODEL LOAD: births and model swaps pay load latency and energy once
+    let model_loaded = model_cache.loaded != Some(gene.model_type);
+    if model_loaded && env.compatibility.crashes(gene.model_type, hardware) {
+        *status = Status::Dead;
+        return StepOutcome {
+            state_of_charge: battery.wh() / health.capacity_wh,
+            recharge_w: 0.0,
+            recharge_wh: WattHours::ZERO,
+            power_w: 0.0,
+            drain_wh: WattHours::ZERO,
+            wants_infer: false,
+            inferred: false,
+            asleep: false,
+            model_loaded,
+            load_energy_wh: WattHours::ZERO,
+            sensor_woke: false,
+            sensor_energy_wh: WattHours::ZERO,
+            crashed: true,
+            alive: false,
+        };
+    }
+    if model_loaded {
+        model_cache.loaded = Some(gene.model_type);
+        // Get power using hybrid system (CSV override or models.rs default)
+        model_cache.inference_power_w = get_model_power(gene.model_type, env.power_overrides).1;
+        model_cache.load_remaining_s = hardware.model_load_time_s(gene.model_type.size_mb());
+        // A different model may need a different input device
+        if sensor.kind != SensorKind::for_model(gene.model_type) {
+            *sensor = Sensor::for_node(gene.model_type, hardware.hardware_type);
+        }
+    }
+    let load_s = model_cache.load_remaining_s.min(window_s);
+    model_cache.load_remaining_s -= load_s;
+    let load_power_w = if window_s > 0.0 {
+        hardware.model_load_power_w * load_s / window_s
+    } else {
+        0.0
+    };
+    let load_energy_wh = Power::from_watts(hardware.model_load_power_w).over_s(load_s);
+    // Scaled at every tick: a new generation may keep the model on another
+    // board
+    let inference_power = hardware.inference_power_w(model_cache.inference_power_w);
+
+    // Catastrophes shade or break this node's panel, which converts what
+    // light is left within its rating and the board's charge input
+    let local_solar_w = panel.output_w(env.solar_w * exposure, hardware);
+
+    // POLICY-BASED INFERENCE DECISION (with lifetime-tuned parameters)
+    let state_of_charge = battery.wh() / health.capacity_wh;
+    gene.policy
+        .adapt(&mut tuned.0, state_of_charge, local_solar_w, hours);
+    // Solar recharge using CSV data (with harsh environment penalty)
+    let recharge_w = local_solar_w * gene.solar_efficiency_factor * SOLAR_EFFICIENCY_PENALTY;
+
+    // ENERGY-NEUTRAL CONTROLLER: re-plan the duty cycle from the harvest window
+    if gene.policy == PowerPolicy::EnergyNeutral {
+        let eno = &mut policy_state.eno;
+        eno.observe_harvest(recharge_w, hours);
+        eno.plan(
+            state_of_charge,
+            tuned.0.battery_threshold,
+            health.capacity_wh,
+            hardware.idle_power_w,
+            hardware.idle_power_w + inference_power + sensor.power_w,
+        );
+    }
+
+    // MPC: re-plan the next 24 h against the solar forecast once per hour
+    if gene.policy == PowerPolicy::Mpc && policy_state.mpc.planned_hour != Some(env.hour) {
+        let hour_at = |i: usize| (env.hour + i) % 24;
+        let panel_scale = gene.solar_efficiency_factor * SOLAR_EFFICIENCY_PENALTY;
+        let problem = MpcProblem {
+            battery_wh: battery.wh(),
+            capacity_wh: health.capacity_wh,
+            reserve_wh: tuned.0.battery_threshold * health.capacity_wh,
+            harvest_wh: std::array::from_fn(|i| {
+                panel_scale * panel.output_w((env.expected_solar_w)(hour_at(i)), hardware)
+            }),
+            base_wh: std::array::from_fn(|i| {
+                if gene.wake_schedule.is_awake(hour_at(i)) {
+                    hardware.idle_power_w
+                } else {
+                    hardware.sleep_power_w
+                }
+            }),
+            infer_wh: inference_power + sensor.power_w,
+            awake: std::array::from_fn(|i| gene.wake_schedule.is_awake(hour_at(i))),
+        };
+        policy_state.mpc = MpcController {
+            planned_hour: Some(env.hour),
+            infer: problem.solve(),
+        };
+    }
+
+    // PID CONTROLLER: inference rate from the state-of-charge error
+    if gene.policy == PowerPolicy::Pid {
+        policy_state
+            .pid
+            .step(state_of_charge, gene.inference_frequency, &tuned.0, hours);
+    }
+
+    // WAKE SCHEDULE: asleep hours override the policy (loads finish first)
+    let asleep = model_cache.load_remaining_s <= 0.0 && !gene.wake_schedule.is_awake(env.hour);
+
+    // No inference until the model is resident
+    let wants_infer = model_cache.load_remaining_s <= 0.0
+        && !asleep
+        && gene.policy.should_infer(
+            state_of_charge,
+            local_solar_w,
+            gene.inference_frequency,
+            &tuned.0,
+            policy_state,
+            rng,
+        );
+
+    // SENSOR: powered only while wanted, usable after its warm-up
+    let sensor_woke = wants_infer && !sensor.on;
+    let inferred = sensor.step(wants_infer, window_s);
+    let sensor_power_w = if sensor.on { sensor.power_w } else { 0.0 };
+
+    let base_power_w = if asleep {
+        hardware.sleep_power_w
+    } else {
+        hardware.idle_power_w
+    };
+    let power_w = base_power_w
+        + load_power_w
+        + sensor_power_w
+        + if inferred {
+            inference_power
+        } else {
+            0.0 // Idle power is already added as baseline
+        };
+
+    // Charging loses the chemistry's round-trip share
+    let chemistry = hardware.battery_chemistry;
+    let recharge_wh = Power::from_watts(recharge_w).over_h(hours).wh();
+    let stored_wh = recharge_wh * chemistry.charge_efficiency();
+    battery.charge(stored_wh);
+
+    *telemetry = Telemetry {
+        solar_input_w: recharge_w,
+        power_draw_w: power_w,
+        inferring: inferred,
+        asleep,
+    };
+
+    // Apply physics with time scaling
+    let drain_wh = Power::from_watts(power_w).over_h(hours).wh();
+    battery.drain(drain_wh);
+
+    // Self-discharge, faster when warm
+    let leak_wh = battery.wh() * (chemistry.self_discharge_per_h(temperature_c) * hours).min(1.0);
+    battery.drain(leak_wh);
+
+    // Cycling wears the battery, then the (aged) capacity caps it: surplus
+    // harvest is clipped
+    health.record(battery.wh(), chemistry.cycle_life(), env.battery_aging);
+    ledger.harvested_wh += WattHours::from_wh(recharge_wh);
+    ledger.battery_loss_wh += WattHours::from_wh(recharge_wh - stored_wh + leak_wh);
+    ledger.clipped_wh += WattHours::from_wh(battery.clip(health.capacity_wh));
+
+    // Death condition
+    let alive = battery.0 > 0.0;
+    if !alive {
+        *status = Status::Dead;
+    }
+
+    StepOutcome {
+        state_of_charge,
+        recharge_w,
+        recharge_wh: WattHours::from_wh(recharge_wh),
+        power_w,
+        drain_wh: WattHours::from_wh(drain_wh),
+        wants_infer,
+        inferred,
+        asleep,
+        model_loaded,
+        load_energy_wh,
+        sensor_woke,
+        sensor_energy_wh: Power::from_watts(sensor_power_w).over_s(window_s),
+        crashed: false,
+        alive,
+    }
+}
+
+/// Reference panel output per hour of the day (0 W for hours the profile
+/// lacks)
+pub fn hourly_solar_w(profiles: &[SolarProfile]) -> [f32; 24] {
+    std::array::from_fn(|hour| {
+        profiles
+            .get(hour)
+            .map_or(0.0, |p| p.power_output_100w_panel())
+    })
+}
+
+/// Random genome for the founding population (and manual spawns)
+pub fn random_gene(rng: &mut impl Rng) -> Gene {
+    // All available models from models.rs
+    let all_models = RealModelType::ALL;
+    let model_type = all_models[rng.random_range(0..all_models.len())];
+
+    Gene {
+        model_type,
+        inference_frequency: rng.random_range(0.3..1.0),
+        solar_efficiency_factor: rng.random_range(0.8..1.2),
+        tx_power_dbm: rng.random_range(MIN_TX_POWER_DBM..=MAX_TX_POWER_DBM),
+        compress_reports: rng.random_bool(0.5),
+        wake_schedule: WakeSchedule::random(0.75, rng),
+        // Assign random policy initially
+        policy: PowerPolicy::random(rng),
+        policy_params: PolicyParams {
+            battery_threshold: rng.random_range(0.2..0.6),
+            request_rate_limit: rng.random_range(0.0..=MAX_REQUEST_RATE_LIMIT),
+            tx_battery_threshold: rng.random_range(0.0..0.5),
+            pid_kp: rng.random_range(0.0..2.0),
+            pid_ki: rng.random_range(0.0..0.5),
+            pid_kd: rng.random_range(0.0..2.0),
+        },
+    }
+}
+
+/// Random hardware tier (the hardware lottery)
+pub fn random_hardware_type(rng: &mut impl Rng) -> HardwareType {
+    match rng.random_range(0..3) {
+        0 => HardwareType::ESP32,
+        1 => HardwareType::JetsonNano,
+        _ => HardwareType::RaspberryPi4,
+    }
+}
+
+/// Number of breeding elites among `survivors` ranked nodes (at least one)
+pub fn elite_count(survivors: usize) -> usize {
+    ((survivors as f32 * ELITE_FRACTION).ceil() as usize).max(1)
+}
+
+/// Offspring genome: the parent with every gene mutated
+pub fn mutate(parent: &Gene, rng: &mut impl Rng) -> Gene {
+    let all_models = RealModelType::ALL;
+    let mut new_gene = *parent;
+
+    // Mutation 1: Inference frequency (±10%)
+    new_gene.inference_frequency =
+        (new_gene.inference_frequency + rng.random_range(-0.1..0.1)).clamp(0.1, 1.0);
+
+    // Mutation 1.5: Policy Switch (5% chance)
+    if rng.random_bool(0.05) {
+        new_gene.policy = PowerPolicy::random(rng);
+    }
+
+    // Mutation 1.6: Policy battery threshold (±5% state of charge)
+    new_gene.policy_params.battery_threshold = (new_gene.policy_params.battery_threshold
+        + rng.random_range(-0.05..0.05))
+    .clamp(MIN_BATTERY_THRESHOLD, MAX_BATTERY_THRESHOLD);
+
+    // Mutation 1.7: Request rate limit (±5 requests/hour)
+    new_gene.policy_params.request_rate_limit = (new_gene.policy_params.request_rate_limit
+        + rng.random_range(-5.0..5.0))
+    .clamp(0.0, MAX_REQUEST_RATE_LIMIT);
+
+    // Mutation 1.8: Store-and-forward threshold (±5% state of charge)
+    new_gene.policy_params.tx_battery_threshold = (new_gene.policy_params.tx_battery_threshold
+        + rng.random_range(-0.05..0.05))
+    .clamp(0.0, MAX_BATTERY_THRESHOLD);
+
+    // Mutation 1.9: PID gains (±0.1 each)
+    let params = &mut new_gene.policy_params;
+    for gain in [&mut params.pid_kp, &mut params.pid_ki, &mut params.pid_kd] {
+        *gain = (*gain + rng.random_range(-0.1..0.1)).clamp(0.0, MAX_PID_GAIN);
+    }
+
+    // Mutation 2: Solar efficiency (±5%)
+    new_gene.solar_efficiency_factor =
+        (new_gene.solar_efficiency_factor + rng.random_range(-0.05..0.05)).clamp(0.7, 1.3);
+
+    // Mutation 2.5: TX power (±1 dB)
+    new_gene.tx_power_dbm = (new_gene.tx_power_dbm + rng.random_range(-1.0..1.0))
+        .clamp(MIN_TX_POWER_DBM, MAX_TX_POWER_DBM);
+
+    // Mutation 2.6: Report compression switch (5% chance)
+    if rng.random_bool(0.05) {
+        new_gene.compress_reports = !new_gene.compress_reports;
+    }
+
+    // Mutation 2.7: Wake schedule (each hour flips with 1/24 chance)
+    new_gene.wake_schedule.mutate(1.0 / 24.0, rng);
+
+    // Mutation 3: Model type (10% chance)
+    if rng.random_bool(0.10) {
+        new_gene.model_type = all_models[rng.random_range(0..all_models.len())];
+    }
+
+    new_gene
+}
+
+/// How a generation is bred: the hardware rules and fleet limits every
+/// birth obeys, in the headless engine and the ECS alike
+#[derive(Clone, Copy)]
+pub struct Breeding<'a> {
+    /// Platforms births draw from (the built-in tiers when empty)
+    pub catalog: &'a HardwareCatalog,
+    /// Every node gets this platform (`--lock-hardware`)
+    pub locked_hardware: Option<HardwareType>,
+    pub compatibility: Compatibility,
+    /// Children keep their parent's platform, with a 10% chance of drawing
+    /// a different one
+    pub heritable_hardware: bool,
+    /// Fleet budget (USD) the generation's hardware is bought from
+    pub budget_usd: Option<f32>,
+    /// Share of a newborn's usable capacity its birth draws from the
+    /// energy the survivors banked
+    pub birth_cost: Option<f32>,
+}
+
+/// One node of the next generation
+#[derive(Debug, Clone)]
+pub struct Birth {
+    /// Slot of the generation it fills (slots skipped for lack of banked
+    /// energy stay empty)
+    pub slot: usize,
+    /// Index of the parent among the survivors selection ranked (None for
+    /// founders)
+    pub parent: Option<usize>,
+    pub gene: Gene,
+    pub hardware: HardwareSpec,
+}
+
+impl<'a> Breeding<'a> {
+    /// Unlocked hardware drawn from `catalog`, no budget, no birth cost
+    pub fn new(catalog: &'a HardwareCatalog, compatibility: Compatibility) -> Self {
+        Self {
+            catalog,
+            locked_hardware: None,
+            compatibility,
+            heritable_hardware: false,
+            budget_usd: None,
+            birth_cost: None,
+        }
+    }
+
+    /// Hardware of a newborn: the locked platform in fair-comparison mode,
+    /// otherwise a random tier (or catalog platform) - every birth rolls again
+    pub fn random_hardware(&self, rng: &mut impl Rng) -> HardwareSpec {
+        if let Some(hw_type) = self.locked_hardware {
+            return HardwareSpec::new(hw_type);
+        }
+        self.catalog
+            .draw(rng)
+            .unwrap_or_else(|| HardwareSpec::new(random_hardware_type(rng)))
+    }
+
+    /// `wanted`, or the best affordable catalog substitute (unless hardware
+    /// is locked), within the remaining fleet budget; None once nothing fits
+    fn within_budget(
+        &self,
+        wanted: HardwareSpec,
+        budget_left: Option<f32>,
+    ) -> Option<HardwareSpec> {
+        let Some(left) = budget_left else {
+            return Some(wanted);
+        };
+        if wanted.cost_usd() <= left {
+            return Some(wanted);
+        }
+        if self.locked_hardware.is_some() {
+            return None;
+        }
+        self.catalog.affordable(left)
+    }
+
+    /// Founders for `slots` slots: `seeds` cycled in order, or random
+    /// genomes when it is empty. A locked hardware type overrides the
+    /// seeded platform. Stops once the budget runs out.
+    pub fn founders(
+        &self,
+        seeds: &[(Gene, HardwareSpec)],
+        slots: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<Birth> {
+        let mut budget_left = self.budget_usd;
+        let mut births = Vec::with_capacity(slots);
+        for slot in 0..slots {
+            let (gene, wanted) = match seeds.get(slot % seeds.len().max(1)) {
+                Some((gene, hardware)) => match self.locked_hardware {
+                    Some(hw_type) => (*gene, HardwareSpec::new(hw_type)),
+                    None => (*gene, hardware.clone()),
+                },
+                None => (random_gene(rng), self.random_hardware(rng)),
+            };
+            let (gene, wanted) = self
+                .compatibility
+                .repair(gene, wanted, self.locked_hardware);
+            let Some(hardware) = self.within_budget(wanted, budget_left) else {
+                break;
+            };
+            charge_budget(&mut budget_left, &hardware);
+            births.push(Birth {
+                slot,
+                parent: None,
+                gene,
+                hardware,
+            });
+        }
+        births
+    }
+
+    /// Children of the elites for `slots` slots, each a mutated copy of a
+    /// random elite. With a birth cost, births draw on `banked_wh` but the
+    /// elites' share is always born; an unaffordable platform only forfeits
+    /// its slot, a cheaper draw may still fit. Stops once the budget runs
+    /// out.
+    pub fn offspring(
+        &self,
+        selection: &Selection,
+        slots: usize,
+        banked_wh: f32,
+        rng: &mut impl Rng,
+    ) -> Vec<Birth> {
+        let elites = selection.elites();
+        let mut budget_left = self.budget_usd;
+        let mut energy_left = banked_wh;
+        let mut births = Vec::with_capacity(slots);
+        for slot in 0..slots {
+            let parent = elites[rng.random_range(0..elites.len())];
+            let (_, parent_gene, parent_hardware) = &selection.survivors[parent];
+            let gene = mutate(parent_gene, rng);
+
+            let mut hardware = self.random_hardware(rng);
+            if self.heritable_hardware && !rng.random_bool(0.10) {
+                hardware = parent_hardware.clone();
+            }
+            let (gene, hardware) = self
+                .compatibility
+                .repair(gene, hardware, self.locked_hardware);
+            let Some(hardware) = self.within_budget(hardware, budget_left) else {
+                break;
+            };
+
+            if let Some(birth_cost) = self.birth_cost {
+                let cost_wh = birth_cost * hardware.usable_capacity_wh();
+                if energy_left < cost_wh && births.len() >= elites.len() {
+                    continue;
+                }
+                energy_left -= cost_wh;
+            }
+            // Only a birth that goes ahead is paid for
+            charge_budget(&mut budget_left, &hardware);
+
+            births.push(Birth {
+                slot,
+                parent: Some(parent),
+                gene,
+                hardware,
+            });
+        }
+        births
+    }
+}
+
+/// Take a node that is actually born out of the remaining fleet budget
+fn charge_budget(budget_left: &mut Option<f32>, hardware: &HardwareSpec) {
+    if let Some(left) = budget_left {
+        *left -= hardware.cost_usd();
+    }
+}
+
+/// A finished generation's `(fitness, genome, hardware)` survivors, ranked
+/// for breeding
+pub struct Selection<'a> {
+    survivors: &'a [(f32, Gene, HardwareSpec)],
+    /// Survivor indices, fittest first (ties keep their order)
+    ranked: Vec<usize>,
+}
+
+impl<'a> Selection<'a> {
+    /// None on extinction
+    pub fn new(survivors: &'a [(f32, Gene, HardwareSpec)]) -> Option<Self> {
+        if survivors.is_empty() {
+            return None;
+        }
+        let mut ranked: Vec<usize> = (0..survivors.len()).collect();
+        ranked.sort_by(|&a, &b| survivors[b].0.total_cmp(&survivors[a].0));
+        Some(Self { survivors, ranked })
+    }
+
+    /// Survivor indices, fittest first
+    pub fn ranked(&self) -> &[usize] {
+        &self.ranked
+    }
+
+    /// The breeding share of the ranking
+    pub fn elites(&self) -> &[usize] {
+        &self.ranked[..elite_count(self.ranked.len())]
+    }
+
+    /// The top-ranked survivor
+    pub fn fittest(&self) -> &'a (f32, Gene, HardwareSpec) {
+        &self.survivors[self.ranked[0]]
+    }
+}
+
+/// One node of a headless `Engine`
+pub struct EngineNode {
+    pub gene: Gene,
+    pub hardware: HardwareSpec,
+    pub panel: SolarPanel,
+    /// Survival time (real seconds, as in the ECS)
+    pub score: f32,
+    battery: Battery,
+    health: BatteryHealth,
+    status: Status,
+    telemetry: Telemetry,
+    tuned: TunedPolicy,
+    policy_state: PolicyState,
+    model_cache: ModelCache,
+    ledger: EnergyLedger,
+    sensor: Sensor,
+    /// Epoch time (real seconds) up to which the node was fast-forwarded
+    parked_until_s: f32,
+}
+
+impl EngineNode {
+    /// Fresh node, battery at 80% (as `EdgeNodeBundle::new`)
+    fn new(gene: Gene, hardware: HardwareSpec, panel: SolarPanel) -> Self {
+        let initial_wh = hardware.usable_capacity_wh() * 0.8;
+        Self {
+            gene,
+            panel,
+            score: 0.0,
+            battery: Battery::new(initial_wh),
+            health: BatteryHealth::new(&hardware, 0.8),
+            status: Status::Alive,
+            telemetry: Telemetry::default(),
+            tuned: TunedPolicy(gene.policy_params),
+            policy_state: PolicyState::default(),
+            model_cache: ModelCache::default(),
+            ledger: EnergyLedger {
+                initial_wh: WattHours::from_wh(initial_wh),
+                ..Default::default()
+            },
+            sensor: Sensor::for_node(gene.model_type, hardware.hardware_type),
+            hardware,
+            parked_until_s: 0.0,
+        }
+    }
+
+    pub fn battery_wh(&self) -> f32 {
+        self.battery.wh()
+    }
+
+    pub fn alive(&self) -> bool {
+        self.status == Status::Alive
+    }
+
+    pub fn health(&self) -> &BatteryHealth {
+        &self.health
+    }
+
+    /// True when the node's draw stays constant per hour over a dark
+    /// stretch: model resident, sensor off, no controller integrating the
+    /// night, and every awake hour idle by policy
+    fn can_park(&self, stretch: &[(usize, f32)]) -> bool {
+        if self.model_cache.loaded != Some(self.gene.model_type)
+            || self.model_cache.load_remaining_s > 0.0
+            || self.sensor.on
+            || matches!(
+                self.gene.policy,
+                PowerPolicy::EnergyNeutral | PowerPolicy::Pid
+            )
+        {
+            return false;
+        }
+        let state_of_charge = self.battery.wh() / self.health.capacity_wh;
+        let idle_when_awake = matches!(
+            self.gene.policy,
+            PowerPolicy::Conservative | PowerPolicy::SmartAdaptive
+        ) && state_of_charge <= self.tuned.0.battery_threshold;
+        idle_when_awake
+            || stretch
+                .iter()
+                .all(|(hour, _)| !self.gene.wake_schedule.is_awake(*hour))
+    }
+
+    /// Drain the node through a dark stretch in closed form; returns the
+    /// energy drawn
+    fn fast_forward(
+        &mut self,
+        stretch: &[(usize, f32)],
+        battery_aging: f32,
+        time_scale: f32,
+    ) -> WattHours {
+        let state_of_charge = self.battery.wh() / self.health.capacity_wh;
+        let stretch_h = stretch.iter().map(|(_, s)| s).sum::<f32>() / SECONDS_PER_HOUR;
+        self.gene
+            .policy
+            .adapt(&mut self.tuned.0, state_of_charge, 0.0, stretch_h);
+
+        let mut drained_wh = 0.0;
+        let mut alive_s = 0.0;
+        for &(hour, seconds) in stretch {
+            let asleep = !self.gene.wake_schedule.is_awake(hour);
+            let power_w = if asleep {
+                self.hardware.sleep_power_w
+            } else {
+                self.hardware.idle_power_w
+            };
+            self.telemetry = Telemetry {
+                solar_input_w: 0.0,
+                power_draw_w: power_w,
+                inferring: false,
+                asleep,
+            };
+            let drain_wh = Power::from_watts(power_w).over_s(seconds).wh();
+            let battery_wh = self.battery.wh();
+            if drain_wh >= battery_wh {
+                // Dies partway through the hour
+                alive_s += battery_wh / power_w * SECONDS_PER_HOUR;
+                drained_wh += battery_wh;
+                self.battery.0 = 0.0;
+                self.status = Status::Dead;
+                break;
+            }
+            self.battery.drain(drain_wh);
+            drained_wh += drain_wh;
+            alive_s += seconds;
+
+            let leak_per_h = self
+                .hardware
+                .battery_chemistry
+                .self_discharge_per_h(DEFAULT_AMBIENT_C);
+            let leak_wh = self.battery.wh() * (leak_per_h * seconds / SECONDS_PER_HOUR).min(1.0);
+            self.battery.drain(leak_wh);
+            self.ledger.battery_loss_wh += WattHours::from_wh(leak_wh);
+        }
+        // The discharge is monotonic, so one record ages it exactly
+        self.health.record(
+            self.battery.wh(),
+            self.hardware.battery_chemistry.cycle_life(),
+            battery_aging,
+        );
+        self.score += alive_s / time_scale;
+        WattHours::from_wh(drained_wh)
+    }
+}
+
+/// Summary of one finished epoch
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct EngineEpoch {
+    pub epoch: u32,
+    pub population: usize,
+    pub survivors: usize,
+    pub fitness_mean: f32,
+    pub fitness_max: f32,
+    pub energy_consumed_wh: WattHours,
+    pub energy_harvested_wh: WattHours,
+}
+
+/// Headless simulation: energy physics + survival-time evolution, advanced
+/// by explicit `step(dt)` calls instead of a scheduler
+pub struct Engine {
+    nodes: Vec<EngineNode>,
+    population: usize,
+    /// Reference panel output per hour of the day (W)
+    solar_w: [f32; 24],
+    /// Benchmark CSV rows replacing models.rs inference power
+    power_overrides: Option<HashMap<String, PowerProfileCSV>>,
+    hour: f32,
+    epoch: u32,
+    /// Real seconds since the last epoch
+    epoch_elapsed_s: f32,
+    energy_consumed_wh: WattHours,
+    energy_harvested_wh: WattHours,
+    env_rng: StdRng,
+    genetic_rng: StdRng,
+    /// Fast-forward quiescent nodes through the night
+    fast_night: bool,
+    /// Battery cycle-aging acceleration
+    battery_aging: f32,
+    /// Solar source through the year and the date, when the profile varies
+    seasons: Option<(SolarSeasons, Calendar)>,
+    /// Simulated seconds per real second
+    time_scale: f32,
+    /// Simulated hours run before the first epoch (see warmup.rs)
+    warm_up_h: f32,
+    /// Still in the warm-up: `epoch_elapsed_s` counts the warm-up instead
+    warming_up: bool,
+    /// Panel fitted to every node
+    panel: SolarPanel,
+    /// Hardware/model compatibility rule
+    compatibility: Compatibility,
+    /// Platforms births draw from (empty = the built-in lottery)
+    hardware_catalog: HardwareCatalog,
+}
+
+impl Engine {
+    /// Founding population of random genomes on random hardware, with the
+    /// same two RNG streams as the ECS run
+    pub fn new(genetic_seed: u64, env_seed: u64, population: usize, solar_w: [f32; 24]) -> Self {
+        let mut engine = Self {
+            nodes: Vec::with_capacity(population),
+            population,
+            solar_w,
+            power_overrides: None,
+            hour: 6.0,
+            epoch: 0,
+            epoch_elapsed_s: 0.0,
+            energy_consumed_wh: WattHours::ZERO,
+            energy_harvested_wh: WattHours::ZERO,
+            env_rng: StdRng::seed_from_u64(env_seed),
+            genetic_rng: StdRng::seed_from_u64(genetic_seed),
+            fast_night: false,
+            battery_aging: 1.0,
+            seasons: None,
+            time_scale: DEFAULT_TIME_SCALE,
+            warm_up_h: 0.0,
+            warming_up: false,
+            panel: SolarPanel::default(),
+            compatibility: Compatibility::Off,
+            hardware_catalog: HardwareCatalog::default(),
+        };
+        engine.spawn_founders();
+        engine
+    }
+
+    /// Use measured inference power for the profiled models
+    pub fn with_power_overrides(mut self, profiles: Vec<PowerProfileCSV>) -> Self {
+        let overrides = profiles
+            .into_iter()
+            .map(|p| (p.model_name.clone(), p))
+            .collect();
+        self.power_overrides = Some(overrides);
+        self
+    }
+
+    /// Advance quiescent nodes analytically through dark hours
+    pub fn with_fast_night(mut self, fast_night: bool) -> Self {
+        self.fast_night = fast_night;
+        self
+    }
+
+    /// Scale battery cycle aging (0 disables it)
+    pub fn with_battery_aging(mut self, battery_aging: f32) -> Self {
+        self.battery_aging = battery_aging;
+        self
+    }
+
+    /// Swap in each new day's solar profile from `seasons`, starting on
+    /// the calendar's day (a fixed source changes nothing)
+    pub fn with_seasons(mut self, seasons: SolarSeasons, mut calendar: Calendar) -> Self {
+        if seasons.varies() {
+            calendar.observe_hour(self.hour);
+            self.seasons = Some((seasons, calendar));
+        }
+        self
+    }
+
+    /// Run `hours` simulated hours before the first epoch, then zero the
+    /// scores and energy totals
+    pub fn with_warm_up(mut self, hours: f32) -> Self {
+        self.warm_up_h = hours;
+        self.warming_up = hours > 0.0;
+        self
+    }
+
+    /// Run `time_scale` simulated seconds per real second of `step`
+    pub fn with_time_scale(mut self, time_scale: f32) -> Self {
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// Fit `panel` to every node, founders included
+    pub fn with_panel(mut self, panel: SolarPanel) -> Self {
+        self.panel = panel;
+        for node in &mut self.nodes {
+            node.panel = panel;
+        }
+        self
+    }
+
+    /// Draw every platform from `catalog`; the founders are drawn again
+    pub fn with_hardware_catalog(mut self, catalog: HardwareCatalog) -> Self {
+        if !catalog.0.is_empty() {
+            self.hardware_catalog = catalog;
+            self.nodes.clear();
+            self.spawn_founders();
+        }
+        self
+    }
+
+    /// Enforce hardware/model compatibility; with `forbid` the founders are
+    /// repaired too
+    pub fn with_compatibility(mut self, compatibility: Compatibility) -> Self {
+        self.compatibility = compatibility;
+        for node in &mut self.nodes {
+            let (gene, hardware) = compatibility.repair(node.gene, node.hardware.clone(), None);
+            if (gene.model_type, hardware.hardware_type)
+                != (node.gene.model_type, node.hardware.hardware_type)
+            {
+                *node = EngineNode::new(gene, hardware, self.panel);
+            }
+        }
+        self
+    }
+
+    /// Date of the run (None without a varying solar source)
+    pub fn calendar(&self) -> Option<&Calendar> {
+        self.seasons.as_ref().map(|(_, calendar)| calendar)
+    }
+
+    /// Load the new day's solar profile after midnight
+    fn follow_calendar(&mut self) {
+        if let Some((seasons, calendar)) = &mut self.seasons
+            && calendar.observe_hour(self.hour)
+        {
+            self.solar_w = hourly_solar_w(&seasons.profiles_on(calendar.day_of_year()));
+        }
+    }
+
+    pub fn nodes(&self) -> &[EngineNode] {
+        &self.nodes
+    }
+
+    /// Genetic epochs completed
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Simulated days since the start
+    pub fn simulated_days(&self) -> f32 {
+        let warm_up_s = if self.warming_up {
+            0.0
+        } else {
+            self.warm_up_s()
+        };
+        let real_s = warm_up_s + self.epoch as f32 * EPOCH_SECONDS as f32 + self.epoch_elapsed_s;
+        real_s * self.time_scale / 86_400.0
+    }
+
+    /// Length of the warm-up (real seconds)
+    fn warm_up_s(&self) -> f32 {
+        self.warm_up_h * SECONDS_PER_HOUR / self.time_scale
+    }
+
+    /// Length of the current phase, warm-up or epoch (real seconds)
+    fn phase_s(&self) -> f32 {
+        if self.warming_up {
+            self.warm_up_s()
+        } else {
+            EPOCH_SECONDS as f32
+        }
+    }
+
+    /// Simulated seconds per hour of the dark stretch that this step opens
+    /// (the step's own window included), capped at the phase's end; None
+    /// while the panel produces
+    fn dark_stretch(&self, window_s: f32) -> Option<Vec<(usize, f32)>> {
+        let hour = self.hour as usize % 24;
+        if self.solar_w[hour] > 0.0 {
+            return None;
+        }
+        let mut left_s = (self.phase_s() - self.epoch_elapsed_s) * self.time_scale;
+        let mut stretch = Vec::new();
+        let mut seconds = (hour as f32 + 1.0 - self.hour) * SECONDS_PER_HOUR + window_s;
+        for offset in 0..24 {
+            let dark_hour = (hour + offset) % 24;
+            if left_s <= 0.0 || self.solar_w[dark_hour] > 0.0 {
+                break;
+            }
+            let seconds_here = seconds.min(left_s);
+            stretch.push((dark_hour, seconds_here));
+            left_s -= seconds_here;
+            seconds = SECONDS_PER_HOUR;
+        }
+        Some(stretch)
+    }
+
+    /// Advance by `dt` real seconds (×`time_scale` simulated);
+    /// returns the summary when an epoch completes during the step. With
+    /// night fast-forward a step may cover a whole dark stretch.
+    pub fn step(&mut self, mut dt: f32) -> Option<EngineEpoch> {
+        let window_s = dt * self.time_scale;
+        self.hour = (self.hour + window_s / SECONDS_PER_HOUR) % 24.0;
+        self.follow_calendar();
+        let solar_w = self.solar_w;
+        let env = StepEnv {
+            window_s,
+            hour: self.hour as usize % 24,
+            solar_w: solar_w[self.hour as usize % 24],
+            expected_solar_w: &|hour| solar_w[hour % 24],
+            power_overrides: self.power_overrides.as_ref(),
+            battery_aging: self.battery_aging,
+            compatibility: self.compatibility,
+        };
+        let stretch = if self.fast_night {
+            self.dark_stretch(window_s)
+        } else {
+            None
+        };
+        let now_s = self.epoch_elapsed_s;
+
+        for node in self.nodes.iter_mut().filter(|node| node.alive()) {
+            if node.parked_until_s > now_s {
+                continue;
+            }
+            if let Some(stretch) = stretch.as_deref().filter(|s| node.can_park(s)) {
+                let stretch_s: f32 = stretch.iter().map(|(_, s)| s).sum();
+                node.parked_until_s = now_s + stretch_s / self.time_scale;
+                self.energy_consumed_wh +=
+                    node.fast_forward(stretch, self.battery_aging, self.time_scale);
+                continue;
+            }
+            let outcome = step_node(
+                NodeMut {
+                    battery: &mut node.battery,
+                    health: &mut node.health,
+                    status: &mut node.status,
+                    telemetry: &mut node.telemetry,
+                    tuned: &mut node.tuned,
+                    policy_state: &mut node.policy_state,
+                    model_cache: &mut node.model_cache,
+                    ledger: &mut node.ledger,
+                    sensor: &mut node.sensor,
+                    gene: &node.gene,
+                    hardware: &node.hardware,
+                    panel: &node.panel,
+                    exposure: 1.0,
+                    temperature_c: DEFAULT_AMBIENT_C,
+                },
+                &env,
+                &mut self.env_rng,
+            );
+            self.energy_consumed_wh += outcome.drain_wh;
+            self.energy_harvested_wh += outcome.recharge_wh;
+            if outcome.alive {
+                node.score += dt;
+            }
+        }
+
+        // Nothing left to step: jump to the first node's wake-up (or the
+        // phase's end once all are dead)
+        if self.fast_night
+            && self
+                .nodes
+                .iter()
+                .all(|node| !node.alive() || node.parked_until_s > now_s + dt)
+        {
+            let until_s = self
+                .nodes
+                .iter()
+                .filter(|node| node.alive())
+                .map(|node| node.parked_until_s)
+                .fold(self.phase_s(), f32::min);
+            let jump_s = (until_s - now_s - dt).max(0.0);
+            self.hour = (self.hour + jump_s * self.time_scale / SECONDS_PER_HOUR) % 24.0;
+            self.follow_calendar();
+            dt += jump_s;
+        }
+
+        self.epoch_elapsed_s += dt;
+        let phase_s = self.phase_s();
+        if self.epoch_elapsed_s < phase_s {
+            return None;
+        }
+        self.epoch_elapsed_s -= phase_s;
+        if self.warming_up {
+            self.end_warm_up();
+            return None;
+        }
+        Some(self.next_generation())
+    }
+
+    /// Start measuring: the first epoch begins with the settled field
+    fn end_warm_up(&mut self) {
+        self.warming_up = false;
+        let warm_up_s = self.warm_up_s();
+        self.energy_consumed_wh = WattHours::ZERO;
+        self.energy_harvested_wh = WattHours::ZERO;
+        for node in &mut self.nodes {
+            node.score = 0.0;
+            node.ledger = EnergyLedger {
+                initial_wh: WattHours::charge_of(&node.battery),
+                ..Default::default()
+            };
+            // Parking times are relative to the phase start
+            node.parked_until_s -= warm_up_s;
+        }
+    }
+
+    fn spawn_founders(&mut self) {
+        let breeding = Breeding::new(&self.hardware_catalog, self.compatibility);
+        for birth in breeding.founders(&[], self.population, &mut self.genetic_rng) {
+            self.nodes
+                .push(EngineNode::new(birth.gene, birth.hardware, self.panel));
+        }
+    }
+
+    /// Rank by survival time, breed the elite share, replace the population
+    fn next_generation(&mut self) -> EngineEpoch {
+        let scores: Vec<f32> = self.nodes.iter().map(|node| node.score).collect();
+        let survivors: Vec<(f32, Gene, HardwareSpec)> = self
+            .nodes
+            .iter()
+            .filter(|node| node.alive())
+            .map(|node| (node.score, node.gene, node.hardware.clone()))
+            .collect();
+        let summary = EngineEpoch {
+            epoch: self.epoch,
+            population: self.nodes.len(),
+            survivors: survivors.len(),
+            fitness_mean: scores.iter().sum::<f32>() / scores.len().max(1) as f32,
+            fitness_max: scores.iter().copied().fold(0.0, f32::max),
+            energy_consumed_wh: self.energy_consumed_wh,
+            energy_harvested_wh: self.energy_harvested_wh,
+        };
+        self.epoch += 1;
+        self.energy_consumed_wh = WattHours::ZERO;
+        self.energy_harvested_wh = WattHours::ZERO;
+        self.nodes.clear();
+
+        let Some(selection) = Selection::new(&survivors) else {
+            self.spawn_founders();
+            return summary;
+        };
+        let breeding = Breeding::new(&self.hardware_catalog, self.compatibility);
+        for birth in breeding.offspring(&selection, self.population, 0.0, &mut self.genetic_rng) {
+            self.nodes
+                .push(EngineNode::new(birth.gene, birth.hardware, self.panel));
+        }
+        summary
+    }
+}
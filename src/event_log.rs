@@ -0,0 +1,72 @@
+/// Simulation event log
+/// Notable happenings (catastrophes, campaigns, ...) are announced on the
+/// console with their simulated timestamp and, with `--event-log <path>`,
+/// appended to a plain-text file for later correlation with epoch metrics
+use bevy::prelude::*;
+use std::fs::File;
+use std::io::Write;
+
+/// One logged event
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub epoch: u32,
+    /// Simulated hours since the run started
+    pub elapsed_h: f32,
+    pub message: String,
+}
+
+/// Run-wide event log
+#[derive(Resource, Default)]
+pub struct EventLog {
+    pub entries: Vec<LogEntry>,
+    file: Option<File>,
+}
+
+impl EventLog {
+    /// Console-only log, plus a file sink when a path is given
+    pub fn create(path: Option<&str>) -> Self {
+        let file = path.and_then(|path| {
+            if let Some(parent) = std::path::Path::new(path).parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            match File::create(path) {
+                Ok(file) => {
+                    info!(target: "edgegenesis::event_log", "📒 Writing event log to {}", path);
+                    Some(file)
+                }
+                Err(e) => {
+                    warn!(target: "edgegenesis::event_log",
+                        "⚠️ Cannot create event log {}: {}", path, e
+                    );
+                    None
+                }
+            }
+        });
+
+        Self {
+            entries: Vec::new(),
+            file,
+        }
+    }
+
+    /// Record and announce an event
+    pub fn push(&mut self, epoch: u32, elapsed_h: f32, message: impl Into<String>) {
+        let entry = LogEntry {
+            epoch,
+            elapsed_h,
+            message: message.into(),
+        };
+        let line = format!(
+            "[epoch {} | t={:.1}h] {}",
+            entry.epoch, entry.elapsed_h, entry.message
+        );
+        info!(target: "edgegenesis::event_log", "📣 {}", line);
+
+        if let Some(file) = self.file.as_mut()
+            && let Err(e) = writeln!(file, "{}", line)
+        {
+            warn!(target: "edgegenesis::event_log", "⚠️ Event log write failed: {}", e);
+        }
+        self.entries.push(entry);
+    }
+}
@@ -0,0 +1,477 @@
+/// Per-epoch metrics export for offline analysis
+/// One CSV row per genetic epoch, written and flushed as the run progresses
+/// so partial results survive an interrupted run. Library users add
+/// research-specific columns with a `MetricCollector`: it sees each epoch's
+/// record and survivor snapshot and returns named values, appended after
+/// the built-in columns.
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::Serialize;
+use std::error::Error;
+use std::fs::File;
+
+use crate::genome::EliteExporter;
+use crate::report::RunReport;
+use crate::sizing::SizingReport;
+use crate::units::WattHours;
+use crate::web::WebDashboard;
+use std::path::PathBuf;
+
+/// All epoch-level exporters, bundled so the genetic system takes one param
+#[derive(SystemParam)]
+pub struct Exporters<'w> {
+    pub metrics: ResMut<'w, MetricsExporter>,
+    pub snapshots: Res<'w, SnapshotExporter>,
+    pub policy_stats: ResMut<'w, PolicyStatsExporter>,
+    pub latency: ResMut<'w, LatencyExporter>,
+    pub sizing: ResMut<'w, SizingReport>,
+    pub elite: ResMut<'w, EliteExporter>,
+    pub report: ResMut<'w, RunReport>,
+    pub web: Res<'w, WebDashboard>,
+    pub collectors: ResMut<'w, MetricCollectors>,
+}
+
+/// Custom statistics contributed to the metrics CSV
+/// Called once per finished epoch with the epoch's record and the survivor
+/// snapshot (the rows `--snapshot-dir` writes); each returned name becomes
+/// a column. Names must not change between epochs: columns are fixed by
+/// the first epoch and later values are matched by name.
+pub trait MetricCollector: Send + Sync + 'static {
+    fn collect(&mut self, record: &EpochRecord, survivors: &[SnapshotRow]) -> Vec<(String, f32)>;
+}
+
+impl<F> MetricCollector for F
+where
+    F: FnMut(&EpochRecord, &[SnapshotRow]) -> Vec<(String, f32)> + Send + Sync + 'static,
+{
+    fn collect(&mut self, record: &EpochRecord, survivors: &[SnapshotRow]) -> Vec<(String, f32)> {
+        self(record, survivors)
+    }
+}
+
+/// Registered metric collectors, in registration order
+#[derive(Resource, Default)]
+pub struct MetricCollectors(pub Vec<Box<dyn MetricCollector>>);
+
+impl MetricCollectors {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Every collector's values for one epoch
+    pub fn collect(
+        &mut self,
+        record: &EpochRecord,
+        survivors: &[SnapshotRow],
+    ) -> Vec<(String, f32)> {
+        self.0
+            .iter_mut()
+            .flat_map(|collector| collector.collect(record, survivors))
+            .collect()
+    }
+}
+
+/// Register a collector (before or after adding `EdgeGenesisPlugin`)
+pub fn add_metric_collector(app: &mut App, collector: impl MetricCollector) {
+    app.init_resource::<MetricCollectors>();
+    app.world_mut()
+        .resource_mut::<MetricCollectors>()
+        .0
+        .push(Box::new(collector));
+}
+
+/// One row of the epoch metrics CSV
+#[derive(Debug, Clone, Serialize)]
+pub struct EpochRecord {
+    pub epoch: u32,
+    pub population: usize,
+    pub survivors: usize,
+    pub avg_battery_wh: WattHours,
+    pub energy_consumed_wh: WattHours,
+    pub energy_harvested_wh: WattHours,
+    pub model_loads: u64,
+    pub model_load_energy_wh: WattHours,
+    pub sensor_wakeups: u64,
+    pub sensor_energy_wh: WattHours,
+    /// Fitness distribution over the whole population (dead nodes included)
+    pub fitness_mean: f32,
+    pub fitness_std: f32,
+    pub fitness_min: f32,
+    pub fitness_q1: f32,
+    pub fitness_median: f32,
+    pub fitness_q3: f32,
+    pub fitness_max: f32,
+    /// Result-report radio traffic (see radio.rs)
+    pub radio_attempts: u64,
+    pub radio_collision_rate: f32,
+    pub radio_link_loss_rate: f32,
+    pub radio_delivered: u64,
+    pub radio_dropped: u64,
+    pub radio_energy_wh: WattHours,
+    pub radio_deferred: u64,
+    pub radio_overflowed: u64,
+    pub radio_backlog: u64,
+    /// Radio energy per delivered result bit (µJ), overall and per
+    /// technology; empty when nothing was delivered
+    pub radio_uj_per_bit: Option<f32>,
+    pub lora_uj_per_bit: Option<f32>,
+    pub wifi_uj_per_bit: Option<f32>,
+    /// Horizontal gene transfer (zero when gossip is disabled)
+    pub gossip_exchanges: u64,
+    pub gossip_energy_wh: WattHours,
+    /// Ground-truth events across the population and how many were detected
+    pub events: u64,
+    pub detections: u64,
+    pub missed_events: u64,
+    /// Failed detection attempts and alarms raised on empty scenes
+    pub false_negatives: u64,
+    pub false_alarms: u64,
+    /// Response latency percentiles of serviced events (simulated seconds);
+    /// empty when nothing was serviced
+    pub latency_p50_s: Option<f32>,
+    pub latency_p95_s: Option<f32>,
+    pub latency_p99_s: Option<f32>,
+    /// Over-the-air update transfers (zero without campaigns)
+    pub ota_started: u64,
+    pub ota_completed: u64,
+    pub ota_aborted: u64,
+    pub ota_energy_wh: WattHours,
+    /// Request traffic (zero without attackers)
+    pub legit_requests: u64,
+    pub legit_served: u64,
+    pub bogus_requests: u64,
+    pub bogus_served: u64,
+    pub request_energy_wh: WattHours,
+    /// Byzantine behavior and mean reputation of each group
+    pub misreports: u64,
+    pub refusals: u64,
+    pub trust_byzantine: f32,
+    pub trust_honest: f32,
+    /// Storms / failures / shading still in progress at epoch end
+    pub active_catastrophes: usize,
+    pub hardware_failures: u64,
+    /// Hardware spend of the generation (board + panel + battery)
+    pub fleet_cost_usd: f32,
+    /// Embodied carbon amortized over this epoch (gCO2e)
+    pub embodied_gco2: f32,
+    /// Energy drawn from batteries (Wh)
+    pub operational_wh: WattHours,
+    /// Empty when nothing was detected
+    pub gco2_per_detection: Option<f32>,
+    pub gateway_forwarded: u64,
+    pub backhaul_energy_wh: WattHours,
+    pub reports_stored: u64,
+    pub reports_flushed: u64,
+    pub storage_overflowed: u64,
+    pub storage_energy_wh: WattHours,
+    /// Highest fraction of rated write endurance used by any node
+    pub storage_peak_wear: f32,
+    pub compressed_reports: u64,
+    pub compression_energy_wh: WattHours,
+    /// Population mean of scheduled awake hours per day
+    pub mean_awake_hours: f32,
+    /// Mean absolute error of the hourly solar forecasts (W per reference
+    /// panel); empty until a forecast hour has been checked
+    pub forecast_mae_w: Option<f32>,
+    /// Share of alive node-hours spent thermally throttled
+    pub throttled_fraction: f32,
+    /// Hottest node temperature of the epoch (°C)
+    pub peak_temp_c: f32,
+    /// Mean remaining battery capacity (fraction of nominal)
+    pub mean_battery_health: f32,
+    /// Nodes serviced by the technician, batteries replaced, and the cost
+    /// of labor and parts (USD); zero without maintenance
+    pub maintenance_visits: u64,
+    pub battery_swaps: u64,
+    pub maintenance_cost_usd: f32,
+    /// Mean cloud multiplier on the solar output (1 = clear sky) and the
+    /// longest solar drought so far in hours (see weather.rs)
+    pub mean_solar_factor: f32,
+    pub longest_drought_h: f32,
+    /// Calendar day (1-365) at the end of the epoch
+    pub day_of_year: u16,
+}
+
+/// Optional CSV sink for epoch metrics (flag: `--metrics-out <path>`)
+#[derive(Resource, Default)]
+pub struct MetricsExporter {
+    writer: Option<csv::Writer<File>>,
+    /// Collector columns, fixed when the header is written
+    custom_columns: Option<Vec<String>>,
+}
+
+impl MetricsExporter {
+    /// Open the output file; export is disabled (with a warning) on failure
+    pub fn create(path: Option<&str>) -> Self {
+        // The header is written by hand to append the collector columns
+        let writer = path.and_then(|path| match open_csv_with(path, false) {
+            Ok(writer) => {
+                println!("📝 Exporting epoch metrics to {}", path);
+                Some(writer)
+            }
+            Err(e) => {
+                eprintln!("⚠️ Cannot create metrics file {}: {}", path, e);
+                None
+            }
+        });
+
+        Self {
+            writer,
+            custom_columns: None,
+        }
+    }
+
+    /// Append one epoch row with the collectors' values (no-op when export
+    /// is disabled)
+    pub fn record(&mut self, record: &EpochRecord, custom: &[(String, f32)]) {
+        let Self {
+            writer: Some(writer),
+            custom_columns,
+        } = self
+        else {
+            return;
+        };
+
+        let result = (|| -> Result<(), Box<dyn Error>> {
+            let columns = match custom_columns {
+                Some(columns) => columns,
+                None => {
+                    let columns: Vec<String> =
+                        custom.iter().map(|(name, _)| name.clone()).collect();
+                    let mut header = record_header(record)?;
+                    for name in &columns {
+                        header.push_field(name);
+                    }
+                    writer.write_record(&header)?;
+                    custom_columns.insert(columns)
+                }
+            };
+            let values: Vec<Option<f32>> = columns
+                .iter()
+                .map(|column| {
+                    custom
+                        .iter()
+                        .find(|(name, _)| name == column)
+                        .map(|(_, value)| *value)
+                })
+                .collect();
+            writer.serialize((record, values))?;
+            writer.flush()?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            eprintln!("⚠️ Metrics export failed: {}", e);
+        }
+    }
+}
+
+/// One policy's scores in one epoch (see policy_stats.rs)
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyStatsRow {
+    pub epoch: u32,
+    pub policy: &'static str,
+    pub nodes: usize,
+    pub survivors: usize,
+    pub survival_rate: f32,
+    pub mean_fitness: f32,
+    pub energy_wh: WattHours,
+    pub detections: u64,
+    /// Empty when no energy was drawn
+    pub detections_per_wh: Option<f32>,
+    pub inferences: f32,
+    /// Per alive node-hour; empty without node-hours
+    pub inferences_per_hour: Option<f32>,
+}
+
+/// Optional per-policy CSV (flag: `--policy-stats-out <path>`)
+#[derive(Resource, Default)]
+pub struct PolicyStatsExporter {
+    writer: Option<csv::Writer<File>>,
+}
+
+impl PolicyStatsExporter {
+    /// Open the output file; export is disabled (with a warning) on failure
+    pub fn create(path: Option<&str>) -> Self {
+        let writer = path.and_then(|path| match open_csv(path) {
+            Ok(writer) => {
+                println!("📝 Exporting per-policy scores to {}", path);
+                Some(writer)
+            }
+            Err(e) => {
+                eprintln!("⚠️ Cannot create policy stats file {}: {}", path, e);
+                None
+            }
+        });
+
+        Self { writer }
+    }
+
+    /// Append one epoch's rows (no-op when export is disabled)
+    pub fn record(&mut self, rows: &[PolicyStatsRow]) {
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+
+        let result = rows
+            .iter()
+            .try_for_each(|row| writer.serialize(row))
+            .and_then(|_| Ok(writer.flush()?));
+        if let Err(e) = result {
+            eprintln!("⚠️ Policy stats export failed: {}", e);
+        }
+    }
+}
+
+/// Response latency of one model's serviced events in one epoch
+/// (see sensing.rs)
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyRow {
+    pub epoch: u32,
+    pub model: &'static str,
+    pub serviced: usize,
+    pub p50_s: f32,
+    pub p95_s: f32,
+    pub p99_s: f32,
+}
+
+/// Optional per-model latency CSV (flag: `--latency-out <path>`)
+#[derive(Resource, Default)]
+pub struct LatencyExporter {
+    writer: Option<csv::Writer<File>>,
+}
+
+impl LatencyExporter {
+    /// Open the output file; export is disabled (with a warning) on failure
+    pub fn create(path: Option<&str>) -> Self {
+        let writer = path.and_then(|path| match open_csv(path) {
+            Ok(writer) => {
+                println!("📝 Exporting per-model latency to {}", path);
+                Some(writer)
+            }
+            Err(e) => {
+                eprintln!("⚠️ Cannot create latency file {}: {}", path, e);
+                None
+            }
+        });
+
+        Self { writer }
+    }
+
+    /// Append one epoch's rows (no-op when export is disabled)
+    pub fn record(&mut self, rows: &[LatencyRow]) {
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+
+        let result = rows
+            .iter()
+            .try_for_each(|row| writer.serialize(row))
+            .and_then(|_| Ok(writer.flush()?));
+        if let Err(e) = result {
+            eprintln!("⚠️ Latency export failed: {}", e);
+        }
+    }
+}
+
+/// One survivor in a population snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotRow {
+    pub epoch: u32,
+    pub node_id: u64,
+    /// Empty for founders, injected and manually spawned nodes
+    pub parent_id: Option<u64>,
+    pub model: &'static str,
+    pub policy: &'static str,
+    pub inference_frequency: f32,
+    pub solar_efficiency_factor: f32,
+    pub tx_power_dbm: f32,
+    pub compress_reports: bool,
+    /// 24 characters from 00:00, `#` awake, `.` asleep
+    pub wake_schedule: String,
+    /// Inherited policy threshold vs. the value tuned during life
+    pub battery_threshold: f32,
+    pub tuned_battery_threshold: f32,
+    pub request_rate_limit: f32,
+    pub tx_battery_threshold: f32,
+    pub pid_kp: f32,
+    pub pid_ki: f32,
+    pub pid_kd: f32,
+    pub hardware: String,
+    pub battery_capacity_wh: f32,
+    pub fitness: f32,
+    pub battery_wh: f32,
+    pub events: u32,
+    pub detections: u32,
+    pub missed_events: u32,
+    pub false_alarms: u32,
+    /// Radio energy per delivered result bit (µJ); empty before any delivery
+    pub radio_uj_per_bit: Option<f32>,
+}
+
+/// Optional per-epoch survivor dumps (flag: `--snapshot-dir <dir>`)
+/// Each epoch writes `<dir>/epoch_NNNN.csv` so offline tools can rebuild
+/// the full evolutionary trajectory, not just the summary statistics
+#[derive(Resource, Default)]
+pub struct SnapshotExporter {
+    dir: Option<PathBuf>,
+}
+
+impl SnapshotExporter {
+    pub fn new(dir: Option<&str>) -> Self {
+        if let Some(dir) = dir {
+            println!("📸 Writing population snapshots to {}/", dir);
+        }
+        Self {
+            dir: dir.map(PathBuf::from),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    /// Write one epoch's survivors (no-op when snapshots are disabled)
+    pub fn write(&self, epoch: u32, rows: &[SnapshotRow]) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+
+        let path = dir.join(format!("epoch_{:04}.csv", epoch));
+        let result = open_csv(&path.to_string_lossy()).and_then(|mut writer| {
+            for row in rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+            Ok(())
+        });
+
+        if let Err(e) = result {
+            eprintln!("⚠️ Snapshot export failed ({}): {}", path.display(), e);
+        }
+    }
+}
+
+/// Column names of the built-in metrics, in CSV order
+fn record_header(record: &EpochRecord) -> Result<csv::StringRecord, Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.serialize(record)?;
+    let bytes = writer.into_inner()?;
+    Ok(csv::Reader::from_reader(bytes.as_slice())
+        .headers()?
+        .clone())
+}
+
+/// Create a CSV writer, creating missing parent directories first
+pub fn open_csv(path: &str) -> Result<csv::Writer<File>, Box<dyn Error>> {
+    open_csv_with(path, true)
+}
+
+/// `open_csv`, optionally without the automatic header row
+fn open_csv_with(path: &str, has_headers: bool) -> Result<csv::Writer<File>, Box<dyn Error>> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(csv::WriterBuilder::new()
+        .has_headers(has_headers)
+        .from_path(path)?)
+}
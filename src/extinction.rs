@@ -0,0 +1,279 @@
+/// Extinction analytics and automatic easing
+/// The moment the last node dies, the environmental context is captured:
+/// time of day, simulated time, panel output, catastrophes in progress, the
+/// final death (cause and genome) and the dominant model/policy of the dying
+/// generation. At the epoch's reseed this becomes an extinction report on
+/// the console and event log and, with `--extinction-log <path>`, a JSON
+/// line. With `--auto-ease` every extinction eases the environment one
+/// level (more sun, fewer catastrophes, lighter workload), and each run of
+/// `RECOVERY_EPOCHS` epochs without one tightens it back a level.
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::catastrophe::Catastrophes;
+use crate::components::{EpochCount, Gene, LoadedSolarProfiles, SimulationMetrics, Status};
+use crate::config::SimConfig;
+use crate::curriculum::{self, Stage};
+use crate::event_log::EventLog;
+use crate::genome::SavedGenome;
+use crate::hardware::HardwareSpec;
+use crate::milestones::{EpochCompleted, ExtinctionOccurred, NodeDied};
+use crate::systems::solar_output_w;
+
+/// Most easing levels applied at once
+pub const MAX_EASE_LEVEL: u32 = 4;
+
+/// Extinction-free epochs before one easing level is withdrawn
+pub const RECOVERY_EPOCHS: u32 = 5;
+
+/// Panel output bonus per easing level
+pub const EASE_SOLAR_STEP: f32 = 0.25;
+
+/// Catastrophe and workload multipliers per easing level
+pub const EASE_CATASTROPHE_FACTOR: f32 = 0.5;
+pub const EASE_WORKLOAD_FACTOR: f32 = 0.75;
+
+/// Automatic difficulty reduction after extinctions (`--auto-ease`)
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct Easing {
+    pub level: u32,
+    /// Consecutive epochs without extinction at the current level
+    calm_epochs: u32,
+}
+
+impl Easing {
+    /// Conditions of a curriculum stage, eased by the current level
+    pub fn apply(&self, stage: Stage) -> Stage {
+        if self.level == 0 {
+            return stage;
+        }
+        let level = self.level as i32;
+        Stage {
+            solar_scale: stage.solar_scale * (1.0 + EASE_SOLAR_STEP * self.level as f32),
+            workload_scale: stage.workload_scale * EASE_WORKLOAD_FACTOR.powi(level),
+            catastrophe_scale: stage.catastrophe_scale * EASE_CATASTROPHE_FACTOR.powi(level),
+            ..stage
+        }
+    }
+}
+
+/// Environment and population at the moment the last node died
+#[derive(Debug, Clone, Serialize)]
+pub struct CollapseContext {
+    /// Hour of day (0-24)
+    pub hour: f32,
+    /// Simulated hours since the run started
+    pub elapsed_h: f32,
+    /// Reference panel output at that hour, after curriculum and easing
+    pub solar_w: f32,
+    /// Catastrophes in progress
+    pub catastrophes: Vec<String>,
+    pub last_death_cause: String,
+    /// Lineage id of the last node to die (joins the other exports)
+    pub last_death_node_id: u64,
+    /// Genome of the node that held out longest
+    pub last_survivor: Option<SavedGenome>,
+    /// Most common model + policy in the generation, and its share
+    pub dominant_model: String,
+    pub dominant_policy: String,
+    pub dominant_share: f32,
+}
+
+/// One line of the extinction log
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtinctionRecord {
+    pub epoch: u32,
+    /// None when the collapse itself was not observed (e.g. all nodes died
+    /// in the epoch's final tick)
+    #[serde(flatten)]
+    pub collapse: Option<CollapseContext>,
+    /// Easing level in force from the next epoch
+    pub ease_level: u32,
+}
+
+/// Collapse capture and extinction history
+#[derive(Resource, Default)]
+pub struct ExtinctionMonitor {
+    /// Context captured during the current epoch
+    collapse: Option<CollapseContext>,
+    pub history: Vec<ExtinctionRecord>,
+    writer: Option<BufWriter<File>>,
+}
+
+impl ExtinctionMonitor {
+    /// Open the optional JSON-lines log; disabled (with a warning) on failure
+    pub fn create(path: Option<&str>) -> Self {
+        let writer = path.and_then(|path| match create_file(path) {
+            Ok(file) => {
+                info!(target: "edgegenesis::extinction",
+                    "🦴 Writing extinction reports to {}", path
+                );
+                Some(BufWriter::new(file))
+            }
+            Err(e) => {
+                warn!(target: "edgegenesis::extinction",
+                    "⚠️ Cannot create extinction log {}: {}", path, e
+                );
+                None
+            }
+        });
+        Self {
+            writer,
+            ..Default::default()
+        }
+    }
+
+    fn record(&mut self, record: ExtinctionRecord) {
+        if let Some(writer) = self.writer.as_mut() {
+            let result = serde_json::to_writer(&mut *writer, &record)
+                .map_err(Box::<dyn Error>::from)
+                .and_then(|_| Ok(writer.write_all(b"\n")?))
+                .and_then(|_| Ok(writer.flush()?));
+            if let Err(e) = result {
+                warn!(target: "edgegenesis::extinction", "⚠️ Extinction log failed: {}", e);
+            }
+        }
+        self.history.push(record);
+    }
+}
+
+/// Capture the context when a death leaves no node alive
+#[allow(clippy::too_many_arguments)]
+pub fn extinction_watch_system(
+    mut deaths: MessageReader<NodeDied>,
+    config: Res<SimConfig>,
+    epoch_count: Res<EpochCount>,
+    metrics: Res<SimulationMetrics>,
+    catastrophes: Res<Catastrophes>,
+    solar_profiles: Res<LoadedSolarProfiles>,
+    easing: Res<Easing>,
+    mut monitor: ResMut<ExtinctionMonitor>,
+    nodes: Query<(&Status, &Gene, &HardwareSpec)>,
+) {
+    let Some(last_death) = deaths.read().last().copied() else {
+        return;
+    };
+    if monitor.collapse.is_some() || nodes.iter().any(|(status, _, _)| *status != Status::Dead) {
+        return;
+    }
+
+    let mut classes: HashMap<(&str, &str), usize> = HashMap::new();
+    for (_, gene, _) in nodes.iter() {
+        *classes
+            .entry((gene.model_type.name(), gene.policy.name()))
+            .or_default() += 1;
+    }
+    let ((model, policy), count) = classes
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .unwrap_or((("-", "-"), 0));
+
+    let stage = easing.apply(curriculum::stage(&config, epoch_count.0));
+    let hour = metrics.current_hour;
+    monitor.collapse = Some(CollapseContext {
+        hour,
+        elapsed_h: catastrophes.elapsed_h,
+        solar_w: solar_output_w(&solar_profiles.0, stage.solar_hour(hour as usize % 24))
+            * stage.solar_scale,
+        catastrophes: catastrophes
+            .active
+            .iter()
+            .map(|event| event.kind.name().to_string())
+            .collect(),
+        last_death_cause: format!("{:?}", last_death.cause),
+        last_death_node_id: last_death.node_id,
+        last_survivor: nodes
+            .get(last_death.entity)
+            .ok()
+            .map(|(_, gene, hardware)| SavedGenome::new(*gene, hardware)),
+        dominant_model: model.to_string(),
+        dominant_policy: policy.to_string(),
+        dominant_share: count as f32 / nodes.iter().len().max(1) as f32,
+    });
+}
+
+/// Report extinctions and adjust the easing level
+pub fn extinction_report_system(
+    mut extinctions: MessageReader<ExtinctionOccurred>,
+    mut completed: MessageReader<EpochCompleted>,
+    config: Res<SimConfig>,
+    catastrophes: Res<Catastrophes>,
+    mut monitor: ResMut<ExtinctionMonitor>,
+    mut easing: ResMut<Easing>,
+    mut event_log: ResMut<EventLog>,
+) {
+    let extinct: Vec<u32> = extinctions.read().map(|e| e.epoch).collect();
+
+    for epoch in completed.read().map(|e| e.epoch) {
+        let collapse = monitor.collapse.take();
+        if !extinct.contains(&epoch) {
+            if config.auto_ease && easing.level > 0 {
+                easing.calm_epochs += 1;
+                if easing.calm_epochs >= RECOVERY_EPOCHS {
+                    easing.level -= 1;
+                    easing.calm_epochs = 0;
+                    info!(target: "edgegenesis::extinction",
+                        "📈 {} epochs without extinction: easing level {}",
+                        RECOVERY_EPOCHS, easing.level
+                    );
+                }
+            }
+            continue;
+        }
+
+        if config.auto_ease {
+            easing.level = (easing.level + 1).min(MAX_EASE_LEVEL);
+            easing.calm_epochs = 0;
+        }
+
+        let summary = match &collapse {
+            Some(c) => format!(
+                "Extinction at {:04.1}h ({:.2} W solar, catastrophes: {}); last death {} (node {}); dominant {} + {} ({:.0}%)",
+                c.hour,
+                c.solar_w,
+                if c.catastrophes.is_empty() {
+                    "none".to_string()
+                } else {
+                    c.catastrophes.join(", ")
+                },
+                c.last_death_cause,
+                c.last_death_node_id,
+                c.dominant_model,
+                c.dominant_policy,
+                c.dominant_share * 100.0
+            ),
+            None => "Extinction (collapse not observed before the epoch ended)".to_string(),
+        };
+        let elapsed_h = collapse
+            .as_ref()
+            .map_or(catastrophes.elapsed_h, |c| c.elapsed_h);
+        event_log.push(epoch, elapsed_h, summary);
+        if config.auto_ease {
+            info!(target: "edgegenesis::extinction",
+                "🩹 Easing level {}: solar ×{:.2}, catastrophes ×{:.2}, workload ×{:.2}",
+                easing.level,
+                1.0 + EASE_SOLAR_STEP * easing.level as f32,
+                EASE_CATASTROPHE_FACTOR.powi(easing.level as i32),
+                EASE_WORKLOAD_FACTOR.powi(easing.level as i32)
+            );
+        }
+
+        monitor.record(ExtinctionRecord {
+            epoch,
+            collapse,
+            ease_level: easing.level,
+        });
+    }
+}
+
+/// Create a file, creating missing parent directories first
+fn create_file(path: &str) -> Result<File, Box<dyn Error>> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(File::create(path)?)
+}
@@ -0,0 +1,92 @@
+/// Hardware failure model
+/// Every platform has a field mean time between failures (`HardwareSpec::
+/// mtbf_h`). Faults follow a constant hazard (exponential lifetimes), so a
+/// node fails during a tick of `h` hours with probability `1 - exp(-h / MTBF)`
+/// regardless of its battery. Real MTBFs are far longer than an experiment,
+/// so `--failure-acceleration <factor>` divides them, as in accelerated life
+/// testing (0 disables faults). Storage wear raises the hazard towards the
+/// end of the medium's write endurance (the wear-out end of the bathtub).
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::{EnvRng, Lineage, Status};
+use crate::config::SimConfig;
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::milestones::{DeathCause, NodeDied};
+use crate::speed::SimTime;
+use crate::storage::StorageBuffer;
+
+/// Hazard multiplier gained at full storage wear (hazard × (1 + gain × wear²))
+pub const WEAR_HAZARD_GAIN: f32 = 10.0;
+
+/// Exposure and faults of one hardware type
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FailureStats {
+    /// Operating hours accumulated by alive nodes
+    pub node_hours: f32,
+    pub failures: u64,
+    /// Faults the nodes' rated (accelerated) MTBFs predict for that exposure
+    pub expected_failures: f32,
+}
+
+impl FailureStats {
+    /// Maximum-likelihood MTBF estimate (None before the first fault)
+    pub fn observed_mtbf_h(&self) -> Option<f32> {
+        (self.failures > 0).then(|| self.node_hours / self.failures as f32)
+    }
+
+    /// MTBF the exposed platforms are rated for, weighted by their hours
+    pub fn expected_mtbf_h(&self) -> Option<f32> {
+        (self.expected_failures > 0.0).then(|| self.node_hours / self.expected_failures)
+    }
+}
+
+/// Per-epoch failure statistics, indexed like `HardwareType::ALL`
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct FailureMetrics {
+    pub by_type: [FailureStats; HardwareType::ALL.len()],
+}
+
+impl FailureMetrics {
+    pub fn total_failures(&self) -> u64 {
+        self.by_type.iter().map(|s| s.failures).sum()
+    }
+}
+
+/// Failure system - random hardware faults at each platform's hazard rate
+pub fn hardware_failure_system(
+    time: SimTime,
+    config: Res<SimConfig>,
+    mut env_rng: ResMut<EnvRng>,
+    mut failure_metrics: ResMut<FailureMetrics>,
+    mut deaths: MessageWriter<NodeDied>,
+    mut query: Query<(Entity, &mut Status, &HardwareSpec, &StorageBuffer, &Lineage)>,
+) {
+    if config.failure_acceleration <= 0.0 {
+        return;
+    }
+    let hours = time.window_h();
+
+    for (entity, mut status, hardware, storage, lineage) in query.iter_mut() {
+        if *status == Status::Dead {
+            continue;
+        }
+        let stats = &mut failure_metrics.by_type[hardware.hardware_type as usize];
+        stats.node_hours += hours;
+        stats.expected_failures += hours * config.failure_acceleration / hardware.mtbf_h;
+
+        let wear = storage.wear(config.failure_acceleration);
+        let hazard =
+            config.failure_acceleration / hardware.mtbf_h * (1.0 + WEAR_HAZARD_GAIN * wear * wear);
+        let p = 1.0 - (-hazard * hours).exp();
+        if env_rng.0.random_bool(p.clamp(0.0, 1.0) as f64) {
+            stats.failures += 1;
+            *status = Status::Dead;
+            deaths.write(NodeDied {
+                entity,
+                node_id: lineage.id,
+                cause: DeathCause::HardwareFault,
+            });
+        }
+    }
+}
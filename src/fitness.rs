@@ -0,0 +1,160 @@
+/// Fitness presets
+/// The objective selection ranks nodes by, chosen per run so comparative
+/// studies need no code edits:
+/// - survival time: the accumulated `SurvivalScore` (survival plus the
+///   application credits and penalties);
+/// - inference count: inferences run during life;
+/// - accuracy weighted: inferences weighted by model accuracy (expected
+///   correct results);
+/// - energy efficiency: useful work (detections) per Wh drawn;
+/// - cost efficiency: detections per dollar of deployed hardware (board,
+///   panel and battery). This preset makes hardware heritable so evolution
+///   can trade a few expensive nodes against many cheap ones under a fleet
+///   budget.
+///
+/// With random hardware, raw fitness partly measures the hardware lottery: a
+/// Jetson's 20 Wh battery outlasts an ESP32's 1.5 Wh whatever the genome.
+/// `--normalize-fitness` divides it out, either per Wh of battery capacity or
+/// by the mean fitness of the node's hardware class that epoch, so genomes
+/// compete on strategy rather than on the board they were dealt.
+use std::str::FromStr;
+
+use crate::components::Gene;
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::sensing::Sensing;
+use crate::units::WattHours;
+
+/// Fitness used for selection
+/// (flag: `--fitness <survival|inferences|accuracy|energy|cost>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FitnessMode {
+    /// Accumulated survival score
+    #[default]
+    SurvivalTime,
+    /// Inferences run
+    InferenceCount,
+    /// Inferences × model accuracy
+    AccuracyWeighted,
+    /// Detections per Wh drawn from the battery
+    EnergyEfficiency,
+    /// Useful inferences (detections) per USD of hardware
+    CostEfficiency,
+}
+
+impl FitnessMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FitnessMode::SurvivalTime => "Survival time",
+            FitnessMode::InferenceCount => "Inference count",
+            FitnessMode::AccuracyWeighted => "Accuracy-weighted inferences",
+            FitnessMode::EnergyEfficiency => "Energy efficiency",
+            FitnessMode::CostEfficiency => "Cost efficiency",
+        }
+    }
+
+    /// Fitness of one node at the end of an epoch; `consumed_wh` is the
+    /// energy it drew from its battery (see `EnergyLedger`)
+    pub fn evaluate(
+        &self,
+        score: f32,
+        sensing: &Sensing,
+        hardware: &HardwareSpec,
+        gene: &Gene,
+        consumed_wh: WattHours,
+    ) -> f32 {
+        match self {
+            FitnessMode::SurvivalTime => score,
+            FitnessMode::InferenceCount => sensing.inferences,
+            FitnessMode::AccuracyWeighted => {
+                sensing.inferences * gene.model_type.accuracy_percent() / 100.0
+            }
+            FitnessMode::EnergyEfficiency => {
+                sensing.detections as f32 / consumed_wh.wh().max(f32::EPSILON)
+            }
+            FitnessMode::CostEfficiency => sensing.detections as f32 / hardware.cost_usd(),
+        }
+    }
+}
+
+impl FromStr for FitnessMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "survival" | "survival-time" => Ok(FitnessMode::SurvivalTime),
+            "inferences" | "inference-count" => Ok(FitnessMode::InferenceCount),
+            "accuracy" | "accuracy-weighted" => Ok(FitnessMode::AccuracyWeighted),
+            "energy" | "energy-efficiency" => Ok(FitnessMode::EnergyEfficiency),
+            "cost" | "cost-efficiency" => Ok(FitnessMode::CostEfficiency),
+            other => Err(format!("unknown fitness mode {:?}", other)),
+        }
+    }
+}
+
+/// Hardware normalization of fitness (flag: `--normalize-fitness <none|capacity|class>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FitnessNormalization {
+    /// Raw fitness
+    #[default]
+    None,
+    /// Fitness per Wh of battery capacity
+    Capacity,
+    /// Fitness relative to the mean of the same hardware class
+    HardwareClass,
+}
+
+impl FitnessNormalization {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FitnessNormalization::None => "none",
+            FitnessNormalization::Capacity => "per Wh of battery capacity",
+            FitnessNormalization::HardwareClass => "relative to hardware class mean",
+        }
+    }
+
+    /// Divisor of each hardware class (indexed like `HardwareType::ALL`),
+    /// from the raw fitness of this epoch's population
+    pub fn divisors<'a>(
+        &self,
+        population: impl Iterator<Item = (f32, &'a HardwareSpec)>,
+    ) -> [f32; HardwareType::ALL.len()] {
+        let mut divisors = [1.0; HardwareType::ALL.len()];
+        match self {
+            FitnessNormalization::None => {}
+            FitnessNormalization::Capacity => {
+                for (_, hardware) in population {
+                    divisors[hardware.hardware_type as usize] = hardware.battery_capacity_wh;
+                }
+            }
+            FitnessNormalization::HardwareClass => {
+                let mut sums = [(0.0, 0); HardwareType::ALL.len()];
+                for (fitness, hardware) in population {
+                    let (sum, n) = &mut sums[hardware.hardware_type as usize];
+                    *sum += fitness;
+                    *n += 1;
+                }
+                for (divisor, (sum, n)) in divisors.iter_mut().zip(sums) {
+                    let mean = if n > 0 { sum / n as f32 } else { 0.0 };
+                    // A class with no positive fitness keeps its raw values
+                    if mean > 0.0 {
+                        *divisor = mean;
+                    }
+                }
+            }
+        }
+        divisors
+    }
+}
+
+impl FromStr for FitnessNormalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(FitnessNormalization::None),
+            "capacity" => Ok(FitnessNormalization::Capacity),
+            "class" | "hardware-class" => Ok(FitnessNormalization::HardwareClass),
+            other => Err(format!("unknown fitness normalization {:?}", other)),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+/// Solar harvest forecaster
+/// Keeps one prediction per hour of day, each a scalar Kalman filter over
+/// the hourly mean solar output observed on previous days. Between two
+/// observations of the same hour (one day) the estimate drifts by the
+/// process variance; each observation pulls it towards the measurement in
+/// proportion to the current uncertainty. Output is the fleet-average panel
+/// output (per 100 W reference panel, clouds and catastrophe exposure
+/// included), so a storm shows up as a forecast error and is then slowly
+/// learned.
+use bevy::prelude::*;
+
+use crate::catastrophe::SolarExposure;
+use crate::components::{LoadedSolarProfiles, SimulationMetrics, Status};
+use crate::systems::solar_output_w;
+use crate::weather::Weather;
+
+/// Day-to-day variance of an hour's output (W²)
+pub const FORECAST_PROCESS_VAR: f32 = 25.0;
+/// Variance of one hourly observation around the true mean (W²)
+pub const FORECAST_MEASUREMENT_VAR: f32 = 100.0;
+
+/// Kalman estimate of one hour of the day
+#[derive(Debug, Clone, Copy, Default)]
+struct HourEstimate {
+    /// Expected output (W); None until the hour has been observed once
+    mean_w: Option<f32>,
+    variance: f32,
+}
+
+impl HourEstimate {
+    fn update(&mut self, observed_w: f32) {
+        match self.mean_w.as_mut() {
+            Some(mean_w) => {
+                let prior_var = self.variance + FORECAST_PROCESS_VAR;
+                let gain = prior_var / (prior_var + FORECAST_MEASUREMENT_VAR);
+                *mean_w += gain * (observed_w - *mean_w);
+                self.variance = (1.0 - gain) * prior_var;
+            }
+            None => {
+                self.mean_w = Some(observed_w);
+                self.variance = FORECAST_MEASUREMENT_VAR;
+            }
+        }
+    }
+}
+
+/// Per-hour harvest predictions learned from observed history
+#[derive(Resource, Debug, Default)]
+pub struct SolarForecast {
+    hours: [HourEstimate; 24],
+    /// Hour being observed, with its running sum and sample count
+    open_hour: Option<usize>,
+    open_sum_w: f32,
+    open_samples: u32,
+}
+
+impl SolarForecast {
+    /// Expected output for an hour of the day (W), once observed
+    pub fn predict(&self, hour: usize) -> Option<f32> {
+        self.hours[hour % 24].mean_w
+    }
+
+    /// One standard deviation of the prediction (W)
+    pub fn std_dev(&self, hour: usize) -> Option<f32> {
+        let estimate = &self.hours[hour % 24];
+        estimate.mean_w.map(|_| estimate.variance.sqrt())
+    }
+
+    /// Predicted energy over the next `hours` hours starting at `from_hour`
+    /// (Wh); None while any of those hours is still unobserved
+    pub fn energy_ahead_wh(&self, from_hour: usize, hours: usize) -> Option<f32> {
+        (from_hour..from_hour + hours)
+            .map(|hour| self.predict(hour))
+            .sum()
+    }
+
+    /// Add one sample of the current hour; closing an hour returns its
+    /// absolute forecast error (W) when a prediction existed
+    fn observe(&mut self, hour: usize, output_w: f32) -> Option<f32> {
+        let mut error = None;
+        if self.open_hour != Some(hour) {
+            if let Some(closed) = self.open_hour
+                && self.open_samples > 0
+            {
+                let mean_w = self.open_sum_w / self.open_samples as f32;
+                error = self.predict(closed).map(|p| (mean_w - p).abs());
+                self.hours[closed].update(mean_w);
+            }
+            self.open_hour = Some(hour);
+            self.open_sum_w = 0.0;
+            self.open_samples = 0;
+        }
+        self.open_sum_w += output_w;
+        self.open_samples += 1;
+        error
+    }
+}
+
+/// Per-epoch forecast accuracy
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct ForecastMetrics {
+    /// Hours closed with a prediction available
+    pub hours: u64,
+    pub abs_error_w: f32,
+}
+
+impl ForecastMetrics {
+    /// Mean absolute error of the hourly forecasts (W)
+    pub fn mean_abs_error_w(&self) -> Option<f32> {
+        (self.hours > 0).then(|| self.abs_error_w / self.hours as f32)
+    }
+}
+
+/// Forecast system - feed the fleet-average solar output to the forecaster
+pub fn forecast_system(
+    metrics: Res<SimulationMetrics>,
+    solar_profiles: Res<LoadedSolarProfiles>,
+    weather: Res<Weather>,
+    mut forecast: ResMut<SolarForecast>,
+    mut forecast_metrics: ResMut<ForecastMetrics>,
+    nodes: Query<(&SolarExposure, &Status)>,
+) {
+    let (exposure_sum, alive) = nodes
+        .iter()
+        .filter(|(_, status)| **status == Status::Alive)
+        .fold((0.0, 0), |(sum, n), (exposure, _)| {
+            (sum + exposure.0, n + 1)
+        });
+    if alive == 0 {
+        return;
+    }
+
+    let hour = metrics.current_hour as usize % 24;
+    let output_w = solar_output_w(&solar_profiles.0, hour) * weather.solar_factor() * exposure_sum
+        / alive as f32;
+    if let Some(error) = forecast.observe(hour, output_w) {
+        forecast_metrics.hours += 1;
+        forecast_metrics.abs_error_w += error;
+    }
+}
@@ -0,0 +1,122 @@
+/// Gateway nodes: two-tier edge-fog-cloud topology
+/// Gateways listed in the scenario file sit between the nodes and the
+/// cloud sink. Nodes address their reports to the nearest gateway instead
+/// of the sink, so link success depends on that short hop and low TX power
+/// becomes viable. Each gateway buffers what it receives and every
+/// `FLUSH_INTERVAL_S` forwards the batch over a cellular backhaul, paying a
+/// fixed connection cost plus a per-byte cost. Gateways are infrastructure:
+/// they do not evolve, and their energy is accounted separately.
+use bevy::prelude::*;
+
+use crate::scenario::Scenario;
+use crate::speed::SimTime;
+use crate::topology::Topology;
+use crate::units::WattHours;
+
+/// Simulated seconds between backhaul uplinks
+pub const FLUSH_INTERVAL_S: f32 = 900.0;
+/// Modem wake-up, attach and connection teardown per uplink
+pub const BACKHAUL_CONNECT: WattHours = WattHours::from_joules(5.0);
+/// Cellular transfer cost per payload byte
+pub const BACKHAUL_PER_BYTE: WattHours = WattHours::from_joules(5e-5);
+
+/// Aggregating gateway
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Gateway {
+    /// Reports received since the last uplink
+    pub pending_reports: u32,
+    /// Their payload, forwarded as received (bytes)
+    pub pending_bytes: f32,
+    /// Simulated seconds until the next uplink
+    pub flush_timer_s: f32,
+}
+
+/// Per-epoch gateway statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct GatewayMetrics {
+    /// Reports forwarded to the cloud
+    pub forwarded: u64,
+    pub uplinks: u64,
+    pub backhaul_energy_wh: WattHours,
+}
+
+/// Run condition: true when the scenario defines gateways
+pub fn gateways_enabled(scenario: Res<Scenario>) -> bool {
+    !scenario.gateways.is_empty()
+}
+
+/// Nearest gateway to a position, if any
+pub fn nearest_gateway(
+    topology: &Topology,
+    position: Vec2,
+    gateways: &[(Entity, Vec2)],
+) -> Option<(Entity, Vec2)> {
+    gateways.iter().copied().min_by(|(_, a), (_, b)| {
+        topology
+            .distance(position, *a)
+            .total_cmp(&topology.distance(position, *b))
+    })
+}
+
+/// Place the scenario's gateways (persist across epochs)
+pub fn spawn_gateways_system(mut commands: Commands, scenario: Res<Scenario>) {
+    for site in &scenario.gateways {
+        commands.spawn((
+            Gateway {
+                pending_reports: 0,
+                pending_bytes: 0.0,
+                flush_timer_s: FLUSH_INTERVAL_S,
+            },
+            Transform::from_xyz(site.x, site.y, 0.0),
+        ));
+    }
+    println!(
+        "🏢 {} gateways placed (backhaul uplink every {:.0} min)",
+        scenario.gateways.len(),
+        FLUSH_INTERVAL_S / 60.0
+    );
+}
+
+/// Backhaul system - forward buffered reports in periodic uplinks
+pub fn gateway_backhaul_system(
+    time: SimTime,
+    mut gateway_metrics: ResMut<GatewayMetrics>,
+    mut gateways: Query<&mut Gateway>,
+) {
+    let window_s = time.window_s();
+
+    for mut gateway in gateways.iter_mut() {
+        gateway.flush_timer_s -= window_s;
+        if gateway.flush_timer_s > 0.0 {
+            continue;
+        }
+        gateway.flush_timer_s += FLUSH_INTERVAL_S;
+        if gateway.pending_reports == 0 {
+            continue;
+        }
+
+        let energy = BACKHAUL_CONNECT + BACKHAUL_PER_BYTE * gateway.pending_bytes;
+        gateway_metrics.forwarded += gateway.pending_reports as u64;
+        gateway_metrics.uplinks += 1;
+        gateway_metrics.backhaul_energy_wh += energy;
+        gateway.pending_reports = 0;
+        gateway.pending_bytes = 0.0;
+    }
+}
+
+/// Draw gateways as blue squares
+pub fn render_gateways_system(mut gizmos: Gizmos, gateways: Query<&Transform, With<Gateway>>) {
+    let color = Color::srgb(0.2, 0.5, 1.0);
+    for transform in gateways.iter() {
+        let p = transform.translation.truncate();
+        let corners = [
+            p + Vec2::new(-8.0, -8.0),
+            p + Vec2::new(8.0, -8.0),
+            p + Vec2::new(8.0, 8.0),
+            p + Vec2::new(-8.0, 8.0),
+        ];
+        for i in 0..4 {
+            gizmos.line_2d(corners[i], corners[(i + 1) % 4], color);
+        }
+    }
+}
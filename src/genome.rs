@@ -0,0 +1,169 @@
+/// Genome persistence
+/// A genome (gene + hardware platform) is stored as one JSON object per
+/// line. `--seed-population <file>` warm-starts the grid from such a file
+/// instead of random founders, to continue evolution from a previous run's
+/// elite or to test how genomes transfer between climates. Extra fields on a
+/// line (epoch, fitness, ...) are ignored when loading. Catalog platforms
+/// are stored by name and resolved against the run's `--hardware-csv`;
+/// a name the catalog lacks falls back to the generic spec of its class.
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::components::Gene;
+use crate::hardware::{HardwareCatalog, HardwareSpec, HardwareType};
+
+/// One stored genome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGenome {
+    pub gene: Gene,
+    /// Class of the platform
+    pub hardware: HardwareType,
+    /// Platform name: a catalog row's, or the class's built-in one (absent
+    /// in files written before hardware catalogs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+impl SavedGenome {
+    pub fn new(gene: Gene, hardware: &HardwareSpec) -> Self {
+        Self {
+            gene,
+            hardware: hardware.hardware_type,
+            platform: Some(hardware.name().to_string()),
+        }
+    }
+
+    /// Whether `catalog` knows the saved platform (always true without a
+    /// platform name)
+    pub fn resolves(&self, catalog: &HardwareCatalog) -> bool {
+        self.platform
+            .as_deref()
+            .is_none_or(|name| catalog.find(name, self.hardware).is_some())
+    }
+
+    /// Platform to rebuild the node on: the named one from `catalog`, else
+    /// the generic spec of the saved class
+    pub fn hardware_spec(&self, catalog: &HardwareCatalog) -> HardwareSpec {
+        self.platform
+            .as_deref()
+            .and_then(|name| catalog.find(name, self.hardware))
+            .unwrap_or_else(|| HardwareSpec::new(self.hardware))
+    }
+}
+
+/// Genomes the initial grid is filled with (empty = random founders)
+#[derive(Resource, Debug, Default)]
+pub struct SeedPopulation(pub Vec<SavedGenome>);
+
+/// Read a JSON-lines genome file (blank lines are skipped)
+pub fn load_population(path: &str) -> Result<Vec<SavedGenome>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let mut genomes = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let genome = serde_json::from_str(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        genomes.push(genome);
+    }
+    if genomes.is_empty() {
+        return Err("no genomes in file".into());
+    }
+    Ok(genomes)
+}
+
+/// Saved genomes whose platform `catalog` does not know, one per platform
+/// name; they would run on the generic spec of their class
+pub fn unresolved_platforms<'a>(
+    genomes: &'a [SavedGenome],
+    catalog: &HardwareCatalog,
+) -> Vec<&'a SavedGenome> {
+    let mut unresolved: Vec<&SavedGenome> = Vec::new();
+    for genome in genomes {
+        if !genome.resolves(catalog)
+            && unresolved
+                .iter()
+                .all(|other| other.platform != genome.platform)
+        {
+            unresolved.push(genome);
+        }
+    }
+    unresolved
+}
+
+/// One line of the elite file: a genome plus where it ranked
+#[derive(Debug, Clone, Serialize)]
+pub struct EliteRecord {
+    pub epoch: u32,
+    /// 1 = fittest survivor of the epoch
+    pub rank: usize,
+    pub fitness: f32,
+    pub node_id: u64,
+    /// The node's annotation labels
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(flatten)]
+    pub genome: SavedGenome,
+}
+
+/// Optional best-genome export (flag: `--elite-out <path>`)
+/// Every epoch appends the top `--elite-top` survivors and flushes, so the
+/// best-so-far genomes survive a crash or an interrupted run. The file
+/// loads directly with `--seed-population`.
+#[derive(Resource, Default)]
+pub struct EliteExporter {
+    writer: Option<BufWriter<File>>,
+    top: usize,
+}
+
+impl EliteExporter {
+    /// Open the output file; export is disabled (with a warning) on failure
+    pub fn create(path: Option<&str>, top: usize) -> Self {
+        let writer = path.and_then(|path| match create_file(path) {
+            Ok(file) => {
+                println!("🏆 Saving the top {} genomes per epoch to {}", top, path);
+                Some(BufWriter::new(file))
+            }
+            Err(e) => {
+                eprintln!("⚠️ Cannot create elite file {}: {}", path, e);
+                None
+            }
+        });
+
+        Self { writer, top }
+    }
+
+    /// Number of genomes wanted per epoch (0 when export is disabled)
+    pub fn top(&self) -> usize {
+        if self.writer.is_some() { self.top } else { 0 }
+    }
+
+    /// Append one epoch's elite (no-op when export is disabled)
+    pub fn record(&mut self, records: &[EliteRecord]) {
+        let Some(writer) = self.writer.as_mut() else {
+            return;
+        };
+
+        let result = records
+            .iter()
+            .try_for_each(|record| -> Result<(), Box<dyn Error>> {
+                serde_json::to_writer(&mut *writer, record)?;
+                writer.write_all(b"\n")?;
+                Ok(())
+            });
+        if let Err(e) = result.and_then(|_| Ok(writer.flush()?)) {
+            eprintln!("⚠️ Elite export failed: {}", e);
+        }
+    }
+}
+
+/// Create a file, creating missing parent directories first
+fn create_file(path: &str) -> Result<File, Box<dyn Error>> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(File::create(path)?)
+}
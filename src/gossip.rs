@@ -0,0 +1,185 @@
+/// Horizontal gene transfer via neighbor gossip
+/// Alive nodes occasionally ask a random alive grid neighbor for one gene
+/// fragment (policy and its thresholds, inference frequency or TX power)
+/// and adopt it mid-epoch.
+/// Both sides pay one radio packet, so gossip competes with result reports
+/// for energy and (on LoRa) duty-cycle budget. Donors below the trust
+/// threshold are avoided; Byzantine donors take the request and refuse.
+/// Disabled by default: vertical inheritance only, unless `--gossip-rate`
+/// is given.
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::components::{Battery, Gene, GeneticRng, SimulationMetrics, Status, TunedPolicy};
+use crate::config::SimConfig;
+use crate::radio::Radio;
+use crate::speed::SimTime;
+use crate::topology::Topology;
+use crate::trust::{Byzantine, Reputation, TrustMetrics};
+use crate::units::{SECONDS_PER_HOUR, WattHours};
+
+/// Request or gene-fragment packet (header + serialized fragment)
+pub const GOSSIP_PAYLOAD_BYTES: f32 = 24.0;
+
+/// Gene fragment that can travel between neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneFragment {
+    Policy,
+    InferenceFrequency,
+    TxPower,
+}
+
+impl GeneFragment {
+    pub const ALL: [GeneFragment; 3] = [
+        GeneFragment::Policy,
+        GeneFragment::InferenceFrequency,
+        GeneFragment::TxPower,
+    ];
+
+    /// Copy this fragment from `donor` into `gene`
+    pub fn transfer(&self, donor: &Gene, gene: &mut Gene) {
+        match self {
+            GeneFragment::Policy => {
+                gene.policy = donor.policy;
+                gene.policy_params = donor.policy_params;
+            }
+            GeneFragment::InferenceFrequency => {
+                gene.inference_frequency = donor.inference_frequency
+            }
+            GeneFragment::TxPower => gene.tx_power_dbm = donor.tx_power_dbm,
+        }
+    }
+}
+
+/// Per-epoch gossip statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct GossipMetrics {
+    pub exchanges: u64,
+    pub energy_wh: WattHours,
+}
+
+/// Run condition: true when gossip is enabled
+pub fn gossip_enabled(config: Res<SimConfig>) -> bool {
+    config.gossip_rate > 0.0
+}
+
+/// Everything gossip reads or updates on a node
+type GossipQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static mut Gene,
+        &'static mut Radio,
+        &'static mut Battery,
+        &'static mut TunedPolicy,
+        &'static mut Reputation,
+        &'static Status,
+        Has<Byzantine>,
+    ),
+>;
+
+/// Gossip system - neighbor gene-fragment exchange
+/// Each alive node starts an exchange with probability
+/// `rate × simulated hours this tick`, picking a donor among its alive
+/// Moore neighbors (radius 1, boundary per `Topology`)
+#[allow(clippy::too_many_arguments)]
+pub fn gossip_system(
+    time: SimTime,
+    config: Res<SimConfig>,
+    topology: Res<Topology>,
+    mut genetic_rng: ResMut<GeneticRng>,
+    mut gossip_metrics: ResMut<GossipMetrics>,
+    mut metrics: ResMut<SimulationMetrics>,
+    mut trust_metrics: ResMut<TrustMetrics>,
+    mut query: GossipQuery,
+) {
+    let window_s = time.window_s();
+    let exchange_p = (config.gossip_rate * window_s / SECONDS_PER_HOUR).clamp(0.0, 1.0);
+    let rng = &mut genetic_rng.0;
+
+    // 1. Snapshot alive nodes by grid cell (genes as of the start of the tick)
+    let mut by_cell: HashMap<IVec2, (Entity, Gene, bool)> = HashMap::new();
+    for (entity, transform, gene, _, _, _, reputation, status, _) in query.iter() {
+        if *status != Status::Dead {
+            let cell = topology.cell_of(transform.translation.truncate());
+            by_cell.insert(cell, (entity, *gene, reputation.trusted()));
+        }
+    }
+
+    // 2. Pick exchanges among trusted donors: (receiver, donor, donor genome, fragment)
+    let mut cells: Vec<IVec2> = by_cell.keys().copied().collect();
+    cells.sort_by_key(|cell| (cell.x, cell.y)); // Stable order for reproducible draws
+
+    let mut exchanges = Vec::new();
+    for cell in cells {
+        if !rng.random_bool(exchange_p as f64) {
+            continue;
+        }
+        let donors: Vec<IVec2> = topology
+            .neighbors(cell, 1)
+            .into_iter()
+            .filter(|neighbor| {
+                by_cell
+                    .get(neighbor)
+                    .is_some_and(|(_, _, trusted)| *trusted)
+            })
+            .collect();
+        if donors.is_empty() {
+            continue;
+        }
+
+        let (receiver, _, _) = by_cell[&cell];
+        let (donor, donor_gene, _) = by_cell[&donors[rng.random_range(0..donors.len())]];
+        let fragment = GeneFragment::ALL[rng.random_range(0..GeneFragment::ALL.len())];
+        exchanges.push((receiver, donor, donor_gene, fragment));
+    }
+
+    // 3. Apply: receiver adopts the fragment, both sides pay one packet
+    //    (a Byzantine donor keeps its packet and sends nothing back)
+    for (receiver, donor, donor_gene, fragment) in exchanges {
+        let refused = query.get(donor).is_ok_and(|(.., byzantine)| byzantine);
+        if let Ok((.., mut reputation, _, _)) = query.get_mut(donor) {
+            reputation.record(!refused);
+        }
+        if refused {
+            trust_metrics.refusals += 1;
+        }
+
+        for entity in [receiver, donor] {
+            if refused && entity == donor {
+                continue;
+            }
+            let Ok((_, _, mut gene, mut radio, mut battery, mut tuned, _, _, _)) =
+                query.get_mut(entity)
+            else {
+                continue;
+            };
+
+            let airtime_s = radio.radio_type.airtime_s(GOSSIP_PAYLOAD_BYTES);
+            let energy = radio
+                .radio_type
+                .tx_power(radio.tx_power_dbm)
+                .over_s(airtime_s);
+            battery.drain(energy.wh());
+            radio.off_time_s += radio.radio_type.off_time_s(airtime_s);
+            gossip_metrics.energy_wh += energy;
+            metrics.total_energy_consumed += energy;
+
+            if entity == receiver && !refused {
+                fragment.transfer(&donor_gene, &mut gene);
+                let tx_power_dbm = gene.tx_power_dbm;
+                radio.set_tx_power_dbm(tx_power_dbm);
+                if fragment == GeneFragment::Policy {
+                    // An adopted policy restarts lifetime tuning from the donor's genome
+                    tuned.0 = gene.policy_params;
+                }
+            }
+        }
+        if !refused {
+            gossip_metrics.exchanges += 1;
+        }
+    }
+}
@@ -0,0 +1,339 @@
+/// gRPC experiment control (flag: `--grpc-port <port>`)
+/// Lets an orchestrator drive the simulation as an evaluation worker:
+/// start, step N genetic epochs, read the population, inject genomes, stop.
+/// The run starts paused and only advances on StartRun or Step. The tonic
+/// server runs on its own thread; each call becomes a command that
+/// `grpc_control_system` executes inside the ECS and answers over a oneshot
+/// channel. Wire contract: proto/edgegenesis.proto.
+use bevy::prelude::*;
+use std::net::SocketAddr;
+use tokio::sync::{mpsc, oneshot};
+use tonic::{Request, Response, Status as RpcStatus};
+
+use crate::components::{
+    Battery, EdgeNodeBundle, EpochCount, Gene, Lineage, Status, SurvivalScore,
+};
+use crate::config::SimConfig;
+use crate::genome::SavedGenome;
+use crate::hardware::{HardwareCatalog, HardwareSpec};
+use crate::phylogeny::LineageLog;
+
+/// Generated by build.rs
+mod proto {
+    include!(concat!(env!("OUT_DIR"), "/edgegenesis.Control.rs"));
+}
+
+use proto::control_server::{Control, ControlServer};
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StartRunRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StepRequest {
+    #[prost(uint32, tag = "1")]
+    pub epochs: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetPopulationRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StopRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RunStatus {
+    /// Genetic epochs completed
+    #[prost(uint32, tag = "1")]
+    pub epoch: u32,
+    #[prost(bool, tag = "2")]
+    pub paused: bool,
+    #[prost(uint32, tag = "3")]
+    pub alive: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Node {
+    #[prost(uint64, tag = "1")]
+    pub id: u64,
+    #[prost(uint64, optional, tag = "2")]
+    pub parent: Option<u64>,
+    #[prost(string, tag = "3")]
+    pub model: String,
+    #[prost(string, tag = "4")]
+    pub policy: String,
+    #[prost(string, tag = "5")]
+    pub hardware: String,
+    #[prost(float, tag = "6")]
+    pub battery_wh: f32,
+    #[prost(bool, tag = "7")]
+    pub alive: bool,
+    #[prost(float, tag = "8")]
+    pub survival_score: f32,
+    /// SavedGenome JSON, accepted by InjectGenome and `--seed-population`
+    #[prost(string, tag = "9")]
+    pub genome_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Population {
+    #[prost(uint32, tag = "1")]
+    pub epoch: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub nodes: Vec<Node>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct InjectGenomeRequest {
+    #[prost(string, tag = "1")]
+    pub genome_json: String,
+    /// World position (grid center is 0, 0)
+    #[prost(float, tag = "2")]
+    pub x: f32,
+    #[prost(float, tag = "3")]
+    pub y: f32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct InjectGenomeReply {
+    #[prost(uint64, tag = "1")]
+    pub node_id: u64,
+}
+
+/// One RPC, handed from the server thread to the ECS
+enum ControlCommand {
+    Start(oneshot::Sender<RunStatus>),
+    Step(u32, oneshot::Sender<RunStatus>),
+    Population(oneshot::Sender<Population>),
+    Inject(SavedGenome, Vec2, oneshot::Sender<InjectGenomeReply>),
+    Stop(oneshot::Sender<RunStatus>),
+}
+
+/// Server side: forwards every call to the ECS and awaits the answer
+struct ControlService {
+    commands: mpsc::UnboundedSender<ControlCommand>,
+}
+
+impl ControlService {
+    async fn call<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> ControlCommand,
+    ) -> Result<Response<T>, RpcStatus> {
+        let (reply, answer) = oneshot::channel();
+        self.commands
+            .send(command(reply))
+            .map_err(|_| RpcStatus::unavailable("simulation has stopped"))?;
+        answer
+            .await
+            .map(Response::new)
+            .map_err(|_| RpcStatus::aborted("request superseded or simulation stopped"))
+    }
+}
+
+#[tonic::async_trait]
+impl Control for ControlService {
+    async fn start_run(
+        &self,
+        _request: Request<StartRunRequest>,
+    ) -> Result<Response<RunStatus>, RpcStatus> {
+        self.call(ControlCommand::Start).await
+    }
+
+    async fn step(&self, request: Request<StepRequest>) -> Result<Response<RunStatus>, RpcStatus> {
+        let epochs = request.into_inner().epochs.max(1);
+        self.call(|reply| ControlCommand::Step(epochs, reply)).await
+    }
+
+    async fn get_population(
+        &self,
+        _request: Request<GetPopulationRequest>,
+    ) -> Result<Response<Population>, RpcStatus> {
+        self.call(ControlCommand::Population).await
+    }
+
+    async fn inject_genome(
+        &self,
+        request: Request<InjectGenomeRequest>,
+    ) -> Result<Response<InjectGenomeReply>, RpcStatus> {
+        let request = request.into_inner();
+        let genome: SavedGenome = serde_json::from_str(&request.genome_json)
+            .map_err(|e| RpcStatus::invalid_argument(format!("bad genome: {}", e)))?;
+        let position = Vec2::new(request.x, request.y);
+        self.call(|reply| ControlCommand::Inject(genome, position, reply))
+            .await
+    }
+
+    async fn stop(&self, _request: Request<StopRequest>) -> Result<Response<RunStatus>, RpcStatus> {
+        self.call(ControlCommand::Stop).await
+    }
+}
+
+/// ECS side of the control channel (inert when gRPC is disabled)
+#[derive(Resource, Default)]
+pub struct GrpcControl {
+    commands: Option<mpsc::UnboundedReceiver<ControlCommand>>,
+    /// Epoch a running Step pauses at, and its caller
+    step_until: Option<(u32, oneshot::Sender<RunStatus>)>,
+}
+
+impl GrpcControl {
+    /// Serve the control API on `port` from a background thread
+    pub fn start(port: Option<u16>) -> Self {
+        let Some(port) = port else {
+            return Self::default();
+        };
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let addr = SocketAddr::from(([0, 0, 0, 0], port));
+
+        std::thread::spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    eprintln!("⚠️ Cannot start gRPC runtime: {}", e);
+                    return;
+                }
+            };
+            let service = ControlServer::new(ControlService { commands: sender });
+            let served = runtime.block_on(
+                tonic::transport::Server::builder()
+                    .add_service(service)
+                    .serve(addr),
+            );
+            if let Err(e) = served {
+                eprintln!("⚠️ gRPC control server on port {} failed: {}", port, e);
+            }
+        });
+        println!(
+            "🛰️ gRPC control on port {} (paused until StartRun/Step)",
+            port
+        );
+
+        Self {
+            commands: Some(receiver),
+            step_until: None,
+        }
+    }
+}
+
+pub fn grpc_enabled(config: Res<SimConfig>) -> bool {
+    config.grpc_port.is_some()
+}
+
+/// Startup: hold simulated time until the orchestrator starts the run
+pub fn pause_for_grpc_system(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+/// Execute pending control commands and finish Step calls
+#[allow(clippy::too_many_arguments)]
+pub fn grpc_control_system(
+    mut commands: Commands,
+    mut control: ResMut<GrpcControl>,
+    mut time: ResMut<Time<Virtual>>,
+    epoch_count: Res<EpochCount>,
+    config: Res<SimConfig>,
+    catalog: Res<HardwareCatalog>,
+    mut lineage_log: ResMut<LineageLog>,
+    mut exit: MessageWriter<AppExit>,
+    nodes: Query<(
+        &Lineage,
+        &Gene,
+        &HardwareSpec,
+        &Battery,
+        &Status,
+        &SurvivalScore,
+    )>,
+) {
+    let status = |time: &Time<Virtual>| RunStatus {
+        epoch: epoch_count.0,
+        paused: time.is_paused(),
+        alive: nodes
+            .iter()
+            .filter(|(_, _, _, _, status, _)| **status == Status::Alive)
+            .count() as u32,
+    };
+
+    if let Some((target, _)) = &control.step_until
+        && epoch_count.0 >= *target
+        && let Some((_, reply)) = control.step_until.take()
+    {
+        time.pause();
+        let _ = reply.send(status(&time));
+    }
+
+    let control = control.as_mut();
+    let Some(receiver) = control.commands.as_mut() else {
+        return;
+    };
+    while let Ok(command) = receiver.try_recv() {
+        match command {
+            ControlCommand::Start(reply) => {
+                time.unpause();
+                let _ = reply.send(status(&time));
+            }
+            ControlCommand::Step(epochs, reply) => {
+                // A newer Step replaces an unfinished one (its caller is aborted)
+                control.step_until = Some((epoch_count.0 + epochs, reply));
+                time.unpause();
+            }
+            ControlCommand::Population(reply) => {
+                let nodes = nodes
+                    .iter()
+                    .map(|(lineage, gene, hardware, battery, status, score)| Node {
+                        id: lineage.id,
+                        parent: lineage.parent,
+                        model: gene.model_type.name().to_string(),
+                        policy: gene.policy.name().to_string(),
+                        hardware: hardware.name().to_string(),
+                        battery_wh: battery.wh(),
+                        alive: *status == Status::Alive,
+                        survival_score: score.0,
+                        genome_json: serde_json::to_string(&SavedGenome::new(*gene, hardware))
+                            .unwrap_or_default(),
+                    })
+                    .collect();
+                let _ = reply.send(Population {
+                    epoch: epoch_count.0,
+                    nodes,
+                });
+            }
+            ControlCommand::Inject(genome, position, reply) => {
+                let hardware = match config.locked_hardware {
+                    Some(hw_type) => HardwareSpec::new(hw_type),
+                    None => {
+                        if !genome.resolves(&catalog) {
+                            warn!(
+                                "Injected platform {:?} is not in the hardware catalog: using the generic {} spec",
+                                genome.platform,
+                                HardwareSpec::new(genome.hardware).name()
+                            );
+                        }
+                        genome.hardware_spec(&catalog)
+                    }
+                };
+                let lineage = lineage_log.register(None, &genome.gene);
+                commands.spawn(EdgeNodeBundle::new(
+                    genome.gene,
+                    hardware,
+                    lineage,
+                    Transform::from_xyz(position.x, position.y, 0.0),
+                ));
+                println!(
+                    "🛰️ gRPC injected {} / {} (node {})",
+                    genome.gene.model_type.name(),
+                    genome.gene.policy.name(),
+                    lineage.id
+                );
+                let _ = reply.send(InjectGenomeReply {
+                    node_id: lineage.id,
+                });
+            }
+            ControlCommand::Stop(reply) => {
+                let _ = reply.send(status(&time));
+                exit.write(AppExit::Success);
+            }
+        }
+    }
+}
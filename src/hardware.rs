@@ -1,20 +1,339 @@
-use bevy::prelude::*;
-use serde::Deserialize;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+use crate::data_loader::{HardwareProfileCSV, REFERENCE_PANEL_AREA_M2};
+use crate::models::{DeviceType, RealModelType};
+use crate::solar::PANEL_EFFICIENCY;
+
+/// Solar panel price (USD per rated watt, small off-grid modules)
+pub const PANEL_USD_PER_W: f32 = 1.5;
+/// Battery pack price (USD per Wh, small LiPo / LiFePO4 packs)
+pub const BATTERY_USD_PER_WH: f32 = 1.0;
+/// Panel embodied carbon (kgCO2e per rated watt, crystalline silicon)
+pub const PANEL_KGCO2_PER_W: f32 = 0.5;
+/// Battery embodied carbon (kgCO2e per Wh, lithium-ion cells)
+pub const BATTERY_KGCO2_PER_WH: f32 = 0.1;
+/// Longest acceptable single inference on a board (seconds)
+pub const INFERENCE_DEADLINE_S: f32 = 1.0;
+/// Catalog rows without a class below this RAM are microcontrollers (MB)
+const MCU_RAM_MB: f32 = 64.0;
+/// Catalog rows without a class from this compute are GPU boards (GFLOPS)
+const GPU_GFLOPS: f32 = 200.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HardwareType {
     ESP32,
     RaspberryPi4,
     JetsonNano,
 }
 
+impl FromStr for HardwareType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "esp32" => Ok(HardwareType::ESP32),
+            "rpi4" | "pi4" | "raspberrypi4" => Ok(HardwareType::RaspberryPi4),
+            "jetson" | "jetsonnano" => Ok(HardwareType::JetsonNano),
+            other => Err(format!("unknown hardware {:?}", other)),
+        }
+    }
+}
+
+/// Irradiance of standard test conditions, at which panels are rated (W/m²)
+const STC_IRRADIANCE_W_M2: f32 = 1000.0;
+
+/// Best conversion efficiency of an MPPT controller
+const MPPT_PEAK_EFFICIENCY: f32 = 0.97;
+
+/// MPPT fixed overhead (quiescent draw, tracking perturbation) as a share
+/// of the panel's rating; it dominates in dim light
+const MPPT_OVERHEAD: f32 = 0.02;
+
+/// PWM efficiency at full rating: the panel is held at the battery
+/// voltage, well below its maximum power point
+const PWM_FULL_SUN_EFFICIENCY: f32 = 0.75;
+
+/// PWM efficiency gain in dim light, where the maximum power point voltage
+/// sags towards the battery's
+const PWM_DIM_LIGHT_GAIN: f32 = 0.05;
+
+/// Charge controller between a node's panel and its battery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChargeController {
+    /// Lossless: all of the panel output reaches the battery
+    Ideal,
+    /// Switch tying the panel to the battery voltage
+    Pwm,
+    /// Maximum power point tracking DC-DC converter
+    #[default]
+    Mppt,
+}
+
+impl FromStr for ChargeController {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ideal" => Ok(ChargeController::Ideal),
+            "pwm" => Ok(ChargeController::Pwm),
+            "mppt" => Ok(ChargeController::Mppt),
+            other => Err(format!("unknown charge controller {:?}", other)),
+        }
+    }
+}
+
+impl ChargeController {
+    /// Share of the panel output delivered to the battery with the panel at
+    /// `load` of its rating (0-1)
+    pub fn efficiency(&self, load: f32) -> f32 {
+        let load = load.clamp(0.0, 1.0);
+        match self {
+            ChargeController::Ideal => 1.0,
+            ChargeController::Pwm => PWM_FULL_SUN_EFFICIENCY + PWM_DIM_LIGHT_GAIN * (1.0 - load),
+            ChargeController::Mppt => MPPT_PEAK_EFFICIENCY * load / (load + MPPT_OVERHEAD),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChargeController::Ideal => "ideal",
+            ChargeController::Pwm => "PWM",
+            ChargeController::Mppt => "MPPT",
+        }
+    }
+}
+
+/// Solar panel fitted to a node, with its charge controller
+/// The solar profiles give the output of a reference panel (0.12 m²,
+/// horizontal, the profile's efficiency); a node's panel scales that by
+/// its area, its efficiency against the reference 18% and its orientation,
+/// and delivers at most its rating and the board's charge input
+/// (`HardwareSpec::max_solar_input_w`). The controller then loses a share
+/// that depends on how hard the panel is lit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct SolarPanel {
+    /// Nameplate output at 1000 W/m² (W)
+    pub rated_w: f32,
+    pub area_m2: f32,
+    /// Module efficiency at standard test conditions (0-1)
+    pub efficiency: f32,
+    /// Tilt and orientation: irradiance captured relative to the reference
+    /// panel (1 = as the profile, below 1 facing away from the sun)
+    pub orientation: f32,
+    pub controller: ChargeController,
+}
+
+impl Default for SolarPanel {
+    /// The reference panel
+    fn default() -> Self {
+        Self::rated(
+            REFERENCE_PANEL_AREA_M2 * PANEL_EFFICIENCY * STC_IRRADIANCE_W_M2,
+            PANEL_EFFICIENCY,
+        )
+    }
+}
+
+impl FromStr for SolarPanel {
+    type Err = String;
+
+    /// `rated_w[,area_m2[,efficiency[,orientation]]]`, e.g. `5` or
+    /// `10,0.08,0.2,0.9`; the area defaults to the rating's at standard
+    /// test conditions
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<f32>()
+                    .map_err(|e| format!("{:?}: {}", value, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (&rated_w, rest) = values.split_first().ok_or("empty panel spec")?;
+        if values.len() > 4
+            || values
+                .iter()
+                .any(|value| !value.is_finite() || *value <= 0.0)
+        {
+            return Err(format!(
+                "expected rated_w[,area_m2[,efficiency[,orientation]]] > 0, got {:?}",
+                s
+            ));
+        }
+        let efficiency = rest.get(1).copied().unwrap_or(PANEL_EFFICIENCY);
+        if efficiency > 1.0 {
+            return Err(format!("panel efficiency {} is above 1", efficiency));
+        }
+        let mut panel = Self::rated(rated_w, efficiency);
+        if let Some(&area_m2) = rest.first() {
+            panel.area_m2 = area_m2;
+        }
+        if let Some(&orientation) = rest.get(2) {
+            panel.orientation = orientation;
+        }
+        Ok(panel)
+    }
+}
+
+impl SolarPanel {
+    /// Horizontal panel of `rated_w`, sized for it at standard test
+    /// conditions
+    pub fn rated(rated_w: f32, efficiency: f32) -> Self {
+        Self {
+            rated_w,
+            area_m2: rated_w / (STC_IRRADIANCE_W_M2 * efficiency),
+            efficiency,
+            orientation: 1.0,
+            controller: ChargeController::default(),
+        }
+    }
+
+    /// Output under the light giving the reference panel `reference_w`,
+    /// capped by the rating (W)
+    pub fn converted_w(&self, reference_w: f32) -> f32 {
+        let scale = self.area_m2 / REFERENCE_PANEL_AREA_M2 * self.efficiency / PANEL_EFFICIENCY
+            * self.orientation;
+        (reference_w * scale).min(self.rated_w)
+    }
+
+    /// What reaches the battery: the output, capped by the board's charge
+    /// input, less the controller's losses (W)
+    pub fn output_w(&self, reference_w: f32, hardware: &HardwareSpec) -> f32 {
+        let panel_w = self.converted_w(reference_w);
+        panel_w.min(hardware.max_solar_input_w) * self.controller.efficiency(panel_w / self.rated_w)
+    }
+}
+
+/// Battery cell chemistry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BatteryChemistry {
+    LiIon,
+    LiFePO4,
+    Supercapacitor,
+}
+
+/// Reference temperature of the self-discharge rates (°C)
+pub const SELF_DISCHARGE_REFERENCE_C: f32 = 25.0;
+
+/// Ambient temperature without profile data (°C)
+pub const DEFAULT_AMBIENT_C: f32 = 25.0;
+
+impl BatteryChemistry {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatteryChemistry::LiIon => "Li-ion",
+            BatteryChemistry::LiFePO4 => "LiFePO4",
+            BatteryChemistry::Supercapacitor => "Supercap",
+        }
+    }
+
+    /// Share of the charging energy that can be drawn back out (the
+    /// round-trip loss, booked while charging)
+    pub fn charge_efficiency(&self) -> f32 {
+        match self {
+            BatteryChemistry::LiIon => 0.90,
+            BatteryChemistry::LiFePO4 => 0.94,
+            BatteryChemistry::Supercapacitor => 0.97,
+        }
+    }
+
+    /// Share of the charge lost per day at rest, at 25 °C
+    pub fn self_discharge_per_day(&self) -> f32 {
+        match self {
+            BatteryChemistry::LiIon => 0.0007,        // ~2% a month
+            BatteryChemistry::LiFePO4 => 0.001,       // ~3% a month
+            BatteryChemistry::Supercapacitor => 0.15, // Leakage current
+        }
+    }
+
+    /// Warming that doubles the self-discharge rate (°C)
+    pub fn self_discharge_doubling_c(&self) -> f32 {
+        match self {
+            BatteryChemistry::LiIon => 10.0,
+            BatteryChemistry::LiFePO4 => 12.0,
+            BatteryChemistry::Supercapacitor => 8.0,
+        }
+    }
+
+    /// Share of the rated capacity above the protection cutoff
+    pub fn usable_depth_of_discharge(&self) -> f32 {
+        match self {
+            BatteryChemistry::LiIon => 0.85,
+            BatteryChemistry::LiFePO4 => 0.95,
+            // Energy goes with V²: the converter cuts off at half voltage
+            BatteryChemistry::Supercapacitor => 0.75,
+        }
+    }
+
+    /// Full-depth cycles until end of life
+    pub fn cycle_life(&self) -> f32 {
+        match self {
+            BatteryChemistry::LiIon => 500.0,
+            BatteryChemistry::LiFePO4 => 2000.0,
+            BatteryChemistry::Supercapacitor => 500_000.0,
+        }
+    }
+
+    /// Share of the charge lost per hour at `celsius`
+    pub fn self_discharge_per_h(&self, celsius: f32) -> f32 {
+        let warming = (celsius - SELF_DISCHARGE_REFERENCE_C) / self.self_discharge_doubling_c();
+        self.self_discharge_per_day() / 24.0 * 2f32.powf(warming)
+    }
+}
+
 /// Hardware specification component
-#[derive(Component, Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
 pub struct HardwareSpec {
     pub hardware_type: HardwareType,
+    /// Display name: the platform's, or the catalog row's
+    pub name: Arc<str>,
+    /// Rated battery capacity (Wh)
     pub battery_capacity_wh: f32,
+    pub battery_chemistry: BatteryChemistry,
     pub idle_power_w: f32,
+    /// Draw while scheduled asleep (deep sleep / suspend, W)
+    pub sleep_power_w: f32,
     pub max_solar_input_w: f32,
+    /// Storage → RAM model load throughput, deserialization included (MB/s)
+    pub model_load_mb_per_s: f32,
+    /// Extra draw above idle while loading a model (W)
+    pub model_load_power_w: f32,
+    /// Field mean time between failures (hours)
+    pub mtbf_h: f32,
+    /// Compute board price, enclosure and radio included (USD)
+    pub board_cost_usd: f32,
+    /// Compute board embodied carbon, manufacturing to shipping (kgCO2e)
+    pub board_embodied_kgco2: f32,
+    /// Steady-state rise above ambient per watt drawn, enclosure included
+    /// (°C/W)
+    pub thermal_resistance_c_per_w: f32,
+    /// Time to close 63% of the gap to steady state (simulated seconds)
+    pub thermal_time_constant_s: f32,
+    /// Die temperature where the governor starts cutting clocks (°C)
+    pub throttle_temp_c: f32,
+    /// Memory a model can be loaded into (MB)
+    pub ram_mb: f32,
+    /// Sustained compute (GFLOPS)
+    pub compute_gflops: f32,
+    /// Inference speed relative to the Raspberry Pi 4 the model figures of
+    /// models.rs (and the power CSV) were measured on
+    pub inference_speedup: f32,
+    /// Inference draw above idle relative to the Raspberry Pi 4
+    pub inference_power_factor: f32,
+}
+
+impl HardwareType {
+    /// Every hardware platform that can be deployed
+    pub const ALL: [HardwareType; 3] = [
+        HardwareType::ESP32,
+        HardwareType::RaspberryPi4,
+        HardwareType::JetsonNano,
+    ];
 }
 
 impl HardwareSpec {
@@ -22,30 +341,215 @@ impl HardwareSpec {
         match hw_type {
             HardwareType::ESP32 => Self {
                 hardware_type: HardwareType::ESP32,
+                name: "ESP32".into(),
                 battery_capacity_wh: 1.5, // Tiny LiPo/Capacitor
+                battery_chemistry: BatteryChemistry::LiIon, // Pouch LiPo
                 idle_power_w: 0.1,        // Ultra-low power
+                sleep_power_w: 0.0005,    // Deep sleep, RTC running
                 max_solar_input_w: 2.0,   // Tiny 2W panel
+                model_load_mb_per_s: 2.0, // SPI flash
+                model_load_power_w: 0.15,
+                mtbf_h: 30_000.0, // Few parts, no storage wear
+                board_cost_usd: 8.0,
+                board_embodied_kgco2: 1.5,
+                thermal_resistance_c_per_w: 30.0, // Bare module, no heatsink
+                thermal_time_constant_s: 300.0,
+                throttle_temp_c: 100.0,
+                ram_mb: DeviceType::ESP32.available_ram_mb(),
+                compute_gflops: DeviceType::ESP32.compute_gflops(),
+                inference_speedup: 0.02,      // Single-precision FPU, no SIMD
+                inference_power_factor: 0.02, // ~0.1 W above idle
             },
             HardwareType::RaspberryPi4 => Self {
                 hardware_type: HardwareType::RaspberryPi4,
-                battery_capacity_wh: 11.1, // UPS HAT
-                idle_power_w: 2.5,         // Standard idle
-                max_solar_input_w: 20.0,   // 20W Panel
+                name: "RPi4".into(),
+                battery_capacity_wh: 11.1,                  // UPS HAT
+                battery_chemistry: BatteryChemistry::LiIon, // 18650 cells
+                idle_power_w: 2.5,                          // Standard idle
+                sleep_power_w: 0.4,      // Halted, wake on GPIO (no true suspend)
+                max_solar_input_w: 20.0, // 20W Panel
+                model_load_mb_per_s: 40.0, // microSD
+                model_load_power_w: 1.5,
+                mtbf_h: 8_000.0, // microSD corruption dominates
+                board_cost_usd: 55.0,
+                board_embodied_kgco2: 8.0,
+                thermal_resistance_c_per_w: 8.0, // Passive case
+                thermal_time_constant_s: 900.0,
+                throttle_temp_c: 80.0, // Firmware soft limit
+                ram_mb: DeviceType::RaspberryPi4.available_ram_mb(),
+                compute_gflops: DeviceType::RaspberryPi4.compute_gflops(),
+                inference_speedup: 1.0, // Reference board
+                inference_power_factor: 1.0,
             },
             HardwareType::JetsonNano => Self {
                 hardware_type: HardwareType::JetsonNano,
+                name: "Jetson".into(),
                 battery_capacity_wh: 20.0, // Larger battery
+                battery_chemistry: BatteryChemistry::LiFePO4,
                 idle_power_w: 5.0,         // GPU idle
+                sleep_power_w: 0.6,        // SC7 suspend
                 max_solar_input_w: 40.0,   // 40W Panel
+                model_load_mb_per_s: 80.0, // eMMC + TensorRT engine load
+                model_load_power_w: 2.0,
+                mtbf_h: 15_000.0, // Fan and thermal cycling
+                board_cost_usd: 149.0,
+                board_embodied_kgco2: 20.0,
+                thermal_resistance_c_per_w: 4.0, // Heatsink + fan
+                thermal_time_constant_s: 1200.0,
+                throttle_temp_c: 90.0,
+                ram_mb: DeviceType::JetsonNano.available_ram_mb(),
+                compute_gflops: DeviceType::JetsonNano.compute_gflops(),
+                inference_speedup: 4.0,      // TensorRT FP16 on the GPU
+                inference_power_factor: 1.2, // GPU rails on top of the CPU
             },
         }
     }
 
-    pub fn name(&self) -> &'static str {
-        match self.hardware_type {
-            HardwareType::ESP32 => "ESP32",
-            HardwareType::RaspberryPi4 => "RPi4",
-            HardwareType::JetsonNano => "Jetson",
+    /// Whether the board can hold `model` in memory and run one inference
+    /// within `INFERENCE_DEADLINE_S`
+    pub fn runs(&self, model: RealModelType) -> bool {
+        model.size_mb() <= self.ram_mb
+            && model.gflops() / INFERENCE_DEADLINE_S <= self.compute_gflops
+    }
+
+    /// Time one inference of `model` takes on this board at full clocks (ms)
+    pub fn inference_time_ms(&self, model: RealModelType) -> f32 {
+        model.inference_time_ms() / self.inference_speedup
+    }
+
+    /// Draw above idle while inferring on this board, from a Raspberry Pi 4
+    /// figure (W)
+    pub fn inference_power_w(&self, reference_w: f32) -> f32 {
+        reference_w * self.inference_power_factor
+    }
+
+    /// Time to load a model of `size_mb` into memory (seconds)
+    pub fn model_load_time_s(&self, size_mb: f32) -> f32 {
+        size_mb / self.model_load_mb_per_s
+    }
+
+    /// Capacity above the chemistry's cutoff (Wh), what a new battery
+    /// delivers
+    pub fn usable_capacity_wh(&self) -> f32 {
+        self.battery_capacity_wh * self.battery_chemistry.usable_depth_of_discharge()
+    }
+
+    /// Deployment cost: board + panel + battery (USD)
+    pub fn cost_usd(&self) -> f32 {
+        self.board_cost_usd + self.max_solar_input_w * PANEL_USD_PER_W + self.battery_cost_usd()
+    }
+
+    /// Price of a replacement battery (USD)
+    pub fn battery_cost_usd(&self) -> f32 {
+        self.battery_capacity_wh * BATTERY_USD_PER_WH
+    }
+
+    /// Embodied carbon: board + panel + battery (kgCO2e)
+    pub fn embodied_kgco2(&self) -> f32 {
+        self.board_embodied_kgco2
+            + self.max_solar_input_w * PANEL_KGCO2_PER_W
+            + self.battery_capacity_wh * BATTERY_KGCO2_PER_WH
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Platform of a catalog row: the row's figures on top of its class;
+    /// inference speed and draw scale from the Raspberry Pi 4's compute and
+    /// dynamic power
+    pub fn from_profile(profile: &HardwareProfileCSV) -> Result<Self, String> {
+        let figures = [
+            profile.battery_wh,
+            profile.idle_w,
+            profile.peak_w,
+            profile.ram_mb,
+            profile.gflops,
+            profile.max_solar_w,
+        ];
+        if figures
+            .iter()
+            .any(|value| !value.is_finite() || *value <= 0.0)
+        {
+            return Err(format!("{}: every figure must be positive", profile.name));
+        }
+        if profile.peak_w <= profile.idle_w {
+            return Err(format!(
+                "{}: peak power must exceed idle power",
+                profile.name
+            ));
         }
+        let class = match &profile.class {
+            Some(class) => class.parse()?,
+            None if profile.ram_mb < MCU_RAM_MB => HardwareType::ESP32,
+            None if profile.gflops >= GPU_GFLOPS => HardwareType::JetsonNano,
+            None => HardwareType::RaspberryPi4,
+        };
+        let base = Self::new(class);
+        let reference = DeviceType::RaspberryPi4;
+        Ok(Self {
+            name: profile.name.as_str().into(),
+            battery_capacity_wh: profile.battery_wh,
+            idle_power_w: profile.idle_w,
+            sleep_power_w: base.sleep_power_w.min(profile.idle_w),
+            max_solar_input_w: profile.max_solar_w,
+            ram_mb: profile.ram_mb,
+            compute_gflops: profile.gflops,
+            inference_speedup: profile.gflops / reference.compute_gflops(),
+            inference_power_factor: (profile.peak_w - profile.idle_w)
+                / (reference.peak_power_w() - reference.idle_power_w()),
+            ..base
+        })
+    }
+}
+
+/// Platforms the hardware lottery draws from (flag: `--hardware-csv <path>`)
+/// Empty for the built-in platforms. Catalog rows are full platforms for the
+/// energy model, fitness and reports; radio, storage, sensors and the
+/// per-type statistics follow the row's class.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Resource))]
+pub struct HardwareCatalog(pub Vec<HardwareSpec>);
+
+impl HardwareCatalog {
+    pub fn from_profiles(profiles: &[HardwareProfileCSV]) -> Result<Self, String> {
+        if profiles.is_empty() {
+            return Err("hardware catalog has no rows".to_string());
+        }
+        let platforms = profiles
+            .iter()
+            .map(HardwareSpec::from_profile)
+            .collect::<Result<_, _>>()?;
+        Ok(Self(platforms))
+    }
+
+    /// A uniformly drawn catalog platform (None: use the built-in lottery)
+    pub fn draw(&self, rng: &mut impl Rng) -> Option<HardwareSpec> {
+        (!self.0.is_empty()).then(|| self.0[rng.random_range(0..self.0.len())].clone())
+    }
+
+    /// Deployable platform of class `class` named `name` (case-insensitive)
+    pub fn find(&self, name: &str, class: HardwareType) -> Option<HardwareSpec> {
+        self.platforms()
+            .into_iter()
+            .find(|spec| spec.hardware_type == class && spec.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Every platform a birth can draw
+    pub fn platforms(&self) -> Vec<HardwareSpec> {
+        if self.0.is_empty() {
+            HardwareType::ALL.map(HardwareSpec::new).to_vec()
+        } else {
+            self.0.clone()
+        }
+    }
+
+    /// The most expensive platform that fits `budget_usd` (None when nothing
+    /// fits)
+    pub fn affordable(&self, budget_usd: f32) -> Option<HardwareSpec> {
+        self.platforms()
+            .into_iter()
+            .filter(|spec| spec.cost_usd() <= budget_usd)
+            .max_by(|a, b| a.cost_usd().total_cmp(&b.cost_usd()))
     }
 }
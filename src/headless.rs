@@ -0,0 +1,153 @@
+/// `headless` command: the Bevy-free `Engine` run from the command line
+/// Parses the run's flags into a `SimConfig`, loads the solar source,
+/// power profiles and hardware catalog the ECS run would, and steps the
+/// engine epoch by epoch with a progress bar, optionally slaved to an
+/// external clock (see clock.rs) and writing per-epoch summaries.
+use indicatif::{ProgressBar, ProgressStyle};
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::Duration;
+
+use crate::clock::ExternalClock;
+use crate::config::SimConfig;
+use crate::data_loader::{PowerProfileCSV, load_power_profiles};
+use crate::engine::{Engine, EngineNode, HEADLESS_DT, hourly_solar_w};
+use crate::export::open_csv;
+use crate::preflight::config_warnings;
+
+/// `headless [--seed <u64>] [--epochs <n>] [--population <n>] [--fast-night]
+/// [--battery-aging <f32>] [--location <lat,lon>] [--day-of-year <n>]
+/// [--solar-csv <path>] [--warm-up <hours>] [--time-scale <x>] [--panel <spec>]
+/// [--charge-controller <ideal|pwm|mppt>] [--compatibility <off|forbid|crash>]
+/// [--hardware-csv <path>] [--summary-out <csv>]`:
+/// energy and evolution only, no window and no ECS
+pub fn run_headless(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut config = SimConfig::default();
+    let mut epochs = 20u32;
+    let mut population = 100usize;
+    let mut fast_night = false;
+    let mut summary_out = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--seed" => config.seed = value()?.parse()?,
+            "--epochs" => epochs = value()?.parse()?,
+            "--population" => population = value()?.parse()?,
+            "--fast-night" => fast_night = true,
+            "--battery-aging" => config.battery_aging = value()?.parse()?,
+            "--clock" => config.clock = Some(value()?.parse()?),
+            "--clock-origin" => config.clock_origin = Some(value()?.parse()?),
+            "--location" => config.location = Some(value()?.parse()?),
+            "--day-of-year" => config.day_of_year = value()?.parse::<u16>()?.clamp(1, 365),
+            "--solar-csv" => config.solar_csv = Some(value()?),
+            "--summary-out" => summary_out = Some(value()?),
+            "--warm-up" => config.warm_up_h = value()?.parse::<f32>()?.max(0.0),
+            "--time-scale" => {
+                config.time_scale = value()?.parse()?;
+                if config.time_scale <= 0.0 {
+                    return Err("--time-scale must be positive".into());
+                }
+            }
+            "--panel" => config.panel = Some(value()?.parse()?),
+            "--charge-controller" => config.charge_controller = Some(value()?.parse()?),
+            "--compatibility" => config.compatibility = value()?.parse()?,
+            "--hardware-csv" => config.hardware_csv = Some(value()?),
+            other => return Err(format!("unknown headless option {}", other).into()),
+        }
+    }
+
+    let seasons = config.solar_seasons()?;
+    let profiles = seasons.profiles_on(config.day_of_year);
+    let solar_w = hourly_solar_w(&profiles);
+    let power_profiles =
+        load_power_profiles("data/power_profiles/raspberry_pi_4.csv").unwrap_or_default();
+    let overrides: HashMap<String, PowerProfileCSV> = power_profiles
+        .iter()
+        .map(|p| (p.model_name.clone(), p.clone()))
+        .collect();
+    let catalog = config.hardware_catalog()?;
+    for warning in config_warnings(&config, &profiles, Some(&overrides), &catalog) {
+        eprintln!("⚠️ {}", warning);
+    }
+
+    println!(
+        "🧮 Headless engine: seed {}, {} nodes, {} epochs",
+        config.seed, population, epochs
+    );
+    // Drawn on stderr, hidden when it is not a terminal
+    let progress = ProgressBar::new(epochs as u64).with_style(ProgressStyle::with_template(
+        "{spinner} [{elapsed_precise}] {bar:30} {pos}/{len} epochs | {msg} | ETA {eta}",
+    )?);
+    progress.set_message("day 0.0 | best -");
+    progress.enable_steady_tick(Duration::from_millis(200));
+
+    let mut engine = Engine::new(
+        config.genetic_seed(),
+        config.env_seed(),
+        population,
+        solar_w,
+    )
+    .with_power_overrides(power_profiles)
+    .with_fast_night(fast_night)
+    .with_battery_aging(config.battery_aging)
+    .with_seasons(seasons, config.calendar())
+    .with_time_scale(config.time_scale)
+    .with_warm_up(config.warm_up_h)
+    .with_hardware_catalog(catalog)
+    .with_panel(config.fitted_panel().unwrap_or_default())
+    .with_compatibility(config.compatibility);
+    // Per-epoch summaries for other tools (see tournament.rs)
+    let mut summary_writer = summary_out.as_deref().map(open_csv).transpose()?;
+    // Each step waits for the external clock to reach its end
+    let clock = ExternalClock::new(&config);
+    let mut steps = 0u64;
+    while engine.epoch() < epochs {
+        steps += 1;
+        clock.wait_until(steps as f64 * (HEADLESS_DT * config.time_scale) as f64);
+        let nodes = engine.nodes();
+        let avg_battery_wh =
+            nodes.iter().map(EngineNode::battery_wh).sum::<f32>() / nodes.len().max(1) as f32;
+        if let Some(summary) = engine.step(HEADLESS_DT) {
+            if let Some(writer) = &mut summary_writer {
+                writer.serialize(summary)?;
+            }
+            progress.inc(1);
+            let date = engine
+                .calendar()
+                .map_or(String::new(), |calendar| format!(" | {}", calendar));
+            progress.set_message(format!(
+                "day {:.1}{} | best {:.2}s",
+                engine.simulated_days(),
+                date,
+                summary.fitness_max
+            ));
+            let line = format!(
+                "=== EPOCH {} === {}/{} survived | fitness mean {:.2}s, max {:.2}s | {:.2} Wh used, {:.2} Wh harvested | avg battery {:.2} Wh",
+                summary.epoch,
+                summary.survivors,
+                summary.population,
+                summary.fitness_mean,
+                summary.fitness_max,
+                summary.energy_consumed_wh.wh(),
+                summary.energy_harvested_wh.wh(),
+                avg_battery_wh
+            );
+            // A hidden bar swallows println, so print directly
+            if progress.is_hidden() {
+                println!("{}", line);
+            } else {
+                progress.println(line);
+            }
+        }
+    }
+    progress.finish_and_clear();
+    if let Some(writer) = &mut summary_writer {
+        writer.flush()?;
+    }
+    println!(
+        "🏁 Completed {} epochs ({:.1} simulated days)",
+        epochs,
+        engine.simulated_days()
+    );
+    Ok(())
+}
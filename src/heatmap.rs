@@ -0,0 +1,147 @@
+/// Spatial heatmap layers
+/// `H` cycles the node rendering through layers that color each node's grid
+/// cell (and circle) by one quantity: battery charge, survival score
+/// (relative to the best alive node), power policy or hardware type. Cells
+/// are filled so regions of the grid that thrive or struggle stand out; a
+/// legend window names the layer and its colors. The default layer keeps
+/// the plain battery traffic-light circles.
+use bevy::prelude::*;
+use bevy_egui::input::EguiWantsInput;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::policies::PowerPolicy;
+
+/// Cell fill opacity, so node circles stay visible on top
+pub const FILL_ALPHA: f32 = 0.35;
+
+/// Quantity the grid is colored by
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatmapLayer {
+    /// Plain circles colored by battery level
+    #[default]
+    Off,
+    BatteryCharge,
+    SurvivalScore,
+    Policy,
+    Hardware,
+}
+
+impl HeatmapLayer {
+    /// The layer after this one (wrapping back to `Off`)
+    pub fn next(self) -> Self {
+        match self {
+            HeatmapLayer::Off => HeatmapLayer::BatteryCharge,
+            HeatmapLayer::BatteryCharge => HeatmapLayer::SurvivalScore,
+            HeatmapLayer::SurvivalScore => HeatmapLayer::Policy,
+            HeatmapLayer::Policy => HeatmapLayer::Hardware,
+            HeatmapLayer::Hardware => HeatmapLayer::Off,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeatmapLayer::Off => "off",
+            HeatmapLayer::BatteryCharge => "battery charge",
+            HeatmapLayer::SurvivalScore => "survival score",
+            HeatmapLayer::Policy => "power policy",
+            HeatmapLayer::Hardware => "hardware type",
+        }
+    }
+}
+
+/// Red (0) → yellow → green (1)
+pub fn ramp(t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    Color::srgb((2.0 * (1.0 - t)).min(1.0), (2.0 * t).min(1.0), 0.0)
+}
+
+/// Distinct colors for categorical layers
+pub fn category_color(index: usize) -> Color {
+    const PALETTE: [(f32, f32, f32); 6] = [
+        (0.90, 0.30, 0.30),
+        (0.30, 0.60, 0.95),
+        (0.35, 0.85, 0.40),
+        (0.95, 0.75, 0.20),
+        (0.75, 0.40, 0.90),
+        (0.30, 0.85, 0.85),
+    ];
+    let (r, g, b) = PALETTE[index % PALETTE.len()];
+    Color::srgb(r, g, b)
+}
+
+pub fn policy_color(policy: PowerPolicy) -> Color {
+    category_color(policy as usize)
+}
+
+pub fn hardware_color(hardware: &HardwareSpec) -> Color {
+    category_color(hardware.hardware_type as usize)
+}
+
+/// `H` cycles the heatmap layer
+pub fn heatmap_toggle_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    mut layer: ResMut<HeatmapLayer>,
+) {
+    if egui_input.wants_any_keyboard_input() || !keys.just_pressed(KeyCode::KeyH) {
+        return;
+    }
+    *layer = layer.next();
+    info!(target: "edgegenesis::epoch", "🗺️ Heatmap layer: {}", layer.name());
+}
+
+/// Legend of the active layer
+pub fn heatmap_legend_system(mut contexts: EguiContexts, layer: Res<HeatmapLayer>) -> Result {
+    if *layer == HeatmapLayer::Off {
+        return Ok(());
+    }
+
+    let swatch = |ui: &mut egui::Ui, color: Color, label: &str| {
+        let [r, g, b, _] = color.to_srgba().to_u8_array();
+        ui.horizontal(|ui| {
+            ui.colored_label(egui::Color32::from_rgb(r, g, b), "■");
+            ui.label(label);
+        });
+    };
+
+    egui::Window::new("Heatmap")
+        .anchor(egui::Align2::LEFT_BOTTOM, [12.0, -12.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut()?, |ui| {
+            ui.label(format!("Layer: {} (H to cycle)", layer.name()));
+            match *layer {
+                HeatmapLayer::Off => {}
+                HeatmapLayer::BatteryCharge => {
+                    swatch(ui, ramp(1.0), "full");
+                    swatch(ui, ramp(0.5), "half");
+                    swatch(ui, ramp(0.0), "empty");
+                }
+                HeatmapLayer::SurvivalScore => {
+                    swatch(ui, ramp(1.0), "best alive");
+                    swatch(ui, ramp(0.5), "half of best");
+                    swatch(ui, ramp(0.0), "zero");
+                }
+                HeatmapLayer::Policy => {
+                    for policy in PowerPolicy::ALL {
+                        swatch(ui, policy_color(policy), policy.name());
+                    }
+                }
+                HeatmapLayer::Hardware => {
+                    for hardware_type in HardwareType::ALL {
+                        let spec = HardwareSpec::new(hardware_type);
+                        swatch(ui, hardware_color(&spec), spec.name());
+                    }
+                }
+            }
+        });
+    Ok(())
+}
+
+/// Register the heatmap resource and systems
+pub fn register_heatmap(app: &mut App) {
+    app.init_resource::<HeatmapLayer>()
+        .add_systems(EguiPrimaryContextPass, heatmap_legend_system)
+        .add_systems(Update, heatmap_toggle_system);
+}
@@ -0,0 +1,323 @@
+/// Click-to-inspect node panel
+/// A plain left click (no shift, injection not armed) selects the node
+/// under the cursor; an egui window then shows its genome, hardware,
+/// battery, fitness, lifetime-tuned policy and the inference history of its
+/// last `HISTORY_TICKS` physics ticks, and a ring marks it in the world.
+/// Labels can be added to and removed from the node there (see
+/// annotations.rs).
+/// Nodes are matched by entity and lineage id, so the selection clears when
+/// the next generation takes the entity over.
+use bevy::prelude::*;
+use bevy_egui::input::EguiWantsInput;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+use std::collections::VecDeque;
+
+use crate::aging::BatteryHealth;
+use crate::components::{Battery, Gene, Lineage, Status, SurvivalScore, Telemetry, TunedPolicy};
+use crate::editor::{GeneEditor, PICK_RADIUS, WorldCursor};
+use crate::hardware::{HardwareSpec, SolarPanel};
+use crate::phylogeny::LineageLog;
+use crate::speed::SimSpeed;
+use crate::systems::resource_physics_system;
+use crate::thermal::Temperature;
+use crate::units::SECONDS_PER_HOUR;
+
+/// Physics ticks kept in the inference history (one simulated hour at
+/// `HEADLESS_DT`)
+pub const HISTORY_TICKS: usize = 1200;
+
+/// Characters in the inference history strip
+pub const STRIP_WIDTH: usize = 60;
+
+/// Node shown in the inspector and its recent activity
+#[derive(Resource, Default)]
+pub struct SelectedNode {
+    pub entity: Option<Entity>,
+    /// Lineage id at selection time
+    pub node_id: u64,
+    /// Inferring flag per physics tick, oldest first
+    pub history: VecDeque<bool>,
+    /// Label being typed in the inspector
+    pub label_draft: String,
+}
+
+impl SelectedNode {
+    pub fn select(&mut self, entity: Entity, node_id: u64) {
+        *self = Self {
+            entity: Some(entity),
+            node_id,
+            history: VecDeque::with_capacity(HISTORY_TICKS),
+            label_draft: String::new(),
+        };
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Select the nearest node within the pick radius of a plain left click
+pub fn select_on_click_system(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    editor: Res<GeneEditor>,
+    cursor: WorldCursor,
+    mut selected: ResMut<SelectedNode>,
+    nodes: Query<(Entity, &Transform, &Lineage)>,
+) {
+    // Armed clicks inject, shift-clicks spawn
+    if editor.armed
+        || !mouse.just_pressed(MouseButton::Left)
+        || keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+        || egui_input.wants_any_pointer_input()
+    {
+        return;
+    }
+
+    let Some(world_pos) = cursor.position() else {
+        return;
+    };
+
+    let target = nodes
+        .iter()
+        .map(|(entity, transform, lineage)| {
+            let distance = transform.translation.truncate().distance(world_pos);
+            (distance, entity, lineage.id)
+        })
+        .filter(|(distance, _, _)| *distance <= PICK_RADIUS)
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+
+    match target {
+        Some((_, entity, node_id)) => selected.select(entity, node_id),
+        None => selected.clear(),
+    }
+}
+
+/// Append the selected node's inference flag after every physics tick
+pub fn record_selected_history_system(
+    mut selected: ResMut<SelectedNode>,
+    nodes: Query<(&Lineage, &Telemetry)>,
+) {
+    let Some(entity) = selected.entity else {
+        return;
+    };
+    match nodes.get(entity) {
+        Ok((lineage, telemetry)) if lineage.id == selected.node_id => {
+            if selected.history.len() == HISTORY_TICKS {
+                selected.history.pop_front();
+            }
+            selected.history.push_back(telemetry.inferring);
+        }
+        // Despawned or taken over by the next generation
+        _ => selected.clear(),
+    }
+}
+
+/// Mark the selected node with a ring
+pub fn render_selection_system(
+    mut gizmos: Gizmos,
+    selected: Res<SelectedNode>,
+    nodes: Query<&Transform>,
+) {
+    let Some(transform) = selected.entity.and_then(|entity| nodes.get(entity).ok()) else {
+        return;
+    };
+    gizmos.circle_2d(
+        transform.translation.truncate(),
+        PICK_RADIUS + 4.0,
+        Color::srgb(0.3, 0.8, 1.0),
+    );
+}
+
+/// Everything the inspector window shows about a node
+type InspectedQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Lineage,
+        &'static Gene,
+        &'static Battery,
+        &'static BatteryHealth,
+        (&'static HardwareSpec, &'static SolarPanel),
+        &'static SurvivalScore,
+        &'static Status,
+        &'static TunedPolicy,
+        &'static Telemetry,
+        &'static Temperature,
+    ),
+>;
+
+/// Inspector window for the selected node
+pub fn inspector_ui_system(
+    mut contexts: EguiContexts,
+    fixed: Res<Time<Fixed>>,
+    speed: Res<SimSpeed>,
+    mut selected: ResMut<SelectedNode>,
+    mut lineage_log: ResMut<LineageLog>,
+    nodes: InspectedQuery,
+) -> Result {
+    let Some(entity) = selected.entity else {
+        return Ok(());
+    };
+    let Ok((
+        lineage,
+        gene,
+        battery,
+        health,
+        (hardware, panel),
+        score,
+        status,
+        tuned,
+        telemetry,
+        temperature,
+    )) = nodes.get(entity)
+    else {
+        return Ok(());
+    };
+
+    let mut open = true;
+    egui::Window::new("Node Inspector")
+        .open(&mut open)
+        .show(contexts.ctx_mut()?, |ui| {
+            let parent = lineage
+                .parent
+                .map_or("founder".to_string(), |id| format!("child of #{}", id));
+            ui.heading(format!("Node #{} ({})", lineage.id, parent));
+            ui.label(format!("Status: {:?}", status));
+            ui.label(format!("Fitness: {:.2}s survived", score.0));
+            if let Some(ancestor) = lineage_log.labeled_ancestor(lineage.id) {
+                ui.label(format!(
+                    "Descends from #{} ({})",
+                    ancestor,
+                    lineage_log.labels(ancestor).join(", ")
+                ));
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Labels:");
+                for label in lineage_log.labels(lineage.id).to_vec() {
+                    if ui.small_button(format!("{} ✕", label)).clicked() {
+                        lineage_log.remove_label(lineage.id, &label);
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                let field = ui.text_edit_singleline(&mut selected.label_draft);
+                let entered =
+                    field.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+                if ui.button("Add label").clicked() || entered {
+                    lineage_log.add_label(lineage.id, &selected.label_draft);
+                    selected.label_draft.clear();
+                }
+            });
+
+            ui.separator();
+            ui.label(format!(
+                "Hardware: {} ({:.0} W charge input, {} battery)",
+                hardware.name(),
+                hardware.max_solar_input_w,
+                hardware.battery_chemistry.name()
+            ));
+            ui.label(format!(
+                "Panel: {:.1} W rated, {:.3} m² at {:.0}%, orientation {:.2}, {} controller",
+                panel.rated_w,
+                panel.area_m2,
+                100.0 * panel.efficiency,
+                panel.orientation,
+                panel.controller.name()
+            ));
+            ui.label(format!(
+                "Battery: {:.2} / {:.1} Wh ({:.0}%)",
+                battery.wh(),
+                health.capacity_wh,
+                100.0 * battery.wh() / health.capacity_wh
+            ));
+            ui.label(format!(
+                "Battery health {:.1}% after {:.2} cycles",
+                100.0 * health.state_of_health(),
+                health.cycles
+            ));
+            ui.label(format!(
+                "Solar {:.2} W, draw {:.2} W{}",
+                telemetry.solar_input_w,
+                telemetry.power_draw_w,
+                if telemetry.asleep { " (asleep)" } else { "" }
+            ));
+            ui.label(format!(
+                "Temperature {:.1} °C{}",
+                temperature.celsius,
+                if temperature.throttled() {
+                    format!(", throttled to {:.0}%", temperature.throttle * 100.0)
+                } else {
+                    String::new()
+                }
+            ));
+
+            ui.separator();
+            ui.label(format!(
+                "Model: {} ({:.1}% acc, {:.1} MB, {:.0} ms here)",
+                gene.model_type.name(),
+                gene.model_type.accuracy_percent(),
+                gene.model_type.size_mb(),
+                temperature.inference_time_ms(gene.model_type, hardware)
+            ));
+            ui.label(format!(
+                "Policy: {} (threshold {:.2}, tuned {:.2})",
+                gene.policy.name(),
+                gene.policy_params.battery_threshold,
+                tuned.0.battery_threshold
+            ));
+            ui.label(format!(
+                "Inference frequency {:.2}, solar efficiency {:.2}",
+                gene.inference_frequency, gene.solar_efficiency_factor
+            ));
+            ui.label(format!(
+                "TX {:.1} dBm, compress reports: {}",
+                gene.tx_power_dbm, gene.compress_reports
+            ));
+            ui.label(format!("Awake: {}", gene.wake_schedule));
+
+            ui.separator();
+            let history = &selected.history;
+            let inferred = history.iter().filter(|inferring| **inferring).count();
+            ui.label(format!(
+                "Inference, last {:.1} h: {}/{} ticks",
+                speed.sim_seconds(history.len() as f32 * fixed.timestep().as_secs_f32())
+                    / SECONDS_PER_HOUR,
+                inferred,
+                history.len()
+            ));
+            // Each character covers an equal run of ticks: full when the node
+            // inferred in most of them, half when in some, a dot when idle
+            let ticks: Vec<bool> = history.iter().copied().collect();
+            let strip: String = ticks
+                .chunks(HISTORY_TICKS / STRIP_WIDTH)
+                .map(|chunk| {
+                    let busy = chunk.iter().filter(|inferring| **inferring).count();
+                    match busy * 2 {
+                        0 => '·',
+                        n if n < chunk.len() => '▄',
+                        _ => '█',
+                    }
+                })
+                .collect();
+            ui.monospace(strip);
+        });
+
+    if !open {
+        selected.clear();
+    }
+    Ok(())
+}
+
+/// Register the inspector resource and systems
+pub fn register_inspector(app: &mut App) {
+    app.init_resource::<SelectedNode>()
+        .add_systems(EguiPrimaryContextPass, inspector_ui_system)
+        .add_systems(
+            FixedUpdate,
+            record_selected_history_system.after(resource_physics_system),
+        )
+        .add_systems(Update, (select_on_click_system, render_selection_system));
+}
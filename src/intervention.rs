@@ -0,0 +1,137 @@
+/// Live policy interventions
+/// Replaces the power policy of every alive node matching a filter while
+/// the run goes on, e.g. to watch how the fleet responds when all ESP32s
+/// switch to MPC. Requests arrive over REST on the dashboard port:
+/// `POST /policy?set=<policy>[&hardware=<hw>][&model=<model>][&from=<policy>]`
+/// answers with the number of nodes switched. Swapped nodes keep their
+/// evolved and lifetime-tuned parameters and restart the new policy's
+/// controller from scratch; the change is in their genome, so offspring
+/// inherit it. Every swap goes to the event log, for correlating
+/// interventions with the epoch metrics.
+use bevy::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::mpsc;
+
+use crate::catastrophe::Catastrophes;
+use crate::components::{Dead, EpochCount, Gene, Status};
+use crate::event_log::EventLog;
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::models::RealModelType;
+use crate::policies::{PolicyState, PowerPolicy};
+use crate::web::WebDashboard;
+
+/// Which nodes an intervention applies to (unset fields match all)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NodeFilter {
+    pub hardware: Option<HardwareType>,
+    pub model: Option<RealModelType>,
+    /// Policy the node runs now
+    pub policy: Option<PowerPolicy>,
+}
+
+impl NodeFilter {
+    pub fn matches(&self, gene: &Gene, hardware: &HardwareSpec) -> bool {
+        self.hardware.is_none_or(|h| h == hardware.hardware_type)
+            && self.model.is_none_or(|m| m == gene.model_type)
+            && self.policy.is_none_or(|p| p == gene.policy)
+    }
+}
+
+/// Switch the matching nodes to `policy`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolicySwap {
+    pub policy: PowerPolicy,
+    pub filter: NodeFilter,
+}
+
+impl FromStr for PolicySwap {
+    type Err = String;
+
+    /// Query string form: `set=pid&hardware=esp32&model=yolov8n&from=aggressive`
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let mut policy = None;
+        let mut filter = NodeFilter::default();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got {:?}", pair))?;
+            let parse_policy = || {
+                PowerPolicy::from_name(value).ok_or_else(|| format!("unknown policy {:?}", value))
+            };
+            match key {
+                "set" => policy = Some(parse_policy()?),
+                "from" => filter.policy = Some(parse_policy()?),
+                "hardware" => filter.hardware = Some(value.parse()?),
+                "model" => {
+                    let model = RealModelType::from_name(value)
+                        .ok_or_else(|| format!("unknown model {:?}", value))?;
+                    filter.model = Some(model);
+                }
+                other => return Err(format!("unknown parameter {:?}", other)),
+            }
+        }
+        let policy = policy.ok_or("missing set=<policy>")?;
+        Ok(Self { policy, filter })
+    }
+}
+
+impl fmt::Display for PolicySwap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "policy {} for", self.policy.name())?;
+        if let Some(hw_type) = self.filter.hardware {
+            write!(f, " {}", HardwareSpec::new(hw_type).name())?;
+        } else {
+            write!(f, " all")?;
+        }
+        write!(f, " nodes")?;
+        if let Some(model) = self.filter.model {
+            write!(f, " with {}", model.name())?;
+        }
+        if let Some(policy) = self.filter.policy {
+            write!(f, " running {}", policy.name())?;
+        }
+        Ok(())
+    }
+}
+
+/// A swap waiting for the ECS, answered with the number of nodes switched
+pub struct SwapRequest {
+    pub swap: PolicySwap,
+    pub reply: mpsc::Sender<usize>,
+}
+
+/// Apply the queued swaps to the alive population
+pub fn policy_swap_system(
+    dashboard: Res<WebDashboard>,
+    epoch_count: Res<EpochCount>,
+    catastrophes: Res<Catastrophes>,
+    mut event_log: ResMut<EventLog>,
+    mut nodes: Query<(&mut Gene, &mut PolicyState, &HardwareSpec, &Status), Without<Dead>>,
+) {
+    for SwapRequest { swap, reply } in dashboard.swap_requests() {
+        let mut switched = 0;
+        for (mut gene, mut policy_state, hardware, status) in nodes.iter_mut() {
+            if *status == Status::Dead || !swap.filter.matches(&gene, hardware) {
+                continue;
+            }
+            // Only the strategy changes: the evolved and lifetime-tuned
+            // parameters carry over, the controller state starts afresh
+            gene.policy = swap.policy;
+            *policy_state = PolicyState::default();
+            switched += 1;
+        }
+        event_log.push(
+            epoch_count.0,
+            catastrophes.elapsed_h,
+            format!("Intervention: {} ({} switched)", swap, switched),
+        );
+        // The requester may have timed out
+        let _ = reply.send(switched);
+    }
+}
+
+/// Register the intervention system
+pub fn register_intervention(app: &mut App) {
+    app.add_systems(Update, policy_swap_system);
+}
@@ -0,0 +1,144 @@
+/// EdgeGenesis as a library
+/// Evolutionary simulation of solar-powered edge AI nodes. Add
+/// `EdgeGenesisPlugin` to a Bevy app to embed the full simulation (for
+/// example inside a larger digital-twin tool); the component and resource
+/// types are public through their modules so host systems can query and
+/// drive the population. `engine` is the Bevy-free core for headless use:
+/// it and the data modules it steps build without the default `ecs`
+/// feature, which brings in Bevy and everything else.
+// Bevy-free core
+pub mod aging;
+pub mod calendar;
+pub mod compatibility;
+pub mod data_loader;
+pub mod engine;
+pub mod hardware;
+pub mod models;
+pub mod node;
+pub mod policies;
+pub mod sensor;
+pub mod solar;
+pub mod units;
+
+// The Bevy app
+#[cfg(feature = "ecs")]
+pub mod analyze;
+#[cfg(feature = "ecs")]
+pub mod annotations;
+#[cfg(feature = "ecs")]
+pub mod attack;
+#[cfg(feature = "ecs")]
+pub mod carbon;
+#[cfg(feature = "ecs")]
+pub mod catastrophe;
+#[cfg(feature = "ecs")]
+pub mod clock;
+#[cfg(feature = "ecs")]
+pub mod coevolution;
+#[cfg(feature = "ecs")]
+pub mod components;
+#[cfg(feature = "ecs")]
+pub mod compression;
+#[cfg(feature = "ecs")]
+pub mod config;
+#[cfg(feature = "ecs")]
+pub mod crossfade;
+#[cfg(feature = "ecs")]
+pub mod curriculum;
+#[cfg(feature = "ecs")]
+pub mod decision_log;
+#[cfg(feature = "ecs")]
+pub mod editor;
+#[cfg(feature = "ecs")]
+pub mod event_log;
+#[cfg(feature = "ecs")]
+pub mod export;
+#[cfg(feature = "ecs")]
+pub mod extinction;
+#[cfg(feature = "ecs")]
+pub mod failure;
+#[cfg(feature = "ecs")]
+pub mod fitness;
+#[cfg(feature = "ecs")]
+pub mod forecast;
+#[cfg(feature = "ecs")]
+pub mod gateway;
+#[cfg(feature = "ecs")]
+pub mod genome;
+#[cfg(feature = "ecs")]
+pub mod gossip;
+#[cfg(feature = "ecs")]
+pub mod grpc;
+#[cfg(feature = "ecs")]
+pub mod headless;
+#[cfg(feature = "ecs")]
+pub mod heatmap;
+#[cfg(feature = "ecs")]
+pub mod inspector;
+#[cfg(feature = "ecs")]
+pub mod intervention;
+#[cfg(feature = "ecs")]
+pub mod logging;
+#[cfg(feature = "ecs")]
+pub mod maintenance;
+#[cfg(feature = "ecs")]
+pub mod milestones;
+#[cfg(feature = "ecs")]
+pub mod net_trace;
+#[cfg(feature = "ecs")]
+pub mod ota;
+#[cfg(feature = "ecs")]
+pub mod phylogeny;
+#[cfg(feature = "ecs")]
+pub mod plugin;
+#[cfg(feature = "ecs")]
+pub mod policy_stats;
+#[cfg(feature = "ecs")]
+pub mod preflight;
+#[cfg(feature = "ecs")]
+pub mod radio;
+#[cfg(feature = "ecs")]
+pub mod report;
+#[cfg(feature = "ecs")]
+pub mod scenario;
+#[cfg(feature = "ecs")]
+pub mod sensing;
+#[cfg(feature = "ecs")]
+pub mod sizing;
+#[cfg(feature = "ecs")]
+pub mod spectator;
+#[cfg(feature = "ecs")]
+pub mod speed;
+#[cfg(feature = "ecs")]
+pub mod sprites;
+#[cfg(feature = "ecs")]
+pub mod stats;
+#[cfg(feature = "ecs")]
+pub mod storage;
+#[cfg(feature = "ecs")]
+pub mod sweep;
+#[cfg(feature = "ecs")]
+pub mod systems;
+#[cfg(feature = "ecs")]
+pub mod thermal;
+#[cfg(feature = "ecs")]
+pub mod timeline;
+#[cfg(feature = "ecs")]
+pub mod topology;
+#[cfg(feature = "ecs")]
+pub mod tournament;
+#[cfg(feature = "ecs")]
+pub mod tradeoff;
+#[cfg(feature = "ecs")]
+pub mod trust;
+#[cfg(feature = "ecs")]
+pub mod warmup;
+#[cfg(feature = "ecs")]
+pub mod weather;
+#[cfg(feature = "ecs")]
+pub mod web;
+
+#[cfg(feature = "ecs")]
+pub use config::SimConfig;
+#[cfg(feature = "ecs")]
+pub use plugin::EdgeGenesisPlugin;
@@ -0,0 +1,44 @@
+/// Log output configuration
+/// Console output goes through `tracing` (Bevy's `LogPlugin`). Epoch
+/// reports use one target per subsystem (`edgegenesis::radio`,
+/// `edgegenesis::sensing`, ...; `edgegenesis::epoch` and
+/// `edgegenesis::evolution` for the overall summary and selection), so
+/// `--log-filter` can silence or raise each one. Every event of an epoch
+/// runs inside an `epoch` span carrying its number; `--log-json` switches
+/// the formatter to one JSON object per event for analysis tools.
+use bevy::log::{BoxedFmtLayer, DEFAULT_FILTER, LogPlugin};
+use bevy::prelude::*;
+use tracing_subscriber::Layer;
+
+use crate::config::SimConfig;
+
+/// `LogPlugin` for the configured level, filter and format
+pub fn log_plugin(config: &SimConfig) -> LogPlugin {
+    let filter = match &config.log_filter {
+        Some(directives) => format!("{},{}", DEFAULT_FILTER, directives),
+        None => DEFAULT_FILTER.to_string(),
+    };
+    LogPlugin {
+        level: config.log_level,
+        filter,
+        fmt_layer: if config.log_json {
+            json_layer
+        } else {
+            |_| None
+        },
+        ..default()
+    }
+}
+
+/// Newline-delimited JSON events with span context and target
+fn json_layer(_app: &mut App) -> Option<BoxedFmtLayer> {
+    Some(
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_target(true)
+            .with_writer(std::io::stderr)
+            .boxed(),
+    )
+}
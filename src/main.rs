@@ -1,46 +1,31 @@
-mod components;
-mod data_loader;
-mod hardware;
-mod models;
-mod policies;
-mod systems;
-
 use bevy::prelude::*;
-use components::EpochCount;
+use edgegenesis::{EdgeGenesisPlugin, SimConfig, analyze, headless, logging, sweep, tournament};
 
 fn main() {
-    let mut app = App::new();
-
-    // Load CSV data (optional overrides)
-    let power_csv = data_loader::load_power_profiles("data/power_profiles/raspberry_pi_4.csv")
-        .ok()
-        .map(|profiles| {
-            let mut map = std::collections::HashMap::new();
-            for p in profiles {
-                println!(
-                    "📦 CSV Override: {} ({}W)",
-                    p.model_name, p.inference_power_w
-                );
-                map.insert(p.model_name.clone(), p);
-            }
-            map
-        });
-
-    let solar_profiles = data_loader::load_solar_profiles("data/solar_profiles/algiers_solar.csv")
-        .unwrap_or_else(|e| {
-            eprintln!("⚠️ Solar CSV not found: {}. Using synthetic data.", e);
-            Vec::new()
-        });
-
-    println!("☀️ Loaded {} solar hours", solar_profiles.len());
-    println!("🧬 Using models.rs as primary data source");
-
-    app.add_plugins(DefaultPlugins)
-        .insert_resource(EpochCount(1))
-        .insert_resource(components::PowerOverrides(power_csv))
-        .insert_resource(components::LoadedSolarProfiles(solar_profiles));
-
-    systems::register_systems(&mut app);
-
-    app.run();
+    // Subcommands that don't start the simulation
+    let subcommand = match std::env::args().nth(1).as_deref() {
+        Some("analyze") => Some(analyze::run as fn(_) -> _),
+        Some("coordinate") => Some(sweep::coordinate as fn(_) -> _),
+        Some("headless") => Some(headless::run_headless as fn(_) -> _),
+        Some("tournament") => Some(tournament::run as fn(_) -> _),
+        Some("worker") => Some(sweep::worker as fn(_) -> _),
+        _ => None,
+    };
+    if let Some(run) = subcommand {
+        if let Err(e) = run(std::env::args().skip(2)) {
+            eprintln!(
+                "⚠️ {} failed: {}",
+                std::env::args().nth(1).unwrap_or_default(),
+                e
+            );
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let config = SimConfig::from_args();
+    App::new()
+        .add_plugins(DefaultPlugins.set(logging::log_plugin(&config)))
+        .add_plugins(EdgeGenesisPlugin::new(config))
+        .run();
 }
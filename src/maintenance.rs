@@ -0,0 +1,345 @@
+/// Maintenance visits and panel soiling
+/// Panels gather dust at the scenario's `soiling_per_day` (up to
+/// `MAX_SOILING`), which cuts their output like shading does. A technician
+/// entity works through scheduled rounds (scenario `[maintenance]` table):
+/// every `interval_h` simulated hours it picks `visits` alive nodes by the
+/// plan's selection (emptiest batteries, most faded, dustiest panels),
+/// orders them into a nearest-neighbor tour from the depot and drives it.
+/// Travel takes distance / speed and each stop `service_h`, after which the
+/// node gets a new full battery and/or a clean panel; nodes that died in
+/// the meantime are passed over. Technician hours (travel included) and
+/// replacement batteries are costed, so maintenance policies can be
+/// compared per epoch and per visit.
+use bevy::prelude::*;
+
+use crate::aging::BatteryHealth;
+use crate::catastrophe::Catastrophes;
+use crate::components::{Battery, Dead, EnergyLedger, EpochCount, Status};
+use crate::event_log::EventLog;
+use crate::hardware::HardwareSpec;
+use crate::scenario::{MaintenancePlan, Scenario, VisitSelection};
+use crate::speed::SimTime;
+use crate::units::WattHours;
+
+/// Highest fraction of panel output dust can block
+pub const MAX_SOILING: f32 = 0.5;
+
+/// Fraction of a node's panel output blocked by dust (0 = clean)
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct PanelSoiling(pub f32);
+
+impl PanelSoiling {
+    /// Share of the output still reaching the cells
+    pub fn clean_fraction(&self) -> f32 {
+        1.0 - self.0
+    }
+}
+
+/// What the technician is doing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TechnicianTask {
+    /// At the depot until the next round
+    Idle,
+    /// Driving to the current stop, or back to the depot after the last
+    Traveling,
+    /// On site at the current stop (hours left)
+    Servicing(f32),
+}
+
+/// The maintenance technician
+#[derive(Component, Debug, Clone)]
+pub struct Technician {
+    pub task: TechnicianTask,
+    /// Nodes of the current round in visiting order
+    pub route: Vec<Entity>,
+    /// Index of the current stop (past the end when heading home)
+    pub stop: usize,
+    /// Simulated hour the next round starts
+    pub next_round_h: f32,
+}
+
+/// Per-epoch maintenance statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct MaintenanceMetrics {
+    pub rounds: u64,
+    /// Nodes serviced (nodes passed over are not counted)
+    pub visits: u64,
+    pub battery_swaps: u64,
+    pub panel_cleans: u64,
+    /// Technician hours, travel included
+    pub labor_h: f32,
+    /// Labor plus replacement batteries (USD)
+    pub cost_usd: f32,
+}
+
+impl MaintenanceMetrics {
+    /// Mean cost of one serviced node, travel shared out over the visits
+    pub fn cost_per_visit_usd(&self) -> Option<f32> {
+        (self.visits > 0).then(|| self.cost_usd / self.visits as f32)
+    }
+}
+
+/// Run condition: true when the scenario schedules maintenance
+pub fn maintenance_enabled(scenario: Res<Scenario>) -> bool {
+    scenario.maintenance.is_some()
+}
+
+/// Run condition: true when panels gather dust
+pub fn soiling_enabled(scenario: Res<Scenario>) -> bool {
+    scenario.soiling_per_day > 0.0
+}
+
+/// Nearest-neighbor tour from `depot` through the `visits` most urgent
+/// candidates (lowest urgency key first)
+pub fn plan_route(
+    depot: Vec2,
+    mut candidates: Vec<(Entity, Vec2, f32)>,
+    visits: usize,
+) -> Vec<Entity> {
+    candidates.sort_by(|a, b| a.2.total_cmp(&b.2));
+    candidates.truncate(visits);
+
+    let mut route = Vec::with_capacity(candidates.len());
+    let mut at = depot;
+    while let Some(next) = candidates
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| at.distance(a.1).total_cmp(&at.distance(b.1)))
+        .map(|(i, _)| i)
+    {
+        let (entity, position, _) = candidates.swap_remove(next);
+        route.push(entity);
+        at = position;
+    }
+    route
+}
+
+/// Place the technician at the depot
+pub fn spawn_technician_system(mut commands: Commands, scenario: Res<Scenario>) {
+    let Some(plan) = scenario.maintenance else {
+        return;
+    };
+    commands.spawn((
+        Technician {
+            task: TechnicianTask::Idle,
+            route: Vec::new(),
+            stop: 0,
+            next_round_h: plan.first_h,
+        },
+        Transform::from_xyz(plan.depot[0], plan.depot[1], 0.0),
+    ));
+    info!(target: "edgegenesis::maintenance",
+        "🧰 Technician at ({:.0}, {:.0}): {} visits every {:.0} h from hour {:.0}",
+        plan.depot[0], plan.depot[1], plan.visits, plan.interval_h, plan.first_h
+    );
+}
+
+/// Soiling system - dust settles on every alive node's panel
+pub fn soiling_system(
+    time: SimTime,
+    scenario: Res<Scenario>,
+    mut query: Query<(&mut PanelSoiling, &Status), Without<Dead>>,
+) {
+    let days = time.window_s() / 86_400.0;
+    for (mut soiling, status) in query.iter_mut() {
+        if *status == Status::Dead {
+            continue;
+        }
+        soiling.0 = (soiling.0 + scenario.soiling_per_day * days).min(MAX_SOILING);
+    }
+}
+
+/// Nodes the technician can service
+type ServiceQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static mut Battery,
+        &'static mut BatteryHealth,
+        &'static mut EnergyLedger,
+        &'static mut PanelSoiling,
+        &'static HardwareSpec,
+        &'static Status,
+    ),
+    Without<Technician>,
+>;
+
+/// How urgently a node needs a visit (lower first)
+fn urgency(
+    select: VisitSelection,
+    battery: &Battery,
+    health: &BatteryHealth,
+    soiling: &PanelSoiling,
+) -> f32 {
+    match select {
+        VisitSelection::LowestCharge => battery.wh() / health.capacity_wh,
+        VisitSelection::LowestHealth => health.state_of_health(),
+        VisitSelection::Dirtiest => -soiling.0,
+    }
+}
+
+/// Swap the battery and/or clean the panel of one node
+fn service(
+    plan: &MaintenancePlan,
+    nodes: &mut ServiceQuery,
+    entity: Entity,
+    metrics: &mut MaintenanceMetrics,
+) {
+    let Ok((_, _, mut battery, mut health, mut ledger, mut soiling, hardware, status)) =
+        nodes.get_mut(entity)
+    else {
+        return;
+    };
+    if *status == Status::Dead {
+        return;
+    }
+    if plan.swap_battery {
+        let full_wh = hardware.usable_capacity_wh();
+        ledger.swapped_wh += WattHours::from_wh(full_wh) - WattHours::charge_of(&battery);
+        *battery = Battery::new(full_wh);
+        *health = BatteryHealth::new(hardware, 1.0);
+        metrics.battery_swaps += 1;
+        metrics.cost_usd += hardware.battery_cost_usd();
+    }
+    if plan.clean_panel {
+        soiling.0 = 0.0;
+        metrics.panel_cleans += 1;
+    }
+    metrics.visits += 1;
+}
+
+/// Maintenance system - start due rounds and move the technician along its
+/// route for this tick's simulated hours
+#[allow(clippy::too_many_arguments)]
+pub fn maintenance_system(
+    time: SimTime,
+    scenario: Res<Scenario>,
+    epoch_count: Res<EpochCount>,
+    catastrophes: Res<Catastrophes>,
+    mut maintenance_metrics: ResMut<MaintenanceMetrics>,
+    mut event_log: ResMut<EventLog>,
+    mut technicians: Query<(&mut Technician, &mut Transform)>,
+    mut nodes: ServiceQuery,
+) {
+    let Some(plan) = scenario.maintenance else {
+        return;
+    };
+    let depot = Vec2::from(plan.depot);
+    let now_h = catastrophes.elapsed_h;
+    let tick_h = time.window_h();
+
+    for (mut technician, mut transform) in technicians.iter_mut() {
+        let mut hours_left = tick_h;
+        // A tick can cover several legs and stops
+        while hours_left > 0.0 {
+            match technician.task {
+                TechnicianTask::Idle => {
+                    if now_h < technician.next_round_h {
+                        break;
+                    }
+                    technician.next_round_h += plan.interval_h;
+                    let candidates = nodes
+                        .iter()
+                        .filter(|(.., status)| **status != Status::Dead)
+                        .map(
+                            |(entity, node_transform, battery, health, _, soiling, ..)| {
+                                (
+                                    entity,
+                                    node_transform.translation.truncate(),
+                                    urgency(plan.select, battery, health, soiling),
+                                )
+                            },
+                        )
+                        .collect();
+                    technician.route = plan_route(depot, candidates, plan.visits);
+                    technician.stop = 0;
+                    if technician.route.is_empty() {
+                        break;
+                    }
+                    maintenance_metrics.rounds += 1;
+                    event_log.push(
+                        epoch_count.0,
+                        now_h,
+                        format!(
+                            "Maintenance round: {} nodes to visit",
+                            technician.route.len()
+                        ),
+                    );
+                    technician.task = TechnicianTask::Traveling;
+                }
+                TechnicianTask::Traveling => {
+                    // Skip stops whose node died since the round was planned
+                    while technician
+                        .route
+                        .get(technician.stop)
+                        .is_some_and(|&entity| {
+                            nodes
+                                .get(entity)
+                                .is_ok_and(|(.., status)| *status == Status::Dead)
+                        })
+                    {
+                        technician.stop += 1;
+                    }
+                    let target = technician
+                        .route
+                        .get(technician.stop)
+                        .and_then(|&entity| nodes.get(entity).ok())
+                        .map_or(depot, |(_, node_transform, ..)| {
+                            node_transform.translation.truncate()
+                        });
+
+                    let position = transform.translation.truncate();
+                    let travel_h = position.distance(target) / plan.speed_m_per_h;
+                    let step_h = travel_h.min(hours_left);
+                    hours_left -= step_h;
+                    maintenance_metrics.labor_h += step_h;
+                    maintenance_metrics.cost_usd += step_h * plan.hourly_cost_usd;
+                    if step_h < travel_h {
+                        let moved = position.move_towards(target, step_h * plan.speed_m_per_h);
+                        transform.translation = moved.extend(0.0);
+                        break;
+                    }
+                    transform.translation = target.extend(0.0);
+                    technician.task = if technician.stop < technician.route.len() {
+                        TechnicianTask::Servicing(plan.service_h)
+                    } else {
+                        technician.route.clear();
+                        TechnicianTask::Idle
+                    };
+                }
+                TechnicianTask::Servicing(remaining_h) => {
+                    let step_h = remaining_h.min(hours_left);
+                    hours_left -= step_h;
+                    maintenance_metrics.labor_h += step_h;
+                    maintenance_metrics.cost_usd += step_h * plan.hourly_cost_usd;
+                    if step_h < remaining_h {
+                        technician.task = TechnicianTask::Servicing(remaining_h - step_h);
+                        break;
+                    }
+                    let entity = technician.route[technician.stop];
+                    service(&plan, &mut nodes, entity, &mut maintenance_metrics);
+                    technician.stop += 1;
+                    technician.task = TechnicianTask::Traveling;
+                }
+            }
+        }
+    }
+}
+
+/// Draw the technician as a yellow diamond
+pub fn render_technician_system(
+    mut gizmos: Gizmos,
+    technicians: Query<&Transform, With<Technician>>,
+) {
+    let color = Color::srgb(1.0, 0.85, 0.1);
+    for transform in technicians.iter() {
+        let center = transform.translation.truncate();
+        gizmos.rect_2d(
+            Isometry2d::new(center, Rot2::degrees(45.0)),
+            Vec2::splat(12.0),
+            color,
+        );
+    }
+}
@@ -0,0 +1,80 @@
+/// Simulation milestone messages
+/// Births, deaths, finished epochs and extinctions are written as Bevy
+/// messages, so observer systems (sound, UI, exporters) can react through a
+/// `MessageReader` without touching the systems that cause them. Births are
+/// detected from newly written `Lineage` components (offspring overwrite
+/// the previous generation's entities, so `Changed` rather than `Added`),
+/// which covers every spawn path (founders, offspring, editor, gRPC);
+/// deaths are written where they happen, with their cause.
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::components::Lineage;
+use crate::radio::{RadioType, UsefulBits};
+
+/// A node joined the population
+#[derive(Message, Debug, Clone, Copy)]
+pub struct NodeSpawned {
+    pub entity: Entity,
+    pub node_id: u64,
+    /// Elite it was bred from (None for founders and manual spawns)
+    pub parent: Option<u64>,
+}
+
+/// Why a node died
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeathCause {
+    BatteryDepleted,
+    HardwareFault,
+    /// The board could not load the model (`--compatibility crash`)
+    IncompatibleModel,
+    /// Right-click in the editor
+    ManualKill,
+}
+
+/// A node's status turned dead
+#[derive(Message, Debug, Clone, Copy)]
+pub struct NodeDied {
+    pub entity: Entity,
+    pub node_id: u64,
+    pub cause: DeathCause,
+}
+
+/// A genetic epoch finished (written before the next generation spawns)
+#[derive(Message, Debug, Clone, Copy)]
+pub struct EpochCompleted {
+    pub epoch: u32,
+    pub population: usize,
+    pub survivors: usize,
+    pub fitness_mean: f32,
+    pub fitness_max: f32,
+    /// Radio energy and delivered results of each technology, indexed by
+    /// `RadioType as usize`
+    pub radio_useful: [UsefulBits; RadioType::ALL.len()],
+}
+
+/// No node survived the epoch; random founders are spawned instead
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ExtinctionOccurred {
+    pub epoch: u32,
+}
+
+/// Epoch-level writers, bundled to keep the epoch system under Bevy's
+/// parameter limit
+#[derive(SystemParam)]
+pub struct EpochMilestones<'w> {
+    pub completed: MessageWriter<'w, EpochCompleted>,
+    pub extinctions: MessageWriter<'w, ExtinctionOccurred>,
+}
+
+/// Announce nodes spawned since the last run of this system
+pub fn node_spawned_system(
+    nodes: Query<(Entity, &Lineage), Changed<Lineage>>,
+    mut spawned: MessageWriter<NodeSpawned>,
+) {
+    spawned.write_batch(nodes.iter().map(|(entity, lineage)| NodeSpawned {
+        entity,
+        node_id: lineage.id,
+        parent: lineage.parent,
+    }));
+}
@@ -1,11 +1,11 @@
 /// Real-world ML model definitions and characteristics for edge computing
 /// All data verified from academic papers, official benchmarks, and manufacturer specs
 /// Sources: Ultralytics YOLOv8, TensorFlow/Keras, NVIDIA, Google Coral documentation
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Real ML model types deployed on edge devices
 /// Each variant represents a production model with verified specifications
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum RealModelType {
     /// YOLOv8-nano: Lightweight object detection
@@ -55,6 +55,26 @@ pub enum RealModelType {
 
 #[allow(dead_code)]
 impl RealModelType {
+    /// Every model available to the genome
+    pub const ALL: [RealModelType; 8] = [
+        RealModelType::YOLOv8Nano,
+        RealModelType::YOLOv8Small,
+        RealModelType::MobileNetV2,
+        RealModelType::EfficientNetB0,
+        RealModelType::TinyBERT,
+        RealModelType::EfficientNetB1,
+        RealModelType::MobileNetV3Small,
+        RealModelType::DistilBERT,
+    ];
+
+    /// Look up a model by canonical name or variant name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|model| {
+            model.name().eq_ignore_ascii_case(name)
+                || format!("{:?}", model).eq_ignore_ascii_case(name)
+        })
+    }
+
     /// Get the canonical model name as a string
     pub fn name(&self) -> &'static str {
         match self {
@@ -128,6 +148,11 @@ impl RealModelType {
         }
     }
 
+    /// Energy of one inference (mJ) on Raspberry Pi 4
+    pub fn inference_energy_mj(&self) -> f32 {
+        self.inference_power_w() * self.inference_time_ms()
+    }
+
     /// Get model accuracy (%) on standard benchmarks
     /// Vision models: ImageNet top-1 accuracy
     /// Detection models: mAP@0.5 on COCO
@@ -145,6 +170,34 @@ impl RealModelType {
         }
     }
 
+    /// Serialized result of one inference (bytes): detectors send boxes,
+    /// classifiers top-5 labels with scores, NLP models a label and score
+    pub fn result_bytes(&self) -> f32 {
+        match self {
+            RealModelType::YOLOv8Nano | RealModelType::YOLOv8Small => 200.0, // ≤ 8 boxes × 24 B + count
+            RealModelType::MobileNetV2
+            | RealModelType::MobileNetV3Small
+            | RealModelType::EfficientNetB0
+            | RealModelType::EfficientNetB1 => 40.0, // Top-5 × (class id, score)
+            RealModelType::TinyBERT | RealModelType::DistilBERT => 24.0,
+        }
+    }
+
+    /// Compute per inference in GFLOPs, at the input sizes of the
+    /// inference times above (NLP models: 128-token sequences)
+    pub fn gflops(&self) -> f32 {
+        match self {
+            RealModelType::YOLOv8Nano => 8.7,
+            RealModelType::YOLOv8Small => 28.6,
+            RealModelType::MobileNetV2 => 0.30,
+            RealModelType::EfficientNetB0 => 0.39,
+            RealModelType::TinyBERT => 1.2,
+            RealModelType::MobileNetV3Small => 0.06,
+            RealModelType::EfficientNetB1 => 0.70,
+            RealModelType::DistilBERT => 11.3,
+        }
+    }
+
     /// Get energy efficiency ratio: accuracy per watt
     /// Higher values = better efficiency (more accuracy per unit power)
     pub fn efficiency_ratio(&self) -> f32 {
@@ -235,7 +288,7 @@ impl DeviceType {
             DeviceType::RaspberryPi4 => 4096.0, // Typical 4GB
             DeviceType::JetsonNano => 4096.0,   // 4GB LPDDR4
             DeviceType::CoralUSB => 2000.0,     // Host system RAM
-            DeviceType::ESP32 => 320.0,         // 320KB total SRAM
+            DeviceType::ESP32 => 4.3,           // 320KB SRAM + 4MB PSRAM
         }
     }
 
@@ -0,0 +1,172 @@
+/// Radio trace export for network simulators (flag: `--net-trace <dir>`)
+/// Every transmission attempt of the radio layer is written out so the
+/// traffic EdgeGenesis's application behavior generates can be replayed in
+/// ns-3 or OMNeT++ with their own PHY/MAC models. The directory holds:
+/// - `nodes.ns_movements`: ns-2 mobility lines giving every endpoint its
+///   (static) position, readable by ns-3's `Ns2MobilityHelper` and INET's
+///   `Ns2MotionMobility`. Trace node 0 is the sink; gateways and sensor
+///   nodes are numbered as they first appear.
+/// - `packets.csv`: one row per attempt with its start time, source and
+///   destination trace nodes, radio, TX power, payload and airtime, plus the
+///   outcome EdgeGenesis drew for it, for comparison with the re-simulation.
+///
+/// Contention is modeled statistically within a tick, so attempts carry no
+/// exact start time; each is placed uniformly in its tick window from the
+/// trace's own generator, leaving the simulation's random streams (and so
+/// the run itself) unchanged by tracing.
+use bevy::prelude::*;
+use rand::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::export::open_csv;
+use crate::radio::SINK_POSITION;
+
+/// A node of the exported network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Sink,
+    Gateway(Entity),
+    /// Sensor node, by lineage id
+    Node(u64),
+}
+
+/// What happened to an attempt in EdgeGenesis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptOutcome {
+    Delivered,
+    Collision,
+    LinkLoss,
+}
+
+/// One row of `packets.csv`
+#[derive(Debug, Clone, Serialize)]
+pub struct PacketRow {
+    /// Simulated seconds since the run started
+    pub start_s: f64,
+    /// Trace node numbers (see `nodes.ns_movements`)
+    pub src: usize,
+    pub dst: usize,
+    pub node_id: u64,
+    pub radio: &'static str,
+    pub tx_power_dbm: f32,
+    pub payload_bytes: f32,
+    pub airtime_s: f32,
+    /// 1 for a report's first attempt, higher for retransmissions
+    pub attempt: u32,
+    pub outcome: AttemptOutcome,
+}
+
+struct TraceFiles {
+    packets: csv::Writer<File>,
+    mobility: BufWriter<File>,
+}
+
+/// Optional radio trace sink
+#[derive(Resource, Default)]
+pub struct NetTrace {
+    files: Option<TraceFiles>,
+    /// Trace node number of every endpoint seen so far
+    endpoints: HashMap<Endpoint, usize>,
+    /// Places attempts within their tick
+    rng: Option<SmallRng>,
+    /// Rows written since the last flush
+    dirty: bool,
+}
+
+impl NetTrace {
+    /// Open the trace files in `dir`; tracing is disabled (with a warning)
+    /// when they cannot be created
+    pub fn create(dir: Option<&str>, seed: u64) -> Self {
+        let Some(dir) = dir else {
+            return Self::default();
+        };
+        let files = (|| -> Result<TraceFiles, Box<dyn Error>> {
+            let dir = Path::new(dir);
+            let packets = open_csv(&dir.join("packets.csv").to_string_lossy())?;
+            let mobility = BufWriter::new(File::create(dir.join("nodes.ns_movements"))?);
+            Ok(TraceFiles { packets, mobility })
+        })();
+        let mut trace = match files {
+            Ok(files) => {
+                info!(target: "edgegenesis::net_trace", "📡 Exporting the radio trace to {}/", dir);
+                Self {
+                    files: Some(files),
+                    rng: Some(SmallRng::seed_from_u64(seed)),
+                    ..default()
+                }
+            }
+            Err(e) => {
+                warn!(target: "edgegenesis::net_trace",
+                    "⚠️ Cannot create radio trace in {}: {}", dir, e
+                );
+                return Self::default();
+            }
+        };
+        trace.endpoint(Endpoint::Sink, SINK_POSITION);
+        trace
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.files.is_some()
+    }
+
+    /// Trace node number of an endpoint, placing it on first sight
+    pub fn endpoint(&mut self, endpoint: Endpoint, position: Vec2) -> usize {
+        if let Some(&index) = self.endpoints.get(&endpoint) {
+            return index;
+        }
+        let index = self.endpoints.len();
+        self.endpoints.insert(endpoint, index);
+        if let Some(files) = self.files.as_mut() {
+            let result = writeln!(files.mobility, "$node_({}) set X_ {:.2}", index, position.x)
+                .and_then(|_| {
+                    writeln!(files.mobility, "$node_({}) set Y_ {:.2}", index, position.y)
+                })
+                .and_then(|_| writeln!(files.mobility, "$node_({}) set Z_ 0.00", index));
+            if let Err(e) = result {
+                warn!(target: "edgegenesis::net_trace", "⚠️ Radio trace export failed: {}", e);
+            }
+            self.dirty = true;
+        }
+        index
+    }
+
+    /// Start time for an attempt of `airtime_s` in the tick that began at
+    /// `tick_start_s` and lasts `window_s`
+    pub fn start_s(&mut self, tick_start_s: f64, window_s: f32, airtime_s: f32) -> f64 {
+        let slack = (window_s - airtime_s).max(0.0) as f64;
+        let offset = self.rng.as_mut().map_or(0.0, |rng| rng.random::<f64>());
+        tick_start_s + offset * slack
+    }
+
+    /// Write one attempt (no-op when tracing is disabled)
+    pub fn record(&mut self, row: &PacketRow) {
+        let Some(files) = self.files.as_mut() else {
+            return;
+        };
+        if let Err(e) = files.packets.serialize(row) {
+            warn!(target: "edgegenesis::net_trace", "⚠️ Radio trace export failed: {}", e);
+        }
+        self.dirty = true;
+    }
+
+    /// Flush this tick's rows, so an interrupted run keeps its trace
+    pub fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        if let Some(files) = self.files.as_mut() {
+            let result = files.packets.flush().and_then(|_| files.mobility.flush());
+            if let Err(e) = result {
+                warn!(target: "edgegenesis::net_trace", "⚠️ Radio trace export failed: {}", e);
+            }
+        }
+    }
+}
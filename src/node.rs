@@ -0,0 +1,159 @@
+/// Per-node state of the energy model
+/// The battery, genome, status, telemetry, energy ledger, model cache and
+/// tuned policy that `engine::step_node` advances. Bevy-free like the rest
+/// of the engine's inputs: the ECS stores them as components through the
+/// `ecs` feature's derives, and components.rs re-exports them next to the
+/// ECS-only node state.
+use serde::{Deserialize, Serialize};
+
+use crate::models::RealModelType;
+use crate::policies::{PolicyParams, PowerPolicy, WakeSchedule};
+use crate::units::WattHours;
+
+/// Gene range for requested TX power (dBm)
+pub const MIN_TX_POWER_DBM: f32 = 0.0;
+pub const MAX_TX_POWER_DBM: f32 = 20.0;
+
+/// Precision of the battery charge and the energy totals: f64 with the
+/// `f64-energy` feature, for multi-week runs where f32 sums of tiny
+/// per-tick steps drift measurably
+#[cfg(feature = "f64-energy")]
+pub type Energy = f64;
+#[cfg(not(feature = "f64-energy"))]
+pub type Energy = f32;
+
+/// Battery component - stores energy level in Wh (Watt-hours)
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct Battery(pub Energy);
+
+// The casts are no-ops unless `f64-energy` is enabled
+#[allow(clippy::unnecessary_cast)]
+impl Battery {
+    pub fn new(wh: f32) -> Self {
+        Self(wh as Energy)
+    }
+
+    /// Charge (Wh)
+    pub fn wh(&self) -> f32 {
+        self.0 as f32
+    }
+
+    pub fn charge(&mut self, wh: f32) {
+        self.0 += wh as Energy;
+    }
+
+    /// Draw energy, stopping at empty
+    pub fn drain(&mut self, wh: f32) {
+        self.0 = (self.0 - wh as Energy).max(0.0);
+    }
+
+    /// Cap the charge at the capacity; returns the surplus cut off (Wh)
+    pub fn clip(&mut self, capacity_wh: f32) -> f32 {
+        let capacity_wh = capacity_wh as Energy;
+        let surplus_wh = (self.0 - capacity_wh).max(0.0);
+        self.0 = self.0.min(capacity_wh);
+        surplus_wh as f32
+    }
+}
+
+/// Gene component - evolutionary configuration
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct Gene {
+    /// Model type from models.rs (type-safe, documented)
+    pub model_type: RealModelType,
+
+    /// Inference frequency ratio (0.0 - 1.0)
+    pub inference_frequency: f32,
+
+    /// Solar panel efficiency multiplier (0.8 - 1.2)
+    pub solar_efficiency_factor: f32,
+
+    /// Power management strategy
+    pub policy: PowerPolicy,
+
+    /// Inherited policy parameters (starting point for lifetime tuning)
+    pub policy_params: PolicyParams,
+
+    /// Requested radio output power (dBm, 0 - 20): range vs. TX energy
+    pub tx_power_dbm: f32,
+
+    /// Compress result reports before queueing: CPU energy vs. airtime
+    pub compress_reports: bool,
+
+    /// Hours of the day the node intends to be awake
+    pub wake_schedule: WakeSchedule,
+}
+
+/// Node status
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub enum Status {
+    Alive,
+    Dead,
+}
+
+/// Instantaneous power readings from the last physics step
+/// Solar input and draw are in Watts; used by gauges and debugging tools
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct Telemetry {
+    pub solar_input_w: f32,
+    pub power_draw_w: f32,
+    pub inferring: bool,
+    /// Sleeping per the wake schedule
+    pub asleep: bool,
+}
+
+/// Lifetime energy balance of one node
+/// Every consumer drains `Battery` directly, so energy actually used is
+/// recovered from the balance: initial + swapped + harvested - clipped -
+/// battery losses - current. The totals run for the node's whole life, so
+/// they are kept at `Energy` precision like the battery.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct EnergyLedger {
+    /// Battery charge at birth (Wh)
+    pub initial_wh: WattHours,
+    /// Charge gained by maintenance battery swaps, net of the charge the
+    /// removed batteries still held (Wh)
+    pub swapped_wh: WattHours,
+    /// Solar energy delivered to the battery (Wh)
+    pub harvested_wh: WattHours,
+    /// Harvest lost because the battery was full (Wh)
+    pub clipped_wh: WattHours,
+    /// Charging losses and self-discharge (Wh)
+    pub battery_loss_wh: WattHours,
+}
+
+impl EnergyLedger {
+    /// Energy drawn from the battery so far
+    pub fn consumed_wh(&self, battery: &Battery) -> WattHours {
+        let balance = self.initial_wh + self.swapped_wh + self.harvested_wh
+            - self.clipped_wh
+            - self.battery_loss_wh
+            - WattHours::charge_of(battery);
+        balance.max(WattHours::ZERO)
+    }
+}
+
+/// Model resident in memory and any load still in progress
+/// Loading (at birth or on a model swap) blocks inference and draws extra
+/// power for `size_mb / load throughput` simulated seconds
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct ModelCache {
+    pub loaded: Option<RealModelType>,
+    /// Simulated seconds until the load completes
+    pub load_remaining_s: f32,
+    /// Inference power of the loaded model on a Raspberry Pi 4 (W), resolved
+    /// against the CSV overrides once per load instead of every tick
+    pub inference_power_w: f32,
+}
+
+/// Policy parameters as tuned during this node's life
+/// Starts from the genome's `policy_params`; passed on to offspring only in
+/// Lamarckian mode (flag: `--lamarckian`)
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct TunedPolicy(pub PolicyParams);
@@ -0,0 +1,172 @@
+/// Over-the-air model update campaigns
+/// A campaign (flag: `--ota <epoch>:<model>[:<fraction>]`) selects a random
+/// fraction of the alive nodes at the start of an epoch and pushes a new
+/// model to them over their own radio. Receiving costs `rx_power × size /
+/// data rate`, so a 6 MB model is seconds over WiFi but hours over LoRa.
+/// Transfers abort when the battery falls below `OTA_ABORT_SOC`; completed
+/// transfers replace the node's model, which then still has to be loaded.
+use bevy::prelude::*;
+use rand::Rng;
+use std::str::FromStr;
+
+use crate::compatibility::Compatibility;
+use crate::components::{Battery, EnvRng, EpochCount, Gene, SimulationMetrics, Status};
+use crate::config::SimConfig;
+use crate::hardware::HardwareSpec;
+use crate::models::RealModelType;
+use crate::radio::Radio;
+use crate::speed::SimTime;
+use crate::units::WattHours;
+
+/// State of charge below which a node abandons a transfer to stay alive
+pub const OTA_ABORT_SOC: f32 = 0.10;
+
+/// One scripted update campaign
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OtaCampaign {
+    /// Epoch at whose start the campaign is launched
+    pub epoch: u32,
+    pub model: RealModelType,
+    /// Fraction of alive nodes targeted (0-1)
+    pub fraction: f32,
+}
+
+impl FromStr for OtaCampaign {
+    type Err = String;
+
+    /// Parse `<epoch>:<model>[:<fraction>]`, fraction defaulting to 1.0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let epoch = parts
+            .next()
+            .and_then(|e| e.parse().ok())
+            .ok_or_else(|| format!("invalid epoch in {:?}", s))?;
+        let model = parts
+            .next()
+            .and_then(RealModelType::from_name)
+            .ok_or_else(|| format!("unknown model in {:?}", s))?;
+        let fraction = match parts.next() {
+            Some(f) => f
+                .parse::<f32>()
+                .map_err(|_| format!("invalid fraction in {:?}", s))?,
+            None => 1.0,
+        };
+
+        Ok(Self {
+            epoch,
+            model,
+            fraction: fraction.clamp(0.0, 1.0),
+        })
+    }
+}
+
+/// Download in progress on one node
+#[derive(Component, Debug, Clone, Copy)]
+pub struct OtaDownload {
+    pub model: RealModelType,
+    pub remaining_mb: f32,
+}
+
+/// Per-epoch OTA statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct OtaMetrics {
+    pub started: u64,
+    pub completed: u64,
+    /// Transfers abandoned on low battery or node death
+    pub aborted: u64,
+    pub energy_wh: WattHours,
+}
+
+/// Run condition: true when any campaign is scheduled
+pub fn ota_enabled(config: Res<SimConfig>) -> bool {
+    !config.ota_campaigns.is_empty()
+}
+
+/// Launch the campaigns scheduled for the current epoch (once per epoch)
+pub fn ota_campaign_system(
+    mut commands: Commands,
+    mut launched_epoch: Local<Option<u32>>,
+    epoch_count: Res<EpochCount>,
+    config: Res<SimConfig>,
+    mut env_rng: ResMut<EnvRng>,
+    mut ota_metrics: ResMut<OtaMetrics>,
+    nodes: Query<(Entity, &Status, &HardwareSpec), Without<OtaDownload>>,
+) {
+    if *launched_epoch == Some(epoch_count.0) {
+        return;
+    }
+    *launched_epoch = Some(epoch_count.0);
+
+    for campaign in config
+        .ota_campaigns
+        .iter()
+        .filter(|c| c.epoch == epoch_count.0)
+    {
+        let mut targeted = 0;
+        for (entity, status, hardware) in nodes.iter() {
+            if *status == Status::Dead || !env_rng.0.random_bool(campaign.fraction as f64) {
+                continue;
+            }
+            // The board could not run the update
+            if config.compatibility == Compatibility::Forbid && !hardware.runs(campaign.model) {
+                continue;
+            }
+            commands.entity(entity).try_insert(OtaDownload {
+                model: campaign.model,
+                remaining_mb: campaign.model.size_mb(),
+            });
+            targeted += 1;
+        }
+        ota_metrics.started += targeted;
+        println!(
+            "📦 OTA campaign: pushing {} ({:.1} MB) to {} nodes",
+            campaign.model.name(),
+            campaign.model.size_mb(),
+            targeted
+        );
+    }
+}
+
+/// Advance downloads: receive at the radio's data rate, pay RX energy,
+/// abort on low battery, swap the model in on completion
+pub fn ota_transfer_system(
+    mut commands: Commands,
+    time: SimTime,
+    mut ota_metrics: ResMut<OtaMetrics>,
+    mut metrics: ResMut<SimulationMetrics>,
+    mut query: Query<(
+        Entity,
+        &mut OtaDownload,
+        &mut Gene,
+        &mut Battery,
+        &Radio,
+        &HardwareSpec,
+        &Status,
+    )>,
+) {
+    let window_s = time.window_s();
+
+    for (entity, mut download, mut gene, mut battery, radio, hardware, status) in query.iter_mut() {
+        let state_of_charge = battery.wh() / hardware.usable_capacity_wh();
+        if *status == Status::Dead || state_of_charge < OTA_ABORT_SOC {
+            ota_metrics.aborted += 1;
+            commands.entity(entity).try_remove::<OtaDownload>();
+            continue;
+        }
+
+        let rate_mb_s = radio.radio_type.data_rate_bps() / 8.0 / 1e6;
+        let rx_s = (download.remaining_mb / rate_mb_s).min(window_s);
+        let energy = radio.radio_type.rx_power().over_s(rx_s);
+        battery.drain(energy.wh());
+        ota_metrics.energy_wh += energy;
+        metrics.total_energy_consumed += energy;
+
+        download.remaining_mb -= rx_s * rate_mb_s;
+        if download.remaining_mb <= 1e-6 {
+            // Model cache sees the new gene and charges the load next tick
+            gene.model_type = download.model;
+            ota_metrics.completed += 1;
+            commands.entity(entity).try_remove::<OtaDownload>();
+        }
+    }
+}
@@ -0,0 +1,345 @@
+/// Lineage tracking and phylogenetic tree export
+/// Every birth is recorded with its parent, so the ancestry of the current
+/// survivors can be reconstructed and rendered as an SVG tree: x = epoch of
+/// birth, branches colored by the child's model type. Extinct side branches
+/// are pruned, leaving the clades that actually carried the population.
+/// Nodes can carry free-text labels ("patient zero", "weird survivor"),
+/// kept by lineage id after the node is gone: labeled nodes stay in the
+/// tree with their labels, and the elite and annotation exports carry them
+/// (see annotations.rs).
+use bevy::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+
+use crate::components::{Gene, Lineage};
+use crate::models::RealModelType;
+
+/// Horizontal distance between epochs (px)
+const EPOCH_SPACING: f32 = 40.0;
+/// Vertical distance between leaves (px)
+const LEAF_SPACING: f32 = 12.0;
+const MARGIN: f32 = 40.0;
+const LEGEND_WIDTH: f32 = 180.0;
+
+/// Birth record of one node
+#[derive(Debug, Clone)]
+pub struct LineageRecord {
+    pub parent: Option<u64>,
+    pub born_epoch: u32,
+    pub model_type: RealModelType,
+    /// Fitness reached by the end of the node's epoch (None while alive)
+    pub fitness: Option<f32>,
+}
+
+/// Registry of every node ever born in this run
+#[derive(Resource, Default)]
+pub struct LineageLog {
+    next_id: u64,
+    epoch: u32,
+    records: BTreeMap<u64, LineageRecord>,
+    /// Labels by lineage id (ids may be labeled before they are born, when
+    /// annotations of an earlier run with the same seed are loaded)
+    labels: BTreeMap<u64, Vec<String>>,
+    /// Labels, or the fitness of a labeled node, changed since last taken
+    labels_changed: bool,
+}
+
+impl LineageLog {
+    /// Register a birth and return the new node's lineage component
+    pub fn register(&mut self, parent: Option<u64>, gene: &Gene) -> Lineage {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.records.insert(
+            id,
+            LineageRecord {
+                parent,
+                born_epoch: self.epoch,
+                model_type: gene.model_type,
+                fitness: None,
+            },
+        );
+
+        Lineage { id, parent }
+    }
+
+    /// Store a node's end-of-epoch fitness
+    pub fn record_fitness(&mut self, id: u64, fitness: f32) {
+        if let Some(record) = self.records.get_mut(&id) {
+            record.fitness = Some(fitness);
+            self.labels_changed |= self.labels.contains_key(&id);
+        }
+    }
+
+    /// Birth record of a node
+    pub fn record(&self, id: u64) -> Option<&LineageRecord> {
+        self.records.get(&id)
+    }
+
+    /// Labels of a node (empty when unlabeled)
+    pub fn labels(&self, id: u64) -> &[String] {
+        self.labels.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every labeled node, by id
+    pub fn labeled(&self) -> impl Iterator<Item = (u64, &[String])> {
+        self.labels
+            .iter()
+            .map(|(&id, labels)| (id, labels.as_slice()))
+    }
+
+    /// Tag a node; blank and repeated labels are ignored
+    pub fn add_label(&mut self, id: u64, label: &str) {
+        let label = label.trim();
+        let labels = self.labels.entry(id).or_default();
+        if !label.is_empty() && !labels.iter().any(|l| l == label) {
+            labels.push(label.to_string());
+            self.labels_changed = true;
+        }
+        if labels.is_empty() {
+            self.labels.remove(&id);
+        }
+    }
+
+    pub fn remove_label(&mut self, id: u64, label: &str) {
+        let Some(labels) = self.labels.get_mut(&id) else {
+            return;
+        };
+        labels.retain(|l| l != label);
+        if labels.is_empty() {
+            self.labels.remove(&id);
+        }
+        self.labels_changed = true;
+    }
+
+    /// Nearest labeled ancestor of a node
+    pub fn labeled_ancestor(&self, id: u64) -> Option<u64> {
+        let mut current = self.records.get(&id)?.parent;
+        while let Some(ancestor) = current {
+            if self.labels.contains_key(&ancestor) {
+                return Some(ancestor);
+            }
+            current = self.records.get(&ancestor)?.parent;
+        }
+        None
+    }
+
+    /// Whether labels changed since the last call
+    pub fn take_labels_changed(&mut self) -> bool {
+        std::mem::take(&mut self.labels_changed)
+    }
+
+    /// Advance the log's epoch counter (births after this belong to the next epoch)
+    pub fn advance_epoch(&mut self) {
+        self.epoch += 1;
+    }
+
+    /// Genetic epochs completed since the run started (extinctions included)
+    pub fn epochs(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Render the ancestry of `leaves` (and of every labeled node) as a
+    /// standalone SVG document
+    pub fn render_svg(&self, leaves: &[u64]) -> String {
+        // 1. Collect every ancestor of the leaves (pruning extinct branches)
+        let mut members = BTreeSet::new();
+        let labeled = self
+            .labels
+            .keys()
+            .filter(|id| self.records.contains_key(id));
+        for &leaf in leaves.iter().chain(labeled) {
+            let mut current = Some(leaf);
+            while let Some(id) = current {
+                if !members.insert(id) {
+                    break; // Remaining ancestry already collected
+                }
+                current = self.records.get(&id).and_then(|r| r.parent);
+            }
+        }
+
+        let mut children: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+        let mut roots = Vec::new();
+        for &id in &members {
+            match self.records.get(&id).and_then(|r| r.parent) {
+                Some(parent) if members.contains(&parent) => {
+                    children.entry(parent).or_default().push(id)
+                }
+                _ => roots.push(id),
+            }
+        }
+
+        // 2. Layout: leaves get consecutive rows, parents sit at their children's mean
+        let mut row_of: BTreeMap<u64, f32> = BTreeMap::new();
+        let mut next_row = 0.0;
+        for &root in &roots {
+            self.layout(root, &children, &mut row_of, &mut next_row);
+        }
+
+        let max_epoch = members
+            .iter()
+            .filter_map(|id| self.records.get(id))
+            .map(|r| r.born_epoch)
+            .max()
+            .unwrap_or(0);
+        let width = MARGIN * 2.0 + max_epoch as f32 * EPOCH_SPACING + LEGEND_WIDTH;
+        let height = MARGIN * 2.0 + (next_row.max(1.0) - 1.0) * LEAF_SPACING;
+        let position = |id: u64| -> (f32, f32) {
+            let epoch = self.records.get(&id).map_or(0, |r| r.born_epoch);
+            (
+                MARGIN + epoch as f32 * EPOCH_SPACING,
+                MARGIN + row_of.get(&id).copied().unwrap_or(0.0) * LEAF_SPACING,
+            )
+        };
+
+        // 3. Draw elbow branches (colored by child model) and node markers
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" font-family="monospace" font-size="11">"##,
+            width,
+            height.max(MARGIN * 2.0 + RealModelType::ALL.len() as f32 * 16.0)
+        );
+        let _ = writeln!(
+            svg,
+            r##"<rect width="100%" height="100%" fill="#101418"/>"##
+        );
+        let _ = writeln!(
+            svg,
+            r##"<text x="{:.0}" y="20" fill="#c0c8d0">Phylogeny of {} survivors ({} ancestors, {} epochs)</text>"##,
+            MARGIN,
+            leaves.len(),
+            members.len(),
+            max_epoch + 1
+        );
+
+        for (&parent, kids) in &children {
+            let (px, py) = position(parent);
+            for &child in kids {
+                let (cx, cy) = position(child);
+                let color = self
+                    .records
+                    .get(&child)
+                    .map_or("#808080", |r| model_color(r.model_type));
+                let _ = writeln!(
+                    svg,
+                    r##"<path d="M{:.1},{:.1} V{:.1} H{:.1}" stroke="{}" fill="none" stroke-width="1.5"/>"##,
+                    px, py, cy, cx, color
+                );
+            }
+        }
+
+        for &id in &members {
+            let Some(record) = self.records.get(&id) else {
+                continue;
+            };
+            let (x, y) = position(id);
+            let labels = self.labels(id).join(", ");
+            let _ = writeln!(
+                svg,
+                r##"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{}"><title>#{} {} (epoch {}, fitness {}){}</title></circle>"##,
+                x,
+                y,
+                model_color(record.model_type),
+                id,
+                record.model_type.name(),
+                record.born_epoch,
+                record
+                    .fitness
+                    .map_or("alive".to_string(), |f| format!("{:.1}s", f)),
+                if labels.is_empty() {
+                    String::new()
+                } else {
+                    format!(" {}", xml_escape(&labels))
+                }
+            );
+            if !labels.is_empty() {
+                let _ = writeln!(
+                    svg,
+                    r##"<circle cx="{:.1}" cy="{:.1}" r="5" fill="none" stroke="#ffd54f"/><text x="{:.1}" y="{:.1}" fill="#ffd54f">{}</text>"##,
+                    x,
+                    y,
+                    x + 7.0,
+                    y - 5.0,
+                    xml_escape(&labels)
+                );
+            }
+        }
+
+        // 4. Legend
+        let legend_x = width - LEGEND_WIDTH + 10.0;
+        for (i, model) in RealModelType::ALL.iter().enumerate() {
+            let y = MARGIN + i as f32 * 16.0;
+            let _ = writeln!(
+                svg,
+                r##"<rect x="{:.0}" y="{:.0}" width="10" height="10" fill="{}"/><text x="{:.0}" y="{:.0}" fill="#c0c8d0">{}</text>"##,
+                legend_x,
+                y - 9.0,
+                model_color(*model),
+                legend_x + 16.0,
+                y,
+                model.name()
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Depth-first row assignment; returns the row of `id`
+    fn layout(
+        &self,
+        id: u64,
+        children: &BTreeMap<u64, Vec<u64>>,
+        row_of: &mut BTreeMap<u64, f32>,
+        next_row: &mut f32,
+    ) -> f32 {
+        let row = match children.get(&id) {
+            Some(kids) if !kids.is_empty() => {
+                let rows: Vec<f32> = kids
+                    .iter()
+                    .map(|&kid| self.layout(kid, children, row_of, next_row))
+                    .collect();
+                rows.iter().sum::<f32>() / rows.len() as f32
+            }
+            _ => {
+                let row = *next_row;
+                *next_row += 1.0;
+                row
+            }
+        };
+        row_of.insert(id, row);
+        row
+    }
+
+    /// Write the survivors' phylogeny to `path` (overwritten every epoch)
+    pub fn write_svg(&self, path: &str, leaves: &[u64]) {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = std::fs::write(path, self.render_svg(leaves)) {
+            eprintln!("⚠️ Phylogeny export failed ({}): {}", path, e);
+        }
+    }
+}
+
+/// Escape user text for SVG content
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Stable branch color per model family (hex, SVG-ready)
+pub fn model_color(model: RealModelType) -> &'static str {
+    match model {
+        RealModelType::YOLOv8Nano => "#4fc3f7",
+        RealModelType::YOLOv8Small => "#0288d1",
+        RealModelType::MobileNetV2 => "#81c784",
+        RealModelType::MobileNetV3Small => "#2e7d32",
+        RealModelType::EfficientNetB0 => "#ffb74d",
+        RealModelType::EfficientNetB1 => "#ef6c00",
+        RealModelType::TinyBERT => "#ba68c8",
+        RealModelType::DistilBERT => "#d81b60",
+    }
+}
@@ -0,0 +1,369 @@
+/// Bevy plugin: the whole simulation as one unit
+/// `EdgeGenesisPlugin` loads the data files (power overrides, solar profile,
+/// scenario, seed population), inserts every resource the systems expect
+/// and registers the simulation, rendering and editor systems. The host app
+/// supplies the window and rendering (`DefaultPlugins` or an equivalent);
+/// `EguiPlugin` is added when the host has not added it already. Data paths
+/// are relative to the working directory, as for the binary.
+use bevy::prelude::*;
+use bevy_egui::EguiPlugin;
+
+use crate::components::{self, EnvRng, EpochCount, GeneticRng};
+use crate::config::SimConfig;
+use crate::{
+    annotations, attack, catastrophe, clock, compression, crossfade, data_loader, decision_log,
+    editor, event_log, export, extinction, failure, fitness, forecast, gateway, genome, gossip,
+    grpc, hardware, heatmap, inspector, intervention, maintenance, milestones, net_trace, ota,
+    phylogeny, policy_stats, preflight, radio, report, scenario, sensing, sizing, solar, spectator,
+    speed, sprites, storage, systems, thermal, timeline, topology, tradeoff, trust, warmup,
+    weather, web,
+};
+
+/// Runs one simulation configured by `config`
+#[derive(Default)]
+pub struct EdgeGenesisPlugin {
+    pub config: SimConfig,
+}
+
+impl EdgeGenesisPlugin {
+    pub fn new(config: SimConfig) -> Self {
+        Self { config }
+    }
+
+    /// Load the data files and insert every simulation resource, without
+    /// registering any system (what `build` does before scheduling; also
+    /// the setup for driving single systems in tests)
+    pub fn insert_resources(&self, app: &mut App) {
+        let config = &self.config;
+
+        info!(
+            "🎲 Seed: {} (env stream: {}, genetic stream: {})",
+            config.seed,
+            config.env_seed(),
+            config.genetic_seed()
+        );
+
+        info!("🌐 Grid topology: {:?}", config.topology);
+
+        if config.fitness != fitness::FitnessMode::SurvivalTime {
+            info!("🎯 Fitness: {}", config.fitness.name());
+        }
+
+        if config.fitness_normalization != fitness::FitnessNormalization::None {
+            info!(
+                "⚖️ Fitness normalization: {}",
+                config.fitness_normalization.name()
+            );
+        }
+
+        if let Some(hw_type) = config.locked_hardware {
+            info!(
+                "🔒 Hardware locked: every node is a {}",
+                hardware::HardwareSpec::new(hw_type).name()
+            );
+        }
+
+        if let Some(budget) = config.budget_usd {
+            info!("💵 Fleet budget: ${:.0} per generation", budget);
+        }
+
+        if config.lamarckian {
+            info!("🧬 Inheritance: Lamarckian (offspring inherit tuned policy parameters)");
+        }
+
+        if config.gossip_rate > 0.0 {
+            info!(
+                "🗣️ Gossip gene transfer: {} exchanges/node/hour",
+                config.gossip_rate
+            );
+        }
+
+        if config.debug_single_node {
+            info!("🔍 Debug mode: single node, verbose policy logging");
+        }
+
+        // Load CSV data (optional overrides)
+        let power_csv = data_loader::load_power_profiles("data/power_profiles/raspberry_pi_4.csv")
+            .ok()
+            .map(|profiles| {
+                let mut map = std::collections::HashMap::new();
+                for p in profiles {
+                    info!(
+                        "📦 CSV Override: {} ({}W)",
+                        p.model_name, p.inference_power_w
+                    );
+                    map.insert(p.model_name.clone(), p);
+                }
+                map
+            });
+
+        let solar_seasons = config.solar_seasons().unwrap_or_else(|e| {
+            warn!("Solar CSV not found: {}. Using synthetic data.", e);
+            solar::SolarSeasons::Fixed(Vec::new())
+        });
+        let solar_profiles = solar_seasons.profiles_on(config.day_of_year);
+        let calendar = config.calendar();
+
+        match &solar_seasons {
+            solar::SolarSeasons::Modeled(location) => info!(
+                "☀️ Modeling solar hours at {:.2}, {:.2} from {}",
+                location.latitude_deg, location.longitude_deg, calendar
+            ),
+            solar::SolarSeasons::Monthly(_) => {
+                info!(
+                    "☀️ Loaded 12 monthly solar profiles, starting on {}",
+                    calendar
+                )
+            }
+            solar::SolarSeasons::Daily(_) => {
+                info!(
+                    "☀️ Loaded a full-year hourly solar timeline, starting on {}",
+                    calendar
+                )
+            }
+            solar::SolarSeasons::Fixed(_) => {
+                info!("☀️ Loaded {} solar hours", solar_profiles.len())
+            }
+        }
+
+        let ambient_profiles =
+            data_loader::load_ambient_profiles("data/ambient_profiles/algiers_summer.csv")
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "Ambient CSV not found: {}. Using a constant {} °C.",
+                        e,
+                        thermal::DEFAULT_AMBIENT_C
+                    );
+                    Vec::new()
+                });
+
+        info!("🌡️ Loaded {} ambient hours", ambient_profiles.len());
+
+        let hardware_catalog = config.hardware_catalog().unwrap_or_else(|e| {
+            warn!(
+                "Cannot load hardware catalog: {}. Using the built-in platforms.",
+                e
+            );
+            hardware::HardwareCatalog::default()
+        });
+        for spec in &hardware_catalog.0 {
+            info!(
+                "🖥️ Catalog platform: {} ({:.0} GFLOPS, {:.0} MB, {:.1} W idle)",
+                spec.name(),
+                spec.compute_gflops,
+                spec.ram_mb,
+                spec.idle_power_w
+            );
+        }
+
+        for warning in preflight::config_warnings(
+            config,
+            &solar_profiles,
+            power_csv.as_ref(),
+            &hardware_catalog,
+        ) {
+            warn!("⚠️ {}", warning);
+        }
+
+        let scenario = match config.scenario.as_deref() {
+            Some(path) => match scenario::load_scenario(path) {
+                Ok(scenario) => {
+                    info!(
+                        "🗺️ Scenario {}: {} gateways, {} microclimates",
+                        path,
+                        scenario.gateways.len(),
+                        scenario.microclimates.len()
+                    );
+                    scenario
+                }
+                Err(e) => {
+                    warn!("Cannot load scenario {}: {}. Using defaults.", path, e);
+                    scenario::Scenario::default()
+                }
+            },
+            None => scenario::Scenario::default(),
+        };
+
+        let weather = match &config.weather {
+            weather::WeatherModel::Trace(path) => match data_loader::load_cloud_trace(path) {
+                Ok(trace) => {
+                    info!(
+                        "☁️ Replaying {} hours of cloud cover from {}",
+                        trace.len(),
+                        path
+                    );
+                    weather::Weather::from_trace(&trace)
+                }
+                Err(e) => {
+                    warn!(
+                        "Cannot load cloud trace {}: {}. Using a clear sky.",
+                        path, e
+                    );
+                    weather::Weather::default()
+                }
+            },
+            _ => weather::Weather::default(),
+        };
+
+        // A missing annotation file is created on the first label
+        let mut lineage_log = phylogeny::LineageLog::default();
+        if let Some(path) = config.annotations.as_deref()
+            && std::path::Path::new(path).exists()
+        {
+            match annotations::load_annotations(path) {
+                Ok(annotations) => {
+                    info!(
+                        "🔖 Loaded {} annotated nodes from {}",
+                        annotations.len(),
+                        path
+                    );
+                    for annotation in annotations {
+                        for label in &annotation.labels {
+                            lineage_log.add_label(annotation.id, label);
+                        }
+                    }
+                }
+                Err(e) => warn!("Cannot load annotations {}: {}", path, e),
+            }
+        }
+
+        let seed_population = match config.seed_population.as_deref() {
+            Some(path) => match genome::load_population(path) {
+                Ok(genomes) => {
+                    info!(
+                        "🌱 Seeding founders from {} ({} genomes)",
+                        path,
+                        genomes.len()
+                    );
+                    if config.locked_hardware.is_none() {
+                        for genome in genome::unresolved_platforms(&genomes, &hardware_catalog) {
+                            warn!(
+                                "Seed platform {:?} is not in the hardware catalog: its genomes run \
+                                 on the generic {} spec. Pass the --hardware-csv the genomes were \
+                                 evolved with",
+                                genome.platform,
+                                hardware::HardwareSpec::new(genome.hardware).name()
+                            );
+                        }
+                    }
+                    genomes
+                }
+                Err(e) => {
+                    warn!(
+                        "Cannot load seed population {}: {}. Using random founders.",
+                        path, e
+                    );
+                    Vec::new()
+                }
+            },
+            None => Vec::new(),
+        };
+        info!("🧬 Using models.rs as primary data source");
+
+        app.insert_resource(EpochCount(1))
+            .insert_resource(EnvRng::from_seed(config.env_seed()))
+            .insert_resource(GeneticRng::from_seed(config.genetic_seed()))
+            .insert_resource(export::MetricsExporter::create(
+                config.metrics_out.as_deref(),
+            ))
+            .insert_resource(timeline::TimelineRecorder::create(
+                config.metrics_tick_out.as_deref(),
+                config.metrics_hourly_out.as_deref(),
+            ))
+            .insert_resource(export::SnapshotExporter::new(
+                config.snapshot_dir.as_deref(),
+            ))
+            .insert_resource(net_trace::NetTrace::create(
+                config.net_trace.as_deref(),
+                config.env_seed(),
+            ))
+            .insert_resource(export::PolicyStatsExporter::create(
+                config.policy_stats_out.as_deref(),
+            ))
+            .insert_resource(export::LatencyExporter::create(
+                config.latency_out.as_deref(),
+            ))
+            .insert_resource(web::WebDashboard::start(
+                config.dashboard_bind,
+                config.dashboard_port,
+            ))
+            .insert_resource(grpc::GrpcControl::start(config.grpc_port))
+            .insert_resource(report::RunReport::create(config.report.as_deref(), config))
+            .insert_resource(genome::EliteExporter::create(
+                config.elite_out.as_deref(),
+                config.elite_top,
+            ))
+            .insert_resource(lineage_log)
+            .init_resource::<radio::RadioMetrics>()
+            .init_resource::<gossip::GossipMetrics>()
+            .init_resource::<sensing::SensingMetrics>()
+            .init_resource::<sensing::Workload>()
+            .init_resource::<ota::OtaMetrics>()
+            .init_resource::<attack::AttackMetrics>()
+            .init_resource::<trust::TrustMetrics>()
+            .init_resource::<failure::FailureMetrics>()
+            .init_resource::<gateway::GatewayMetrics>()
+            .init_resource::<storage::StorageMetrics>()
+            .init_resource::<compression::CompressionMetrics>()
+            .init_resource::<forecast::ForecastMetrics>()
+            .init_resource::<thermal::ThermalMetrics>()
+            .init_resource::<maintenance::MaintenanceMetrics>()
+            .init_resource::<weather::WeatherMetrics>()
+            .init_resource::<forecast::SolarForecast>()
+            .init_resource::<policy_stats::PolicyMetrics>()
+            .init_resource::<policy_stats::PolicyDashboard>()
+            .init_resource::<catastrophe::Catastrophes>()
+            .init_resource::<systems::EpochClock>()
+            .init_resource::<export::MetricCollectors>()
+            .add_message::<milestones::NodeSpawned>()
+            .add_message::<milestones::NodeDied>()
+            .add_message::<milestones::EpochCompleted>()
+            .add_message::<milestones::ExtinctionOccurred>()
+            .insert_resource(event_log::EventLog::create(config.event_log.as_deref()))
+            .insert_resource(extinction::ExtinctionMonitor::create(
+                config.extinction_log.as_deref(),
+            ))
+            .init_resource::<extinction::Easing>()
+            .init_resource::<sizing::SizingReport>()
+            .insert_resource(decision_log::DecisionLog::create(
+                config.decision_log.as_deref(),
+                config.decision_log_raw,
+            ))
+            .insert_resource(topology::Topology::new(config.topology))
+            .insert_resource(speed::SimSpeed::new(config))
+            .insert_resource(spectator::Spectator::new(config))
+            .insert_resource(crossfade::Crossfade::new(config))
+            .insert_resource(clock::ExternalClock::new(config))
+            .insert_resource(config.clone())
+            .insert_resource(scenario)
+            .insert_resource(hardware_catalog)
+            .insert_resource(weather)
+            .insert_resource(genome::SeedPopulation(seed_population))
+            .insert_resource(components::PowerOverrides(power_csv))
+            .insert_resource(components::LoadedSolarProfiles(solar_profiles))
+            .insert_resource(solar_seasons)
+            .insert_resource(calendar)
+            .insert_resource(warmup::WarmUp::new(config))
+            .insert_resource(components::LoadedAmbientProfiles(ambient_profiles));
+    }
+}
+
+impl Plugin for EdgeGenesisPlugin {
+    fn build(&self, app: &mut App) {
+        self.insert_resources(app);
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin::default());
+        }
+        systems::register_systems(app);
+        editor::register_editor(app);
+        inspector::register_inspector(app);
+        annotations::register_annotations(app);
+        intervention::register_intervention(app);
+        heatmap::register_heatmap(app);
+        spectator::register_spectator(app);
+        sprites::register_sprites(app);
+        crossfade::register_crossfade(app);
+        tradeoff::register_tradeoff(app);
+        clock::register_clock(app);
+    }
+}
@@ -1,30 +1,147 @@
 use rand::Rng;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+/// Battery threshold range shared by mutation, adaptation and the editor
+pub const MIN_BATTERY_THRESHOLD: f32 = 0.05;
+pub const MAX_BATTERY_THRESHOLD: f32 = 0.95;
+
+/// Request rate limit range (requests served per simulated hour)
+pub const MAX_REQUEST_RATE_LIMIT: f32 = 120.0;
+
+/// Upper bound of each evolvable PID gain
+pub const MAX_PID_GAIN: f32 = 5.0;
+
+/// Anti-windup bound of the PID integral (state-of-charge error × hours)
+const PID_INTEGRAL_LIMIT: f32 = 10.0;
+
+/// Planning horizon of the MPC policy (hours)
+pub const MPC_HORIZON_H: usize = 24;
+
+/// Battery discretization of the MPC dynamic program
+const MPC_BATTERY_BINS: usize = 48;
+
+/// Lifetime adaptation speed of tunable policies (threshold change per simulated hour)
+const ADAPTATION_RATE_PER_HOUR: f32 = 0.05;
+
+/// Sliding window of the energy-neutral controller (one diurnal cycle, hours)
+pub const ENO_WINDOW_H: f32 = 24.0;
+
+/// Tunable policy parameters: inherited as genes, adapted during life
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PolicyParams {
+    /// State of charge (0-1) below which cautious policies stop inferring
+    pub battery_threshold: f32,
+    /// Incoming requests served per simulated hour (token bucket refill);
+    /// the defense against denial-of-sleep floods
+    pub request_rate_limit: f32,
+    /// State of charge (0-1) below which results are stored locally
+    /// instead of transmitted, and flushed once the battery recovers
+    pub tx_battery_threshold: f32,
+    /// PID gains on the state-of-charge error (per unit SOC, per SOC·hour,
+    /// per SOC/hour); used by the PID policy only
+    pub pid_kp: f32,
+    pub pid_ki: f32,
+    pub pid_kd: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PowerPolicy {
     /// Always runs inference (Subject to frequency). Risk taker.
     Aggressive,
 
-    /// Only runs if battery is healthy (> 50%). Safe but low score.
+    /// Only runs if battery is healthy (above threshold, default 50%). Safe but low score.
     Conservative,
 
-    /// Adapts to environment: Runs if Solar is present OR Battery is high. Sleeps at night if low.
+    /// Adapts to environment: Runs if Solar is present OR Battery is above threshold
+    /// (default 30%). Sleeps at night if low, and tunes its threshold during life.
     SmartAdaptive,
+
+    /// Energy-neutral operation (Kansal et al., 2007): sets its duty cycle so
+    /// that consumption over a sliding window matches the harvest, steering
+    /// the battery towards its threshold. The literature baseline; ignores
+    /// the gene's inference frequency.
+    EnergyNeutral,
+
+    /// Continuous rate control: a PID loop on the state of charge (setpoint
+    /// = battery threshold) sets the inference rate around the gene's
+    /// frequency, with evolvable gains.
+    Pid,
+
+    /// Model-predictive control: every simulated hour, plans the next 24
+    /// hours of infer/sleep against the solar forecast and executes the
+    /// first decision. Expensive-but-strong baseline; ignores the frequency.
+    Mpc,
 }
 
 impl PowerPolicy {
+    /// Every available power management strategy
+    pub const ALL: [PowerPolicy; 6] = [
+        PowerPolicy::Aggressive,
+        PowerPolicy::Conservative,
+        PowerPolicy::SmartAdaptive,
+        PowerPolicy::EnergyNeutral,
+        PowerPolicy::Pid,
+        PowerPolicy::Mpc,
+    ];
+
+    /// Look up a policy by display or variant name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|policy| {
+            policy.name().eq_ignore_ascii_case(name)
+                || format!("{:?}", policy).eq_ignore_ascii_case(name)
+        })
+    }
+
+    /// Uniformly random policy
+    pub fn random(rng: &mut impl Rng) -> Self {
+        Self::ALL[rng.random_range(0..Self::ALL.len())]
+    }
+
+    /// Hand-tuned parameters each policy was originally designed with
+    pub fn default_params(&self) -> PolicyParams {
+        let (battery_threshold, request_rate_limit, tx_battery_threshold) = match self {
+            PowerPolicy::Aggressive => (MIN_BATTERY_THRESHOLD, MAX_REQUEST_RATE_LIMIT, 0.0), // Threshold unused
+            PowerPolicy::Conservative => (0.5, 10.0, 0.5),
+            PowerPolicy::SmartAdaptive => (0.3, 30.0, 0.3),
+            PowerPolicy::EnergyNeutral => (0.5, 30.0, 0.3), // Threshold = target charge
+            PowerPolicy::Pid => (0.5, 30.0, 0.3),           // Threshold = setpoint
+            PowerPolicy::Mpc => (0.3, 30.0, 0.3),           // Threshold = end-of-horizon reserve
+        };
+        PolicyParams {
+            battery_threshold,
+            request_rate_limit,
+            tx_battery_threshold,
+            pid_kp: 1.0,
+            pid_ki: 0.1,
+            pid_kd: 0.5,
+        }
+    }
+
     /// Decides whether to run inference based on current state
+    /// Randomness comes from the caller's (environment) stream
     pub fn should_infer(
         &self,
-        battery_wh: f32,
+        state_of_charge: f32,
         solar_output_w: f32,
         base_probability: f32,
+        params: &PolicyParams,
+        state: &PolicyState,
+        rng: &mut impl Rng,
     ) -> bool {
-        let mut rng = rand::rng();
-
-        // Base probabilistic check (Gene frequency)
-        if !rng.random_bool(base_probability as f64) {
+        // Base probabilistic check (Gene frequency, or the controller output)
+        let probability = match self {
+            PowerPolicy::EnergyNeutral => state.eno.duty_cycle,
+            PowerPolicy::Pid => state.pid.rate,
+            PowerPolicy::Mpc => {
+                if state.mpc.infer {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => base_probability,
+        };
+        if !rng.random_bool(probability.clamp(0.0, 1.0) as f64) {
             return false;
         }
 
@@ -34,19 +151,48 @@ impl PowerPolicy {
                 true
             }
             PowerPolicy::Conservative => {
-                // Requires > 50% charge (assuming 40Wh max)
-                battery_wh > 20.0
+                // Requires a healthy charge
+                state_of_charge > params.battery_threshold
             }
             PowerPolicy::SmartAdaptive => {
                 // If Solar is active (> 5W), run freely.
-                // If Night/Cloudy, conserve unless battery is robust (> 30%).
+                // If Night/Cloudy, conserve unless battery is robust.
                 if solar_output_w > 5.0 {
                     true
                 } else {
-                    battery_wh > 12.0 // 30% of 40Wh
+                    state_of_charge > params.battery_threshold
                 }
             }
+            PowerPolicy::EnergyNeutral | PowerPolicy::Pid | PowerPolicy::Mpc => {
+                // The controller output already encodes the energy budget
+                true
+            }
+        }
+    }
+
+    /// Lifetime learning: SmartAdaptive nudges its threshold up after
+    /// running close to empty and down while sitting on a full battery in
+    /// sunlight (wasted harvest). Other policies keep their genome values.
+    pub fn adapt(
+        &self,
+        params: &mut PolicyParams,
+        state_of_charge: f32,
+        solar_output_w: f32,
+        hours: f32,
+    ) {
+        if *self != PowerPolicy::SmartAdaptive {
+            return;
+        }
+
+        let step = ADAPTATION_RATE_PER_HOUR * hours;
+        if state_of_charge < 0.1 {
+            params.battery_threshold += step;
+        } else if state_of_charge > 0.95 && solar_output_w > 5.0 {
+            params.battery_threshold -= step;
         }
+        params.battery_threshold = params
+            .battery_threshold
+            .clamp(MIN_BATTERY_THRESHOLD, MAX_BATTERY_THRESHOLD);
     }
 
     pub fn name(&self) -> &'static str {
@@ -54,6 +200,229 @@ impl PowerPolicy {
             PowerPolicy::Aggressive => "Aggressive",
             PowerPolicy::Conservative => "Conservative",
             PowerPolicy::SmartAdaptive => "SmartAdaptive",
+            PowerPolicy::EnergyNeutral => "EnergyNeutral",
+            PowerPolicy::Pid => "PID",
+            PowerPolicy::Mpc => "MPC",
+        }
+    }
+}
+
+/// Runtime state of the stateful controller policies (one per node)
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct PolicyState {
+    pub eno: EnoController,
+    pub pid: PidController,
+    pub mpc: MpcController,
+}
+
+/// Energy-neutral duty-cycle controller
+/// Harvest is averaged over the last `ENO_WINDOW_H` hours (exponential
+/// window). The duty cycle is the share of time the node can spend inferring
+/// so that its average draw equals that harvest, plus the charge above the
+/// target state of charge spread over the same window (a deficit below the
+/// target is recovered the same way).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnoController {
+    /// Average harvested power (W); None before the first observation
+    pub harvest_w: Option<f32>,
+    /// Fraction of time spent inferring (0-1)
+    pub duty_cycle: f32,
+}
+
+impl EnoController {
+    /// Fold `hours` of harvest at `harvest_w` into the sliding average
+    pub fn observe_harvest(&mut self, harvest_w: f32, hours: f32) {
+        let average = self.harvest_w.get_or_insert(harvest_w);
+        let alpha = 1.0 - (-hours / ENO_WINDOW_H).exp();
+        *average += alpha * (harvest_w - *average);
+    }
+
+    /// Re-plan the duty cycle between the idle draw and the full inference
+    /// draw (sensor included) for the given battery state
+    pub fn plan(
+        &mut self,
+        state_of_charge: f32,
+        target_soc: f32,
+        capacity_wh: f32,
+        idle_w: f32,
+        active_w: f32,
+    ) {
+        let budget_w = self.harvest_w.unwrap_or(0.0)
+            + (state_of_charge - target_soc) * capacity_wh / ENO_WINDOW_H;
+        self.duty_cycle = if active_w > idle_w {
+            ((budget_w - idle_w) / (active_w - idle_w)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+    }
+}
+
+/// PID inference-rate controller
+/// Process variable: state of charge; setpoint: the battery threshold;
+/// output: inference rate = gene frequency + Kp·e + Ki·∫e dt + Kd·de/dt
+/// (hours), clamped to 0-1. A surplus (e > 0) speeds inference up. The
+/// integral is bounded against wind-up while the output saturates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PidController {
+    pub integral: f32,
+    /// Error of the previous step; None before the first
+    pub last_error: Option<f32>,
+    /// Current inference rate (0-1)
+    pub rate: f32,
+}
+
+impl PidController {
+    pub fn step(&mut self, state_of_charge: f32, bias: f32, params: &PolicyParams, hours: f32) {
+        let error = state_of_charge - params.battery_threshold;
+        self.integral =
+            (self.integral + error * hours).clamp(-PID_INTEGRAL_LIMIT, PID_INTEGRAL_LIMIT);
+        let derivative = match self.last_error {
+            Some(last) if hours > 0.0 => (error - last) / hours,
+            _ => 0.0,
+        };
+        self.last_error = Some(error);
+        self.rate = (bias
+            + params.pid_kp * error
+            + params.pid_ki * self.integral
+            + params.pid_kd * derivative)
+            .clamp(0.0, 1.0);
+    }
+}
+
+/// Receding-horizon state of the MPC policy
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MpcController {
+    /// Hour of day of the last re-plan; None before the first
+    pub planned_hour: Option<usize>,
+    /// First decision of the current plan
+    pub infer: bool,
+}
+
+/// One MPC re-plan: hourly energy flows over the horizon, index 0 = now
+#[derive(Debug, Clone)]
+pub struct MpcProblem {
+    pub battery_wh: f32,
+    pub capacity_wh: f32,
+    /// Charge that must remain at the end of the horizon (Wh)
+    pub reserve_wh: f32,
+    /// Predicted harvest per hour (Wh)
+    pub harvest_wh: [f32; MPC_HORIZON_H],
+    /// Baseline draw per hour (idle, or sleep power when scheduled off), Wh
+    pub base_wh: [f32; MPC_HORIZON_H],
+    /// Extra draw of an hour spent inferring (Wh)
+    pub infer_wh: f32,
+    /// Hours in which the wake schedule allows inference
+    pub awake: [bool; MPC_HORIZON_H],
+}
+
+impl MpcProblem {
+    /// Dynamic program over (hour, battery level) maximizing inference
+    /// hours without emptying the battery and ending at or above the
+    /// reserve. Returns whether to infer during the current hour; false
+    /// when no plan is feasible (saving energy is the best remaining move).
+    pub fn solve(&self) -> bool {
+        let capacity = self.capacity_wh.max(f32::EPSILON);
+        let level = |bin: usize| capacity * bin as f32 / (MPC_BATTERY_BINS - 1) as f32;
+        // Round down: the plan never counts on charge it might not have
+        let bin = |wh: f32| {
+            ((wh / capacity * (MPC_BATTERY_BINS - 1) as f32) as usize).min(MPC_BATTERY_BINS - 1)
+        };
+        let step = |hour: usize, wh: f32, infer: bool| {
+            let drain = self.base_wh[hour] + if infer { self.infer_wh } else { 0.0 };
+            let next = (wh + self.harvest_wh[hour] - drain).min(capacity);
+            (next > 0.0).then_some(next)
+        };
+
+        // value[b] = best inference hours from the next hour on, at level b
+        let mut value: Vec<f32> = (0..MPC_BATTERY_BINS)
+            .map(|b| {
+                if level(b) >= self.reserve_wh {
+                    0.0
+                } else {
+                    f32::NEG_INFINITY
+                }
+            })
+            .collect();
+        for hour in (1..MPC_HORIZON_H).rev() {
+            value = (0..MPC_BATTERY_BINS)
+                .map(|b| {
+                    let options = [false, true].map(|infer| {
+                        if infer && !self.awake[hour] {
+                            return f32::NEG_INFINITY;
+                        }
+                        step(hour, level(b), infer).map_or(f32::NEG_INFINITY, |next| {
+                            value[bin(next)] + if infer { 1.0 } else { 0.0 }
+                        })
+                    });
+                    options[0].max(options[1])
+                })
+                .collect();
+        }
+
+        if !self.awake[0] {
+            return false;
+        }
+        let outcome = |infer: bool| {
+            step(0, self.battery_wh, infer).map_or(f32::NEG_INFINITY, |next| value[bin(next)])
+        };
+        let infer_value = outcome(true) + 1.0;
+        infer_value.is_finite() && infer_value > outcome(false)
+    }
+}
+
+/// Daily wake schedule: bit `h` set = the node intends to be awake during
+/// hour `h` (0-23). Asleep, a node drops to its sleep power and neither
+/// infers, transmits nor answers requests, whatever its policy says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WakeSchedule(pub u32);
+
+impl WakeSchedule {
+    const MASK: u32 = (1 << 24) - 1;
+    /// Awake around the clock (threshold-only behavior)
+    pub const ALWAYS: WakeSchedule = WakeSchedule(Self::MASK);
+
+    /// Random schedule, each hour awake with probability `p_awake`
+    pub fn random(p_awake: f64, rng: &mut impl Rng) -> Self {
+        Self(
+            (0..24)
+                .filter(|_| rng.random_bool(p_awake))
+                .fold(0, |bits, hour| bits | (1 << hour)),
+        )
+    }
+
+    pub fn is_awake(&self, hour: usize) -> bool {
+        self.0 & (1 << (hour % 24)) != 0
+    }
+
+    pub fn set_awake(&mut self, hour: usize, awake: bool) {
+        if awake {
+            self.0 |= 1 << (hour % 24);
+        } else {
+            self.0 &= !(1 << (hour % 24));
+        }
+    }
+
+    pub fn awake_hours(&self) -> u32 {
+        (self.0 & Self::MASK).count_ones()
+    }
+
+    /// Flip each hour independently with probability `rate`
+    pub fn mutate(&mut self, rate: f64, rng: &mut impl Rng) {
+        for hour in 0..24 {
+            if rng.random_bool(rate) {
+                self.0 ^= 1 << hour;
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for WakeSchedule {
+    /// 24-character timeline from 00:00, `#` awake, `.` asleep
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for hour in 0..24 {
+            f.write_str(if self.is_awake(hour) { "#" } else { "." })?;
         }
+        Ok(())
     }
 }
@@ -0,0 +1,145 @@
+/// Per-policy performance dashboard
+/// The dominant-policy count only says which policy is most common, not
+/// whether it is better. Each epoch the population is grouped by
+/// `PowerPolicy` and every group is scored on survival rate, mean fitness,
+/// energy efficiency (detections per Wh drawn) and inference throughput
+/// (inferences per alive node-hour). The latest table is shown in an egui
+/// window and, with `--policy-stats-out <path>`, appended to a CSV.
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+use crate::components::{Battery, EnergyLedger, Gene, Status};
+use crate::policies::PowerPolicy;
+use crate::sensing::Sensing;
+use crate::units::WattHours;
+
+/// Per-epoch activity counters, indexed like `PowerPolicy::ALL`
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct PolicyMetrics {
+    /// Inferences run (inference time / model latency)
+    pub inferences: [f32; PowerPolicy::ALL.len()],
+    /// Hours spent alive
+    pub node_hours: [f32; PowerPolicy::ALL.len()],
+}
+
+/// One policy's scores over an epoch
+#[derive(Debug, Clone, Copy)]
+pub struct PolicySummary {
+    pub policy: PowerPolicy,
+    pub nodes: usize,
+    pub survivors: usize,
+    pub mean_fitness: f32,
+    /// Energy drawn from batteries
+    pub energy_wh: WattHours,
+    pub detections: u64,
+    pub inferences: f32,
+    pub node_hours: f32,
+}
+
+impl PolicySummary {
+    pub fn survival_rate(&self) -> f32 {
+        self.survivors as f32 / self.nodes as f32
+    }
+
+    /// Detections per Wh drawn (None before any energy was used)
+    pub fn detections_per_wh(&self) -> Option<f32> {
+        (self.energy_wh > WattHours::ZERO).then(|| self.detections as f32 / self.energy_wh.wh())
+    }
+
+    /// Inferences per alive node-hour (None before any node-hour)
+    pub fn inferences_per_hour(&self) -> Option<f32> {
+        (self.node_hours > 0.0).then(|| self.inferences / self.node_hours)
+    }
+}
+
+/// Latest per-policy table, for the dashboard window
+#[derive(Resource, Debug, Default)]
+pub struct PolicyDashboard {
+    pub epoch: u32,
+    pub summaries: Vec<PolicySummary>,
+}
+
+/// Score every policy present in the population; `nodes` yields each
+/// node's genome, status, evaluated fitness, sensing record and energy
+pub fn policy_summaries<'a>(
+    nodes: impl Iterator<
+        Item = (
+            &'a Gene,
+            &'a Status,
+            f32,
+            &'a Sensing,
+            &'a Battery,
+            &'a EnergyLedger,
+        ),
+    >,
+    metrics: &PolicyMetrics,
+) -> Vec<PolicySummary> {
+    let mut summaries = PowerPolicy::ALL.map(|policy| PolicySummary {
+        policy,
+        nodes: 0,
+        survivors: 0,
+        mean_fitness: 0.0,
+        energy_wh: WattHours::ZERO,
+        detections: 0,
+        inferences: metrics.inferences[policy as usize],
+        node_hours: metrics.node_hours[policy as usize],
+    });
+
+    for (gene, status, fitness, sensing, battery, ledger) in nodes {
+        let summary = &mut summaries[gene.policy as usize];
+        summary.nodes += 1;
+        if *status != Status::Dead {
+            summary.survivors += 1;
+        }
+        // Running sum, divided below
+        summary.mean_fitness += fitness;
+        summary.energy_wh += ledger.consumed_wh(battery);
+        summary.detections += sensing.detections as u64;
+    }
+
+    summaries
+        .into_iter()
+        .filter(|summary| summary.nodes > 0)
+        .map(|mut summary| {
+            summary.mean_fitness /= summary.nodes as f32;
+            summary
+        })
+        .collect()
+}
+
+/// Dashboard window - last epoch's scores, one row per policy
+pub fn policy_dashboard_ui_system(
+    mut contexts: EguiContexts,
+    dashboard: Res<PolicyDashboard>,
+) -> Result {
+    egui::Window::new("Policy Dashboard")
+        .default_open(false)
+        .show(contexts.ctx_mut()?, |ui| {
+            if dashboard.summaries.is_empty() {
+                ui.label("Waiting for the first epoch...");
+                return;
+            }
+            ui.label(format!("Epoch {}", dashboard.epoch));
+            let optional = |value: Option<f32>, precision: usize| {
+                value.map_or("-".to_string(), |v| format!("{:.*}", precision, v))
+            };
+            egui::Grid::new("policy_dashboard")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in ["Policy", "Nodes", "Survival", "Fitness", "Det/Wh", "Inf/h"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    for summary in &dashboard.summaries {
+                        ui.label(summary.policy.name());
+                        ui.label(summary.nodes.to_string());
+                        ui.label(format!("{:.0}%", summary.survival_rate() * 100.0));
+                        ui.label(format!("{:.2}", summary.mean_fitness));
+                        ui.label(optional(summary.detections_per_wh(), 3));
+                        ui.label(optional(summary.inferences_per_hour(), 0));
+                        ui.end_row();
+                    }
+                });
+        });
+    Ok(())
+}
@@ -0,0 +1,114 @@
+/// Startup configuration sanity checks
+/// Before the first tick the configuration and the loaded data are
+/// cross-checked for combinations the physics cannot represent: panels
+/// that deliver more than a hardware type's charge input (the surplus is
+/// clipped), an epoch shorter than one simulated hour (selection would
+/// never see a full day/night cycle), power overrides listing inference
+/// power below idle power, and a locked platform that runs no model under
+/// `--compatibility`. Each finding is one warning saying what to change;
+/// the run still starts.
+use std::collections::HashMap;
+
+use crate::compatibility::Compatibility;
+use crate::config::SimConfig;
+use crate::curriculum::START_SOLAR_SCALE;
+use crate::data_loader::{PowerProfileCSV, SolarProfile, get_model_power};
+use crate::extinction::{EASE_SOLAR_STEP, MAX_EASE_LEVEL};
+use crate::hardware::{HardwareCatalog, HardwareSpec};
+use crate::models::RealModelType;
+use crate::systems::{EPOCH_SECONDS, solar_output_w};
+use crate::units::SECONDS_PER_HOUR;
+
+/// Simulated hours covered by one epoch
+fn epoch_hours(config: &SimConfig) -> f32 {
+    EPOCH_SECONDS as f32 * config.time_scale / SECONDS_PER_HOUR
+}
+
+/// Largest multiplier the curriculum and auto-easing can put on the panel
+/// output
+fn max_solar_scale(config: &SimConfig) -> f32 {
+    let curriculum = if config.curriculum_epochs > 0 {
+        START_SOLAR_SCALE
+    } else {
+        1.0
+    };
+    let easing = if config.auto_ease {
+        1.0 + EASE_SOLAR_STEP * MAX_EASE_LEVEL as f32
+    } else {
+        1.0
+    };
+    curriculum * easing
+}
+
+/// Every inconsistency found, as actionable warning messages
+pub fn config_warnings(
+    config: &SimConfig,
+    solar_profiles: &[SolarProfile],
+    power_overrides: Option<&HashMap<String, PowerProfileCSV>>,
+    catalog: &HardwareCatalog,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    // Panel output above the rated input of the hardware that can be deployed
+    let panel = config.fitted_panel().unwrap_or_default();
+    let peak_solar_w = panel.converted_w(
+        (0..24)
+            .map(|hour| solar_output_w(solar_profiles, hour))
+            .fold(0.0, f32::max)
+            * max_solar_scale(config),
+    );
+    let deployable = match config.locked_hardware {
+        Some(hw_type) => vec![HardwareSpec::new(hw_type)],
+        None => catalog.platforms(),
+    };
+    for spec in deployable {
+        if peak_solar_w > spec.max_solar_input_w {
+            warnings.push(format!(
+                "Panels peak at {:.1} W but {} boards take {:.1} W, the surplus is clipped: \
+                 fit a smaller panel with --panel, or lock larger hardware with --hardware",
+                peak_solar_w,
+                spec.name(),
+                spec.max_solar_input_w
+            ));
+        }
+    }
+
+    // Epochs must span at least one simulated hour
+    if epoch_hours(config) < 1.0 {
+        warnings.push(format!(
+            "An epoch covers only {:.2} simulated hours: raise EPOCH_SECONDS or \
+             --time-scale so selection sees at least a full hour",
+            epoch_hours(config)
+        ));
+    }
+
+    // A locked board that runs no model can never be repaired
+    if let Some(hw_type) = config.locked_hardware
+        && config.compatibility != Compatibility::Off
+    {
+        let spec = HardwareSpec::new(hw_type);
+        if !RealModelType::ALL.into_iter().any(|model| spec.runs(model)) {
+            warnings.push(format!(
+                "{} boards run none of the models, so every node is incompatible: lock \
+                 other hardware with --hardware or use --compatibility off",
+                spec.name()
+            ));
+        }
+    }
+
+    // Inference is charged on top of idle; a lower figure is a swapped column
+    for model in RealModelType::ALL {
+        let (idle_power_w, inference_power_w) = get_model_power(model, power_overrides);
+        if inference_power_w < idle_power_w {
+            warnings.push(format!(
+                "{} lists inference power {:.2} W below idle power {:.2} W: check the \
+                 power profile CSV columns",
+                model.name(),
+                inference_power_w,
+                idle_power_w
+            ));
+        }
+    }
+
+    warnings
+}
@@ -0,0 +1,562 @@
+/// Radio communication layer: periodic result reports over a shared channel
+/// Nodes that ran inference since their last report transmit a small result
+/// packet every `REPORT_INTERVAL_S` simulated seconds. The channel is shared:
+/// transmissions that overlap in time within interference range collide
+/// (unslotted ALOHA), forcing retries that cost extra TX energy. Dense
+/// deployments therefore pay far more per delivered report than isolated links.
+/// LoRa additionally obeys the EU868 1% duty-cycle limit: after every
+/// transmission the node stays silent for 99× its airtime, so reports (and
+/// retries) queue up and can overflow - a node may infer but be unable to report.
+/// Reports go to a single sink at the grid center over a log-distance link:
+/// far nodes need more TX power (a gene) or pay for retransmissions.
+/// The cost is summed up as the energy per useful bit: TX energy over the
+/// result bits delivered, per node and per radio technology.
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::{Battery, EnvRng, Gene, Lineage, SimulationMetrics, Status, Telemetry};
+use crate::compression::{
+    CompressionMetrics, compression_energy, payload_bytes, raw_payload_bytes,
+};
+use crate::gateway::{Gateway, nearest_gateway};
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::milestones::EpochCompleted;
+use crate::net_trace::{AttemptOutcome, Endpoint, NetTrace, PacketRow};
+use crate::speed::SimTime;
+use crate::topology::Topology;
+use crate::trust::{Byzantine, MISREPORT_PROBABILITY, Reputation, TrustMetrics};
+use crate::units::{Power, WattHours};
+
+// Gene range for requested TX power (dBm), bounded by the genome
+pub use crate::node::{MAX_TX_POWER_DBM, MIN_TX_POWER_DBM};
+
+/// Simulated seconds between result reports
+pub const REPORT_INTERVAL_S: f32 = 300.0;
+/// Retransmissions attempted after a failed attempt before the report is dropped
+pub const MAX_RETRIES: u32 = 3;
+/// Reports a node can hold while waiting for channel access; the oldest is
+/// dropped on overflow
+pub const MAX_BACKLOG: u32 = 8;
+/// Data sink (gateway / access point) all reports are sent to.
+/// World units are meters for every radio range and path-loss figure
+pub const SINK_POSITION: Vec2 = Vec2::ZERO;
+/// Log-normal shadowing spread around the mean path loss (dB)
+const SHADOWING_SIGMA_DB: f32 = 6.0;
+
+/// Radio technology fitted to a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadioType {
+    /// LoRa (SX1276 @ SF7/125 kHz, 14 dBm): long range, very low bitrate
+    LoRa,
+    /// 802.11n WiFi: high bitrate, short bursts, higher TX power
+    WiFi,
+}
+
+impl RadioType {
+    /// Every radio technology, indexed by `RadioType as usize`
+    pub const ALL: [RadioType; 2] = [RadioType::LoRa, RadioType::WiFi];
+
+    /// Typical radio for each hardware platform
+    pub fn for_hardware(hw_type: HardwareType) -> Self {
+        match hw_type {
+            HardwareType::ESP32 => RadioType::LoRa, // Low-power field node + LoRa module
+            HardwareType::RaspberryPi4 => RadioType::WiFi,
+            HardwareType::JetsonNano => RadioType::WiFi,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RadioType::LoRa => "LoRa",
+            RadioType::WiFi => "WiFi",
+        }
+    }
+
+    /// Power drawn while receiving
+    pub fn rx_power(&self) -> Power {
+        match self {
+            RadioType::LoRa => Power::from_milliwatts(40.0), // ~12 mA @ 3.3 V
+            RadioType::WiFi => Power::from_milliwatts(600.0), // Onboard WiFi RX
+        }
+    }
+
+    /// Highest RF output the radio supports (dBm)
+    pub fn max_tx_power_dbm(&self) -> f32 {
+        match self {
+            RadioType::LoRa => 20.0, // SX1276 PA_BOOST
+            RadioType::WiFi => 20.0,
+        }
+    }
+
+    /// Transmitter electronics drawn regardless of output power (Watts)
+    fn circuit_power(&self) -> Power {
+        match self {
+            RadioType::LoRa => Power::from_milliwatts(66.0), // ~20 mA @ 3.3 V
+            RadioType::WiFi => Power::from_milliwatts(500.0), // Baseband + MAC during a TX burst
+        }
+    }
+
+    /// Power amplifier efficiency (RF out / DC in)
+    fn pa_efficiency(&self) -> f32 {
+        match self {
+            RadioType::LoRa => 0.30,
+            RadioType::WiFi => 0.20,
+        }
+    }
+
+    /// Power drawn while transmitting at `tx_power_dbm`
+    /// LoRa: 14 dBm ≈ 0.15 W, 20 dBm ≈ 0.40 W (SX1276 datasheet)
+    pub fn tx_power(&self, tx_power_dbm: f32) -> Power {
+        self.circuit_power() + Power::from_dbm(tx_power_dbm) / self.pa_efficiency()
+    }
+
+    /// Mean path loss at 1 m (dB): free space at 868 MHz / 2.4 GHz
+    fn reference_loss_db(&self) -> f32 {
+        match self {
+            RadioType::LoRa => 31.2,
+            RadioType::WiFi => 40.0,
+        }
+    }
+
+    /// Path-loss exponent for an outdoor deployment with clutter
+    fn path_loss_exponent(&self) -> f32 {
+        match self {
+            RadioType::LoRa => 2.7,
+            RadioType::WiFi => 3.0,
+        }
+    }
+
+    /// Receiver sensitivity at the configured data rate (dBm)
+    fn sensitivity_dbm(&self) -> f32 {
+        match self {
+            RadioType::LoRa => -123.0, // SF7 / 125 kHz
+            RadioType::WiFi => -82.0,  // 6 Mbit/s OFDM
+        }
+    }
+
+    /// Probability a packet survives the link (no collision involved)
+    /// Log-distance path loss with log-normal shadowing: the fade margin
+    /// above sensitivity is mapped through a logistic approximation of the
+    /// normal CDF, so the link degrades smoothly around its nominal range
+    pub fn link_success_probability(&self, tx_power_dbm: f32, distance_m: f32) -> f32 {
+        let path_loss_db = self.reference_loss_db()
+            + 10.0 * self.path_loss_exponent() * distance_m.max(1.0).log10();
+        let margin_db = tx_power_dbm - path_loss_db - self.sensitivity_dbm();
+        1.0 / (1.0 + (-1.7 * margin_db / SHADOWING_SIGMA_DB).exp())
+    }
+
+    /// Effective PHY bitrate (bits per second)
+    pub fn data_rate_bps(&self) -> f32 {
+        match self {
+            RadioType::LoRa => 5_470.0,
+            RadioType::WiFi => 6_000_000.0,
+        }
+    }
+
+    /// Protocol overhead added to every packet (headers, preamble, CRC)
+    pub fn overhead_bytes(&self) -> f32 {
+        match self {
+            RadioType::LoRa => 13.0,
+            RadioType::WiFi => 58.0,
+        }
+    }
+
+    /// Distance within which two transmissions interfere (world units)
+    pub fn interference_range(&self) -> f32 {
+        match self {
+            RadioType::LoRa => 400.0,
+            RadioType::WiFi => 150.0,
+        }
+    }
+
+    /// Regulatory duty-cycle limit (fraction of time on air), if any
+    pub fn duty_cycle(&self) -> Option<f32> {
+        match self {
+            RadioType::LoRa => Some(0.01), // ETSI EN 300 220, EU868 g1 sub-band
+            RadioType::WiFi => None,
+        }
+    }
+
+    /// Mandatory silence after `airtime_s` on air (seconds)
+    pub fn off_time_s(&self, airtime_s: f32) -> f32 {
+        self.duty_cycle()
+            .map_or(0.0, |duty| airtime_s * (1.0 / duty - 1.0))
+    }
+
+    /// Time on air for one packet carrying `payload_bytes` (seconds)
+    pub fn airtime_s(&self, payload_bytes: f32) -> f32 {
+        (payload_bytes + self.overhead_bytes()) * 8.0 / self.data_rate_bps()
+    }
+}
+
+/// Radio component - per-node communication state
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Radio {
+    pub radio_type: RadioType,
+    /// RF output power (dBm), the node's gene clamped to what the radio supports
+    pub tx_power_dbm: f32,
+    /// Simulated seconds until the next report slot
+    pub report_timer_s: f32,
+    /// Ran inference since the last report (has results to send)
+    pub has_results: bool,
+    /// Reports waiting for channel access
+    pub backlog: u32,
+    /// Failed attempts on the report at the head of the queue
+    pub retries: u32,
+    /// Remaining duty-cycle silence (simulated seconds)
+    pub off_time_s: f32,
+    /// Battery too low to transmit: reports go to local storage instead
+    pub holding: bool,
+    /// Energy spent on and results delivered by this node's reports
+    pub useful: UsefulBits,
+}
+
+impl Radio {
+    pub fn new(radio_type: RadioType, tx_power_dbm: f32) -> Self {
+        let mut radio = Self {
+            radio_type,
+            tx_power_dbm: 0.0,
+            report_timer_s: REPORT_INTERVAL_S,
+            has_results: false,
+            backlog: 0,
+            retries: 0,
+            off_time_s: 0.0,
+            holding: false,
+            useful: UsefulBits::default(),
+        };
+        radio.set_tx_power_dbm(tx_power_dbm);
+        radio
+    }
+
+    /// Apply a requested output power, clamped to what the radio supports
+    pub fn set_tx_power_dbm(&mut self, tx_power_dbm: f32) {
+        self.tx_power_dbm =
+            tx_power_dbm.clamp(MIN_TX_POWER_DBM, self.radio_type.max_tx_power_dbm());
+    }
+}
+
+/// TX energy against delivered results: the energy per useful bit, the
+/// usual figure for comparing WSN protocols
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UsefulBits {
+    /// TX energy of every attempt, collided, lost and retried ones included
+    pub energy_wh: WattHours,
+    /// Results carried by delivered reports, before compression and without
+    /// headers (bytes)
+    pub delivered_bytes: f32,
+}
+
+impl UsefulBits {
+    /// Energy per delivered result bit (µJ); None before any delivery
+    pub fn uj_per_bit(&self) -> Option<f32> {
+        (self.delivered_bytes > 0.0)
+            .then(|| self.energy_wh.joules() * 1e6 / (self.delivered_bytes * 8.0))
+    }
+
+    pub fn add(&mut self, other: &UsefulBits) {
+        self.energy_wh += other.energy_wh;
+        self.delivered_bytes += other.delivered_bytes;
+    }
+
+    /// Sum of `parts`
+    pub fn total(parts: &[UsefulBits]) -> Self {
+        let mut total = Self::default();
+        for part in parts {
+            total.add(part);
+        }
+        total
+    }
+}
+
+/// Per-epoch radio statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct RadioMetrics {
+    pub attempts: u64,
+    pub collisions: u64,
+    /// Attempts lost to path loss / fading on the way to the sink
+    pub link_losses: u64,
+    pub delivered: u64,
+    /// Reports abandoned after `MAX_RETRIES` failed retransmissions
+    pub dropped: u64,
+    /// Reports generated while the duty cycle held the radio silent
+    pub deferred: u64,
+    /// Reports discarded because the backlog was full
+    pub overflowed: u64,
+    /// Reports queued across the population (latest tick)
+    pub backlog: u64,
+    /// Largest population backlog seen this epoch
+    pub peak_backlog: u64,
+    pub energy_wh: WattHours,
+    /// Energy per useful bit of each technology, indexed by
+    /// `RadioType as usize`
+    pub by_radio: [UsefulBits; RadioType::ALL.len()],
+}
+
+impl RadioMetrics {
+    /// Fraction of transmission attempts lost to collisions
+    pub fn collision_rate(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.collisions as f32 / self.attempts as f32
+        }
+    }
+
+    /// Energy per useful bit over every technology
+    pub fn useful_bits(&self) -> UsefulBits {
+        UsefulBits::total(&self.by_radio)
+    }
+
+    /// Fraction of transmission attempts lost on the link to the sink
+    pub fn link_loss_rate(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.link_losses as f32 / self.attempts as f32
+        }
+    }
+}
+
+/// Log each finished epoch's energy per useful bit, overall and per radio
+/// technology
+pub fn useful_bits_report_system(mut completed: MessageReader<EpochCompleted>) {
+    let uj_per_bit = |useful: UsefulBits| {
+        useful
+            .uj_per_bit()
+            .map_or("-".to_string(), |uj| format!("{:.2} µJ/bit", uj))
+    };
+    for epoch in completed.read() {
+        info!(target: "edgegenesis::radio",
+            "⚡ Energy per useful bit (epoch {}): {} (LoRa {}, WiFi {})",
+            epoch.epoch,
+            uj_per_bit(UsefulBits::total(&epoch.radio_useful)),
+            uj_per_bit(epoch.radio_useful[RadioType::LoRa as usize]),
+            uj_per_bit(epoch.radio_useful[RadioType::WiFi as usize])
+        );
+    }
+}
+
+/// Spread first report slots uniformly so nodes do not transmit in lockstep
+/// (newborns carry a new `Lineage`, on fresh or recycled entities)
+pub fn randomize_report_phase_system(
+    mut env_rng: ResMut<EnvRng>,
+    mut radios: Query<&mut Radio, Changed<Lineage>>,
+) {
+    for mut radio in radios.iter_mut() {
+        radio.report_timer_s = env_rng.0.random_range(0.0..REPORT_INTERVAL_S);
+    }
+}
+
+/// Everything the radio step reads or updates on a node
+type RadioQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static mut Radio,
+        &'static mut Battery,
+        &'static mut Reputation,
+        &'static Status,
+        &'static Telemetry,
+        &'static Gene,
+        &'static HardwareSpec,
+        &'static Lineage,
+        Has<Byzantine>,
+    ),
+>;
+
+/// Radio system - report queueing, duty cycle, channel contention and TX energy
+///
+/// Every node with a queued report and no pending duty-cycle silence sends
+/// one attempt per tick. Attempts in the same tick share a window of
+/// `dt × speedup` simulated seconds; for unslotted ALOHA two packets of
+/// airtime T with uniform start times in a window W overlap with
+/// probability ≈ (T₁ + T₂)/W, so a sender avoids a collision with the
+/// product of (1 − (T + Tᵢ)/W) over its interfering neighbors (same
+/// technology, within range). Airtime follows the node's report payload:
+/// the model's result size, shrunk when the genome compresses. A packet
+/// that does not collide must still survive the link to the sink. Failed
+/// reports stay at the head of the queue and are retried after the off-time.
+/// Every attempt drains `tx_power × airtime` from the battery. Delivered
+/// reports are cross-checked by the sink, feeding the sender's reputation.
+/// When the scenario defines gateways, reports go to the nearest gateway
+/// instead of the sink and wait there for its next backhaul uplink. With
+/// `--net-trace`, every attempt is also written to the radio trace.
+#[allow(clippy::too_many_arguments)]
+pub fn radio_system(
+    time: SimTime,
+    topology: Res<Topology>,
+    mut env_rng: ResMut<EnvRng>,
+    mut radio_metrics: ResMut<RadioMetrics>,
+    mut trust_metrics: ResMut<TrustMetrics>,
+    mut metrics: ResMut<SimulationMetrics>,
+    mut compression_metrics: ResMut<CompressionMetrics>,
+    mut net_trace: ResMut<NetTrace>,
+    mut query: RadioQuery,
+    mut gateways: Query<(Entity, &Transform, &mut Gateway)>,
+) {
+    let window_s = time.window_s();
+    if window_s <= 0.0 {
+        return;
+    }
+    let tick_start_s = time.start_s();
+
+    // 1. Advance timers, enqueue new reports, collect this tick's transmitters
+    let mut transmitters: Vec<(Entity, Vec2, RadioType, f32, f32)> = Vec::new();
+    let mut backlog = 0;
+    for (entity, transform, mut radio, mut battery, _, status, telemetry, gene, hardware, _, _) in
+        query.iter_mut()
+    {
+        if *status == Status::Dead {
+            continue;
+        }
+        radio.has_results |= telemetry.inferring;
+        radio.report_timer_s -= window_s;
+        radio.off_time_s = (radio.off_time_s - window_s).max(0.0);
+
+        if radio.report_timer_s <= 0.0 {
+            radio.report_timer_s += REPORT_INTERVAL_S;
+            if radio.has_results {
+                radio.has_results = false;
+                if gene.compress_reports {
+                    let raw_bytes = raw_payload_bytes(gene);
+                    let energy = compression_energy(hardware.hardware_type, raw_bytes);
+                    battery.drain(energy.wh());
+                    metrics.total_energy_consumed += energy;
+                    compression_metrics.compressed_reports += 1;
+                    compression_metrics.bytes_saved += raw_bytes - payload_bytes(gene);
+                    compression_metrics.cpu_energy_wh += energy;
+                }
+                if radio.off_time_s > 0.0 {
+                    radio_metrics.deferred += 1;
+                }
+                if radio.backlog == MAX_BACKLOG {
+                    // Drop the oldest report to make room for the fresh one
+                    radio_metrics.overflowed += 1;
+                    radio.retries = 0;
+                } else {
+                    radio.backlog += 1;
+                }
+            }
+        }
+
+        if radio.backlog > 0 && radio.off_time_s <= 0.0 && !radio.holding && !telemetry.asleep {
+            transmitters.push((
+                entity,
+                transform.translation.truncate(),
+                radio.radio_type,
+                radio.tx_power_dbm,
+                radio.radio_type.airtime_s(payload_bytes(gene)),
+            ));
+        }
+        backlog += radio.backlog as u64;
+    }
+    radio_metrics.backlog = backlog;
+    radio_metrics.peak_backlog = radio_metrics.peak_backlog.max(backlog);
+
+    let gateway_sites: Vec<(Entity, Vec2)> = gateways
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+        .collect();
+
+    // 2. Contention: each sender vs. concurrent same-technology senders in range
+    for &(entity, position, radio_type, tx_power_dbm, airtime_s) in &transmitters {
+        let clear_p: f32 = transmitters
+            .iter()
+            .filter(|(other, other_pos, other_type, _, _)| {
+                *other != entity
+                    && *other_type == radio_type
+                    && topology.distance(position, *other_pos) <= radio_type.interference_range()
+            })
+            .map(|(.., other_airtime_s)| 1.0 - ((airtime_s + other_airtime_s) / window_s).min(1.0))
+            .product();
+        let collided = !env_rng.0.random_bool(clear_p as f64);
+
+        let gateway = nearest_gateway(&topology, position, &gateway_sites);
+        let destination = gateway.map_or(SINK_POSITION, |(_, site)| site);
+        let link_p = radio_type
+            .link_success_probability(tx_power_dbm, topology.distance(position, destination));
+        let lost = !collided && !env_rng.0.random_bool(link_p as f64);
+
+        // 3. One attempt: pay the energy, start the off-time, update the queue
+        let energy = radio_type.tx_power(tx_power_dbm).over_s(airtime_s);
+        radio_metrics.attempts += 1;
+        radio_metrics.energy_wh += energy;
+        metrics.total_energy_consumed += energy;
+
+        let Ok((_, _, mut radio, mut battery, mut reputation, _, _, gene, _, lineage, byzantine)) =
+            query.get_mut(entity)
+        else {
+            continue;
+        };
+        battery.drain(energy.wh());
+        radio.off_time_s = radio_type.off_time_s(airtime_s);
+        radio.useful.energy_wh += energy;
+        radio_metrics.by_radio[radio_type as usize].energy_wh += energy;
+
+        if net_trace.enabled() {
+            let src = net_trace.endpoint(Endpoint::Node(lineage.id), position);
+            let dst = match gateway {
+                Some((gateway, site)) => net_trace.endpoint(Endpoint::Gateway(gateway), site),
+                None => net_trace.endpoint(Endpoint::Sink, SINK_POSITION),
+            };
+            let start_s = net_trace.start_s(tick_start_s, window_s, airtime_s);
+            net_trace.record(&PacketRow {
+                start_s,
+                src,
+                dst,
+                node_id: lineage.id,
+                radio: radio_type.name(),
+                tx_power_dbm,
+                payload_bytes: payload_bytes(gene),
+                airtime_s,
+                attempt: radio.retries + 1,
+                outcome: if collided {
+                    AttemptOutcome::Collision
+                } else if lost {
+                    AttemptOutcome::LinkLoss
+                } else {
+                    AttemptOutcome::Delivered
+                },
+            });
+        }
+
+        if collided {
+            radio_metrics.collisions += 1;
+        } else if lost {
+            radio_metrics.link_losses += 1;
+        }
+
+        if !collided && !lost {
+            radio_metrics.delivered += 1;
+            // Compression saves airtime, not information
+            let result_bytes = raw_payload_bytes(gene);
+            radio.useful.delivered_bytes += result_bytes;
+            radio_metrics.by_radio[radio_type as usize].delivered_bytes += result_bytes;
+            if let Some((gateway, _)) = gateway
+                && let Ok((.., mut gateway)) = gateways.get_mut(gateway)
+            {
+                gateway.pending_reports += 1;
+                gateway.pending_bytes += payload_bytes(gene);
+            }
+            // Sink cross-check: Byzantine senders misreport half of the time
+            let misreport = byzantine && env_rng.0.random_bool(MISREPORT_PROBABILITY);
+            if misreport {
+                trust_metrics.misreports += 1;
+            }
+            reputation.record(!misreport);
+            radio.backlog -= 1;
+            radio.retries = 0;
+        } else {
+            radio.retries += 1;
+            if radio.retries > MAX_RETRIES {
+                radio_metrics.dropped += 1;
+                radio.backlog -= 1;
+                radio.retries = 0;
+            }
+        }
+    }
+
+    net_trace.flush();
+}
@@ -0,0 +1,221 @@
+/// Self-contained HTML run report (flag: `--report <path>`)
+/// One HTML file with the seed, the full configuration, inline SVG charts
+/// (fitness, survival, model share) and summary tables, with no external
+/// assets, so it can be attached to a lab notebook or mailed as is. It is
+/// rewritten after every epoch and therefore always describes the run so
+/// far, however the run ends.
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write as _;
+
+use crate::analyze::{Series, svg_chart};
+use crate::config::SimConfig;
+use crate::policy_stats::PolicySummary;
+
+/// One epoch's summary line
+#[derive(Debug, Clone)]
+pub struct ReportEpoch {
+    pub epoch: u32,
+    pub population: usize,
+    pub survivors: usize,
+    pub fitness_mean: f32,
+    pub fitness_max: f32,
+    pub energy_consumed_wh: f32,
+    pub detections: u64,
+    /// Survivors per model
+    pub models: BTreeMap<&'static str, usize>,
+}
+
+/// Optional HTML report sink
+#[derive(Resource, Default)]
+pub struct RunReport {
+    path: Option<String>,
+    seed: u64,
+    config: String,
+    epochs: Vec<ReportEpoch>,
+    policies: Vec<PolicySummary>,
+}
+
+impl RunReport {
+    pub fn create(path: Option<&str>, config: &SimConfig) -> Self {
+        if let Some(path) = path {
+            println!("📄 Writing the run report to {}", path);
+        }
+        Self {
+            path: path.map(str::to_string),
+            seed: config.seed,
+            config: format!("{:#?}", config),
+            ..default()
+        }
+    }
+
+    /// Add one epoch and rewrite the report (no-op when disabled)
+    pub fn record(&mut self, epoch: ReportEpoch, policies: &[PolicySummary]) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        self.epochs.push(epoch);
+        self.policies = policies.to_vec();
+
+        let result = self.render().and_then(|html| {
+            if let Some(parent) = std::path::Path::new(path).parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            Ok(std::fs::write(path, html)?)
+        });
+        if let Err(e) = result {
+            eprintln!("⚠️ Run report failed ({}): {}", path, e);
+        }
+    }
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let curve = |value: fn(&ReportEpoch) -> f32| -> Vec<(f32, f32)> {
+            self.epochs
+                .iter()
+                .map(|e| (e.epoch as f32, value(e)))
+                .collect()
+        };
+        let fitness: Vec<Series> = vec![
+            ("mean".to_string(), curve(|e| e.fitness_mean)),
+            ("max".to_string(), curve(|e| e.fitness_max)),
+        ];
+        let survival: Vec<Series> = vec![(
+            "survivors".to_string(),
+            curve(|e| 100.0 * e.survivors as f32 / e.population.max(1) as f32),
+        )];
+
+        let mut model_names: Vec<&'static str> = self
+            .epochs
+            .iter()
+            .flat_map(|e| e.models.keys().copied())
+            .collect();
+        model_names.sort();
+        model_names.dedup();
+        let model_share: Vec<Series> = model_names
+            .iter()
+            .map(|model| {
+                let points = self
+                    .epochs
+                    .iter()
+                    .map(|e| {
+                        let count = e.models.get(model).copied().unwrap_or(0);
+                        (
+                            e.epoch as f32,
+                            100.0 * count as f32 / e.survivors.max(1) as f32,
+                        )
+                    })
+                    .collect();
+                (model.to_string(), points)
+            })
+            .collect();
+
+        let mut html = String::new();
+        writeln!(
+            html,
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>EdgeGenesis run {}</title>",
+            self.seed
+        )?;
+        writeln!(
+            html,
+            "<style>body{{font-family:sans-serif;margin:2em;max-width:1000px}}\
+             table{{border-collapse:collapse;margin:1em 0}}\
+             td,th{{border:1px solid #ccc;padding:4px 8px;text-align:right}}\
+             pre{{background:#f4f4f4;padding:1em;overflow:auto}}</style></head><body>"
+        )?;
+        writeln!(html, "<h1>EdgeGenesis run report</h1>")?;
+        writeln!(
+            html,
+            "<p>Seed <b>{}</b> &middot; {} epochs</p>",
+            self.seed,
+            self.epochs.len()
+        )?;
+
+        if let Some(last) = self.epochs.last() {
+            writeln!(html, "<h2>Final epoch</h2><table>")?;
+            for (name, value) in [
+                ("Epoch", last.epoch.to_string()),
+                ("Population", last.population.to_string()),
+                ("Survivors", last.survivors.to_string()),
+                ("Mean fitness", format!("{:.2}", last.fitness_mean)),
+                ("Max fitness", format!("{:.2}", last.fitness_max)),
+                (
+                    "Energy used (Wh)",
+                    format!("{:.1}", last.energy_consumed_wh),
+                ),
+                ("Detections", last.detections.to_string()),
+            ] {
+                writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", name, value)?;
+            }
+            writeln!(html, "</table>")?;
+        }
+
+        writeln!(html, "<h2>Charts</h2>")?;
+        for (title, y_label, series) in [
+            ("Fitness", "fitness", &fitness),
+            ("Survival", "% of population alive", &survival),
+            ("Model share", "% of survivors", &model_share),
+        ] {
+            writeln!(html, "<div>{}</div>", svg_chart(title, y_label, series)?)?;
+        }
+
+        if !self.policies.is_empty() {
+            writeln!(
+                html,
+                "<h2>Policies (last epoch)</h2><table><tr><th>Policy</th><th>Nodes</th>\
+                 <th>Survival</th><th>Fitness</th><th>Det/Wh</th><th>Inf/h</th></tr>"
+            )?;
+            let optional = |value: Option<f32>, precision: usize| {
+                value.map_or("-".to_string(), |v| format!("{:.*}", precision, v))
+            };
+            for summary in &self.policies {
+                writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{:.0}%</td><td>{:.2}</td><td>{}</td><td>{}</td></tr>",
+                    summary.policy.name(),
+                    summary.nodes,
+                    summary.survival_rate() * 100.0,
+                    summary.mean_fitness,
+                    optional(summary.detections_per_wh(), 3),
+                    optional(summary.inferences_per_hour(), 0)
+                )?;
+            }
+            writeln!(html, "</table>")?;
+        }
+
+        writeln!(
+            html,
+            "<h2>Epochs</h2><table><tr><th>Epoch</th><th>Population</th><th>Survivors</th>\
+             <th>Mean fitness</th><th>Max fitness</th><th>Energy (Wh)</th><th>Detections</th></tr>"
+        )?;
+        for e in &self.epochs {
+            writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{:.2}</td><td>{:.1}</td><td>{}</td></tr>",
+                e.epoch,
+                e.population,
+                e.survivors,
+                e.fitness_mean,
+                e.fitness_max,
+                e.energy_consumed_wh,
+                e.detections
+            )?;
+        }
+        writeln!(html, "</table>")?;
+
+        writeln!(
+            html,
+            "<h2>Configuration</h2><pre>{}</pre>",
+            escape(&self.config)
+        )?;
+        writeln!(html, "</body></html>")?;
+        Ok(html)
+    }
+}
+
+/// Escape text for an HTML element body
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,141 @@
+/// Scenario files
+/// Static deployment layout that does not fit on the command line, loaded
+/// from TOML with `--scenario <path>` (see `data/scenarios/`). Every section
+/// is optional; a missing file or section means the default flat deployment.
+/// Microclimates give sectors of the grid their own solar multiplier (a
+/// corner shaded by a building, a strip with reflective gain off a wall),
+/// so the field has spatial gradients instead of one uniform sky.
+/// A `[maintenance]` table schedules technician rounds (see maintenance.rs).
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::error::Error;
+
+/// Fixed position of one gateway (world units = meters)
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct GatewaySite {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Grid sector with its own weather (`[[microclimate]]` tables)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Microclimate {
+    /// Label for the logs, e.g. "building shade"
+    #[serde(default)]
+    pub name: String,
+    /// First and last cell column covered (inclusive)
+    pub x: [i32; 2],
+    /// First and last cell row covered (inclusive)
+    pub y: [i32; 2],
+    /// Solar multiplier: below 1 shaded, above 1 reflective gain
+    pub solar: f32,
+}
+
+impl Microclimate {
+    pub fn contains(&self, cell: IVec2) -> bool {
+        (self.x[0]..=self.x[1]).contains(&cell.x) && (self.y[0]..=self.y[1]).contains(&cell.y)
+    }
+}
+
+/// Which nodes a maintenance round visits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VisitSelection {
+    /// Emptiest batteries first
+    #[default]
+    LowestCharge,
+    /// Most faded batteries first
+    LowestHealth,
+    /// Dustiest panels first
+    Dirtiest,
+}
+
+/// Scheduled technician rounds (`[maintenance]` table)
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaintenancePlan {
+    /// Simulated hours between the starts of two rounds
+    pub interval_h: f32,
+    /// Start of the first round (simulated hours)
+    pub first_h: f32,
+    /// Where the technician starts and ends each round (meters)
+    pub depot: [f32; 2],
+    /// Travel speed between sites (m/h)
+    pub speed_m_per_h: f32,
+    /// Nodes visited per round
+    pub visits: usize,
+    pub select: VisitSelection,
+    /// Time on site per visit (hours)
+    pub service_h: f32,
+    /// Fit a new, full battery at every visit
+    pub swap_battery: bool,
+    /// Clean the panel at every visit
+    pub clean_panel: bool,
+    /// Technician time, travel included (USD per hour)
+    pub hourly_cost_usd: f32,
+}
+
+impl Default for MaintenancePlan {
+    fn default() -> Self {
+        Self {
+            interval_h: 168.0,
+            first_h: 24.0,
+            depot: [-275.0, -275.0],
+            speed_m_per_h: 4000.0,
+            visits: 10,
+            select: VisitSelection::default(),
+            service_h: 0.5,
+            swap_battery: true,
+            clean_panel: true,
+            hourly_cost_usd: 40.0,
+        }
+    }
+}
+
+/// Deployment layout
+#[derive(Resource, Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Scenario {
+    /// Aggregation gateways (`[[gateway]]` tables); none = nodes report
+    /// straight to the sink
+    #[serde(rename = "gateway")]
+    pub gateways: Vec<GatewaySite>,
+    /// Sector weather (`[[microclimate]]` tables); overlapping sectors
+    /// multiply, cells outside every sector see the profile unchanged
+    #[serde(rename = "microclimate")]
+    pub microclimates: Vec<Microclimate>,
+    /// Panel output lost to dust per simulated day (fraction, 0 = panels
+    /// stay clean)
+    pub soiling_per_day: f32,
+    /// Technician rounds; none = no maintenance
+    pub maintenance: Option<MaintenancePlan>,
+}
+
+impl Scenario {
+    /// Solar multiplier of the microclimate at `cell`
+    pub fn solar_factor(&self, cell: IVec2) -> f32 {
+        self.microclimates
+            .iter()
+            .filter(|sector| sector.contains(cell))
+            .map(|sector| sector.solar)
+            .product()
+    }
+}
+
+/// Load a scenario file
+pub fn load_scenario(path: &str) -> Result<Scenario, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path)?;
+    let scenario: Scenario = toml::from_str(&text)?;
+    for sector in &scenario.microclimates {
+        if sector.solar < 0.0 || sector.x[0] > sector.x[1] || sector.y[0] > sector.y[1] {
+            return Err(format!("invalid microclimate {:?}", sector).into());
+        }
+    }
+    if let Some(plan) = scenario.maintenance
+        && (plan.interval_h <= 0.0 || plan.speed_m_per_h <= 0.0)
+    {
+        return Err("maintenance needs a positive interval_h and speed_m_per_h".into());
+    }
+    Ok(scenario)
+}
@@ -0,0 +1,237 @@
+/// Event-detection application layer with ground truth
+/// Objects appear in each node's field of view as a Poisson process and stay
+/// for an exponentially distributed time. A node that runs inference while
+/// an object is present gets one detection attempt, succeeding with the
+/// model's accuracy; each detection adds fitness credit. Events are generated
+/// for dead nodes too: an object that leaves before the node ever looked at
+/// it (asleep or dead) is a miss and costs a fitness penalty, so doing
+/// nothing is no longer a free survival strategy. The model's error rate
+/// cuts both ways: a failed attempt is a false negative, costing the miss
+/// penalty like an unseen object, and inference on an empty scene raises
+/// false alarms (up to `MAX_FALSE_ALARMS_PER_H` for a model that is always
+/// wrong), each queueing an extra alarm report on the radio and costing the
+/// false-alarm penalty. With burstiness > 0 the
+/// arrival rate is Markov-modulated: field-wide bursts (mean `BURST_HOURS`,
+/// `BURST_FRACTION` of the time) concentrate that share of the traffic
+/// while the mean rate stays the same. The delay from an object's arrival to
+/// the inference that services it is the node's response latency; serviced
+/// latencies are collected per epoch, overall and per model, at the
+/// resolution of one tick (plus the delay thermal throttling adds).
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+use crate::components::{EnvRng, EpochCount, Gene, Status, SurvivalScore, Telemetry};
+use crate::config::SimConfig;
+use crate::curriculum;
+use crate::extinction::Easing;
+use crate::hardware::HardwareSpec;
+use crate::radio::{MAX_BACKLOG, Radio};
+use crate::speed::SimTime;
+use crate::thermal::Temperature;
+use crate::units::SECONDS_PER_HOUR;
+
+/// Mean time an object stays in view (simulated seconds)
+pub const MEAN_EVENT_DURATION_S: f32 = 120.0;
+
+/// False alarms per hour of inference on an empty scene at 0% accuracy;
+/// scales with the error rate (1 - accuracy)
+pub const MAX_FALSE_ALARMS_PER_H: f32 = 20.0;
+
+/// Long-run fraction of time the field spends in a traffic burst
+pub const BURST_FRACTION: f32 = 0.2;
+
+/// Mean burst length (simulated hours)
+pub const BURST_HOURS: f32 = 1.0;
+
+/// Field-wide workload state shared by all nodes
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct Workload {
+    pub in_burst: bool,
+}
+
+impl Workload {
+    /// Arrival-rate multiplier; averages to 1 over time for any burstiness
+    pub fn rate_factor(&self, burstiness: f32) -> f32 {
+        let burstiness = burstiness.clamp(0.0, 1.0);
+        if self.in_burst {
+            1.0 + burstiness * (1.0 / BURST_FRACTION - 1.0)
+        } else {
+            1.0 - burstiness
+        }
+    }
+
+    /// Enter or leave a burst; the switching rates keep the time in bursts
+    /// at `BURST_FRACTION`
+    fn advance(&mut self, hours: f32, rng: &mut impl Rng) {
+        let leave_per_h = 1.0 / BURST_HOURS;
+        let enter_per_h = leave_per_h * BURST_FRACTION / (1.0 - BURST_FRACTION);
+        let rate = if self.in_burst {
+            leave_per_h
+        } else {
+            enter_per_h
+        };
+        if rng.random_bool((rate * hours).clamp(0.0, 1.0) as f64) {
+            self.in_burst = !self.in_burst;
+        }
+    }
+}
+
+/// Sensing component - ground truth and detection record of one node
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Sensing {
+    /// Simulated seconds the current object stays in view (0 = nothing there)
+    pub event_remaining_s: f32,
+    /// The current object already had its detection attempt
+    pub event_resolved: bool,
+    /// Simulated seconds the current object has waited for an inference
+    pub event_age_s: f32,
+    /// Objects that appeared this life
+    pub events: u32,
+    /// Objects correctly detected
+    pub detections: u32,
+    /// Detection attempts that failed to recognize the object
+    pub false_negatives: u32,
+    /// Alarms raised with nothing in view
+    pub false_alarms: u32,
+    /// Objects that left without a detection attempt (asleep or dead)
+    pub missed: u32,
+    /// Inferences run this life (inference time / model latency)
+    pub inferences: f32,
+}
+
+/// Per-epoch sensing statistics
+#[derive(Resource, Debug, Default, Clone)]
+pub struct SensingMetrics {
+    pub events: u64,
+    pub detections: u64,
+    pub missed: u64,
+    pub false_negatives: u64,
+    pub false_alarms: u64,
+    /// Response latency of every serviced event (simulated seconds)
+    pub latencies_s: Vec<f32>,
+    /// The same latencies, by model name
+    pub model_latencies_s: BTreeMap<&'static str, Vec<f32>>,
+}
+
+impl SensingMetrics {
+    /// Fraction of ground-truth events that were detected
+    pub fn detection_rate(&self) -> f32 {
+        if self.events == 0 {
+            0.0
+        } else {
+            self.detections as f32 / self.events as f32
+        }
+    }
+}
+
+/// Everything the sensing system reads and credits per node
+type SensingQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Sensing,
+        &'static mut SurvivalScore,
+        &'static mut Radio,
+        &'static Telemetry,
+        &'static Temperature,
+        &'static Gene,
+        &'static HardwareSpec,
+        &'static Status,
+    ),
+>;
+
+/// Sensing system - spawn ground-truth events and credit detections
+/// Runs after physics so `Telemetry::inferring` reflects this tick
+#[allow(clippy::too_many_arguments)]
+pub fn sensing_system(
+    time: SimTime,
+    config: Res<SimConfig>,
+    epoch_count: Res<EpochCount>,
+    easing: Res<Easing>,
+    mut env_rng: ResMut<EnvRng>,
+    mut sensing_metrics: ResMut<SensingMetrics>,
+    mut workload: ResMut<Workload>,
+    mut query: SensingQuery,
+) {
+    let window_s = time.window_s();
+    let rng = &mut env_rng.0;
+    // Constant-rate runs draw nothing extra, keeping their random stream
+    if config.event_burstiness > 0.0 {
+        workload.advance(window_s / SECONDS_PER_HOUR, rng);
+    }
+    let rate = config.event_rate
+        * workload.rate_factor(config.event_burstiness)
+        * easing
+            .apply(curriculum::stage(&config, epoch_count.0))
+            .workload_scale;
+    let arrival_p = (rate * window_s / SECONDS_PER_HOUR).clamp(0.0, 1.0);
+
+    for (mut sensing, mut score, mut radio, telemetry, temperature, gene, hardware, status) in
+        query.iter_mut()
+    {
+        // 1. Ground truth: the current object leaves, a new one may appear
+        let was_present = sensing.event_remaining_s > 0.0;
+        sensing.event_remaining_s = (sensing.event_remaining_s - window_s).max(0.0);
+        if was_present && !sensing.event_resolved {
+            sensing.event_age_s += window_s;
+        }
+        if was_present && sensing.event_remaining_s <= 0.0 && !sensing.event_resolved {
+            sensing.missed += 1;
+            sensing_metrics.missed += 1;
+            score.0 -= config.miss_penalty;
+        }
+        if sensing.event_remaining_s <= 0.0 && rng.random_bool(arrival_p as f64) {
+            // Exponential dwell time via inverse transform sampling
+            let u: f32 = rng.random_range(f32::EPSILON..1.0);
+            sensing.event_remaining_s = -MEAN_EVENT_DURATION_S * u.ln();
+            sensing.event_resolved = false;
+            sensing.event_age_s = 0.0;
+            sensing.events += 1;
+            sensing_metrics.events += 1;
+        }
+
+        if *status != Status::Dead && telemetry.inferring {
+            sensing.inferences +=
+                window_s * 1000.0 / temperature.inference_time_ms(gene.model_type, hardware);
+        }
+
+        // 2. Detection: first inference during the event classifies it once
+        let accuracy = (gene.model_type.accuracy_percent() / 100.0).clamp(0.0, 1.0);
+        let event_active = sensing.event_remaining_s > 0.0 && !sensing.event_resolved;
+        if event_active && *status != Status::Dead && telemetry.inferring {
+            sensing.event_resolved = true;
+            // A throttled node takes longer to finish the inference
+            let latency_s =
+                sensing.event_age_s + temperature.throttle_delay_s(gene.model_type, hardware);
+            sensing_metrics.latencies_s.push(latency_s);
+            sensing_metrics
+                .model_latencies_s
+                .entry(gene.model_type.name())
+                .or_default()
+                .push(latency_s);
+            if rng.random_bool(accuracy as f64) {
+                sensing.detections += 1;
+                sensing_metrics.detections += 1;
+                score.0 += config.detection_credit;
+            } else {
+                sensing.false_negatives += 1;
+                sensing_metrics.false_negatives += 1;
+                score.0 -= config.miss_penalty;
+            }
+        }
+
+        // 3. False alarm: inference on an empty scene; the alarm is sent as
+        // an extra report
+        let empty_scene = sensing.event_remaining_s <= 0.0;
+        if empty_scene && *status != Status::Dead && telemetry.inferring {
+            let alarm_p = MAX_FALSE_ALARMS_PER_H * (1.0 - accuracy) * window_s / SECONDS_PER_HOUR;
+            if rng.random_bool(alarm_p.clamp(0.0, 1.0) as f64) {
+                sensing.false_alarms += 1;
+                sensing_metrics.false_alarms += 1;
+                score.0 -= config.false_alarm_penalty;
+                radio.backlog = (radio.backlog + 1).min(MAX_BACKLOG);
+            }
+        }
+    }
+}
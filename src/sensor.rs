@@ -0,0 +1,75 @@
+/// Sensor power model
+/// Inference needs input: vision models read a camera, language models a
+/// microphone. The sensor is a load of its own, duty-cycled with the policy:
+/// it is powered only while the node wants to infer, and every power-up
+/// costs a warm-up (driver init, auto-exposure / gain settling) at full draw
+/// before the first usable frame. No inference runs on an unpowered or
+/// warming sensor. The physics step charges the draw on top of idle and
+/// inference power.
+use crate::hardware::HardwareType;
+use crate::models::RealModelType;
+
+/// Input device a model needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorKind {
+    Camera,
+    Microphone,
+}
+
+impl SensorKind {
+    /// Sensor required by a model
+    pub fn for_model(model: RealModelType) -> Self {
+        match model {
+            RealModelType::TinyBERT | RealModelType::DistilBERT => SensorKind::Microphone,
+            _ => SensorKind::Camera,
+        }
+    }
+}
+
+/// Sensor fitted to one node
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "ecs", derive(bevy::prelude::Component))]
+pub struct Sensor {
+    pub kind: SensorKind,
+    /// Draw while powered (W)
+    pub power_w: f32,
+    /// Power-up time before the first usable sample (simulated seconds)
+    pub warmup_s: f32,
+    pub on: bool,
+    pub warmup_remaining_s: f32,
+}
+
+impl Sensor {
+    /// Powered-down sensor for a model on a platform
+    pub fn for_node(model: RealModelType, hardware_type: HardwareType) -> Self {
+        let kind = SensorKind::for_model(model);
+        let (power_w, warmup_s) = match (kind, hardware_type) {
+            (SensorKind::Camera, HardwareType::ESP32) => (0.25, 1.0), // OV2640
+            (SensorKind::Camera, _) => (1.5, 2.0),                    // CSI camera (IMX219)
+            (SensorKind::Microphone, HardwareType::ESP32) => (0.005, 0.05), // I2S MEMS
+            (SensorKind::Microphone, _) => (0.1, 0.2),                // USB microphone
+        };
+        Self {
+            kind,
+            power_w,
+            warmup_s,
+            on: false,
+            warmup_remaining_s: 0.0,
+        }
+    }
+
+    /// Follow the policy's wish to infer for one physics window; true when
+    /// the sensor delivers usable samples this window
+    pub fn step(&mut self, wanted: bool, window_s: f32) -> bool {
+        if !wanted {
+            self.on = false;
+            return false;
+        }
+        if !self.on {
+            self.on = true;
+            self.warmup_remaining_s = self.warmup_s;
+        }
+        self.warmup_remaining_s = (self.warmup_remaining_s - window_s).max(0.0);
+        self.warmup_remaining_s <= 0.0
+    }
+}
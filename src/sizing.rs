@@ -0,0 +1,204 @@
+/// Clipping-aware battery and panel sizing report
+/// Every finished epoch adds each node's energy ledger to totals per
+/// hardware type: harvest, harvest clipped because the battery was full,
+/// energy drawn, and whether the node ran its battery empty. At the end of
+/// the run the totals become a sizing recommendation. Clipping and
+/// depletions together mean the day's surplus cannot reach the night: the
+/// battery should grow by the clipped energy per node-day. Depletions
+/// without clipping mean the panel cannot cover the load: it should grow by
+/// the draw / net harvest ratio. Heavy clipping without depletions means
+/// the panel is oversized. The report goes to the console and, with
+/// `--sizing-out <path>`, to a CSV.
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::components::{Battery, EnergyLedger, Status};
+use crate::config::SimConfig;
+use crate::export::open_csv;
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::speed::SimSpeed;
+use crate::systems::EPOCH_SECONDS;
+
+/// Share of nodes running empty that still counts as adequately sized
+pub const DEPLETION_TOLERANCE: f32 = 0.1;
+
+/// Share of the harvest clipped that still counts as adequately sized
+pub const CLIP_TOLERANCE: f32 = 0.1;
+
+/// Simulated days covered by one epoch of one node at `time_scale`
+fn epoch_days(time_scale: f32) -> f32 {
+    EPOCH_SECONDS as f32 * time_scale / 86_400.0
+}
+
+/// Energy totals of one hardware type
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HardwareUsage {
+    /// Node-epochs observed
+    pub nodes: u64,
+    /// Nodes that ran their battery empty
+    pub depleted: u64,
+    pub harvested_wh: f32,
+    pub clipped_wh: f32,
+    pub consumed_wh: f32,
+}
+
+impl HardwareUsage {
+    pub fn depletion_rate(&self) -> f32 {
+        self.depleted as f32 / self.nodes.max(1) as f32
+    }
+
+    pub fn clip_share(&self) -> f32 {
+        if self.harvested_wh > 0.0 {
+            self.clipped_wh / self.harvested_wh
+        } else {
+            0.0
+        }
+    }
+
+    /// Clipped energy per node per simulated day (Wh)
+    pub fn clipped_per_node_day_wh(&self, time_scale: f32) -> f32 {
+        self.clipped_wh / (self.nodes.max(1) as f32 * epoch_days(time_scale))
+    }
+}
+
+/// One line of the sizing report
+#[derive(Debug, Clone, Serialize)]
+pub struct SizingRow {
+    pub hardware: String,
+    pub node_epochs: u64,
+    pub depletion_rate: f32,
+    pub harvested_wh: f32,
+    pub clipped_wh: f32,
+    pub clip_share: f32,
+    pub consumed_wh: f32,
+    pub battery_wh: f32,
+    pub suggested_battery_wh: f32,
+    pub panel_w: f32,
+    pub suggested_panel_w: f32,
+    pub verdict: &'static str,
+}
+
+impl SizingRow {
+    /// Recommendation for one hardware type, with epochs at `time_scale`
+    pub fn new(hardware_type: HardwareType, usage: &HardwareUsage, time_scale: f32) -> Self {
+        let spec = HardwareSpec::new(hardware_type);
+        let depleting = usage.depletion_rate() > DEPLETION_TOLERANCE;
+        let clipping = usage.clip_share() > CLIP_TOLERANCE;
+        let net_harvest_wh = usage.harvested_wh - usage.clipped_wh;
+
+        let (battery_wh, panel_w, verdict) = match (depleting, clipping) {
+            (true, true) => (
+                spec.battery_capacity_wh + usage.clipped_per_node_day_wh(time_scale),
+                spec.max_solar_input_w,
+                "battery too small",
+            ),
+            (true, false) if net_harvest_wh > 0.0 => (
+                spec.battery_capacity_wh,
+                spec.max_solar_input_w * (usage.consumed_wh / net_harvest_wh).max(1.0),
+                "panel too small",
+            ),
+            (true, false) => (
+                spec.battery_capacity_wh,
+                spec.max_solar_input_w,
+                "no harvest observed",
+            ),
+            (false, true) => (
+                spec.battery_capacity_wh,
+                spec.max_solar_input_w * (1.0 - usage.clip_share()),
+                "panel oversized",
+            ),
+            (false, false) => (spec.battery_capacity_wh, spec.max_solar_input_w, "adequate"),
+        };
+
+        Self {
+            hardware: spec.name().to_string(),
+            node_epochs: usage.nodes,
+            depletion_rate: usage.depletion_rate(),
+            harvested_wh: usage.harvested_wh,
+            clipped_wh: usage.clipped_wh,
+            clip_share: usage.clip_share(),
+            consumed_wh: usage.consumed_wh,
+            battery_wh: spec.battery_capacity_wh,
+            suggested_battery_wh: battery_wh,
+            panel_w: spec.max_solar_input_w,
+            suggested_panel_w: panel_w,
+            verdict,
+        }
+    }
+}
+
+/// Run-long energy totals per hardware type
+#[derive(Resource, Debug, Default)]
+pub struct SizingReport {
+    pub usage: BTreeMap<HardwareType, HardwareUsage>,
+}
+
+impl SizingReport {
+    /// Add one node's life at the end of an epoch
+    pub fn record(
+        &mut self,
+        hardware: &HardwareSpec,
+        ledger: &EnergyLedger,
+        battery: &Battery,
+        status: &Status,
+    ) {
+        let usage = self.usage.entry(hardware.hardware_type).or_default();
+        usage.nodes += 1;
+        if *status == Status::Dead && battery.0 <= 0.0 {
+            usage.depleted += 1;
+        }
+        usage.harvested_wh += ledger.harvested_wh.wh();
+        usage.clipped_wh += ledger.clipped_wh.wh();
+        usage.consumed_wh += ledger.consumed_wh(battery).wh();
+    }
+
+    pub fn rows(&self, time_scale: f32) -> Vec<SizingRow> {
+        self.usage
+            .iter()
+            .map(|(hardware_type, usage)| SizingRow::new(*hardware_type, usage, time_scale))
+            .collect()
+    }
+}
+
+/// Print (and optionally export) the report when the app exits
+pub fn sizing_report_system(
+    mut exits: MessageReader<AppExit>,
+    config: Res<SimConfig>,
+    speed: Res<SimSpeed>,
+    report: Res<SizingReport>,
+) {
+    if exits.read().count() == 0 || report.usage.is_empty() {
+        return;
+    }
+
+    let rows = report.rows(speed.time_scale);
+    info!(target: "edgegenesis::sizing", "📐 Sizing report (completed epochs)");
+    for row in &rows {
+        info!(target: "edgegenesis::sizing",
+            "📐 {}: {:.0}% ran empty, {:.0}% of harvest clipped → battery {:.1} → {:.1} Wh, panel {:.1} → {:.1} W ({})",
+            row.hardware,
+            row.depletion_rate * 100.0,
+            row.clip_share * 100.0,
+            row.battery_wh,
+            row.suggested_battery_wh,
+            row.panel_w,
+            row.suggested_panel_w,
+            row.verdict
+        );
+    }
+
+    if let Some(path) = &config.sizing_out {
+        let result = open_csv(path).and_then(|mut writer| {
+            for row in &rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+            Ok(())
+        });
+        match result {
+            Ok(()) => info!(target: "edgegenesis::sizing", "📐 Sizing report written to {}", path),
+            Err(e) => warn!("Sizing report export failed ({}): {}", path, e),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+/// Spectator mode: the camera follows the fittest alive node
+/// Toggled with `C` (or started on with `--spectator`). Every frame the
+/// alive nodes are ranked by the selection fitness of the run (preset and
+/// hardware normalization, as at the epoch's end); the camera glides to the
+/// leader and a pinned HUD window shows its stats. A new leader must beat
+/// the followed node by `LEADER_MARGIN` so the camera does not jump between
+/// near-ties. Turning the mode off glides the camera back to the grid.
+use bevy::prelude::*;
+use bevy_egui::input::EguiWantsInput;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+use crate::components::{
+    Battery, Dead, EnergyLedger, Gene, Lineage, Status, SurvivalScore, Telemetry,
+};
+use crate::config::SimConfig;
+use crate::hardware::HardwareSpec;
+use crate::sensing::Sensing;
+
+/// Relative fitness lead a challenger needs to take the camera over
+pub const LEADER_MARGIN: f32 = 0.05;
+
+/// Camera approach rate (1/s): the remaining distance shrinks by e every
+/// 1/rate seconds
+pub const CAMERA_FOLLOW_RATE: f32 = 4.0;
+
+/// Spectator state: whether the camera follows and whom
+#[derive(Resource, Debug, Default)]
+pub struct Spectator {
+    pub enabled: bool,
+    pub target: Option<Entity>,
+    /// Fitness of the followed node, updated every frame
+    pub fitness: f32,
+}
+
+impl Spectator {
+    pub fn new(config: &SimConfig) -> Self {
+        Self {
+            enabled: config.spectator,
+            ..Default::default()
+        }
+    }
+}
+
+/// Everything needed to rank a node and pin its stats
+type SpectatedQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static Gene,
+        &'static HardwareSpec,
+        &'static Battery,
+        &'static SurvivalScore,
+        &'static Sensing,
+        &'static EnergyLedger,
+        &'static Status,
+    ),
+    (Without<Dead>, Without<Camera2d>),
+>;
+
+/// `C` toggles spectator mode
+pub fn spectator_toggle_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    mut spectator: ResMut<Spectator>,
+) {
+    if egui_input.wants_any_keyboard_input() || !keys.just_pressed(KeyCode::KeyC) {
+        return;
+    }
+    spectator.enabled = !spectator.enabled;
+    spectator.target = None;
+    info!(target: "edgegenesis::epoch",
+        "🎥 Spectator mode {}", if spectator.enabled { "on" } else { "off" }
+    );
+}
+
+/// Pick the leader and move the camera towards it (or back to the grid)
+pub fn spectator_follow_system(
+    time: Res<Time<Real>>,
+    config: Res<SimConfig>,
+    mut spectator: ResMut<Spectator>,
+    nodes: SpectatedQuery,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+    let Ok(mut camera) = cameras.single_mut() else {
+        return;
+    };
+
+    let mut goal = Vec2::ZERO;
+    if spectator.enabled {
+        // Live selection fitness, normalized over the whole population
+        let raw: Vec<(Entity, f32, &HardwareSpec, bool)> = nodes
+            .iter()
+            .map(
+                |(entity, _, gene, hardware, battery, score, sensing, ledger, status)| {
+                    let consumed_wh = ledger.consumed_wh(battery);
+                    let fitness =
+                        config
+                            .fitness
+                            .evaluate(score.0, sensing, hardware, gene, consumed_wh);
+                    (entity, fitness, hardware, *status == Status::Alive)
+                },
+            )
+            .collect();
+        let divisors = config.fitness_normalization.divisors(
+            raw.iter()
+                .map(|(_, fitness, hardware, _)| (*fitness, *hardware)),
+        );
+        let alive: Vec<(f32, Entity)> = raw
+            .iter()
+            .filter(|(_, _, _, alive)| *alive)
+            .map(|(entity, fitness, hardware, _)| {
+                (fitness / divisors[hardware.hardware_type as usize], *entity)
+            })
+            .collect();
+
+        let leader = alive.iter().copied().max_by(|a, b| a.0.total_cmp(&b.0));
+        let current = spectator
+            .target
+            .and_then(|target| alive.iter().copied().find(|(_, entity)| *entity == target));
+
+        let followed = match (current, leader) {
+            (Some(current), Some(leader))
+                if leader.0 <= current.0 + current.0.abs() * LEADER_MARGIN =>
+            {
+                Some(current)
+            }
+            (_, leader) => leader,
+        };
+        spectator.target = followed.map(|(_, entity)| entity);
+        spectator.fitness = followed.map_or(0.0, |(fitness, _)| fitness);
+
+        if let Some(transform) = spectator.target.and_then(|entity| nodes.get(entity).ok()) {
+            goal = transform.1.translation.truncate();
+        }
+    }
+
+    let blend = 1.0 - (-CAMERA_FOLLOW_RATE * time.delta_secs()).exp();
+    let position = camera.translation.truncate().lerp(goal, blend);
+    camera.translation = position.extend(camera.translation.z);
+}
+
+/// Pinned HUD window with the followed node's stats
+pub fn spectator_hud_system(
+    mut contexts: EguiContexts,
+    spectator: Res<Spectator>,
+    config: Res<SimConfig>,
+    nodes: Query<(
+        &Lineage,
+        &Gene,
+        &HardwareSpec,
+        &Battery,
+        &Telemetry,
+        &Sensing,
+    )>,
+) -> Result {
+    if !spectator.enabled {
+        return Ok(());
+    }
+    let target = spectator.target.and_then(|entity| nodes.get(entity).ok());
+
+    egui::Window::new("Spectator")
+        .anchor(egui::Align2::RIGHT_TOP, [-12.0, 12.0])
+        .resizable(false)
+        .collapsible(false)
+        .show(contexts.ctx_mut()?, |ui| {
+            let Some((lineage, gene, hardware, battery, telemetry, sensing)) = target else {
+                ui.label("No node alive");
+                return;
+            };
+            ui.heading(format!("Following node #{}", lineage.id));
+            ui.label(format!(
+                "{}: {:.2}",
+                config.fitness.name(),
+                spectator.fitness
+            ));
+            ui.label(format!(
+                "{} / {} on {}",
+                gene.model_type.name(),
+                gene.policy.name(),
+                hardware.name()
+            ));
+            ui.label(format!(
+                "Battery {:.2} / {:.1} Wh",
+                battery.wh(),
+                hardware.usable_capacity_wh()
+            ));
+            ui.label(format!(
+                "Solar {:.2} W, draw {:.2} W{}",
+                telemetry.solar_input_w,
+                telemetry.power_draw_w,
+                if telemetry.inferring {
+                    ", inferring"
+                } else {
+                    ""
+                }
+            ));
+            ui.label(format!(
+                "{:.0} inferences, {}/{} events detected",
+                sensing.inferences, sensing.detections, sensing.events
+            ));
+        });
+    Ok(())
+}
+
+/// Register the spectator systems
+pub fn register_spectator(app: &mut App) {
+    app.add_systems(EguiPrimaryContextPass, spectator_hud_system)
+        .add_systems(
+            Update,
+            (spectator_toggle_system, spectator_follow_system).chain(),
+        );
+}
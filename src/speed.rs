@@ -0,0 +1,155 @@
+/// Runtime speed control: pause, single-step and playback speed
+/// Each fixed tick always advances `HEADLESS_DT` real seconds (`time_scale`
+/// times that in simulated time, see `SimTime`), so physics stays identical
+/// at any speed; what changes is how fast virtual time, and with it the
+/// fixed clock, runs. `SimSpeed` is that multiplier (1 = 60 ticks per
+/// second), started from `--ticks-per-frame` and doubled / halved with
+/// `+` / `-`. Space pauses and resumes (the same pause the gRPC control
+/// uses) and `F` advances a paused run by exactly one tick. The physics
+/// step and the epoch timer both run on the fixed clock, so they slow down,
+/// stop and step together. Virtual time may advance `multiplier` frames'
+/// worth per frame before Bevy clamps it, so fast playback is not capped
+/// at Bevy's default quarter second.
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::input::EguiWantsInput;
+use std::time::Duration;
+
+use crate::config::SimConfig;
+use crate::engine::HEADLESS_DT;
+use crate::units::SECONDS_PER_HOUR;
+
+/// Slowest and fastest playback (multiples of 60 ticks per second)
+pub const MIN_SPEED: f32 = 0.125;
+pub const MAX_SPEED: f32 = 64.0;
+
+/// Longest virtual step per frame at ×1 (Bevy's default `max_delta`)
+const BASE_MAX_DELTA: Duration = Duration::from_millis(250);
+
+/// Playback speed (fixed ticks per 60 fps frame) and the simulated time
+/// each tick stands for
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SimSpeed {
+    pub multiplier: f32,
+    /// Simulated seconds per real second of fixed ticks (`--time-scale`)
+    pub time_scale: f32,
+}
+
+impl SimSpeed {
+    pub fn new(config: &SimConfig) -> Self {
+        Self {
+            multiplier: (config.ticks_per_frame as f32).clamp(MIN_SPEED, MAX_SPEED),
+            time_scale: config.time_scale,
+        }
+    }
+
+    /// Simulated seconds standing for `real_s` seconds of fixed ticks
+    pub fn sim_seconds(&self, real_s: f32) -> f32 {
+        real_s * self.time_scale
+    }
+
+    /// Virtual time allowed per frame: Bevy's default scaled with the speed
+    pub fn max_delta(&self) -> Duration {
+        BASE_MAX_DELTA.mul_f32(self.multiplier.max(1.0))
+    }
+
+    pub fn faster(&mut self) {
+        self.multiplier = (self.multiplier * 2.0).min(MAX_SPEED);
+    }
+
+    pub fn slower(&mut self) {
+        self.multiplier = (self.multiplier / 2.0).max(MIN_SPEED);
+    }
+}
+
+/// Fixed simulation step and initial speed: every tick advances
+/// `HEADLESS_DT` real seconds (the headless step), and virtual time runs at
+/// the configured speed, so a 60 fps window renders that many ticks per
+/// frame with unchanged physics
+pub fn setup_tick_rate(
+    speed: Res<SimSpeed>,
+    mut fixed: ResMut<Time<Fixed>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    fixed.set_timestep_seconds(HEADLESS_DT as f64);
+    virtual_time.set_relative_speed(speed.multiplier);
+    virtual_time.set_max_delta(speed.max_delta());
+    if speed.multiplier > 1.0 {
+        info!(target: "edgegenesis::epoch",
+            "⏩ {} simulation ticks per frame", speed.multiplier
+        );
+    }
+}
+
+/// Keyboard control: space pauses, `+` / `-` change speed, `F` single-steps
+pub fn speed_control_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_input: Res<EguiWantsInput>,
+    mut speed: ResMut<SimSpeed>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut fixed: ResMut<Time<Fixed>>,
+) {
+    // Typing into an egui field is not a speed command
+    if egui_input.wants_any_keyboard_input() {
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::Space) {
+        if virtual_time.is_paused() {
+            virtual_time.unpause();
+            info!(target: "edgegenesis::epoch", "▶️ Resumed at ×{}", speed.multiplier);
+        } else {
+            virtual_time.pause();
+            info!(target: "edgegenesis::epoch", "⏸️ Paused (F steps one tick)");
+        }
+    }
+
+    let before = *speed;
+    if keys.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        speed.faster();
+    }
+    if keys.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        speed.slower();
+    }
+    if *speed != before {
+        virtual_time.set_relative_speed(speed.multiplier);
+        virtual_time.set_max_delta(speed.max_delta());
+        info!(target: "edgegenesis::epoch", "⏩ Speed ×{}", speed.multiplier);
+    }
+
+    // The fixed loop runs one tick next frame on the injected time
+    if keys.just_pressed(KeyCode::KeyF) && virtual_time.is_paused() {
+        let timestep = fixed.timestep();
+        fixed.accumulate_overstep(timestep);
+    }
+}
+
+/// The fixed tick's time in simulated units, for systems that would scale
+/// `Res<Time>` themselves
+#[derive(SystemParam)]
+pub struct SimTime<'w> {
+    time: Res<'w, Time>,
+    speed: Res<'w, SimSpeed>,
+}
+
+impl SimTime<'_> {
+    /// Real seconds covered by this tick
+    pub fn delta_secs(&self) -> f32 {
+        self.time.delta_secs()
+    }
+
+    /// Simulated seconds covered by this tick
+    pub fn window_s(&self) -> f32 {
+        self.speed.sim_seconds(self.time.delta_secs())
+    }
+
+    /// Simulated hours covered by this tick
+    pub fn window_h(&self) -> f32 {
+        self.window_s() / SECONDS_PER_HOUR
+    }
+
+    /// Simulated seconds from the start of the run to the start of this tick
+    pub fn start_s(&self) -> f64 {
+        (self.time.elapsed_secs_f64() - self.time.delta_secs_f64()) * self.speed.time_scale as f64
+    }
+}
@@ -0,0 +1,216 @@
+/// Sprite rendering of the node population
+/// Every node carries a `Sprite` drawing a ring texture generated at
+/// startup, tinted by its battery (or heatmap layer) and sized by its
+/// model, plus a child cell sprite that heatmap layers fill. All rings share
+/// one image, so the renderer batches the whole population into a single
+/// instanced draw; sprites are only written when their color or size
+/// actually changes, so steady nodes cost no change-detection churn. Corpses
+/// turn gray once when they are marked `Dead`, and recycled entities keep
+/// their sprites for the next generation, faded in by crossfade.rs.
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+use crate::components::{Battery, Dead, Gene, Status, SurvivalScore};
+use crate::crossfade::Crossfade;
+use crate::hardware::HardwareSpec;
+use crate::heatmap::{FILL_ALPHA, HeatmapLayer, hardware_color, policy_color, ramp};
+use crate::topology::GRID_SPACING;
+
+/// Side of the generated ring texture in pixels
+const RING_TEXTURE_SIZE: u32 = 64;
+
+/// Ring width as a fraction of its radius
+const RING_WIDTH: f32 = 0.15;
+
+/// Color of dead nodes
+const CORPSE_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+/// Image shared by every node sprite
+#[derive(Resource)]
+pub struct NodeSpriteAssets {
+    pub ring: Handle<Image>,
+}
+
+/// The heatmap cell sprite drawn behind a node
+#[derive(Component, Debug, Clone, Copy)]
+pub struct NodeCell(pub Entity);
+
+/// Circle radius of a node: larger models = bigger circles
+fn node_radius(gene: &Gene) -> f32 {
+    (gene.model_type.size_mb() / 10.0).clamp(3.0, 20.0)
+}
+
+/// White anti-aliased ring on a transparent square, tinted per sprite
+fn ring_image() -> Image {
+    let center = RING_TEXTURE_SIZE as f32 / 2.0;
+    let pixel = 1.0 / center;
+    let mut data = Vec::with_capacity((RING_TEXTURE_SIZE * RING_TEXTURE_SIZE * 4) as usize);
+    for y in 0..RING_TEXTURE_SIZE {
+        for x in 0..RING_TEXTURE_SIZE {
+            let offset = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - Vec2::splat(center);
+            let distance = offset.length() / center;
+            // Fade over one pixel at the outer and inner edges
+            let outer = ((1.0 - distance) / pixel + 0.5).clamp(0.0, 1.0);
+            let inner = ((distance - (1.0 - RING_WIDTH)) / pixel + 0.5).clamp(0.0, 1.0);
+            let alpha = (outer * inner * 255.0).round() as u8;
+            data.extend_from_slice(&[255, 255, 255, alpha]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: RING_TEXTURE_SIZE,
+            height: RING_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Generate the ring texture
+pub fn setup_node_sprites(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.insert_resource(NodeSpriteAssets {
+        ring: images.add(ring_image()),
+    });
+}
+
+/// Give nodes without sprites their ring and (hidden) cell
+pub fn attach_node_sprites_system(
+    mut commands: Commands,
+    assets: Res<NodeSpriteAssets>,
+    nodes: Query<Entity, (With<Gene>, Without<NodeCell>)>,
+) {
+    for entity in nodes.iter() {
+        let cell = commands
+            .spawn((
+                Sprite::sized(Vec2::splat(GRID_SPACING)),
+                Transform::from_xyz(0.0, 0.0, -1.0),
+                Visibility::Hidden,
+                ChildOf(entity),
+            ))
+            .id();
+        commands
+            .entity(entity)
+            .try_insert((Sprite::from_image(assets.ring.clone()), NodeCell(cell)));
+    }
+}
+
+/// What node rendering reads and tints (corpses excluded)
+type RenderQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Sprite,
+        &'static NodeCell,
+        &'static Battery,
+        &'static Gene,
+        &'static Status,
+        &'static HardwareSpec,
+        &'static SurvivalScore,
+    ),
+    Without<Dead>,
+>;
+
+/// Rendering system - tints node sprites by state and the heatmap layer,
+/// faded in while a new generation appears
+pub fn render_nodes_system(
+    layer: Res<HeatmapLayer>,
+    crossfade: Res<Crossfade>,
+    mut nodes: RenderQuery,
+    mut cells: Query<(&mut Sprite, &mut Visibility), Without<Gene>>,
+) {
+    let best_score = nodes
+        .iter()
+        .filter(|(_, _, _, _, status, _, _)| **status == Status::Alive)
+        .map(|(_, _, _, _, _, _, score)| score.0)
+        .fold(0.0, f32::max);
+
+    for (mut sprite, cell, battery, gene, status, hardware, score) in nodes.iter_mut() {
+        let charge_ratio = (battery.wh() / hardware.usable_capacity_wh()).clamp(0.0, 1.0);
+
+        let (color, fill) = if *status == Status::Dead {
+            (CORPSE_COLOR, None) // Gray (until marked)
+        } else if *layer != HeatmapLayer::Off {
+            let color = match *layer {
+                HeatmapLayer::SurvivalScore if best_score > 0.0 => ramp(score.0 / best_score),
+                HeatmapLayer::SurvivalScore => ramp(0.0),
+                HeatmapLayer::Policy => policy_color(gene.policy),
+                HeatmapLayer::Hardware => hardware_color(hardware),
+                HeatmapLayer::BatteryCharge | HeatmapLayer::Off => ramp(charge_ratio),
+            };
+            (color, Some(color.with_alpha(FILL_ALPHA)))
+        } else {
+            let color = if charge_ratio > 0.75 {
+                Color::srgb(0.0, 1.0, 0.0) // Green
+            } else if charge_ratio > 0.25 {
+                Color::srgb(1.0, 1.0, 0.0) // Yellow
+            } else {
+                Color::srgb(1.0, 0.0, 0.0) // Red
+            };
+            (color, None)
+        };
+
+        let fade_in = crossfade.fade_in();
+        let color = color.with_alpha(color.alpha() * fade_in);
+        let fill = fill.map(|fill| fill.with_alpha(fill.alpha() * fade_in));
+
+        // Reads go through Deref, so untouched sprites stay unchanged
+        let size = Some(Vec2::splat(2.0 * node_radius(gene)));
+        if sprite.color != color || sprite.custom_size != size {
+            sprite.color = color;
+            sprite.custom_size = size;
+        }
+
+        let Ok((mut cell_sprite, mut visibility)) = cells.get_mut(cell.0) else {
+            continue;
+        };
+        match fill {
+            Some(fill) => {
+                visibility.set_if_neq(Visibility::Inherited);
+                if cell_sprite.color != fill {
+                    cell_sprite.color = fill;
+                }
+            }
+            None => {
+                visibility.set_if_neq(Visibility::Hidden);
+            }
+        }
+    }
+}
+
+/// Corpses not yet grayed: just marked, or given sprites after dying
+type NewCorpseQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Sprite, &'static NodeCell),
+    (With<Dead>, Or<(Added<Dead>, Added<NodeCell>)>),
+>;
+
+/// Turn newly dead nodes gray and hide their cell, once
+pub fn render_corpses_system(
+    mut corpses: NewCorpseQuery,
+    mut cells: Query<&mut Visibility, Without<Gene>>,
+) {
+    for (mut sprite, cell) in corpses.iter_mut() {
+        sprite.color = CORPSE_COLOR;
+        if let Ok(mut visibility) = cells.get_mut(cell.0) {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
+    }
+}
+
+/// Register the node sprite systems
+pub fn register_sprites(app: &mut App) {
+    app.add_systems(Startup, setup_node_sprites).add_systems(
+        Update,
+        (
+            attach_node_sprites_system,
+            render_nodes_system,
+            render_corpses_system,
+        )
+            .chain(),
+    );
+}
@@ -0,0 +1,80 @@
+//! Descriptive statistics for population-level metrics
+//! The mean alone hides multimodal populations (e.g. half the nodes thriving,
+//! half dying at dusk), so epochs report the full five-number summary + spread
+
+/// Five-number summary plus mean and (population) standard deviation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: f32,
+    pub std_dev: f32,
+    pub min: f32,
+    pub q1: f32,
+    pub median: f32,
+    pub q3: f32,
+    pub max: f32,
+}
+
+impl Distribution {
+    /// Summarize a sample; all fields are zero for an empty sample
+    pub fn from_samples(samples: &[f32]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let n = sorted.len() as f32;
+        let mean = sorted.iter().sum::<f32>() / n;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / n;
+
+        Self {
+            count: sorted.len(),
+            mean,
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            q1: quantile(&sorted, 0.25),
+            median: quantile(&sorted, 0.5),
+            q3: quantile(&sorted, 0.75),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Tail percentiles, for quantities where the median hides the slow cases
+/// (response latencies)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percentiles {
+    pub count: usize,
+    pub p50: f32,
+    pub p95: f32,
+    pub p99: f32,
+}
+
+impl Percentiles {
+    /// None for an empty sample
+    pub fn from_samples(samples: &[f32]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        Some(Self {
+            count: sorted.len(),
+            p50: quantile(&sorted, 0.5),
+            p95: quantile(&sorted, 0.95),
+            p99: quantile(&sorted, 0.99),
+        })
+    }
+}
+
+/// Quantile of an ascending-sorted, non-empty sample
+/// Linear interpolation between closest ranks (same as NumPy's default)
+pub fn quantile(sorted: &[f32], q: f32) -> f32 {
+    let rank = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f32;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f32;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
@@ -0,0 +1,123 @@
+/// Store-and-forward result buffering
+/// Besides sending a report now or losing it, a node can keep it in local
+/// storage. While the state of charge is below the policy's
+/// `tx_battery_threshold` the radio holds: queued reports move to the
+/// storage buffer instead of the air. Once the battery recovers, stored
+/// reports are fed back into the radio queue as room allows. Storage is
+/// finite; reports arriving at a full buffer are lost and counted.
+///
+/// Every stored report is a write to flash / SD with a platform-specific
+/// energy cost (small SD writes pay for card wake-up and write
+/// amplification). Writes also wear the medium: wear is the fraction of the
+/// rated write endurance used, scaled by `--failure-acceleration` like the
+/// MTBF, and raises the node's hardware failure hazard.
+use bevy::prelude::*;
+
+use crate::components::{Battery, SimulationMetrics, Status, TunedPolicy};
+use crate::config::SimConfig;
+use crate::hardware::{HardwareSpec, HardwareType};
+use crate::radio::{MAX_BACKLOG, Radio};
+use crate::units::WattHours;
+
+/// Local result storage of one node
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StorageBuffer {
+    /// Reports waiting in storage
+    pub stored: u32,
+    /// Reports the buffer can hold
+    pub capacity: u32,
+    /// Energy of one report write, wake-up and amplification included
+    pub write_energy: WattHours,
+    /// Report writes the medium endures before wearing out
+    pub rated_writes: f32,
+    /// Report writes so far
+    pub writes: u64,
+}
+
+impl StorageBuffer {
+    /// Empty buffer sized for the platform's storage
+    pub fn for_hardware(hardware_type: HardwareType) -> Self {
+        let (capacity, write_j, rated_writes): (_, f32, _) = match hardware_type {
+            HardwareType::ESP32 => (256, 2e-4, 1e8), // Spare NOR flash partition
+            HardwareType::RaspberryPi4 => (4096, 7e-3, 2e6), // Consumer microSD
+            HardwareType::JetsonNano => (4096, 3e-3, 2e7), // eMMC
+        };
+        Self {
+            stored: 0,
+            capacity,
+            write_energy: WattHours::from_joules(write_j),
+            rated_writes,
+            writes: 0,
+        }
+    }
+
+    /// Fraction of the rated write endurance used (1.0 = worn out)
+    pub fn wear(&self, acceleration: f32) -> f32 {
+        self.writes as f32 * acceleration / self.rated_writes
+    }
+}
+
+/// Per-epoch storage statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct StorageMetrics {
+    /// Reports moved into storage
+    pub stored: u64,
+    /// Reports moved back to the radio queue
+    pub flushed: u64,
+    /// Reports lost to a full buffer
+    pub overflowed: u64,
+    /// Write energy
+    pub energy_wh: WattHours,
+    /// Highest medium wear in the population
+    pub peak_wear: f32,
+}
+
+/// Storage system - hold or release the radio queue by state of charge
+/// Runs right before the radio so a holding radio never transmits
+pub fn storage_system(
+    config: Res<SimConfig>,
+    mut storage_metrics: ResMut<StorageMetrics>,
+    mut metrics: ResMut<SimulationMetrics>,
+    mut query: Query<(
+        &mut Radio,
+        &mut StorageBuffer,
+        &mut Battery,
+        &HardwareSpec,
+        &TunedPolicy,
+        &Status,
+    )>,
+) {
+    for (mut radio, mut storage, mut battery, hardware, tuned, status) in query.iter_mut() {
+        if *status == Status::Dead {
+            continue;
+        }
+        let state_of_charge = battery.wh() / hardware.usable_capacity_wh();
+        radio.holding = state_of_charge < tuned.0.tx_battery_threshold;
+
+        if radio.holding {
+            // Store: spill the radio queue into the buffer
+            let room = storage.capacity - storage.stored;
+            let moved = radio.backlog.min(room);
+            storage.stored += moved;
+            storage.writes += moved as u64;
+            storage_metrics.stored += moved as u64;
+
+            let energy = storage.write_energy * moved as f32;
+            battery.drain(energy.wh());
+            storage_metrics.energy_wh += energy;
+            metrics.total_energy_consumed += energy;
+            storage_metrics.peak_wear = storage_metrics
+                .peak_wear
+                .max(storage.wear(config.failure_acceleration));
+            storage_metrics.overflowed += (radio.backlog - moved) as u64;
+            radio.backlog = 0;
+            radio.retries = 0;
+        } else if storage.stored > 0 {
+            // Forward: refill the radio queue from storage
+            let moved = storage.stored.min(MAX_BACKLOG - radio.backlog);
+            storage.stored -= moved;
+            radio.backlog += moved;
+            storage_metrics.flushed += moved as u64;
+        }
+    }
+}
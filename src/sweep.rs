@@ -0,0 +1,340 @@
+/// Distributed parameter sweeps
+/// `EdgeGenesis coordinate` expands scenario × seed combinations into jobs
+/// and hands them out over TCP; `EdgeGenesis worker` (any number, on any
+/// machine) pulls one job per connection, runs it as a child simulation
+/// with `--max-epochs`, and sends back the run's metrics CSV. The
+/// coordinator appends every run's per-epoch rows, prefixed with scenario
+/// and seed, to one CSV and exits when all jobs have reported. Messages are
+/// single JSON lines. A job whose worker disconnects is queued again; a job
+/// whose simulation fails is reported and not retried.
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::export::open_csv;
+
+/// How long an idle worker waits before asking for work again
+const WORKER_RETRY: Duration = Duration::from_secs(5);
+
+/// One simulation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Job {
+    id: usize,
+    /// Scenario file name ("default" without one)
+    scenario_name: String,
+    /// Scenario file contents, shipped so workers need no shared disk
+    scenario_toml: Option<String>,
+    seed: u64,
+    epochs: u32,
+    /// Extra simulation flags, identical for every job
+    args: Vec<String>,
+}
+
+/// A worker's answer to one job
+#[derive(Debug, Serialize, Deserialize)]
+struct JobResult {
+    id: usize,
+    /// Metrics CSV of the run (empty on failure)
+    metrics_csv: String,
+    error: Option<String>,
+}
+
+/// Coordinator bookkeeping shared by the connection threads
+struct SweepState {
+    queue: VecDeque<Job>,
+    /// Jobs not yet reported
+    remaining: usize,
+    failed: usize,
+    writer: csv::Writer<File>,
+    header_written: bool,
+}
+
+/// `coordinate --port <p> --seeds <a-b|a,b,..> --epochs <n> --out <csv>
+/// [--scenario <toml>]... [-- <simulation flags>]`
+pub fn coordinate(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut port = 7878u16;
+    let mut seeds = vec![1];
+    let mut epochs = 20u32;
+    let mut out = String::from("results/sweep.csv");
+    let mut scenarios: Vec<String> = Vec::new();
+    let mut sim_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--port" => port = value()?.parse()?,
+            "--seeds" => seeds = parse_seeds(&value()?)?,
+            "--epochs" => epochs = value()?.parse()?,
+            "--out" => out = value()?,
+            "--scenario" => scenarios.push(value()?),
+            "--" => {
+                sim_args = args.by_ref().collect();
+                break;
+            }
+            other => return Err(format!("unknown coordinate option {}", other).into()),
+        }
+    }
+
+    let mut layouts: Vec<(String, Option<String>)> = Vec::new();
+    for path in &scenarios {
+        let toml = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        layouts.push((path.clone(), Some(toml)));
+    }
+    if layouts.is_empty() {
+        layouts.push(("default".to_string(), None));
+    }
+
+    let mut queue = VecDeque::new();
+    for (scenario_name, scenario_toml) in &layouts {
+        for &seed in &seeds {
+            queue.push_back(Job {
+                id: queue.len(),
+                scenario_name: scenario_name.clone(),
+                scenario_toml: scenario_toml.clone(),
+                seed,
+                epochs,
+                args: sim_args.clone(),
+            });
+        }
+    }
+
+    let total = queue.len();
+    let state = Arc::new(Mutex::new(SweepState {
+        remaining: total,
+        queue,
+        failed: 0,
+        writer: open_csv(&out)?,
+        header_written: false,
+    }));
+
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    listener.set_nonblocking(true)?;
+    println!(
+        "📡 Sweep coordinator on port {}: {} jobs ({} scenarios × {} seeds, {} epochs) → {}",
+        port,
+        total,
+        layouts.len(),
+        seeds.len(),
+        epochs,
+        out
+    );
+
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                let state = Arc::clone(&state);
+                std::thread::spawn(move || serve_worker(stream, &state));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let state = state.lock().map_err(|_| "sweep state poisoned")?;
+        if state.remaining == 0 {
+            println!(
+                "✅ Sweep complete: {} runs, {} failed → {}",
+                total, state.failed, out
+            );
+            return Ok(());
+        }
+    }
+}
+
+/// Hand one job to a connected worker and store its result
+fn serve_worker(stream: TcpStream, state: &Mutex<SweepState>) {
+    let Some(job) = state
+        .lock()
+        .ok()
+        .and_then(|mut state| state.queue.pop_front())
+    else {
+        // Nothing queued: closing tells the worker to retry later
+        return;
+    };
+
+    let result = send_line(&stream, &job).and_then(|_| {
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        Ok(serde_json::from_str::<JobResult>(&line)?)
+    });
+
+    let Ok(mut state) = state.lock() else {
+        return;
+    };
+    match result {
+        Ok(result) => {
+            if let Some(error) = &result.error {
+                eprintln!(
+                    "⚠️ Run {} ({} seed {}) failed: {}",
+                    job.id, job.scenario_name, job.seed, error
+                );
+                state.failed += 1;
+            } else if let Err(e) = append_rows(&mut state, &job, &result.metrics_csv) {
+                eprintln!("⚠️ Cannot store run {}: {}", job.id, e);
+                state.failed += 1;
+            }
+            state.remaining -= 1;
+            println!(
+                "📥 Run {} done ({} seed {}), {} left",
+                job.id, job.scenario_name, job.seed, state.remaining
+            );
+        }
+        Err(e) => {
+            eprintln!("⚠️ Worker lost during run {} ({}), requeued", job.id, e);
+            state.queue.push_back(job);
+        }
+    }
+}
+
+/// Append a run's metrics rows, prefixed with scenario and seed
+fn append_rows(state: &mut SweepState, job: &Job, metrics_csv: &str) -> Result<(), Box<dyn Error>> {
+    let mut reader = csv::Reader::from_reader(metrics_csv.as_bytes());
+    if !state.header_written {
+        let mut header = csv::StringRecord::from(vec!["scenario", "seed"]);
+        header.extend(reader.headers()?.iter());
+        state.writer.write_record(&header)?;
+        state.header_written = true;
+    }
+    for row in reader.records() {
+        let mut record =
+            csv::StringRecord::from(vec![job.scenario_name.clone(), job.seed.to_string()]);
+        record.extend(row?.iter());
+        state.writer.write_record(&record)?;
+    }
+    state.writer.flush()?;
+    Ok(())
+}
+
+/// `worker --connect <host:port> [--jobs <n>]`
+pub fn worker(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut coordinator = String::from("127.0.0.1:7878");
+    let mut jobs = 1usize;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--connect" => coordinator = value()?,
+            "--jobs" => jobs = value()?.parse()?,
+            other => return Err(format!("unknown worker option {}", other).into()),
+        }
+    }
+
+    println!(
+        "🛠️ Sweep worker: {} slot(s) pulling from {}",
+        jobs, coordinator
+    );
+    let slots: Vec<_> = (0..jobs.max(1))
+        .map(|_| {
+            let coordinator = coordinator.clone();
+            std::thread::spawn(move || worker_slot(&coordinator))
+        })
+        .collect();
+    for slot in slots {
+        let _ = slot.join();
+    }
+    println!("👋 Coordinator gone, worker exiting");
+    Ok(())
+}
+
+/// Pull and run jobs until the coordinator stops accepting connections
+fn worker_slot(coordinator: &str) {
+    loop {
+        let Ok(stream) = TcpStream::connect(coordinator) else {
+            return;
+        };
+        let mut line = String::new();
+        if BufReader::new(&stream).read_line(&mut line).unwrap_or(0) == 0 {
+            std::thread::sleep(WORKER_RETRY);
+            continue;
+        }
+        let job: Job = match serde_json::from_str(&line) {
+            Ok(job) => job,
+            Err(e) => {
+                eprintln!("⚠️ Bad job from coordinator: {}", e);
+                return;
+            }
+        };
+
+        println!("▶️ Run {}: {} seed {}", job.id, job.scenario_name, job.seed);
+        let result = match run_job(&job) {
+            Ok(metrics_csv) => JobResult {
+                id: job.id,
+                metrics_csv,
+                error: None,
+            },
+            Err(e) => JobResult {
+                id: job.id,
+                metrics_csv: String::new(),
+                error: Some(e.to_string()),
+            },
+        };
+        if let Err(e) = send_line(&stream, &result) {
+            eprintln!("⚠️ Cannot report run {}: {}", job.id, e);
+        }
+    }
+}
+
+/// Run one job as a child simulation and return its metrics CSV
+fn run_job(job: &Job) -> Result<String, Box<dyn Error>> {
+    let dir: PathBuf = std::env::temp_dir().join(format!(
+        "edgegenesis_sweep_{}_{}",
+        std::process::id(),
+        job.id
+    ));
+    std::fs::create_dir_all(&dir)?;
+    let metrics = dir.join("metrics.csv");
+
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .arg("--seed")
+        .arg(job.seed.to_string())
+        .arg("--max-epochs")
+        .arg(job.epochs.to_string())
+        .arg("--metrics-out")
+        .arg(&metrics)
+        .args(&job.args);
+    if let Some(toml) = &job.scenario_toml {
+        let scenario = dir.join("scenario.toml");
+        std::fs::write(&scenario, toml)?;
+        command.arg("--scenario").arg(scenario);
+    }
+
+    let status = command.status()?;
+    let csv = std::fs::read_to_string(&metrics);
+    let _ = std::fs::remove_dir_all(&dir);
+    if !status.success() {
+        return Err(format!("simulation exited with {}", status).into());
+    }
+    Ok(csv?)
+}
+
+fn send_line(mut stream: &TcpStream, message: &impl Serialize) -> Result<(), Box<dyn Error>> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Seeds as a range (`1-100`) or a list (`3,7,42`)
+pub fn parse_seeds(text: &str) -> Result<Vec<u64>, Box<dyn Error>> {
+    if let Some((first, last)) = text.split_once('-') {
+        let (first, last): (u64, u64) = (first.trim().parse()?, last.trim().parse()?);
+        if first > last {
+            return Err(format!("empty seed range {}", text).into());
+        }
+        return Ok((first..=last).collect());
+    }
+    Ok(text
+        .split(',')
+        .map(|seed| seed.trim().parse())
+        .collect::<Result<_, _>>()?)
+}
@@ -1,256 +1,1548 @@
 /// Bevy systems for simulating solar-powered edge AI devices
 /// All power/solar data is read from CSV files at runtime
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use bevy::time::common_conditions::on_timer;
+use bevy::tasks::{ComputeTaskPool, TaskPool};
+use bevy::utils::Parallel;
+use bevy_egui::EguiPrimaryContextPass;
 use rand::prelude::*;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::aging::BatteryHealth;
+use crate::attack::{
+    AttackMetrics, attack_enabled, render_attackers_system, request_system, spawn_attackers_system,
+};
+use crate::calendar::Calendar;
+use crate::carbon::{CarbonAccount, carbon_accounts};
+use crate::catastrophe::{
+    Catastrophes, SolarExposure, catastrophe_system, render_catastrophes_system,
+};
+use crate::clock::clock_enabled;
+use crate::coevolution::{coevolution_enabled, coevolution_system, setup_adversary_system};
 use crate::components::*;
-use crate::hardware::{HardwareSpec, HardwareType};
-use crate::policies::PowerPolicy;
+use crate::compression::{CompressionMetrics, breakeven_payload_bytes};
+use crate::config::{SimConfig, debug_node_enabled, max_epochs_set};
+use crate::curriculum::{self, curriculum_enabled, curriculum_log_system};
+use crate::data_loader::SolarProfile;
+use crate::decision_log::{DecisionLog, DecisionRow};
+use crate::engine::{NodeMut, Selection, StepEnv, StepOutcome, step_node};
+use crate::event_log::EventLog;
+use crate::export::{EpochRecord, Exporters, LatencyRow, PolicyStatsRow, SnapshotRow};
+use crate::extinction::{Easing, extinction_report_system, extinction_watch_system};
+use crate::failure::{FailureMetrics, hardware_failure_system};
+use crate::fitness::FitnessMode;
+use crate::forecast::{ForecastMetrics, SolarForecast, forecast_system};
+use crate::gateway::{
+    GatewayMetrics, gateway_backhaul_system, gateways_enabled, render_gateways_system,
+    spawn_gateways_system,
+};
+use crate::genome::{EliteRecord, SavedGenome, SeedPopulation};
+use crate::gossip::{GossipMetrics, gossip_enabled, gossip_system};
+use crate::grpc::{grpc_control_system, grpc_enabled, pause_for_grpc_system};
+use crate::hardware::{HardwareCatalog, HardwareSpec, HardwareType, SolarPanel};
+use crate::maintenance::{
+    MaintenanceMetrics, maintenance_enabled, maintenance_system, render_technician_system,
+    soiling_enabled, soiling_system, spawn_technician_system,
+};
+use crate::milestones::{
+    DeathCause, EpochCompleted, EpochMilestones, ExtinctionOccurred, NodeDied, node_spawned_system,
+};
+use crate::ota::{OtaDownload, OtaMetrics, ota_campaign_system, ota_enabled, ota_transfer_system};
+use crate::phylogeny::LineageLog;
+use crate::policies::{PolicyState, PowerPolicy};
+use crate::policy_stats::{
+    PolicyDashboard, PolicyMetrics, policy_dashboard_ui_system, policy_summaries,
+};
+use crate::radio::{
+    Radio, RadioMetrics, RadioType, radio_system, randomize_report_phase_system,
+    useful_bits_report_system,
+};
+use crate::report::ReportEpoch;
+use crate::scenario::Scenario;
+use crate::sensing::{Sensing, SensingMetrics, sensing_system};
+use crate::sensor::Sensor;
+use crate::sizing::sizing_report_system;
+use crate::solar::SolarSeasons;
+use crate::speed::{SimTime, setup_tick_rate, speed_control_system};
+use crate::stats::{Distribution, Percentiles};
+use crate::storage::{StorageMetrics, storage_system};
+use crate::thermal::{Temperature, ThermalMetrics, thermal_system};
+use crate::timeline::{timeline_enabled, timeline_system};
+use crate::topology::{GRID_SIZE, Topology};
+use crate::trust::{Byzantine, Reputation, TrustMetrics, assign_byzantine_system, trust_summary};
+use crate::units::{SECONDS_PER_HOUR, WattHours};
+use crate::warmup::{warm_up_system, warming_up};
+use crate::weather::{Weather, WeatherMetrics, WeatherModel, weather_enabled, weather_system};
 
-const GRID_SIZE: i32 = 10;
-const GRID_SPACING: f32 = 50.0;
 // Note: BATTERY_CAPACITY and BASE_DRAIN are now per-node in HardwareSpec
-const SOLAR_EFFICIENCY_PENALTY: f32 = 1.0; // Real efficiency
-const SIMULATION_SPEEDUP: f32 = 180.0; // 1 real sec = 3 sim minutes
+pub use crate::engine::EPOCH_SECONDS;
+
+/// Debug gauge geometry (world units)
+const GAUGE_HEIGHT: f32 = 200.0;
+const GAUGE_WIDTH: f32 = 24.0;
+
+/// Marker for the on-screen debug readout text
+#[derive(Component)]
+pub struct DebugReadout;
 
 /// Setup camera
 pub fn setup_camera(mut commands: Commands) {
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 }
 
-/// Setup system - spawns initial population of edge nodes
-pub fn setup_grid(mut commands: Commands) {
-    let mut rng = rand::rng();
-    let offset = (GRID_SIZE as f32 * GRID_SPACING) / 2.0;
-
-    // All available models from models.rs
-    let all_models = [
-        crate::models::RealModelType::YOLOv8Nano,
-        crate::models::RealModelType::YOLOv8Small,
-        crate::models::RealModelType::MobileNetV2,
-        crate::models::RealModelType::EfficientNetB0,
-        crate::models::RealModelType::TinyBERT,
-        crate::models::RealModelType::EfficientNetB1,
-        crate::models::RealModelType::MobileNetV3Small,
-        crate::models::RealModelType::DistilBERT,
-    ];
+/// Spawn positions for one generation: the full grid, or a single
+/// node at the origin when debugging policy logic in isolation
+fn node_positions(config: &SimConfig) -> Vec<Transform> {
+    if config.debug_single_node {
+        return vec![Transform::from_xyz(0.0, 0.0, 0.0)];
+    }
 
-    for x in 0..GRID_SIZE {
-        for y in 0..GRID_SIZE {
-            let model_type = all_models[rng.random_range(0..all_models.len())];
-
-            let gene = Gene {
-                model_type,
-                inference_frequency: rng.random_range(0.3..1.0),
-                solar_efficiency_factor: rng.random_range(0.8..1.2),
-                // Assign random policy initially
-                policy: match rng.random_range(0..3) {
-                    0 => PowerPolicy::Aggressive,
-                    1 => PowerPolicy::Conservative,
-                    _ => PowerPolicy::SmartAdaptive,
-                },
-            };
-
-            // Assign Random Hardware
-            let hw_type = match rng.random_range(0..3) {
-                0 => HardwareType::ESP32,
-                1 => HardwareType::JetsonNano,
-                _ => HardwareType::RaspberryPi4,
-            };
-            let hardware = HardwareSpec::new(hw_type);
-
-            commands.spawn(EdgeNodeBundle {
-                battery: Battery(hardware.battery_capacity_wh * 0.8), // Start at 80%
-                gene,
-                hardware,
-                survival_score: SurvivalScore(0.0),
-                status: Status::Alive,
-                transform: Transform::from_xyz(
-                    x as f32 * GRID_SPACING - offset,
-                    y as f32 * GRID_SPACING - offset,
-                    0.0,
-                ),
-            });
+    let topology = Topology::new(config.topology);
+    let mut positions = Vec::with_capacity((GRID_SIZE * GRID_SIZE) as usize);
+    for x in 0..topology.size {
+        for y in 0..topology.size {
+            let world = topology.world_position(IVec2::new(x, y));
+            positions.push(Transform::from_xyz(world.x, world.y, 0.0));
         }
     }
+    positions
+}
+
+/// Grid cells to fill this generation; under a fleet budget the order is
+/// shuffled so cells left empty when money runs out are spread at random
+fn fleet_positions(config: &SimConfig, rng: &mut impl Rng) -> Vec<Transform> {
+    let mut positions = node_positions(config);
+    if config.budget_usd.is_some() || config.birth_cost.is_some() {
+        positions.shuffle(rng);
+    }
+    positions
+}
+
+/// Exit once `--max-epochs` genetic epochs have completed
+pub fn max_epochs_system(
+    config: Res<SimConfig>,
+    lineage_log: Res<LineageLog>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    if let Some(max) = config.max_epochs
+        && lineage_log.epochs() >= max
+    {
+        info!(target: "edgegenesis::epoch", "🏁 Completed {} epochs", lineage_log.epochs());
+        exit.write(AppExit::Success);
+    }
+}
+
+/// Setup system - spawns initial population of edge nodes
+pub fn setup_grid(
+    mut commands: Commands,
+    config: Res<SimConfig>,
+    seeds: Res<SeedPopulation>,
+    catalog: Res<HardwareCatalog>,
+    mut genetic_rng: ResMut<GeneticRng>,
+    mut lineage_log: ResMut<LineageLog>,
+) {
+    let mut placement = Placement::default();
+    spawn_founders(
+        &mut commands,
+        &config,
+        &catalog,
+        &mut genetic_rng.0,
+        &mut lineage_log,
+        &seeds.0,
+        &mut placement,
+    );
+    placement.apply(&mut commands);
+}
+
+/// Fill the grid with founders: saved genomes cycled in file order, or
+/// random ones when `seeds` is empty. A locked hardware type overrides the
+/// saved platform. Founders take over recycled entities first.
+fn spawn_founders(
+    commands: &mut Commands,
+    config: &SimConfig,
+    catalog: &HardwareCatalog,
+    rng: &mut impl Rng,
+    lineage_log: &mut LineageLog,
+    seeds: &[SavedGenome],
+    placement: &mut Placement,
+) {
+    let positions = fleet_positions(config, rng);
+    let seeds: Vec<(Gene, HardwareSpec)> = seeds
+        .iter()
+        .map(|saved| (saved.gene, saved.hardware_spec(catalog)))
+        .collect();
+    for birth in config
+        .breeding(catalog)
+        .founders(&seeds, positions.len(), rng)
+    {
+        let lineage = lineage_log.register(None, &birth.gene);
+        placement.place(EdgeNodeBundle::new(
+            birth.gene,
+            birth.hardware,
+            lineage,
+            positions[birth.slot],
+        ));
+    }
 
     commands.insert_resource(SimulationMetrics::default());
     commands.insert_resource(EpochCount(0));
 }
 
+/// A new generation's nodes, applied to the world in batches
+#[derive(Default)]
+pub struct Placement {
+    /// Entities of the previous generation not yet taken over, popped last
+    /// first
+    recycled: Vec<Entity>,
+    reused: Vec<(Entity, EdgeNodeBundle)>,
+    spawned: Vec<EdgeNodeBundle>,
+}
+
+impl Placement {
+    pub fn new(recycled: Vec<Entity>) -> Self {
+        Self {
+            recycled,
+            ..default()
+        }
+    }
+
+    /// Put a node on an entity of the previous generation, or on a new
+    /// entity once none are left
+    pub fn place(&mut self, node: EdgeNodeBundle) {
+        match self.recycled.pop() {
+            Some(entity) => self.reused.push((entity, node)),
+            None => self.spawned.push(node),
+        }
+    }
+
+    /// Overwrite every bundle component of the reused entities in place (no
+    /// archetype churn) after clearing their per-life markers, spawn the
+    /// rest, and despawn the entities no new node took over: one command
+    /// per batch instead of one per node
+    pub fn apply(self, commands: &mut Commands) {
+        let Placement {
+            recycled,
+            reused,
+            spawned,
+        } = self;
+        let cleared: Vec<Entity> = reused.iter().map(|(entity, _)| *entity).collect();
+        commands.queue(move |world: &mut World| {
+            for entity in cleared {
+                if let Ok(mut node) = world.get_entity_mut(entity) {
+                    node.remove::<(Dead, Byzantine, OtaDownload)>();
+                }
+            }
+            for entity in recycled {
+                world.despawn(entity);
+            }
+        });
+        commands.insert_batch(reused);
+        commands.spawn_batch(spawned);
+    }
+}
+
+/// Everything the physics step reads or updates on a node
+type PhysicsQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        (&'static mut Battery, &'static mut BatteryHealth),
+        &'static mut SurvivalScore,
+        &'static mut Status,
+        &'static mut Telemetry,
+        &'static mut TunedPolicy,
+        &'static mut PolicyState,
+        &'static mut ModelCache,
+        &'static mut EnergyLedger,
+        &'static mut Sensor,
+        &'static SolarExposure,
+        &'static Temperature,
+        &'static Gene,
+        (&'static HardwareSpec, &'static SolarPanel),
+        &'static Lineage,
+    ),
+    Without<Dead>,
+>;
+
+/// Run condition: true when `--panel` or `--charge-controller` replace the
+/// reference panel
+pub fn panel_configured(config: Res<SimConfig>) -> bool {
+    config.fitted_panel().is_some()
+}
+
+/// Fit the configured panel to nodes born since the last tick (bundles
+/// carry the reference panel)
+pub fn fit_panels_system(
+    config: Res<SimConfig>,
+    mut nodes: Query<&mut SolarPanel, Changed<Lineage>>,
+) {
+    let Some(fitted) = config.fitted_panel() else {
+        return;
+    };
+    for mut panel in nodes.iter_mut() {
+        *panel = fitted;
+    }
+}
+
+/// Reference panel output for an hour of the day (0 W without solar data)
+pub fn solar_output_w(profiles: &[SolarProfile], hour: usize) -> f32 {
+    profiles
+        .get(hour)
+        .map(|p| p.power_output_100w_panel())
+        .unwrap_or(0.0)
+}
+
+/// Mixes a node's lineage id into the tick seed (64-bit golden ratio)
+const NODE_SEED_MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// One node's physics step, collected in parallel and folded into the
+/// fleet metrics, decision log and debug trace in lineage order
+pub struct NodeTick {
+    node_id: u64,
+    entity: Entity,
+    policy: PowerPolicy,
+    hardware: Arc<str>,
+    inference_time_ms: f32,
+    battery_wh: f32,
+    battery_threshold: f32,
+    outcome: StepOutcome,
+}
+
+/// Fleet sums of one physics tick, accumulated node by node in lineage
+/// order so floating-point totals do not depend on thread scheduling
+#[derive(Default)]
+struct TickTotals {
+    model_loads: u64,
+    model_load_energy_wh: WattHours,
+    sensor_wakeups: u64,
+    sensor_energy_wh: WattHours,
+    consumed_wh: WattHours,
+    harvested_wh: WattHours,
+    inferences: u64,
+    policy: PolicyMetrics,
+}
+
+impl TickTotals {
+    fn add(&mut self, tick: &NodeTick, window_s: f32) {
+        let outcome = &tick.outcome;
+        if outcome.model_loaded {
+            self.model_loads += 1;
+        }
+        self.model_load_energy_wh += outcome.load_energy_wh;
+        if outcome.sensor_woke {
+            self.sensor_wakeups += 1;
+        }
+        self.sensor_energy_wh += outcome.sensor_energy_wh;
+        self.consumed_wh += outcome.drain_wh;
+        self.harvested_wh += outcome.recharge_wh;
+        if outcome.alive {
+            self.inferences += 1;
+            let policy = tick.policy as usize;
+            self.policy.node_hours[policy] += window_s / SECONDS_PER_HOUR;
+            if outcome.inferred {
+                self.policy.inferences[policy] += window_s * 1000.0 / tick.inference_time_ms;
+            }
+        }
+    }
+
+    /// Add the sums to the fleet metrics
+    fn fold_into(self, metrics: &mut SimulationMetrics, policy_metrics: &mut PolicyMetrics) {
+        metrics.model_loads += self.model_loads;
+        metrics.model_load_energy_wh += self.model_load_energy_wh;
+        metrics.sensor_wakeups += self.sensor_wakeups;
+        metrics.sensor_energy_wh += self.sensor_energy_wh;
+        metrics.total_energy_consumed += self.consumed_wh;
+        metrics.total_energy_harvested += self.harvested_wh;
+        metrics.total_inferences += self.inferences;
+        for policy in 0..PowerPolicy::ALL.len() {
+            policy_metrics.node_hours[policy] += self.policy.node_hours[policy];
+            policy_metrics.inferences[policy] += self.policy.inferences[policy];
+        }
+    }
+}
+
 /// Physics system - uses models.rs with optional CSV overrides
+/// Nodes are stepped in parallel, each with its own generator seeded from
+/// one environment draw and its lineage id, so every node's trajectory
+/// replays identically whatever the thread count. Each worker thread
+/// collects its nodes' steps; they are then sorted by lineage id and folded
+/// one by one into the metrics, traces and deaths, so a seed reproduces the
+/// same totals bit for bit.
+#[allow(clippy::too_many_arguments)]
 pub fn resource_physics_system(
-    time: Res<Time>,
+    time: SimTime,
     power_overrides: Res<PowerOverrides>,
     solar_profiles: Res<LoadedSolarProfiles>,
+    forecast: Res<SolarForecast>,
+    config: Res<SimConfig>,
+    epoch_count: Res<EpochCount>,
+    easing: Res<Easing>,
+    weather: Res<Weather>,
+    mut decision_log: ResMut<DecisionLog>,
+    mut policy_metrics: ResMut<PolicyMetrics>,
+    mut env_rng: ResMut<EnvRng>,
     mut metrics: ResMut<SimulationMetrics>,
-    mut query: Query<(
-        &mut Battery,
-        &mut SurvivalScore,
-        &mut Status,
-        &Gene,
-        &HardwareSpec,
-    )>,
+    mut deaths: MessageWriter<NodeDied>,
+    mut collected: Local<Parallel<Vec<NodeTick>>>,
+    mut ticks: Local<Vec<NodeTick>>,
+    mut query: PhysicsQuery,
 ) {
-    let mut rng = rand::rng();
     let dt = time.delta_secs();
+    let window_s = time.window_s();
 
-    // Update simulation hour (synced with SIMULATION_SPEEDUP)
-    metrics.current_hour = (metrics.current_hour + dt * SIMULATION_SPEEDUP / 3600.0) % 24.0;
+    // Update simulation hour (synced with the time scale)
+    metrics.current_hour = (metrics.current_hour + time.window_h()) % 24.0;
+    if decision_log.enabled() {
+        decision_log.advance(time.window_h());
+    }
 
-    // Get solar output for current hour
+    // Get solar output for current hour, as eased by the curriculum and
+    // dimmed by the clouds
     let current_hour_index = metrics.current_hour as usize % 24;
-    let solar_output_w = solar_profiles
-        .0
-        .get(current_hour_index)
-        .map(|p| p.power_output_100w_panel())
-        .unwrap_or(0.0);
+    let stage = easing.apply(curriculum::stage(&config, epoch_count.0));
+    let staged_solar_w =
+        |hour: usize| solar_output_w(&solar_profiles.0, stage.solar_hour(hour)) * stage.solar_scale;
+    // Climatology until the forecaster has observed the hour
+    let expected_solar_w = |hour: usize| {
+        forecast
+            .predict(hour)
+            .unwrap_or_else(|| staged_solar_w(hour))
+    };
+    let env = StepEnv {
+        window_s,
+        hour: current_hour_index,
+        solar_w: staged_solar_w(current_hour_index) * weather.solar_factor(),
+        expected_solar_w: &expected_solar_w,
+        power_overrides: power_overrides.0.as_ref(),
+        battery_aging: config.battery_aging,
+        compatibility: config.compatibility,
+    };
+    let tick_seed: u64 = env_rng.0.random();
 
-    for (mut battery, mut score, mut status, gene, hardware) in query.iter_mut() {
-        if *status == Status::Dead {
-            continue;
-        }
+    // Bare apps (tests, benches) run without the TaskPoolPlugin
+    ComputeTaskPool::get_or_init(TaskPool::default);
 
-        // Get power using hybrid system (CSV override or models.rs default)
-        let (idle_power, inference_power) =
-            crate::data_loader::get_model_power(gene.model_type, power_overrides.0.as_ref());
-
-        // POLICY-BASED INFERENCE DECISION
-        let should_infer =
-            gene.policy
-                .should_infer(battery.0, solar_output_w, gene.inference_frequency);
-
-        let power_w = hardware.idle_power_w
-            + if should_infer {
-                inference_power
-            } else {
-                0.0 // Idle power is already added as baseline
-            };
-
-        // Solar recharge using CSV data (with harsh environment penalty)
-        let recharge_w = solar_output_w * gene.solar_efficiency_factor * SOLAR_EFFICIENCY_PENALTY;
-        let recharge_wh = (recharge_w * dt * SIMULATION_SPEEDUP) / 3600.0f32;
-        battery.0 += recharge_wh;
-
-        // Apply physics with time scaling
-        let drain_wh = (power_w * dt * SIMULATION_SPEEDUP) / 3600.0f32;
-        battery.0 -= drain_wh;
-
-        // Track metrics
-        metrics.total_energy_consumed += drain_wh;
-        metrics.total_energy_harvested += recharge_wh;
-
-        // Cap battery based on HARDWARE LIMIT
-        battery.0 = battery.0.clamp(0.0, hardware.battery_capacity_wh);
-
-        // Death condition
-        if battery.0 <= 0.0 {
-            if *status != Status::Dead {
-                // println!("💀 Node died! (Battery depleted)"); // Optional: Uncomment for per-node death logs
-                *status = Status::Dead;
+    query.par_iter_mut().for_each(
+        |(
+            entity,
+            (mut battery, mut health),
+            mut score,
+            mut status,
+            mut telemetry,
+            mut tuned,
+            mut policy_state,
+            mut model_cache,
+            mut ledger,
+            mut sensor,
+            exposure,
+            temperature,
+            gene,
+            (hardware, panel),
+            lineage,
+        )| {
+            // Died earlier this tick, not marked yet
+            if *status == Status::Dead {
+                return;
             }
-        } else {
-            score.0 += dt;
-            metrics.total_inferences += 1;
+
+            let mut rng =
+                SmallRng::seed_from_u64(tick_seed ^ lineage.id.wrapping_mul(NODE_SEED_MIX));
+            let outcome = step_node(
+                NodeMut {
+                    battery: &mut battery,
+                    health: &mut health,
+                    status: &mut status,
+                    telemetry: &mut telemetry,
+                    tuned: &mut tuned,
+                    policy_state: &mut policy_state,
+                    model_cache: &mut model_cache,
+                    ledger: &mut ledger,
+                    sensor: &mut sensor,
+                    gene,
+                    hardware,
+                    panel,
+                    exposure: exposure.0,
+                    temperature_c: temperature.celsius,
+                },
+                &env,
+                &mut rng,
+            );
+
+            if outcome.alive {
+                score.0 += dt;
+            }
+            collected.borrow_local_mut().push(NodeTick {
+                node_id: lineage.id,
+                entity,
+                policy: gene.policy,
+                hardware: Arc::clone(&hardware.name),
+                inference_time_ms: temperature.inference_time_ms(gene.model_type, hardware),
+                battery_wh: battery.wh(),
+                battery_threshold: tuned.0.battery_threshold,
+                outcome,
+            });
+        },
+    );
+
+    // Threads finish in any order; fold in lineage order
+    ticks.clear();
+    for local in collected.iter_mut() {
+        ticks.append(local);
+    }
+    ticks.sort_unstable_by_key(|tick| tick.node_id);
+
+    let mut tick_totals = TickTotals::default();
+    for tick in ticks.iter() {
+        tick_totals.add(tick, window_s);
+        let outcome = tick.outcome;
+        if !outcome.alive {
+            deaths.write(NodeDied {
+                entity: tick.entity,
+                node_id: tick.node_id,
+                cause: if outcome.crashed {
+                    DeathCause::IncompatibleModel
+                } else {
+                    DeathCause::BatteryDepleted
+                },
+            });
+        }
+
+        if decision_log.enabled() {
+            let elapsed_h = decision_log.elapsed_h;
+            decision_log.record(DecisionRow {
+                elapsed_h,
+                epoch: epoch_count.0,
+                node_id: tick.node_id,
+                policy: tick.policy.name(),
+                hardware: tick.hardware.to_string(),
+                decisions: 1,
+                state_of_charge: outcome.state_of_charge,
+                solar_input_w: outcome.recharge_w,
+                battery_threshold: tick.battery_threshold,
+                infer_fraction: if outcome.wants_infer { 1.0 } else { 0.0 },
+                asleep_fraction: if outcome.asleep { 1.0 } else { 0.0 },
+            });
         }
+
+        if config.debug_single_node {
+            info!(target: "edgegenesis::physics",
+                "🔍 [{:05.2}h] node {} | {} on {} | battery {:.4} Wh | threshold {:.3} | solar {:.2} W | draw {:.2} W | infer: {}",
+                metrics.current_hour,
+                tick.node_id,
+                tick.policy.name(),
+                tick.hardware,
+                tick.battery_wh,
+                tick.battery_threshold,
+                outcome.recharge_w,
+                outcome.power_w,
+                outcome.inferred
+            );
+        }
+    }
+
+    tick_totals.fold_into(&mut metrics, &mut policy_metrics);
+}
+
+/// Tag announced deaths with `Dead` (the epoch's despawn clears them)
+pub fn mark_dead_system(mut commands: Commands, mut deaths: MessageReader<NodeDied>) {
+    for death in deaths.read() {
+        commands.entity(death.entity).try_insert(Dead);
     }
 }
 
-/// Rendering system - visualizes node state
-pub fn render_nodes_system(
+/// Spawn the HUD text used by single-node debug mode
+pub fn setup_debug_readout(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        DebugReadout,
+    ));
+}
+
+/// Debug gauges - large vertical bars beside the single debug node
+/// Solar input and power draw share one scale (the panel's rated maximum)
+/// so harvest vs consumption can be compared at a glance
+pub fn render_debug_gauges_system(
     mut gizmos: Gizmos,
-    query: Query<(&Transform, &Battery, &Gene, &Status, &HardwareSpec)>,
+    metrics: Res<SimulationMetrics>,
+    query: Query<(
+        &Transform,
+        &Battery,
+        &Gene,
+        &Status,
+        &HardwareSpec,
+        &Telemetry,
+    )>,
+    mut readout: Query<&mut Text, With<DebugReadout>>,
 ) {
-    for (transform, battery, gene, status, hardware) in query.iter() {
-        let position = transform.translation.truncate();
-        // Radius based on model size (larger models = bigger circles)
-        let radius = (gene.model_type.size_mb() / 10.0).clamp(3.0, 20.0);
+    let Some((transform, battery, gene, status, hardware, telemetry)) = query.iter().next() else {
+        return;
+    };
 
-        let color = if *status == Status::Dead {
-            Color::srgb(0.5, 0.5, 0.5) // Gray
-        } else {
-            let charge_ratio = (battery.0 / hardware.battery_capacity_wh).clamp(0.0, 1.0);
-            if charge_ratio > 0.75 {
-                Color::srgb(0.0, 1.0, 0.0) // Green
-            } else if charge_ratio > 0.25 {
-                Color::srgb(1.0, 1.0, 0.0) // Yellow
-            } else {
-                Color::srgb(1.0, 0.0, 0.0) // Red
-            }
-        };
+    let origin = transform.translation.truncate() + Vec2::new(60.0, -GAUGE_HEIGHT / 2.0);
+    let gauges = [
+        (
+            battery.wh() / hardware.usable_capacity_wh(),
+            Color::srgb(0.0, 1.0, 0.0),
+        ),
+        (
+            telemetry.solar_input_w / hardware.max_solar_input_w,
+            Color::srgb(1.0, 0.8, 0.0),
+        ),
+        (
+            telemetry.power_draw_w / hardware.max_solar_input_w,
+            Color::srgb(1.0, 0.2, 0.2),
+        ),
+    ];
+
+    for (i, (fraction, color)) in gauges.iter().enumerate() {
+        let x = origin.x + i as f32 * (GAUGE_WIDTH * 2.0);
+        let fill = fraction.clamp(0.0, 1.0) * GAUGE_HEIGHT;
+
+        // Outline at full scale, filled bar at current level
+        gizmos.rect_2d(
+            Vec2::new(x, origin.y + GAUGE_HEIGHT / 2.0),
+            Vec2::new(GAUGE_WIDTH, GAUGE_HEIGHT),
+            Color::srgb(0.4, 0.4, 0.4),
+        );
+        gizmos.rect_2d(
+            Vec2::new(x, origin.y + fill / 2.0),
+            Vec2::new(GAUGE_WIDTH - 6.0, fill),
+            *color,
+        );
+    }
+
+    if let Ok(mut text) = readout.single_mut() {
+        text.0 = format!(
+            "DEBUG NODE  [{:05.2}h]\n\
+             Hardware: {}\n\
+             Model:    {}\n\
+             Policy:   {}\n\
+             Status:   {:?}\n\
+             Battery:  {:.3} / {:.1} Wh\n\
+             Solar:    {:.2} W\n\
+             Draw:     {:.2} W\n\
+             Inferring: {}",
+            metrics.current_hour,
+            hardware.name(),
+            gene.model_type.name(),
+            gene.policy.name(),
+            status,
+            battery.wh(),
+            hardware.usable_capacity_wh(),
+            telemetry.solar_input_w,
+            telemetry.power_draw_w,
+            telemetry.inferring
+        );
+    }
+}
+
+/// Everything the epoch system reads from a node
+#[derive(QueryData)]
+pub struct EpochNode {
+    pub entity: Entity,
+    pub status: &'static Status,
+    pub score: &'static SurvivalScore,
+    pub gene: &'static Gene,
+    pub battery: &'static Battery,
+    pub hardware: &'static HardwareSpec,
+    pub lineage: &'static Lineage,
+    pub tuned: &'static TunedPolicy,
+    pub sensing: &'static Sensing,
+    pub ledger: &'static EnergyLedger,
+    pub radio: &'static Radio,
+}
+
+type EpochQuery<'w, 's> = Query<'w, 's, EpochNode>;
+
+/// Energy and work record of every node, for carbon and per-policy scores
+type EnergyQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static HardwareSpec,
+        &'static Gene,
+        &'static Sensing,
+        &'static Battery,
+        &'static EnergyLedger,
+        &'static Status,
+        &'static SurvivalScore,
+        &'static BatteryHealth,
+    ),
+>;
+
+/// Per-epoch counters of every subsystem, bundled so the genetic system
+/// takes one param
+#[derive(SystemParam)]
+pub struct SubsystemMetrics<'w> {
+    pub radio: ResMut<'w, RadioMetrics>,
+    pub gossip: ResMut<'w, GossipMetrics>,
+    pub sensing: ResMut<'w, SensingMetrics>,
+    pub ota: ResMut<'w, OtaMetrics>,
+    pub attack: ResMut<'w, AttackMetrics>,
+    pub trust: ResMut<'w, TrustMetrics>,
+    pub failure: ResMut<'w, FailureMetrics>,
+    pub gateway: ResMut<'w, GatewayMetrics>,
+    pub storage: ResMut<'w, StorageMetrics>,
+    pub compression: ResMut<'w, CompressionMetrics>,
+    pub forecast: ResMut<'w, ForecastMetrics>,
+    pub thermal: ResMut<'w, ThermalMetrics>,
+    pub maintenance: ResMut<'w, MaintenanceMetrics>,
+    pub weather: ResMut<'w, WeatherMetrics>,
+    pub policy: ResMut<'w, PolicyMetrics>,
+}
+
+impl SubsystemMetrics<'_> {
+    /// Start a fresh epoch
+    pub fn reset(&mut self) {
+        *self.radio = RadioMetrics::default();
+        *self.gossip = GossipMetrics::default();
+        *self.sensing = SensingMetrics::default();
+        *self.ota = OtaMetrics::default();
+        *self.attack = AttackMetrics::default();
+        *self.trust = TrustMetrics::default();
+        *self.failure = FailureMetrics::default();
+        *self.gateway = GatewayMetrics::default();
+        *self.storage = StorageMetrics::default();
+        *self.compression = CompressionMetrics::default();
+        *self.forecast = ForecastMetrics::default();
+        *self.thermal = ThermalMetrics::default();
+        *self.maintenance = MaintenanceMetrics::default();
+        *self.weather = WeatherMetrics::default();
+        *self.policy = PolicyMetrics::default();
+    }
+}
+
+/// Surroundings of the population read at the epoch, bundled so the
+/// genetic system takes one param
+#[derive(SystemParam)]
+pub struct EpochEnvironment<'w> {
+    pub catastrophes: Res<'w, Catastrophes>,
+    pub scenario: Res<'w, Scenario>,
+    pub forecast: Res<'w, SolarForecast>,
+    pub calendar: Res<'w, Calendar>,
+    pub hardware_catalog: Res<'w, HardwareCatalog>,
+}
+
+/// Registration points around the genetic epoch for library users: systems
+/// added to these sets run only on epoch ticks, in `FixedUpdate`
+/// (`app.add_systems(FixedUpdate, my_system.in_set(EpochSet::BeforeSelection))`)
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EpochSet {
+    /// After the tick's node pipeline, before fitness is evaluated: the
+    /// finishing generation is still in place with its final scores
+    BeforeSelection,
+    /// After the next generation's entities are in place (and after
+    /// `EpochCompleted` / `ExtinctionOccurred` were written)
+    AfterRepopulation,
+}
 
-        gizmos.circle_2d(position, radius, color);
+/// Repeating `EPOCH_SECONDS` timer on the fixed clock, shared by the epoch
+/// system and the `EpochSet` hooks so they fire on the same tick
+#[derive(Resource)]
+pub struct EpochClock(pub Timer);
+
+impl Default for EpochClock {
+    fn default() -> Self {
+        Self(Timer::new(
+            Duration::from_secs(EPOCH_SECONDS),
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+/// Advance the epoch clock (first in every fixed tick)
+pub fn epoch_clock_system(time: Res<Time>, mut clock: ResMut<EpochClock>) {
+    clock.0.tick(time.delta());
+}
+
+/// Calendar system - count midnights and swap in each new day's solar
+/// profile; runs after physics has advanced the hour
+pub fn calendar_system(
+    metrics: Res<SimulationMetrics>,
+    seasons: Res<SolarSeasons>,
+    epoch_count: Res<EpochCount>,
+    catastrophes: Res<Catastrophes>,
+    mut calendar: ResMut<Calendar>,
+    mut solar_profiles: ResMut<LoadedSolarProfiles>,
+    mut event_log: ResMut<EventLog>,
+) {
+    let month = calendar.month();
+    if !calendar.observe_hour(metrics.current_hour) {
+        return;
     }
+    if seasons.varies() {
+        solar_profiles.0 = seasons.profiles_on(calendar.day_of_year());
+    }
+    if calendar.month() != month {
+        event_log.push(
+            epoch_count.0,
+            catastrophes.elapsed_h,
+            format!("New month: {}", *calendar),
+        );
+    }
+}
+
+/// Run condition: this tick ends an epoch
+pub fn epoch_due(clock: Res<EpochClock>) -> bool {
+    clock.0.just_finished()
 }
 
 /// Genetic epoch system - evolutionary selection and mutation
+#[allow(clippy::too_many_arguments)]
 pub fn genetic_epoch_system(
     mut commands: Commands,
     mut epoch_count: ResMut<EpochCount>,
     mut metrics: ResMut<SimulationMetrics>,
-    query: Query<(Entity, &Status, &SurvivalScore, &Gene, &Battery)>,
+    config: Res<SimConfig>,
+    mut genetic_rng: ResMut<GeneticRng>,
+    mut exporters: Exporters,
+    mut lineage_log: ResMut<LineageLog>,
+    mut subsystems: SubsystemMetrics,
+    environment: EpochEnvironment,
+    mut policy_dashboard: ResMut<PolicyDashboard>,
+    mut milestones: EpochMilestones,
+    trust_nodes: Query<(&Reputation, Has<Byzantine>)>,
+    energy_nodes: EnergyQuery,
+    query: EpochQuery,
 ) {
+    // Every event of this epoch carries its number
+    let _span = info_span!("epoch", epoch = epoch_count.0).entered();
+    let EpochEnvironment {
+        catastrophes,
+        scenario,
+        forecast,
+        calendar,
+        hardware_catalog,
+    } = environment;
     let _simulated_hours_passed = (epoch_count.0 as f32 * 30.0) / 60.0; // Assuming 1 real sec = 1 sim minute
 
     // Calculate average battery level
-    let total_battery: f32 = query.iter().map(|(_, _, _, _, battery)| battery.0).sum();
+    let total_battery: f32 = query.iter().map(|node| node.battery.wh()).sum();
     let avg_battery = if !query.is_empty() {
         total_battery / query.iter().count() as f32
     } else {
         0.0
     };
 
-    println!("\n=== EPOCH {} ===", epoch_count.0);
-    println!("⏰ Simulated Time: {:.1} hours", metrics.current_hour); // Current hour of day
-    println!(
+    info!(target: "edgegenesis::epoch", "=== EPOCH {} ===", epoch_count.0);
+    info!(target: "edgegenesis::epoch", "⏰ Simulated Time: {:.1} hours", metrics.current_hour); // Current hour of day
+    info!(target: "edgegenesis::epoch", "📅 Calendar: {}", *calendar);
+    info!(target: "edgegenesis::epoch",
         "🔋 Avg Energy Consumed (Epoch): {:.2} Wh",
-        metrics.total_energy_consumed / 100.0
+        metrics.total_energy_consumed.wh() / 100.0
+    );
+    info!(target: "edgegenesis::energy",
+        "💾 Model loads: {} ({:.3} Wh)",
+        metrics.model_loads, metrics.model_load_energy_wh.wh()
+    );
+    info!(target: "edgegenesis::sensor",
+        "📷 Sensors: {} power-ups ({:.3} Wh)",
+        metrics.sensor_wakeups, metrics.sensor_energy_wh.wh()
+    );
+    info!(target: "edgegenesis::epoch", "⚡ Avg Battery Level: {:.2} Wh", avg_battery);
+
+    // Selection fitness: the configured preset, divided by the hardware
+    // normalization computed over this epoch's population
+    let raw_fitness = |score: &SurvivalScore,
+                       sensing: &Sensing,
+                       hardware: &HardwareSpec,
+                       gene: &Gene,
+                       battery: &Battery,
+                       ledger: &EnergyLedger| {
+        let consumed_wh = ledger.consumed_wh(battery);
+        config
+            .fitness
+            .evaluate(score.0, sensing, hardware, gene, consumed_wh)
+    };
+    let divisors = config
+        .fitness_normalization
+        .divisors(query.iter().map(|node| {
+            let fitness = raw_fitness(
+                node.score,
+                node.sensing,
+                node.hardware,
+                node.gene,
+                node.battery,
+                node.ledger,
+            );
+            (fitness, node.hardware)
+        }));
+    let fitness_of = |score: &SurvivalScore,
+                      sensing: &Sensing,
+                      hardware: &HardwareSpec,
+                      gene: &Gene,
+                      battery: &Battery,
+                      ledger: &EnergyLedger| {
+        raw_fitness(score, sensing, hardware, gene, battery, ledger)
+            / divisors[hardware.hardware_type as usize]
+    };
+    let node_fitness = |node: &EpochNodeItem| {
+        fitness_of(
+            node.score,
+            node.sensing,
+            node.hardware,
+            node.gene,
+            node.battery,
+            node.ledger,
+        )
+    };
+
+    // Fitness distribution over the whole population: dead nodes keep the
+    // score they reached, so bimodal outcomes show up in the quartiles
+    let scores: Vec<f32> = query.iter().map(|node| node_fitness(&node)).collect();
+    let fitness = Distribution::from_samples(&scores);
+    info!(target: "edgegenesis::epoch",
+        "📐 Fitness (n={}): median {:.2}s | Q1 {:.2}s | Q3 {:.2}s | σ {:.2}s | range {:.2}–{:.2}s",
+        fitness.count,
+        fitness.median,
+        fitness.q1,
+        fitness.q3,
+        fitness.std_dev,
+        fitness.min,
+        fitness.max
+    );
+
+    info!(target: "edgegenesis::radio",
+        "📡 Radio: {} reports delivered, {} dropped | {} attempts, {:.1}% collisions, {:.1}% link loss | {:.3} Wh",
+        subsystems.radio.delivered,
+        subsystems.radio.dropped,
+        subsystems.radio.attempts,
+        subsystems.radio.collision_rate() * 100.0,
+        subsystems.radio.link_loss_rate() * 100.0,
+        subsystems.radio.energy_wh
+    );
+    info!(target: "edgegenesis::radio",
+        "⏳ Duty cycle: {} reports deferred, {} overflowed | backlog {} (peak {})",
+        subsystems.radio.deferred,
+        subsystems.radio.overflowed,
+        subsystems.radio.backlog,
+        subsystems.radio.peak_backlog
+    );
+    info!(target: "edgegenesis::sensing",
+        "🎯 Sensing: {}/{} events detected ({:.1}%), {} missed while asleep/dead, {} false negatives, {} false alarms",
+        subsystems.sensing.detections,
+        subsystems.sensing.events,
+        subsystems.sensing.detection_rate() * 100.0,
+        subsystems.sensing.missed,
+        subsystems.sensing.false_negatives,
+        subsystems.sensing.false_alarms
+    );
+    let latency = Percentiles::from_samples(&subsystems.sensing.latencies_s);
+    let model_latency: Vec<(&'static str, Percentiles)> = subsystems
+        .sensing
+        .model_latencies_s
+        .iter()
+        .filter_map(|(model, samples)| Percentiles::from_samples(samples).map(|p| (*model, p)))
+        .collect();
+    if let Some(latency) = latency {
+        let per_model: Vec<String> = model_latency
+            .iter()
+            .map(|(model, p)| format!("{} {:.0}/{:.0}/{:.0}", model, p.p50, p.p95, p.p99))
+            .collect();
+        info!(target: "edgegenesis::sensing",
+            "⏱️ Response latency p50/p95/p99: {:.0}/{:.0}/{:.0} s over {} serviced | {}",
+            latency.p50,
+            latency.p95,
+            latency.p99,
+            latency.count,
+            per_model.join(" | ")
+        );
+    }
+    exporters.latency.record(
+        &model_latency
+            .iter()
+            .map(|(model, p)| LatencyRow {
+                epoch: epoch_count.0,
+                model,
+                serviced: p.count,
+                p50_s: p.p50,
+                p95_s: p.p95,
+                p99_s: p.p99,
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    if config.attackers > 0 {
+        info!(target: "edgegenesis::attack",
+            "🏴 Requests: {}/{} legit served | {}/{} bogus served ({:.3} Wh)",
+            subsystems.attack.legit_served,
+            subsystems.attack.legit_requests,
+            subsystems.attack.bogus_served,
+            subsystems.attack.bogus_requests,
+            subsystems.attack.energy_wh
+        );
+    }
+
+    let (trust_byzantine, trust_honest, byzantine_flagged, honest_flagged) =
+        trust_summary(trust_nodes.iter());
+    if config.byzantine_fraction > 0.0 {
+        info!(target: "edgegenesis::trust",
+            "🛡️ Trust: Byzantine {:.2} ({} flagged) vs honest {:.2} ({} flagged) | {} misreports, {} refusals",
+            trust_byzantine,
+            byzantine_flagged,
+            trust_honest,
+            honest_flagged,
+            subsystems.trust.misreports,
+            subsystems.trust.refusals
+        );
+    }
+
+    if !config.ota_campaigns.is_empty() {
+        info!(target: "edgegenesis::ota",
+            "📦 OTA: {} transfers started, {} completed, {} aborted ({:.3} Wh)",
+            subsystems.ota.started,
+            subsystems.ota.completed,
+            subsystems.ota.aborted,
+            subsystems.ota.energy_wh
+        );
+    }
+
+    if subsystems.compression.compressed_reports > 0 {
+        let breakeven = |hardware_type| {
+            let radio_type = RadioType::for_hardware(hardware_type);
+            breakeven_payload_bytes(hardware_type, radio_type, 14.0)
+                .map_or("never".to_string(), |bytes| format!("{:.0} B", bytes))
+        };
+        info!(target: "edgegenesis::compression",
+            "🗜️ Compression: {} reports, {:.0} bytes saved ({:.4} Wh CPU) | breakeven @14 dBm: ESP32/LoRa {}, RPi4/WiFi {}",
+            subsystems.compression.compressed_reports,
+            subsystems.compression.bytes_saved,
+            subsystems.compression.cpu_energy_wh,
+            breakeven(HardwareType::ESP32),
+            breakeven(HardwareType::RaspberryPi4)
+        );
+    }
+
+    if subsystems.storage.stored > 0 {
+        info!(target: "edgegenesis::storage",
+            "💾 Storage: {} reports stored, {} forwarded later, {} lost to full buffers ({:.4} Wh, peak wear {:.2}%)",
+            subsystems.storage.stored,
+            subsystems.storage.flushed,
+            subsystems.storage.overflowed,
+            subsystems.storage.energy_wh,
+            subsystems.storage.peak_wear * 100.0
+        );
+    }
+
+    if !scenario.gateways.is_empty() {
+        info!(target: "edgegenesis::gateway",
+            "🏢 Gateways: {} reports forwarded in {} backhaul uplinks ({:.3} Wh)",
+            subsystems.gateway.forwarded,
+            subsystems.gateway.uplinks,
+            subsystems.gateway.backhaul_energy_wh
+        );
+    }
+
+    if subsystems.failure.total_failures() > 0 {
+        // Per class, named after the platforms deployed in it (catalog rows
+        // under `--hardware-csv`)
+        let platforms = hardware_catalog.platforms();
+        let per_type: Vec<String> = HardwareType::ALL
+            .iter()
+            .zip(subsystems.failure.by_type.iter())
+            .filter(|(_, stats)| stats.node_hours > 0.0)
+            .map(|(hw_type, stats)| {
+                let names: Vec<&str> = platforms
+                    .iter()
+                    .filter(|spec| spec.hardware_type == *hw_type)
+                    .map(HardwareSpec::name)
+                    .collect();
+                let mtbf = |h: Option<f32>| h.map_or("-".to_string(), |h| format!("{:.0} h", h));
+                format!(
+                    "{} {} (MTBF observed {} vs expected {})",
+                    names.join("/"),
+                    stats.failures,
+                    mtbf(stats.observed_mtbf_h()),
+                    mtbf(stats.expected_mtbf_h())
+                )
+            })
+            .collect();
+        info!(target: "edgegenesis::failure", "🔧 Hardware faults: {}", per_type.join(", "));
+    }
+
+    if subsystems.thermal.throttled_hours > 0.0 {
+        info!(target: "edgegenesis::thermal",
+            "🌡️ Thermal: {:.1}% of node-hours throttled, peak {:.1} °C",
+            subsystems.thermal.throttled_fraction() * 100.0,
+            subsystems.thermal.peak_c
+        );
+    }
+
+    if config.weather != WeatherModel::Clear {
+        info!(target: "edgegenesis::weather",
+            "☁️ Weather: mean solar ×{:.2}, {:.1}% of hours in solar drought, longest drought {:.0} h",
+            subsystems.weather.mean_solar_factor(),
+            subsystems.weather.drought_fraction() * 100.0,
+            subsystems.weather.longest_drought_h
+        );
+    }
+
+    if scenario.maintenance.is_some() {
+        let per_visit = subsystems
+            .maintenance
+            .cost_per_visit_usd()
+            .map_or("-".to_string(), |usd| format!("${:.2}", usd));
+        info!(target: "edgegenesis::maintenance",
+            "🧰 Maintenance: {} rounds, {} visits ({} battery swaps, {} panel cleans), {:.1} technician hours, ${:.2} ({} per visit)",
+            subsystems.maintenance.rounds,
+            subsystems.maintenance.visits,
+            subsystems.maintenance.battery_swaps,
+            subsystems.maintenance.panel_cleans,
+            subsystems.maintenance.labor_h,
+            subsystems.maintenance.cost_usd,
+            per_visit
+        );
+    }
+
+    // Capacity the generation's batteries kept after cycling
+    let node_count = energy_nodes.iter().len().max(1) as f32;
+    let (health_sum, cycles_sum) =
+        energy_nodes
+            .iter()
+            .fold((0.0, 0.0), |(health, cycles), (.., battery_health)| {
+                (
+                    health + battery_health.state_of_health(),
+                    cycles + battery_health.cycles,
+                )
+            });
+    let mean_battery_health = health_sum / node_count;
+    if config.battery_aging > 0.0 {
+        info!(target: "edgegenesis::energy",
+            "🔋 Battery health: {:.2}% of nominal capacity, {:.3} equivalent full cycles per node",
+            mean_battery_health * 100.0,
+            cycles_sum / node_count
+        );
+    }
+
+    if !catastrophes.active.is_empty() {
+        let names: Vec<&str> = catastrophes.active.iter().map(|c| c.kind.name()).collect();
+        info!(target: "edgegenesis::catastrophe", "🌩️ Catastrophes in progress: {}", names.join(", "));
+    }
+
+    // Harvest outlook for the next simulated day (once every hour was observed)
+    let hour = metrics.current_hour as usize;
+    if let (Some(ahead_wh), Some(now_w), Some(std_w)) = (
+        forecast.energy_ahead_wh(hour, 24),
+        forecast.predict(hour),
+        forecast.std_dev(hour),
+    ) {
+        let mae = subsystems
+            .forecast
+            .mean_abs_error_w()
+            .map_or("-".to_string(), |e| format!("{:.2} W", e));
+        info!(target: "edgegenesis::forecast",
+            "🔮 Solar forecast: {:.1} Wh over the next 24 h (this hour {:.2} ± {:.2} W), MAE {}",
+            ahead_wh, now_w, std_w, mae
+        );
+    }
+
+    if config.gossip_rate > 0.0 {
+        info!(target: "edgegenesis::gossip",
+            "🗣️ Gossip: {} gene exchanges ({:.3} Wh)",
+            subsystems.gossip.exchanges, subsystems.gossip.energy_wh
+        );
+    }
+
+    let fleet_cost_usd: f32 = query.iter().map(|node| node.hardware.cost_usd()).sum();
+    if config.budget_usd.is_some() || config.fitness == FitnessMode::CostEfficiency {
+        info!(target: "edgegenesis::epoch",
+            "💵 Fleet: {} nodes, ${:.0}{} | {:.2} detections per $100",
+            scores.len(),
+            fleet_cost_usd,
+            config
+                .budget_usd
+                .map_or(String::new(), |b| format!(" of ${:.0} budget", b)),
+            subsystems.sensing.detections as f32 * 100.0 / fleet_cost_usd.max(f32::EPSILON)
+        );
+    }
+
+    for (hardware, _, _, battery, ledger, status, _, _) in energy_nodes.iter() {
+        exporters.sizing.record(hardware, ledger, battery, status);
+    }
+
+    let epoch_h = EPOCH_SECONDS as f32 * config.time_scale / SECONDS_PER_HOUR;
+    let (carbon, configs) = carbon_accounts(
+        energy_nodes
+            .iter()
+            .map(|(hardware, gene, sensing, battery, ledger, _, _, _)| {
+                (hardware, gene, sensing, battery, ledger)
+            }),
+        epoch_h,
+    );
+    if let (Some(g), Some(wh)) = (carbon.g_per_detection(), carbon.wh_per_detection()) {
+        let (best, worst) = (&configs[0], &configs[configs.len() - 1]);
+        let per_detection = |account: &CarbonAccount| {
+            account
+                .g_per_detection()
+                .map_or("no detections".to_string(), |g| format!("{:.3} g", g))
+        };
+        info!(target: "edgegenesis::carbon",
+            "🌍 Carbon: {:.3} gCO2e/detection embodied | {:.3} Wh/detection operational ({:.1} g if grid-powered) | best {} {}, worst {} {}",
+            g,
+            wh,
+            carbon.grid_equivalent_g(),
+            best.0,
+            per_detection(&best.1),
+            worst.0,
+            per_detection(&worst.1)
+        );
+    }
+
+    // Per-policy scores (evolved heuristics vs. the controller baselines)
+    let policy_summaries = policy_summaries(
+        energy_nodes.iter().map(
+            |(hardware, gene, sensing, battery, ledger, status, score, _)| {
+                let fitness = fitness_of(score, sensing, hardware, gene, battery, ledger);
+                (gene, status, fitness, sensing, battery, ledger)
+            },
+        ),
+        &subsystems.policy,
+    );
+    let per_policy: Vec<String> = policy_summaries
+        .iter()
+        .map(|summary| {
+            format!(
+                "{} {:.0}% alive, fitness {:.2}",
+                summary.policy.name(),
+                summary.survival_rate() * 100.0,
+                summary.mean_fitness
+            )
+        })
+        .collect();
+    info!(target: "edgegenesis::policy_stats", "⚖️ Policies: {}", per_policy.join(" | "));
+    exporters.policy_stats.record(
+        &policy_summaries
+            .iter()
+            .map(|summary| PolicyStatsRow {
+                epoch: epoch_count.0,
+                policy: summary.policy.name(),
+                nodes: summary.nodes,
+                survivors: summary.survivors,
+                survival_rate: summary.survival_rate(),
+                mean_fitness: summary.mean_fitness,
+                energy_wh: summary.energy_wh,
+                detections: summary.detections,
+                detections_per_wh: summary.detections_per_wh(),
+                inferences: summary.inferences,
+                inferences_per_hour: summary.inferences_per_hour(),
+            })
+            .collect::<Vec<_>>(),
+    );
+    *policy_dashboard = PolicyDashboard {
+        epoch: epoch_count.0,
+        summaries: policy_summaries,
+    };
+
+    let mean_awake_hours = query
+        .iter()
+        .map(|node| node.gene.wake_schedule.awake_hours() as f32)
+        .sum::<f32>()
+        / scores.len().max(1) as f32;
+
+    let survivor_count = query
+        .iter()
+        .filter(|node| *node.status != Status::Dead)
+        .count();
+    let record = EpochRecord {
+        epoch: epoch_count.0,
+        population: scores.len(),
+        survivors: survivor_count,
+        avg_battery_wh: WattHours::from_wh(avg_battery),
+        energy_consumed_wh: metrics.total_energy_consumed,
+        energy_harvested_wh: metrics.total_energy_harvested,
+        model_loads: metrics.model_loads,
+        model_load_energy_wh: metrics.model_load_energy_wh,
+        sensor_wakeups: metrics.sensor_wakeups,
+        sensor_energy_wh: metrics.sensor_energy_wh,
+        fitness_mean: fitness.mean,
+        fitness_std: fitness.std_dev,
+        fitness_min: fitness.min,
+        fitness_q1: fitness.q1,
+        fitness_median: fitness.median,
+        fitness_q3: fitness.q3,
+        fitness_max: fitness.max,
+        radio_attempts: subsystems.radio.attempts,
+        radio_collision_rate: subsystems.radio.collision_rate(),
+        radio_link_loss_rate: subsystems.radio.link_loss_rate(),
+        radio_delivered: subsystems.radio.delivered,
+        radio_dropped: subsystems.radio.dropped,
+        radio_energy_wh: subsystems.radio.energy_wh,
+        radio_deferred: subsystems.radio.deferred,
+        radio_overflowed: subsystems.radio.overflowed,
+        radio_backlog: subsystems.radio.backlog,
+        radio_uj_per_bit: subsystems.radio.useful_bits().uj_per_bit(),
+        lora_uj_per_bit: subsystems.radio.by_radio[RadioType::LoRa as usize].uj_per_bit(),
+        wifi_uj_per_bit: subsystems.radio.by_radio[RadioType::WiFi as usize].uj_per_bit(),
+        gossip_exchanges: subsystems.gossip.exchanges,
+        gossip_energy_wh: subsystems.gossip.energy_wh,
+        events: subsystems.sensing.events,
+        detections: subsystems.sensing.detections,
+        missed_events: subsystems.sensing.missed,
+        false_negatives: subsystems.sensing.false_negatives,
+        false_alarms: subsystems.sensing.false_alarms,
+        latency_p50_s: latency.map(|p| p.p50),
+        latency_p95_s: latency.map(|p| p.p95),
+        latency_p99_s: latency.map(|p| p.p99),
+        ota_started: subsystems.ota.started,
+        ota_completed: subsystems.ota.completed,
+        ota_aborted: subsystems.ota.aborted,
+        ota_energy_wh: subsystems.ota.energy_wh,
+        legit_requests: subsystems.attack.legit_requests,
+        legit_served: subsystems.attack.legit_served,
+        bogus_requests: subsystems.attack.bogus_requests,
+        bogus_served: subsystems.attack.bogus_served,
+        request_energy_wh: subsystems.attack.energy_wh,
+        misreports: subsystems.trust.misreports,
+        refusals: subsystems.trust.refusals,
+        trust_byzantine,
+        trust_honest,
+        active_catastrophes: catastrophes.active.len(),
+        hardware_failures: subsystems.failure.total_failures(),
+        fleet_cost_usd,
+        embodied_gco2: carbon.embodied_g,
+        operational_wh: carbon.operational_wh,
+        gco2_per_detection: carbon.g_per_detection(),
+        gateway_forwarded: subsystems.gateway.forwarded,
+        backhaul_energy_wh: subsystems.gateway.backhaul_energy_wh,
+        reports_stored: subsystems.storage.stored,
+        reports_flushed: subsystems.storage.flushed,
+        storage_overflowed: subsystems.storage.overflowed,
+        storage_energy_wh: subsystems.storage.energy_wh,
+        storage_peak_wear: subsystems.storage.peak_wear,
+        compressed_reports: subsystems.compression.compressed_reports,
+        compression_energy_wh: subsystems.compression.cpu_energy_wh,
+        mean_awake_hours,
+        forecast_mae_w: subsystems.forecast.mean_abs_error_w(),
+        throttled_fraction: subsystems.thermal.throttled_fraction(),
+        peak_temp_c: subsystems.thermal.peak_c,
+        mean_battery_health,
+        maintenance_visits: subsystems.maintenance.visits,
+        battery_swaps: subsystems.maintenance.battery_swaps,
+        maintenance_cost_usd: subsystems.maintenance.cost_usd,
+        mean_solar_factor: subsystems.weather.mean_solar_factor(),
+        longest_drought_h: subsystems.weather.longest_drought_h,
+        day_of_year: calendar.day_of_year(),
+    };
+
+    // Survivor snapshot, for the snapshot files and the metric collectors
+    let survivors: Vec<SnapshotRow> =
+        if exporters.snapshots.enabled() || !exporters.collectors.is_empty() {
+            query
+                .iter()
+                .filter(|node| *node.status != Status::Dead)
+                .map(
+                    |EpochNodeItem {
+                         score,
+                         gene,
+                         battery,
+                         hardware,
+                         lineage,
+                         tuned,
+                         sensing,
+                         radio,
+                         ..
+                     }| {
+                        SnapshotRow {
+                            epoch: epoch_count.0,
+                            node_id: lineage.id,
+                            parent_id: lineage.parent,
+                            model: gene.model_type.name(),
+                            policy: gene.policy.name(),
+                            inference_frequency: gene.inference_frequency,
+                            solar_efficiency_factor: gene.solar_efficiency_factor,
+                            tx_power_dbm: gene.tx_power_dbm,
+                            compress_reports: gene.compress_reports,
+                            wake_schedule: gene.wake_schedule.to_string(),
+                            battery_threshold: gene.policy_params.battery_threshold,
+                            tuned_battery_threshold: tuned.0.battery_threshold,
+                            request_rate_limit: gene.policy_params.request_rate_limit,
+                            tx_battery_threshold: gene.policy_params.tx_battery_threshold,
+                            pid_kp: gene.policy_params.pid_kp,
+                            pid_ki: gene.policy_params.pid_ki,
+                            pid_kd: gene.policy_params.pid_kd,
+                            hardware: hardware.name().to_string(),
+                            battery_capacity_wh: hardware.battery_capacity_wh,
+                            fitness: score.0,
+                            battery_wh: battery.wh(),
+                            events: sensing.events,
+                            detections: sensing.detections,
+                            missed_events: sensing.missed,
+                            false_alarms: sensing.false_alarms,
+                            radio_uj_per_bit: radio.useful.uj_per_bit(),
+                        }
+                    },
+                )
+                .collect()
+        } else {
+            Vec::new()
+        };
+    let custom = exporters.collectors.collect(&record, &survivors);
+    for (name, value) in &custom {
+        info!(target: "edgegenesis::epoch", "🧩 {}: {:.3}", name, value);
+    }
+
+    info!(
+        target: "edgegenesis::epoch",
+        population = record.population,
+        survivors = record.survivors,
+        avg_battery_wh = record.avg_battery_wh.wh(),
+        energy_consumed_wh = record.energy_consumed_wh.wh(),
+        energy_harvested_wh = record.energy_harvested_wh.wh(),
+        fitness_mean = record.fitness_mean,
+        fitness_median = record.fitness_median,
+        fitness_max = record.fitness_max,
+        detections = record.detections,
+        missed_events = record.missed_events,
+        false_alarms = record.false_alarms,
+        radio_delivered = record.radio_delivered,
+        radio_dropped = record.radio_dropped,
+        fleet_cost_usd = record.fleet_cost_usd,
+        "📋 Epoch summary"
+    );
+    exporters.metrics.record(&record, &custom);
+    exporters.snapshots.write(epoch_count.0, &survivors);
+    exporters.web.publish(&record);
+    milestones.completed.write(EpochCompleted {
+        epoch: record.epoch,
+        population: record.population,
+        survivors: record.survivors,
+        fitness_mean: record.fitness_mean,
+        fitness_max: record.fitness_max,
+        radio_useful: subsystems.radio.by_radio,
+    });
+
+    let mut survivor_models = std::collections::BTreeMap::new();
+    for node in query.iter() {
+        if *node.status != Status::Dead {
+            *survivor_models
+                .entry(node.gene.model_type.name())
+                .or_default() += 1;
+        }
+    }
+    exporters.report.record(
+        ReportEpoch {
+            epoch: epoch_count.0,
+            population: scores.len(),
+            survivors: survivor_count,
+            fitness_mean: fitness.mean,
+            fitness_max: fitness.max,
+            energy_consumed_wh: metrics.total_energy_consumed.wh(),
+            detections: subsystems.sensing.detections,
+            models: survivor_models,
+        },
+        &policy_dashboard.summaries,
     );
-    println!("⚡ Avg Battery Level: {:.2} Wh", avg_battery);
 
     // Reset epoch metrics
-    metrics.total_energy_consumed = 0.0;
-    metrics.total_energy_harvested = 0.0;
+    metrics.total_energy_consumed = WattHours::ZERO;
+    metrics.total_energy_harvested = WattHours::ZERO;
+    metrics.model_loads = 0;
+    metrics.model_load_energy_wh = WattHours::ZERO;
+    metrics.sensor_wakeups = 0;
+    metrics.sensor_energy_wh = WattHours::ZERO;
+    subsystems.reset();
 
+    let finished_epoch = epoch_count.0;
     epoch_count.0 += 1;
     metrics.generation = epoch_count.0;
 
-    let mut survivors: Vec<(f32, Gene)> = Vec::new();
-    let mut entities_to_despawn = Vec::new();
+    // (fitness, genome, hardware) of every node still alive, and its
+    // lineage id
+    let mut survivors: Vec<(f32, Gene, HardwareSpec)> = Vec::new();
+    let mut survivor_ids: Vec<u64> = Vec::new();
+    // Battery energy the survivors bank for the next generation
+    let mut banked_wh = 0.0;
+    // Entities the next generation takes over, popped in query order
+    let mut recycled = Vec::new();
 
-    for (entity, status, score, gene, _battery) in query.iter() {
-        entities_to_despawn.push(entity);
-        if *status != Status::Dead {
-            survivors.push((score.0, *gene));
+    for node in query.iter() {
+        recycled.push(node.entity);
+        let fitness = node_fitness(&node);
+        lineage_log.record_fitness(node.lineage.id, fitness);
+        if *node.status != Status::Dead {
+            // Lamarckian: what the parent learned becomes the heritable genome
+            let mut heritable = *node.gene;
+            if config.lamarckian {
+                heritable.policy_params = node.tuned.0;
+            }
+            survivors.push((fitness, heritable, node.hardware.clone()));
+            survivor_ids.push(node.lineage.id);
+            banked_wh += node.battery.wh();
         }
     }
 
-    // Despawn all entities
-    for entity in entities_to_despawn {
-        commands.entity(entity).despawn();
+    if let Some(path) = &config.phylogeny_out {
+        lineage_log.write_svg(path, &survivor_ids);
     }
+    lineage_log.advance_epoch();
+    recycled.reverse();
+    let mut placement = Placement::new(recycled);
 
-    if survivors.is_empty() {
-        println!("🔴 EXTINCTION - Reseeding");
-        setup_grid(commands);
+    let Some(selection) = Selection::new(&survivors) else {
+        // Random founders: re-running a seed population that just died out
+        // would only repeat the extinction
+        info!(target: "edgegenesis::evolution", "🔴 EXTINCTION - Reseeding");
+        milestones.extinctions.write(ExtinctionOccurred {
+            epoch: finished_epoch,
+        });
+        spawn_founders(
+            &mut commands,
+            &config,
+            &hardware_catalog,
+            &mut genetic_rng.0,
+            &mut lineage_log,
+            &[],
+            &mut placement,
+        );
+        placement.apply(&mut commands);
         return;
-    }
-
-    // Sort by fitness (descending)
-    survivors.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+    };
+    // Survivors fittest first
+    let ranked: Vec<&(f32, Gene, HardwareSpec)> = selection
+        .ranked()
+        .iter()
+        .map(|&survivor| &survivors[survivor])
+        .collect();
 
-    // Elite selection: top 15%
-    let elite_count = (survivors.len() as f32 * 0.15).ceil() as usize;
-    let elites = &survivors[0..elite_count.max(1)];
+    let best: Vec<EliteRecord> = selection
+        .ranked()
+        .iter()
+        .take(exporters.elite.top())
+        .enumerate()
+        .map(|(i, &survivor)| {
+            let (fitness, gene, hardware) = &survivors[survivor];
+            let id = survivor_ids[survivor];
+            EliteRecord {
+                epoch: finished_epoch,
+                rank: i + 1,
+                fitness: *fitness,
+                node_id: id,
+                labels: lineage_log.labels(id).to_vec(),
+                genome: SavedGenome::new(*gene, hardware),
+            }
+        })
+        .collect();
+    exporters.elite.record(&best);
 
     // --- DETAILED REPORTING START ---
 
     // 1. Dominant Model (Most Common)
     let mut model_counts: std::collections::HashMap<String, usize> =
         std::collections::HashMap::new();
-    for (_, gene) in &survivors {
+    for (_, gene, _) in &ranked {
         *model_counts
             .entry(gene.model_type.name().to_string())
             .or_insert(0) += 1;
@@ -258,18 +1550,18 @@ pub fn genetic_epoch_system(
     let dominant_model = model_counts.iter().max_by_key(|&(_, count)| count).unwrap();
 
     // 2. Elite Model (Highest Accuracy amongst survivors)
-    let best_accuracy_survivor = survivors
+    let best_accuracy_survivor = ranked
         .iter()
-        .max_by_key(|(_, gene)| (gene.model_type.accuracy_percent() * 100.0) as u32)
+        .max_by_key(|(_, gene, _)| (gene.model_type.accuracy_percent() * 100.0) as u32)
         .unwrap();
 
-    // 3. Fittest Model (Longest Survival Duration) - already sorted in elites[0]
-    let fittest_gene = &elites[0].1;
+    // 3. Fittest Model (Longest Survival Duration)
+    let (top_fitness, fittest_gene, _) = selection.fittest();
 
-    println!("📊 Population: {} alive", survivors.len());
+    info!(target: "edgegenesis::evolution", "📊 Population: {} alive", survivors.len());
 
     // Report 1: The "King of the Jungle" (Most Numerous)
-    println!(
+    info!(target: "edgegenesis::evolution",
         "🦁 Dominant Model: {} (Count: {}/{})",
         dominant_model.0,
         dominant_model.1,
@@ -279,114 +1571,187 @@ pub fn genetic_epoch_system(
     // Report 1.5: Dominant Policy
     let mut policy_counts: std::collections::HashMap<String, usize> =
         std::collections::HashMap::new();
-    for (_, gene) in &survivors {
+    for (_, gene, _) in &ranked {
         *policy_counts
             .entry(gene.policy.name().to_string())
             .or_insert(0) += 1;
     }
     if let Some(dom_policy) = policy_counts.iter().max_by_key(|&(_, count)| count) {
-        println!(
+        info!(target: "edgegenesis::evolution",
             "📜 Dominant Policy: {} (Count: {})",
             dom_policy.0, dom_policy.1
         );
     }
 
+    // Report 1.6: Wake schedule consensus (share of survivors awake, 0-9)
+    let timeline: String = (0..24)
+        .map(|hour| {
+            let awake = survivors
+                .iter()
+                .filter(|(_, gene, _)| gene.wake_schedule.is_awake(hour))
+                .count();
+            let level = (awake * 9 + survivors.len() / 2) / survivors.len();
+            char::from_digit(level as u32, 10).unwrap_or('9')
+        })
+        .collect();
+    info!(target: "edgegenesis::evolution", "🌙 Awake by hour (00→23, 0-9): {}", timeline);
+
     // Report 2: The "Scholar" (Highest Accuracy Survivor)
-    println!(
+    info!(target: "edgegenesis::evolution",
         "🧠 Smartest Survivor: {} ({:.1}% acc)",
         best_accuracy_survivor.1.model_type.name(),
         best_accuracy_survivor.1.model_type.accuracy_percent()
     );
 
     // Report 3: The "Survivor" (Top Fitness Score)
-    println!(
+    info!(target: "edgegenesis::evolution",
         "🏆 Top Fitness Specimen: {} (Score: {:.2}s)",
         fittest_gene.model_type.name(),
-        elites[0].0
+        top_fitness
     );
 
-    println!(
+    info!(target: "edgegenesis::evolution",
         "📉 Avg Generation Fitness: {:.2}s",
-        survivors.iter().map(|(f, _)| f).sum::<f32>() / survivors.len() as f32
+        ranked.iter().map(|(f, _, _)| f).sum::<f32>() / survivors.len() as f32
     );
     // --- DETAILED REPORTING END ---
 
-    // Repopulation with mutation
-    let mut rng = rand::rng();
-    let offset = (GRID_SIZE as f32 * GRID_SPACING) / 2.0;
-    let all_models = [
-        crate::models::RealModelType::YOLOv8Nano,
-        crate::models::RealModelType::YOLOv8Small,
-        crate::models::RealModelType::MobileNetV2,
-        crate::models::RealModelType::EfficientNetB0,
-        crate::models::RealModelType::TinyBERT,
-        crate::models::RealModelType::EfficientNetB1,
-        crate::models::RealModelType::MobileNetV3Small,
-        crate::models::RealModelType::DistilBERT,
-    ];
-
-    for x in 0..GRID_SIZE {
-        for y in 0..GRID_SIZE {
-            let parent = &elites[rng.random_range(0..elites.len())].1;
-            let mut new_gene = *parent;
-
-            // Mutation 1: Inference frequency (±10%)
-            new_gene.inference_frequency =
-                (new_gene.inference_frequency + rng.random_range(-0.1..0.1)).clamp(0.1, 1.0);
-
-            // Mutation 1.5: Policy Switch (5% chance)
-            if rng.random_bool(0.05) {
-                new_gene.policy = match rng.random_range(0..3) {
-                    0 => PowerPolicy::Aggressive,
-                    1 => PowerPolicy::Conservative,
-                    _ => PowerPolicy::SmartAdaptive,
-                };
-            }
-
-            // Mutation 2: Solar efficiency (±5%)
-            new_gene.solar_efficiency_factor =
-                (new_gene.solar_efficiency_factor + rng.random_range(-0.05..0.05)).clamp(0.7, 1.3);
-
-            // Mutation 3: Model type (10% chance)
-            if rng.random_bool(0.10) {
-                new_gene.model_type = all_models[rng.random_range(0..all_models.len())];
-            }
-
-            // Assign Random Hardware for new generation
-            let hw_type = match rng.random_range(0..3) {
-                0 => HardwareType::ESP32,
-                1 => HardwareType::JetsonNano,
-                _ => HardwareType::RaspberryPi4,
-            };
-            let new_hardware = HardwareSpec::new(hw_type);
-
-            commands.spawn(EdgeNodeBundle {
-                battery: Battery(new_hardware.battery_capacity_wh * 0.8),
-                gene: new_gene,
-                hardware: new_hardware,
-                survival_score: SurvivalScore(0.0),
-                status: Status::Alive,
-                transform: Transform::from_xyz(
-                    x as f32 * GRID_SPACING - offset,
-                    y as f32 * GRID_SPACING - offset,
-                    0.0,
-                ),
-            });
-        }
+    // Repopulation: the engine breeds, the system places
+    let rng = &mut genetic_rng.0;
+    let positions = fleet_positions(&config, rng);
+    let births =
+        config
+            .breeding(&hardware_catalog)
+            .offspring(&selection, positions.len(), banked_wh, rng);
+    let spawned = births.len();
+    for birth in births {
+        let parent_id = birth.parent.map(|survivor| survivor_ids[survivor]);
+        let lineage = lineage_log.register(parent_id, &birth.gene);
+        placement.place(EdgeNodeBundle::new(
+            birth.gene,
+            birth.hardware,
+            lineage,
+            positions[birth.slot],
+        ));
     }
+    placement.apply(&mut commands);
 
-    println!("✅ New generation spawned ({})", GRID_SIZE * GRID_SIZE);
+    if config.birth_cost.is_some() {
+        info!(target: "edgegenesis::evolution",
+            "🐣 {:.1} Wh banked by {} survivors funded {} births (capacity {})",
+            banked_wh,
+            survivors.len(),
+            spawned,
+            node_positions(&config).len()
+        );
+    }
+    info!(target: "edgegenesis::evolution", "✅ New generation spawned ({})", spawned);
 }
 
 /// Register all systems with Bevy app
 pub fn register_systems(app: &mut App) {
-    app.add_systems(Startup, (setup_camera, setup_grid))
+    app.add_systems(Startup, (setup_tick_rate, setup_camera, setup_grid))
+        .add_systems(Update, speed_control_system.run_if(not(clock_enabled)))
+        .configure_sets(
+            FixedUpdate,
+            (
+                EpochSet::BeforeSelection
+                    .after(mark_dead_system)
+                    .before(genetic_epoch_system)
+                    .run_if(epoch_due),
+                EpochSet::AfterRepopulation
+                    .after(genetic_epoch_system)
+                    .run_if(epoch_due),
+            ),
+        )
         .add_systems(
-            Update,
+            FixedUpdate,
             (
-                resource_physics_system,
-                render_nodes_system,
-                genetic_epoch_system.run_if(on_timer(Duration::from_secs(30))),
+                // Per-tick node pipeline, chained so shared RNG streams are
+                // drawn in a fixed order (reproducible runs)
+                (
+                    catastrophe_system,
+                    weather_system.run_if(weather_enabled),
+                    soiling_system.run_if(soiling_enabled),
+                    maintenance_system.run_if(maintenance_enabled),
+                    resource_physics_system,
+                    calendar_system,
+                    thermal_system,
+                    forecast_system,
+                    hardware_failure_system,
+                    assign_byzantine_system,
+                    sensing_system,
+                    gossip_system.run_if(gossip_enabled),
+                    request_system.run_if(attack_enabled),
+                    randomize_report_phase_system,
+                    storage_system,
+                    radio_system,
+                    gateway_backhaul_system.run_if(gateways_enabled),
+                    ota_transfer_system.run_if(ota_enabled),
+                    mark_dead_system,
+                )
+                    .chain(),
+                fit_panels_system
+                    .before(resource_physics_system)
+                    .run_if(panel_configured),
+                epoch_clock_system
+                    .before(EpochSet::BeforeSelection)
+                    .before(genetic_epoch_system)
+                    .run_if(not(warming_up)),
+                // After the clock, so the tick that ends the warm-up is not
+                // counted towards the first epoch
+                warm_up_system
+                    .after(mark_dead_system)
+                    .after(epoch_clock_system)
+                    .before(EpochSet::BeforeSelection)
+                    .run_if(warming_up),
+                genetic_epoch_system.run_if(epoch_due),
+                // After the epoch's despawn/respawn is applied, so campaigns
+                // target the new generation
+                ota_campaign_system
+                    .after(genetic_epoch_system)
+                    .before(ota_transfer_system)
+                    .run_if(ota_enabled),
+                // Reads this epoch's EpochCompleted and sets the next environment
+                coevolution_system
+                    .after(genetic_epoch_system)
+                    .run_if(coevolution_enabled),
+                extinction_watch_system
+                    .after(hardware_failure_system)
+                    .before(genetic_epoch_system),
+                extinction_report_system.after(genetic_epoch_system),
+                timeline_system
+                    .after(resource_physics_system)
+                    .before(genetic_epoch_system)
+                    .run_if(timeline_enabled),
+                max_epochs_system
+                    .after(genetic_epoch_system)
+                    .run_if(max_epochs_set),
             ),
+        )
+        .add_systems(Update, node_spawned_system)
+        .add_systems(
+            Startup,
+            setup_adversary_system
+                .after(setup_grid)
+                .run_if(coevolution_enabled),
+        )
+        .add_systems(Update, curriculum_log_system.run_if(curriculum_enabled))
+        .add_systems(Update, useful_bits_report_system)
+        .add_systems(Last, sizing_report_system)
+        .add_systems(Startup, spawn_attackers_system.run_if(attack_enabled))
+        .add_systems(Update, render_attackers_system.run_if(attack_enabled))
+        .add_systems(Update, render_catastrophes_system)
+        .add_systems(EguiPrimaryContextPass, policy_dashboard_ui_system)
+        .add_systems(Startup, pause_for_grpc_system.run_if(grpc_enabled))
+        .add_systems(Update, grpc_control_system.run_if(grpc_enabled))
+        .add_systems(Startup, spawn_gateways_system.run_if(gateways_enabled))
+        .add_systems(Update, render_gateways_system.run_if(gateways_enabled))
+        .add_systems(Startup, spawn_technician_system.run_if(maintenance_enabled))
+        .add_systems(Update, render_technician_system.run_if(maintenance_enabled))
+        .add_systems(Startup, setup_debug_readout.run_if(debug_node_enabled))
+        .add_systems(
+            Update,
+            render_debug_gauges_system.run_if(debug_node_enabled),
         );
 }
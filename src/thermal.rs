@@ -0,0 +1,159 @@
+/// Thermal model with throttling
+/// Each node's die temperature relaxes towards a steady state set by the
+/// ambient air (hourly profile), its power draw through the enclosure's
+/// thermal resistance, and direct sun on the box (irradiance × clouds ×
+/// exposure).
+/// The approach is first-order with the hardware's time constant, so a
+/// short inference burst barely warms a node while a sunny afternoon of
+/// continuous inference cooks it. Above the hardware's throttle point the
+/// governor cuts clocks: the throttle factor drops linearly to
+/// `MIN_THROTTLE` over `THROTTLE_SPAN_C`, every inference takes 1/factor as
+/// long, and the node completes that many fewer inferences per hour. Power
+/// draw is unchanged, so a throttled node pays the same for less work.
+use bevy::prelude::*;
+
+use crate::catastrophe::SolarExposure;
+use crate::components::{
+    Dead, LoadedAmbientProfiles, LoadedSolarProfiles, SimulationMetrics, Status, Telemetry,
+};
+use crate::data_loader::AmbientProfile;
+use crate::hardware::HardwareSpec;
+use crate::models::RealModelType;
+use crate::speed::SimTime;
+use crate::units::SECONDS_PER_HOUR;
+use crate::weather::Weather;
+
+// Ambient temperature without profile data, shared with the engine
+pub use crate::hardware::DEFAULT_AMBIENT_C;
+
+/// Enclosure heating by direct sun (°C per W/m² at full exposure)
+pub const SOLAR_HEATING_C_PER_W_M2: f32 = 0.02;
+
+/// Degrees above the throttle point where clocks reach their floor
+pub const THROTTLE_SPAN_C: f32 = 10.0;
+
+/// Lowest throttle factor (fraction of full compute)
+pub const MIN_THROTTLE: f32 = 0.5;
+
+/// Junction limit: thermal protection holds a die here (°C), e.g. an ESP32
+/// evolved onto a multi-watt model
+pub const MAX_DIE_C: f32 = 125.0;
+
+/// Die temperature of a node and the compute it currently gets
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Temperature {
+    pub celsius: f32,
+    /// Fraction of full compute (1 = not throttled)
+    pub throttle: f32,
+}
+
+impl Default for Temperature {
+    fn default() -> Self {
+        Self {
+            celsius: DEFAULT_AMBIENT_C,
+            throttle: 1.0,
+        }
+    }
+}
+
+impl Temperature {
+    /// Throttle factor of a die at `celsius` on `hardware`
+    pub fn throttle_at(celsius: f32, hardware: &HardwareSpec) -> f32 {
+        let over = (celsius - hardware.throttle_temp_c) / THROTTLE_SPAN_C;
+        (1.0 - over.clamp(0.0, 1.0) * (1.0 - MIN_THROTTLE)).max(MIN_THROTTLE)
+    }
+
+    pub fn throttled(&self) -> bool {
+        self.throttle < 1.0
+    }
+
+    /// Time one inference of `model` takes on `hardware` at the current
+    /// clocks (ms)
+    pub fn inference_time_ms(&self, model: RealModelType, hardware: &HardwareSpec) -> f32 {
+        hardware.inference_time_ms(model) / self.throttle
+    }
+
+    /// Extra response delay throttling adds to one inference (seconds)
+    pub fn throttle_delay_s(&self, model: RealModelType, hardware: &HardwareSpec) -> f32 {
+        (self.inference_time_ms(model, hardware) - hardware.inference_time_ms(model)) / 1000.0
+    }
+}
+
+/// Per-epoch thermal statistics
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ThermalMetrics {
+    /// Alive node-hours observed
+    pub node_hours: f32,
+    /// Of those, hours spent throttled
+    pub throttled_hours: f32,
+    /// Hottest die seen (°C)
+    pub peak_c: f32,
+}
+
+impl ThermalMetrics {
+    /// Fraction of alive node-hours spent throttled
+    pub fn throttled_fraction(&self) -> f32 {
+        if self.node_hours > 0.0 {
+            self.throttled_hours / self.node_hours
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Ambient temperature for an hour of the day
+pub fn ambient_c(profiles: &[AmbientProfile], hour: usize) -> f32 {
+    profiles
+        .get(hour)
+        .map_or(DEFAULT_AMBIENT_C, |p| p.ambient_c)
+}
+
+/// Thermal system - relax each node towards its steady-state temperature
+/// Runs after physics so `Telemetry::power_draw_w` is this tick's draw
+pub fn thermal_system(
+    time: SimTime,
+    metrics: Res<SimulationMetrics>,
+    ambient_profiles: Res<LoadedAmbientProfiles>,
+    solar_profiles: Res<LoadedSolarProfiles>,
+    weather: Res<Weather>,
+    mut thermal_metrics: ResMut<ThermalMetrics>,
+    mut query: Query<
+        (
+            &mut Temperature,
+            &Telemetry,
+            &HardwareSpec,
+            &SolarExposure,
+            &Status,
+        ),
+        Without<Dead>,
+    >,
+) {
+    let window_s = time.window_s();
+    let hour = metrics.current_hour as usize % 24;
+    let ambient = ambient_c(&ambient_profiles.0, hour);
+    let irradiance = solar_profiles
+        .0
+        .get(hour)
+        .map_or(0.0, |p| p.avg_irradiance_w_m2)
+        * weather.solar_factor();
+
+    for (mut temperature, telemetry, hardware, exposure, status) in query.iter_mut() {
+        let alive = *status != Status::Dead;
+        let draw_w = if alive { telemetry.power_draw_w } else { 0.0 };
+        let steady_c = ambient
+            + hardware.thermal_resistance_c_per_w * draw_w
+            + SOLAR_HEATING_C_PER_W_M2 * irradiance * exposure.0;
+        let blend = 1.0 - (-window_s / hardware.thermal_time_constant_s).exp();
+        temperature.celsius += (steady_c.min(MAX_DIE_C) - temperature.celsius) * blend;
+        temperature.throttle = Temperature::throttle_at(temperature.celsius, hardware);
+
+        if alive {
+            let hours = window_s / SECONDS_PER_HOUR;
+            thermal_metrics.node_hours += hours;
+            if temperature.throttled() {
+                thermal_metrics.throttled_hours += hours;
+            }
+            thermal_metrics.peak_c = thermal_metrics.peak_c.max(temperature.celsius);
+        }
+    }
+}
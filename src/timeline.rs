@@ -0,0 +1,168 @@
+/// Multi-resolution fleet metrics
+/// Three recording tiers, each switched on by its own flag: per epoch
+/// (`--metrics-out`, see export.rs), per simulated hour
+/// (`--metrics-hourly-out <path>`) and per tick (`--metrics-tick-out
+/// <path>`). The two finer tiers record fleet-level state (alive nodes,
+/// battery, panel input, draw, nodes inferring) and the physics energy
+/// moved since the previous row, so one run can be debugged tick by tick
+/// while sweeps keep only the per-epoch rows.
+use bevy::prelude::*;
+use serde::Serialize;
+use std::fs::File;
+
+use crate::components::{Battery, Dead, EpochCount, Status, Telemetry};
+use crate::config::SimConfig;
+use crate::export::open_csv;
+use crate::speed::SimTime;
+use crate::units::Power;
+
+/// One row of the tick or hourly CSV
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineRow {
+    /// Simulated hours since the run started (start of the hour when
+    /// aggregated)
+    pub elapsed_h: f32,
+    pub epoch: u32,
+    /// Ticks folded into this row (1 per tick)
+    pub ticks: u32,
+    /// Mean over the row's ticks
+    pub alive: f32,
+    pub avg_battery_wh: f32,
+    /// Fleet panel input and draw (W)
+    pub solar_input_w: f32,
+    pub power_draw_w: f32,
+    pub inferring: f32,
+    /// Physics energy moved during the row (Wh)
+    pub harvested_wh: f32,
+    pub consumed_wh: f32,
+}
+
+impl TimelineRow {
+    /// Fold another tick of the same hour into running means and sums
+    fn merge(&mut self, other: &TimelineRow) {
+        let n = self.ticks as f32;
+        let mean = |a: f32, b: f32| (a * n + b) / (n + 1.0);
+        self.alive = mean(self.alive, other.alive);
+        self.avg_battery_wh = mean(self.avg_battery_wh, other.avg_battery_wh);
+        self.solar_input_w = mean(self.solar_input_w, other.solar_input_w);
+        self.power_draw_w = mean(self.power_draw_w, other.power_draw_w);
+        self.inferring = mean(self.inferring, other.inferring);
+        self.harvested_wh += other.harvested_wh;
+        self.consumed_wh += other.consumed_wh;
+        self.ticks += 1;
+    }
+}
+
+/// Optional tick and hourly CSV sinks
+#[derive(Resource, Default)]
+pub struct TimelineRecorder {
+    tick: Option<csv::Writer<File>>,
+    hourly: Option<csv::Writer<File>>,
+    /// Simulated hours since the run started
+    elapsed_h: f32,
+    /// Simulated hour being aggregated
+    open_hour: Option<TimelineRow>,
+}
+
+impl TimelineRecorder {
+    /// Open the enabled tiers; a tier is disabled (with a warning) when its
+    /// file cannot be created
+    pub fn create(tick_path: Option<&str>, hourly_path: Option<&str>) -> Self {
+        let open = |path: Option<&str>, tier: &str| {
+            path.and_then(|path| match open_csv(path) {
+                Ok(writer) => {
+                    info!(target: "edgegenesis::timeline",
+                        "📝 Exporting {} fleet metrics to {}", tier, path
+                    );
+                    Some(writer)
+                }
+                Err(e) => {
+                    warn!(target: "edgegenesis::timeline",
+                        "⚠️ Cannot create {} metrics file {}: {}", tier, path, e
+                    );
+                    None
+                }
+            })
+        };
+        Self {
+            tick: open(tick_path, "per-tick"),
+            hourly: open(hourly_path, "hourly"),
+            ..Default::default()
+        }
+    }
+
+    /// Write one tick's row, closing the hourly row when the hour turns
+    fn record(&mut self, row: TimelineRow) {
+        if let Some(writer) = self.tick.as_mut() {
+            write_row(writer, &row, "Tick");
+        }
+        if self.hourly.is_none() {
+            return;
+        }
+
+        let hour = row.elapsed_h.floor();
+        match self.open_hour.as_mut() {
+            Some(open) if open.elapsed_h == hour => open.merge(&row),
+            _ => {
+                let closed = self.open_hour.replace(TimelineRow {
+                    elapsed_h: hour,
+                    ..row
+                });
+                if let (Some(closed), Some(writer)) = (closed, self.hourly.as_mut()) {
+                    write_row(writer, &closed, "Hourly");
+                }
+            }
+        }
+    }
+}
+
+fn write_row(writer: &mut csv::Writer<File>, row: &TimelineRow, tier: &str) {
+    if let Err(e) = writer.serialize(row).and_then(|_| Ok(writer.flush()?)) {
+        warn!(target: "edgegenesis::timeline", "⚠️ {} metrics export failed: {}", tier, e);
+    }
+}
+
+/// Run condition: true when a tick or hourly tier is configured
+pub fn timeline_enabled(config: Res<SimConfig>) -> bool {
+    config.metrics_tick_out.is_some() || config.metrics_hourly_out.is_some()
+}
+
+/// Sample the fleet after the physics step
+pub fn timeline_system(
+    time: SimTime,
+    epoch_count: Res<EpochCount>,
+    mut recorder: ResMut<TimelineRecorder>,
+    nodes: Query<(&Battery, &Status, &Telemetry), Without<Dead>>,
+) {
+    let hours = time.window_h();
+    recorder.elapsed_h += hours;
+
+    let mut row = TimelineRow {
+        elapsed_h: recorder.elapsed_h,
+        epoch: epoch_count.0,
+        ticks: 1,
+        alive: 0.0,
+        avg_battery_wh: 0.0,
+        solar_input_w: 0.0,
+        power_draw_w: 0.0,
+        inferring: 0.0,
+        harvested_wh: 0.0,
+        consumed_wh: 0.0,
+    };
+    for (battery, status, telemetry) in nodes.iter() {
+        if *status == Status::Dead {
+            continue;
+        }
+        row.alive += 1.0;
+        row.avg_battery_wh += battery.wh();
+        row.solar_input_w += telemetry.solar_input_w;
+        row.power_draw_w += telemetry.power_draw_w;
+        if telemetry.inferring {
+            row.inferring += 1.0;
+        }
+    }
+    row.avg_battery_wh /= row.alive.max(1.0);
+    row.harvested_wh = Power::from_watts(row.solar_input_w).over_h(hours).wh();
+    row.consumed_wh = Power::from_watts(row.power_draw_w).over_h(hours).wh();
+    recorder.record(row);
+}
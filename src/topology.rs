@@ -0,0 +1,111 @@
+/// Spatial topology of the sensor grid
+/// Neighborhoods and distances for every spatial effect (mesh links, radio
+/// interference, gossip) go through `Topology`, so switching the grid to a
+/// torus removes the structural disadvantage of edge and corner nodes,
+/// which otherwise have 3-5 neighbors instead of 8.
+use bevy::prelude::*;
+
+pub const GRID_SIZE: i32 = 10;
+pub const GRID_SPACING: f32 = 50.0;
+
+/// Boundary condition of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TopologyKind {
+    /// Hard edges: cells outside the grid do not exist
+    #[default]
+    Bounded,
+    /// Wrap-around in both axes (periodic boundary conditions)
+    Torus,
+}
+
+/// Grid geometry resource
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct Topology {
+    pub kind: TopologyKind,
+    /// Cells per side
+    pub size: i32,
+    /// World units between neighboring cells
+    pub spacing: f32,
+}
+
+impl Topology {
+    pub fn new(kind: TopologyKind) -> Self {
+        Self {
+            kind,
+            size: GRID_SIZE,
+            spacing: GRID_SPACING,
+        }
+    }
+
+    /// World offset that centers the grid on the origin
+    fn offset(&self) -> f32 {
+        (self.size as f32 * self.spacing) / 2.0
+    }
+
+    /// World position of a cell's center
+    pub fn world_position(&self, cell: IVec2) -> Vec2 {
+        Vec2::new(
+            cell.x as f32 * self.spacing - self.offset(),
+            cell.y as f32 * self.spacing - self.offset(),
+        )
+    }
+
+    /// Nearest cell to a world position (clamped / wrapped into the grid)
+    pub fn cell_of(&self, position: Vec2) -> IVec2 {
+        let raw = ((position + Vec2::splat(self.offset())) / self.spacing)
+            .round()
+            .as_ivec2();
+        match self.kind {
+            TopologyKind::Bounded => raw.clamp(IVec2::ZERO, IVec2::splat(self.size - 1)),
+            TopologyKind::Torus => raw.rem_euclid(IVec2::splat(self.size)),
+        }
+    }
+
+    /// Moore neighborhood of `cell` within Chebyshev `radius` (excluding itself)
+    /// Bounded grids drop out-of-range cells; tori wrap them around
+    pub fn neighbors(&self, cell: IVec2, radius: i32) -> Vec<IVec2> {
+        let mut cells = Vec::new();
+        for dx in -radius..=radius {
+            for dy in -radius..=radius {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let candidate = cell + IVec2::new(dx, dy);
+                match self.kind {
+                    TopologyKind::Bounded => {
+                        if candidate.cmpge(IVec2::ZERO).all()
+                            && candidate.cmplt(IVec2::splat(self.size)).all()
+                        {
+                            cells.push(candidate);
+                        }
+                    }
+                    TopologyKind::Torus => {
+                        let wrapped = candidate.rem_euclid(IVec2::splat(self.size));
+                        // Small tori can reach the same cell twice
+                        if wrapped != cell && !cells.contains(&wrapped) {
+                            cells.push(wrapped);
+                        }
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Displacement from `a` to `b` in world units
+    /// On a torus this is the minimum-image vector (shortest way around)
+    pub fn delta(&self, a: Vec2, b: Vec2) -> Vec2 {
+        let mut d = b - a;
+        if self.kind == TopologyKind::Torus {
+            let period = self.size as f32 * self.spacing;
+            d.x -= period * (d.x / period).round();
+            d.y -= period * (d.y / period).round();
+        }
+        d
+    }
+
+    /// Euclidean distance respecting the boundary condition
+    pub fn distance(&self, a: Vec2, b: Vec2) -> f32 {
+        self.delta(a, b).length()
+    }
+}
@@ -0,0 +1,404 @@
+/// Seed-fair tournament between two builds
+/// `EdgeGenesis tournament` runs the same seeds and simulation flags through
+/// two builds of the crate, e.g. before and after a physics change or with
+/// and without a feature, and reports where their behavior diverges. A
+/// contender is a binary path or `features:<list>`, which is built here with
+/// `cargo build --release --features <list>` into its own target directory
+/// (`features:` alone = default features). Runs use the headless engine
+/// (`--summary-out`), or with `--ecs` the full simulation (`--max-epochs`,
+/// `--metrics-out`). Every column the two per-epoch CSVs share is compared:
+/// the first epoch whose relative difference exceeds `--tolerance` and the
+/// largest difference seen. Same-seed runs of one build are identical, so
+/// any divergence comes from the change under test. Each seed is also a
+/// match, won by the higher final `--score` column (default `fitness_mean`).
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::export::open_csv;
+use crate::sweep::parse_seeds;
+
+/// One side of the tournament
+#[derive(Debug, Clone)]
+enum Contender {
+    /// A compiled EdgeGenesis binary
+    Binary(PathBuf),
+    /// This crate built with these features (comma-separated, may be empty)
+    Features(String),
+}
+
+impl Contender {
+    fn parse(text: &str) -> Self {
+        match text.strip_prefix("features:") {
+            Some(features) => Contender::Features(features.to_string()),
+            None => Contender::Binary(PathBuf::from(text)),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Contender::Binary(path) => path.display().to_string(),
+            Contender::Features(features) if features.is_empty() => "default features".into(),
+            Contender::Features(features) => format!("features {}", features),
+        }
+    }
+
+    /// Path of the binary, building it first when given as features
+    fn binary(&self) -> Result<PathBuf, Box<dyn Error>> {
+        let features = match self {
+            Contender::Binary(path) => return Ok(path.clone()),
+            Contender::Features(features) => features,
+        };
+        let dir_name = if features.is_empty() {
+            "default".to_string()
+        } else {
+            features.replace(',', "+")
+        };
+        let target_dir = Path::new("target").join("tournament").join(dir_name);
+        println!("🔨 Building {} into {}", self.name(), target_dir.display());
+        let mut command = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".into()));
+        command
+            .args(["build", "--release", "--target-dir"])
+            .arg(&target_dir);
+        if !features.is_empty() {
+            command.args(["--features", features]);
+        }
+        if !command.status()?.success() {
+            return Err(format!("build with {} failed", self.name()).into());
+        }
+        Ok(target_dir
+            .join("release")
+            .join(format!("EdgeGenesis{}", std::env::consts::EXE_SUFFIX)))
+    }
+}
+
+/// Per-epoch CSV of one run
+struct RunTable {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl RunTable {
+    fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut reader = csv::Reader::from_path(path)?;
+        let columns = reader.headers()?.iter().map(str::to_string).collect();
+        let rows = reader
+            .records()
+            .map(|record| record.map(|record| record.iter().map(str::to_string).collect()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { columns, rows })
+    }
+
+    fn column(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column == name)
+    }
+
+    /// Value of `column` in the last epoch
+    fn final_value(&self, column: &str) -> Option<f64> {
+        let index = self.column(column)?;
+        self.rows.last()?.get(index)?.parse().ok()
+    }
+
+    /// Epoch number of a row (its index without an `epoch` column)
+    fn epoch_of(&self, row: usize) -> String {
+        self.column("epoch")
+            .and_then(|index| self.rows.get(row)?.get(index).cloned())
+            .unwrap_or_else(|| row.to_string())
+    }
+}
+
+/// Relative difference of two cells; text cells differ by 0 or 1
+fn relative_difference(a: &str, b: &str) -> f64 {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) if a == b => 0.0,
+        (Ok(a), Ok(b)) => (a - b).abs() / a.abs().max(b.abs()),
+        _ if a == b => 0.0,
+        _ => 1.0,
+    }
+}
+
+/// How one shared column of the two runs compares
+struct ColumnDivergence {
+    column: String,
+    /// First row beyond the tolerance (rows past the shorter run count)
+    first_row: Option<usize>,
+    max_relative: f64,
+}
+
+/// Compare every column the two runs share
+fn compare(a: &RunTable, b: &RunTable, tolerance: f64) -> Vec<ColumnDivergence> {
+    let shared_rows = a.rows.len().min(b.rows.len());
+    a.columns
+        .iter()
+        .enumerate()
+        .filter_map(|(index_a, column)| Some((index_a, b.column(column)?, column)))
+        .map(|(index_a, index_b, column)| {
+            let mut divergence = ColumnDivergence {
+                column: column.clone(),
+                first_row: None,
+                max_relative: 0.0,
+            };
+            for row in 0..shared_rows {
+                let cell_a = a.rows[row].get(index_a).map_or("", String::as_str);
+                let cell_b = b.rows[row].get(index_b).map_or("", String::as_str);
+                let relative = relative_difference(cell_a, cell_b);
+                divergence.max_relative = divergence.max_relative.max(relative);
+                if relative > tolerance && divergence.first_row.is_none() {
+                    divergence.first_row = Some(row);
+                }
+            }
+            if a.rows.len() != b.rows.len() && divergence.first_row.is_none() {
+                divergence.first_row = Some(shared_rows);
+            }
+            divergence
+        })
+        .collect()
+}
+
+/// Run settings shared by both contenders
+struct Settings {
+    epochs: u32,
+    ecs: bool,
+    sim_args: Vec<String>,
+}
+
+/// Run one seed on one binary and read its per-epoch CSV
+fn run_seed(
+    binary: &Path,
+    settings: &Settings,
+    seed: u64,
+    out: &Path,
+) -> Result<RunTable, Box<dyn Error>> {
+    let mut command = Command::new(binary);
+    if settings.ecs {
+        command.arg("--max-epochs").arg(settings.epochs.to_string());
+        command.arg("--metrics-out").arg(out);
+    } else {
+        command.arg("headless");
+        command.arg("--epochs").arg(settings.epochs.to_string());
+        command.arg("--summary-out").arg(out);
+    }
+    command
+        .arg("--seed")
+        .arg(seed.to_string())
+        .args(&settings.sim_args)
+        .stdout(Stdio::null());
+    let status = command.status()?;
+    if !status.success() {
+        return Err(format!(
+            "{} exited with {} on seed {}",
+            binary.display(),
+            status,
+            seed
+        )
+        .into());
+    }
+    RunTable::read(out)
+}
+
+/// `tournament --a <binary|features:<list>> --b <binary|features:<list>>
+/// [--seeds <a-b|a,b,..>] [--epochs <n>] [--ecs] [--tolerance <relative>]
+/// [--score <column>] [--out <csv>] [--expect-identical]
+/// [-- <simulation flags>]`
+pub fn run(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut contender_a = None;
+    let mut contender_b = None;
+    let mut seeds = vec![1, 2, 3];
+    let mut tolerance = 0.0f64;
+    let mut score = String::from("fitness_mean");
+    let mut out = None;
+    let mut expect_identical = false;
+    let mut settings = Settings {
+        epochs: 20,
+        ecs: false,
+        sim_args: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--a" => contender_a = Some(Contender::parse(&value()?)),
+            "--b" => contender_b = Some(Contender::parse(&value()?)),
+            "--seeds" => seeds = parse_seeds(&value()?)?,
+            "--epochs" => settings.epochs = value()?.parse()?,
+            "--ecs" => settings.ecs = true,
+            "--tolerance" => tolerance = value()?.parse()?,
+            "--score" => score = value()?,
+            "--out" => out = Some(value()?),
+            "--expect-identical" => expect_identical = true,
+            "--" => {
+                settings.sim_args = args.by_ref().collect();
+                break;
+            }
+            other => return Err(format!("unknown tournament option {}", other).into()),
+        }
+    }
+    let contender_a = contender_a.ok_or("missing --a <binary|features:<list>>")?;
+    let contender_b = contender_b.ok_or("missing --b <binary|features:<list>>")?;
+    let binary_a = contender_a.binary()?;
+    let binary_b = contender_b.binary()?;
+
+    println!(
+        "⚔️ Tournament: A = {} vs B = {}, {} seeds × {} epochs ({})",
+        contender_a.name(),
+        contender_b.name(),
+        seeds.len(),
+        settings.epochs,
+        if settings.ecs {
+            "full simulation"
+        } else {
+            "headless engine"
+        }
+    );
+    let dir = std::env::temp_dir().join(format!("edgegenesis_tournament_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let mut writer = out.as_deref().map(open_csv).transpose()?;
+    if let Some(writer) = &mut writer {
+        writer.write_record([
+            "seed",
+            "column",
+            "first_divergent_epoch",
+            "max_relative_difference",
+            "final_a",
+            "final_b",
+        ])?;
+    }
+
+    let (mut wins_a, mut wins_b, mut diverged) = (0, 0, 0);
+    // Largest difference of each column over all seeds
+    let mut worst: Vec<(String, f64)> = Vec::new();
+    for &seed in &seeds {
+        let a = run_seed(
+            &binary_a,
+            &settings,
+            seed,
+            &dir.join(format!("a_{}.csv", seed)),
+        )?;
+        let b = run_seed(
+            &binary_b,
+            &settings,
+            seed,
+            &dir.join(format!("b_{}.csv", seed)),
+        )?;
+        let mut divergences = compare(&a, &b, tolerance);
+
+        let (final_a, final_b) = (a.final_value(&score), b.final_value(&score));
+        let verdict = match (final_a, final_b) {
+            (Some(fa), Some(fb)) if fa > fb => {
+                wins_a += 1;
+                "A"
+            }
+            (Some(fa), Some(fb)) if fb > fa => {
+                wins_b += 1;
+                "B"
+            }
+            (Some(_), Some(_)) => "tie",
+            _ => "no score",
+        };
+        let format_score = |value: Option<f64>| value.map_or("-".into(), |v| format!("{:.2}", v));
+        let match_line = format!(
+            "A {} vs B {} {} → {}",
+            format_score(final_a),
+            format_score(final_b),
+            score,
+            verdict
+        );
+
+        if let Some(writer) = &mut writer {
+            for divergence in &divergences {
+                let final_cell = |table: &RunTable| {
+                    table
+                        .final_value(&divergence.column)
+                        .map_or(String::new(), |v| v.to_string())
+                };
+                writer.write_record([
+                    seed.to_string(),
+                    divergence.column.clone(),
+                    divergence
+                        .first_row
+                        .map_or(String::new(), |row| a.epoch_of(row)),
+                    divergence.max_relative.to_string(),
+                    final_cell(&a),
+                    final_cell(&b),
+                ])?;
+            }
+        }
+        for divergence in &divergences {
+            match worst
+                .iter_mut()
+                .find(|(column, _)| *column == divergence.column)
+            {
+                Some((_, max)) => *max = max.max(divergence.max_relative),
+                None => worst.push((divergence.column.clone(), divergence.max_relative)),
+            }
+        }
+
+        let first = divergences
+            .iter()
+            .filter_map(|divergence| Some((divergence.first_row?, &divergence.column)))
+            .min();
+        let Some((row, column)) = first else {
+            println!(
+                "🎲 seed {}: identical over {} epochs | {}",
+                seed,
+                a.rows.len(),
+                match_line
+            );
+            continue;
+        };
+        diverged += 1;
+        println!(
+            "🎲 seed {}: diverges at epoch {} ({}{}) | {}",
+            seed,
+            a.epoch_of(row),
+            column,
+            if a.rows.len() != b.rows.len() {
+                format!(", {} vs {} epochs", a.rows.len(), b.rows.len())
+            } else {
+                String::new()
+            },
+            match_line
+        );
+        divergences.sort_by(|x, y| y.max_relative.total_cmp(&x.max_relative));
+        let largest: Vec<String> = divergences
+            .iter()
+            .take(3)
+            .filter(|divergence| divergence.max_relative > tolerance)
+            .map(|divergence| {
+                format!(
+                    "{} {:.2}%",
+                    divergence.column,
+                    divergence.max_relative * 100.0
+                )
+            })
+            .collect();
+        println!("   largest differences: {}", largest.join(", "));
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+    if let Some(writer) = &mut writer {
+        writer.flush()?;
+    }
+
+    println!(
+        "🏆 A won {}, B won {}, {} tied on {} | {}/{} seeds diverged",
+        wins_a,
+        wins_b,
+        seeds.len() - wins_a - wins_b,
+        score,
+        diverged,
+        seeds.len()
+    );
+    worst.sort_by(|x, y| y.1.total_cmp(&x.1));
+    let columns: Vec<String> = worst
+        .iter()
+        .filter(|(_, max)| *max > tolerance)
+        .map(|(column, max)| format!("{} {:.2}%", column, max * 100.0))
+        .collect();
+    if !columns.is_empty() {
+        println!("   diverging columns: {}", columns.join(", "));
+    }
+    if expect_identical && diverged > 0 {
+        return Err(format!("{} of {} seeds diverged", diverged, seeds.len()).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+/// Accuracy / energy tradeoff explorer
+/// A window plotting every model's benchmark accuracy against its energy
+/// per inference (inference power × latency), the point area growing with
+/// the model's size. After each epoch the share of the new
+/// generation carrying each model is counted: points fade with their share
+/// and the dominant models (at least `DOMINANT_SHARE` of the population,
+/// or the most common one) are ringed and highlighted, showing which corner
+/// of the tradeoff the population is converging toward.
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass, egui};
+
+use crate::components::{EpochCount, Gene};
+use crate::models::RealModelType;
+use crate::systems::EpochSet;
+
+/// Population share from which a model counts as dominant
+pub const DOMINANT_SHARE: f32 = 0.25;
+
+/// Plot area of the window (points)
+const PLOT_SIZE: egui::Vec2 = egui::vec2(360.0, 240.0);
+
+/// Point radius of the smallest and largest model (points)
+const RADIUS_RANGE: (f32, f32) = (4.0, 14.0);
+
+/// Share of the current generation carrying each model, indexed by
+/// `RealModelType as usize`
+#[derive(Resource, Debug, Default)]
+pub struct TradeoffView {
+    /// Epoch the shares were counted at (None before the first epoch)
+    pub epoch: Option<u32>,
+    pub shares: [f32; RealModelType::ALL.len()],
+}
+
+impl TradeoffView {
+    /// Whether `model` carries at least `DOMINANT_SHARE` of the population
+    /// or is its most common model
+    pub fn dominant(&self, model: RealModelType) -> bool {
+        let share = self.shares[model as usize];
+        let top = self.shares.iter().copied().fold(0.0, f32::max);
+        share > 0.0 && (share >= DOMINANT_SHARE || share == top)
+    }
+}
+
+/// Share of `genes` carrying each model (all zero for no genes)
+pub fn model_shares<'a>(genes: impl Iterator<Item = &'a Gene>) -> [f32; RealModelType::ALL.len()] {
+    let mut counts = [0usize; RealModelType::ALL.len()];
+    for gene in genes {
+        counts[gene.model_type as usize] += 1;
+    }
+    let total = counts.iter().sum::<usize>().max(1) as f32;
+    counts.map(|count| count as f32 / total)
+}
+
+/// Count the new generation's models
+pub fn tradeoff_shares_system(
+    epoch_count: Res<EpochCount>,
+    mut view: ResMut<TradeoffView>,
+    genes: Query<&Gene>,
+) {
+    *view = TradeoffView {
+        epoch: Some(epoch_count.0),
+        shares: model_shares(genes.iter()),
+    };
+}
+
+/// Tradeoff window: accuracy vs energy per inference, sized by model size
+pub fn tradeoff_ui_system(mut contexts: EguiContexts, view: Res<TradeoffView>) -> Result {
+    egui::Window::new("Model Tradeoffs")
+        .default_open(false)
+        .show(contexts.ctx_mut()?, |ui| {
+            match view.epoch {
+                Some(epoch) => ui.label(format!("Population share after epoch {}", epoch)),
+                None => ui.label("Waiting for the first epoch..."),
+            };
+            plot(ui, &view);
+            egui::Grid::new("model_tradeoffs")
+                .striped(true)
+                .show(ui, |ui| {
+                    for header in ["Model", "Share", "Acc %", "mJ/inf", "ms", "W", "MB"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    for model in RealModelType::ALL {
+                        let name = egui::RichText::new(model.name());
+                        ui.label(if view.dominant(model) {
+                            name.strong()
+                        } else {
+                            name
+                        });
+                        ui.label(format!("{:.0}%", view.shares[model as usize] * 100.0));
+                        ui.label(format!("{:.1}", model.accuracy_percent()));
+                        ui.label(format!("{:.0}", model.inference_energy_mj()));
+                        ui.label(format!("{:.0}", model.inference_time_ms()));
+                        ui.label(format!("{:.1}", model.inference_power_w()));
+                        ui.label(format!("{:.1}", model.size_mb()));
+                        ui.end_row();
+                    }
+                });
+        });
+    Ok(())
+}
+
+/// Draw the scatter plot; axes span the models' ranges with a margin
+fn plot(ui: &mut egui::Ui, view: &TradeoffView) {
+    let (response, painter) = ui.allocate_painter(PLOT_SIZE, egui::Sense::hover());
+    let frame = response.rect;
+    let area = egui::Rect::from_min_max(
+        frame.min + egui::vec2(36.0, 8.0),
+        frame.max - egui::vec2(8.0, 24.0),
+    );
+    let axis = egui::Stroke::new(1.0, egui::Color32::GRAY);
+    let text = egui::Color32::LIGHT_GRAY;
+    let font = egui::FontId::proportional(11.0);
+    painter.line_segment([area.left_bottom(), area.right_bottom()], axis);
+    painter.line_segment([area.left_bottom(), area.left_top()], axis);
+    painter.text(
+        area.center_bottom() + egui::vec2(0.0, 12.0),
+        egui::Align2::CENTER_CENTER,
+        "energy per inference (mJ) →",
+        font.clone(),
+        text,
+    );
+    painter.text(
+        area.left_top() - egui::vec2(4.0, 0.0),
+        egui::Align2::RIGHT_TOP,
+        "acc %",
+        font.clone(),
+        text,
+    );
+
+    let range = |value: fn(&RealModelType) -> f32, margin: f32| {
+        let values = RealModelType::ALL.map(|model| value(&model));
+        let low = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let high = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let margin = (high - low) * margin;
+        (low - margin, high + margin)
+    };
+    let (energy_low, energy_high) = range(RealModelType::inference_energy_mj, 0.1);
+    let (accuracy_low, accuracy_high) = range(RealModelType::accuracy_percent, 0.1);
+    let (size_low, size_high) = range(RealModelType::size_mb, 0.0);
+    for (value, y) in [
+        (accuracy_low, area.bottom()),
+        (accuracy_high, area.top() + 6.0),
+    ] {
+        painter.text(
+            egui::pos2(area.left() - 4.0, y),
+            egui::Align2::RIGHT_BOTTOM,
+            format!("{:.0}", value),
+            font.clone(),
+            text,
+        );
+    }
+    for (value, x) in [(energy_low, area.left()), (energy_high, area.right())] {
+        painter.text(
+            egui::pos2(x, area.bottom() + 2.0),
+            egui::Align2::CENTER_TOP,
+            format!("{:.0}", value),
+            font.clone(),
+            text,
+        );
+    }
+
+    // Dominant models last, so they are drawn on top
+    let mut models = RealModelType::ALL;
+    models.sort_by_key(|model| view.dominant(*model));
+    for model in models {
+        let share = view.shares[model as usize];
+        let center = egui::pos2(
+            egui::remap(
+                model.inference_energy_mj(),
+                energy_low..=energy_high,
+                area.x_range(),
+            ),
+            egui::remap(
+                model.accuracy_percent(),
+                accuracy_low..=accuracy_high,
+                area.y_range().flip(),
+            ),
+        );
+        // Area grows linearly with the model size
+        let t = (model.size_mb() - size_low) / (size_high - size_low);
+        let radius =
+            (RADIUS_RANGE.0.powi(2) + t * (RADIUS_RANGE.1.powi(2) - RADIUS_RANGE.0.powi(2))).sqrt();
+        let (fill, stroke) = if view.dominant(model) {
+            (
+                egui::Color32::from_rgb(240, 170, 40),
+                egui::Stroke::new(2.0, egui::Color32::WHITE),
+            )
+        } else {
+            let alpha = (60.0 + 195.0 * share.sqrt()) as u8;
+            (
+                egui::Color32::from_rgba_unmultiplied(110, 160, 230, alpha),
+                egui::Stroke::NONE,
+            )
+        };
+        painter.circle(center, radius, fill, stroke);
+        painter.text(
+            center + egui::vec2(radius + 2.0, 0.0),
+            egui::Align2::LEFT_CENTER,
+            model.name(),
+            font.clone(),
+            text,
+        );
+    }
+}
+
+/// Register the tradeoff explorer
+pub fn register_tradeoff(app: &mut App) {
+    app.init_resource::<TradeoffView>()
+        .add_systems(
+            FixedUpdate,
+            tradeoff_shares_system.in_set(EpochSet::AfterRepopulation),
+        )
+        .add_systems(EguiPrimaryContextPass, tradeoff_ui_system);
+}
@@ -0,0 +1,104 @@
+/// Byzantine nodes and reputation
+/// A fraction of nodes (flag: `--byzantine <fraction>`) is faulty or
+/// malicious: their result reports are wrong half of the time and they
+/// refuse gossip requests after taking the requester's packet. Every node
+/// carries a beta reputation - (successes + 1) / (interactions + 2) - kept
+/// by the sink (which cross-checks reports against ground truth) and by
+/// gossip partners. Cooperating systems consult it: gossip skips donors
+/// below `TRUST_THRESHOLD`. Byzantine status is not heritable; it is drawn
+/// from the environment stream at birth.
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::components::{EnvRng, Lineage};
+use crate::config::SimConfig;
+
+/// Trust below which cooperating systems stop interacting with a node
+pub const TRUST_THRESHOLD: f32 = 0.4;
+/// Probability a Byzantine node's report carries a wrong result
+pub const MISREPORT_PROBABILITY: f64 = 0.5;
+
+/// Marker for faulty / malicious nodes
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Byzantine;
+
+/// Beta reputation of one node
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Reputation {
+    pub successes: u32,
+    pub failures: u32,
+}
+
+impl Reputation {
+    /// Expected probability of good behavior (0.5 with no evidence)
+    pub fn trust(&self) -> f32 {
+        (self.successes as f32 + 1.0) / (self.successes as f32 + self.failures as f32 + 2.0)
+    }
+
+    pub fn trusted(&self) -> bool {
+        self.trust() >= TRUST_THRESHOLD
+    }
+
+    pub fn record(&mut self, good: bool) {
+        if good {
+            self.successes += 1;
+        } else {
+            self.failures += 1;
+        }
+    }
+}
+
+/// Per-epoch misbehavior statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct TrustMetrics {
+    /// Wrong reports caught by the sink
+    pub misreports: u64,
+    /// Gossip requests refused after the requester paid for them
+    pub refusals: u64,
+}
+
+/// Turn newborn nodes Byzantine with the configured probability
+pub fn assign_byzantine_system(
+    mut commands: Commands,
+    config: Res<SimConfig>,
+    mut env_rng: ResMut<EnvRng>,
+    newborns: Query<Entity, Changed<Lineage>>,
+) {
+    if config.byzantine_fraction <= 0.0 {
+        return;
+    }
+    let p = config.byzantine_fraction.clamp(0.0, 1.0) as f64;
+    for entity in newborns.iter() {
+        if env_rng.0.random_bool(p) {
+            commands.entity(entity).try_insert(Byzantine);
+        }
+    }
+}
+
+/// Mean trust of Byzantine and honest nodes, and how many of each fell
+/// below the threshold: (byzantine mean, honest mean, byzantine flagged,
+/// honest flagged)
+pub fn trust_summary<'a>(
+    nodes: impl Iterator<Item = (&'a Reputation, bool)>,
+) -> (f32, f32, usize, usize) {
+    let (mut byz_sum, mut byz_n, mut honest_sum, mut honest_n) = (0.0, 0, 0.0, 0);
+    let (mut byz_flagged, mut honest_flagged) = (0, 0);
+    for (reputation, byzantine) in nodes {
+        let trust = reputation.trust();
+        if byzantine {
+            byz_sum += trust;
+            byz_n += 1;
+            byz_flagged += usize::from(!reputation.trusted());
+        } else {
+            honest_sum += trust;
+            honest_n += 1;
+            honest_flagged += usize::from(!reputation.trusted());
+        }
+    }
+    (
+        byz_sum / byz_n.max(1) as f32,
+        honest_sum / honest_n.max(1) as f32,
+        byz_flagged,
+        honest_flagged,
+    )
+}
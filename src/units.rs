@@ -0,0 +1,211 @@
+/// Typed power and energy quantities
+/// Subsystems quote their figures in whatever unit the datasheet uses (J
+/// per flash write, mJ per radio burst, µW of harvest, dBm of TX power)
+/// while batteries and metrics count watt-hours. `Power` and `WattHours`
+/// hold one canonical unit each (W, Wh) and only convert at construction
+/// and readout, so a joule can no longer be added to a watt-hour by
+/// accident: `Power::over_s` / `over_h` are where time turns power into
+/// energy. Conversions are computed in f32; `WattHours` stores the result
+/// at the battery's `Energy` precision, so totals summed over weeks keep
+/// the `f64-energy` feature's resolution. Both print with an SI prefix
+/// chosen for their magnitude, rounded to the formatter's precision
+/// (`{:.3}`; 2 decimals by default), e.g. `4.20 mWh`. Watt-hours serialize
+/// as plain f32 numbers, so CSV and JSON columns keep their format.
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
+
+use crate::node::{Battery, Energy};
+
+pub const SECONDS_PER_HOUR: f32 = 3600.0;
+
+/// Decimals printed when the format string gives no precision
+const DEFAULT_DECIMALS: usize = 2;
+
+/// Power (stored in W)
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Power(f32);
+
+/// Energy (stored in Wh, at `Energy` precision)
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct WattHours(Energy);
+
+impl Power {
+    pub const ZERO: Self = Self(0.0);
+
+    pub const fn from_watts(watts: f32) -> Self {
+        Self(watts)
+    }
+
+    pub const fn from_milliwatts(milliwatts: f32) -> Self {
+        Self(milliwatts / 1e3)
+    }
+
+    pub const fn from_microwatts(microwatts: f32) -> Self {
+        Self(microwatts / 1e6)
+    }
+
+    /// RF power level (dBm, referenced to 1 mW)
+    pub fn from_dbm(dbm: f32) -> Self {
+        Self::from_milliwatts(10f32.powf(dbm / 10.0))
+    }
+
+    pub fn watts(self) -> f32 {
+        self.0
+    }
+
+    pub fn milliwatts(self) -> f32 {
+        self.0 * 1e3
+    }
+
+    /// Energy drawn over `seconds`
+    pub fn over_s(self, seconds: f32) -> WattHours {
+        WattHours::from_wh(self.0 * seconds / SECONDS_PER_HOUR)
+    }
+
+    /// Energy drawn over `hours`
+    pub fn over_h(self, hours: f32) -> WattHours {
+        WattHours::from_wh(self.0 * hours)
+    }
+}
+
+// The casts are no-ops unless `f64-energy` is enabled
+#[allow(clippy::unnecessary_cast)]
+impl WattHours {
+    pub const ZERO: Self = Self(0.0);
+
+    pub const fn from_wh(wh: f32) -> Self {
+        Self(wh as Energy)
+    }
+
+    pub const fn from_joules(joules: f32) -> Self {
+        Self::from_wh(joules / SECONDS_PER_HOUR)
+    }
+
+    pub const fn from_millijoules(millijoules: f32) -> Self {
+        Self::from_joules(millijoules / 1e3)
+    }
+
+    /// The battery's charge, without rounding it to f32
+    pub fn charge_of(battery: &Battery) -> Self {
+        Self(battery.0)
+    }
+
+    pub fn wh(self) -> f32 {
+        self.0 as f32
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    pub fn joules(self) -> f32 {
+        self.wh() * SECONDS_PER_HOUR
+    }
+
+    pub fn millijoules(self) -> f32 {
+        self.joules() * 1e3
+    }
+
+    /// Mean power when spread over `seconds`
+    pub fn per_s(self, seconds: f32) -> Power {
+        Power(self.wh() * SECONDS_PER_HOUR / seconds)
+    }
+}
+
+macro_rules! quantity_ops {
+    ($quantity:ident, $inner:ty) => {
+        impl Add for $quantity {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $quantity {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $quantity {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $quantity {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        /// Scaling by a count or ratio
+        #[allow(clippy::unnecessary_cast)]
+        impl Mul<f32> for $quantity {
+            type Output = Self;
+            fn mul(self, rhs: f32) -> Self {
+                Self(self.0 * rhs as $inner)
+            }
+        }
+
+        #[allow(clippy::unnecessary_cast)]
+        impl Div<f32> for $quantity {
+            type Output = Self;
+            fn div(self, rhs: f32) -> Self {
+                Self(self.0 / rhs as $inner)
+            }
+        }
+
+        /// Ratio of two quantities
+        #[allow(clippy::unnecessary_cast)]
+        impl Div for $quantity {
+            type Output = f32;
+            fn div(self, rhs: Self) -> f32 {
+                (self.0 / rhs.0) as f32
+            }
+        }
+
+        impl Sum for $quantity {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|q| q.0).sum())
+            }
+        }
+    };
+}
+
+quantity_ops!(Power, f32);
+quantity_ops!(WattHours, Energy);
+
+/// `value` in `unit` with the SI prefix that keeps it in [1, 1000)
+fn write_si(f: &mut fmt::Formatter, value: f32, unit: &str) -> fmt::Result {
+    let (scale, prefix) = match value.abs() {
+        v if v == 0.0 || !v.is_finite() => (1.0, ""),
+        v if v < 1e-3 => (1e6, "µ"),
+        v if v < 1.0 => (1e3, "m"),
+        v if v < 1e3 => (1.0, ""),
+        _ => (1e-3, "k"),
+    };
+    let decimals = f.precision().unwrap_or(DEFAULT_DECIMALS);
+    write!(f, "{:.*} {}{}", decimals, value * scale, prefix, unit)
+}
+
+impl fmt::Display for Power {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_si(f, self.0, "W")
+    }
+}
+
+impl fmt::Display for WattHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_si(f, self.wh(), "Wh")
+    }
+}
+
+impl Serialize for WattHours {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f32(self.wh())
+    }
+}
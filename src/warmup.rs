@@ -0,0 +1,94 @@
+/// Warm-up period (flag: `--warm-up <hours>`)
+/// Every run starts at 6 AM with all batteries at 80%, an arbitrary state
+/// the field takes a day or so to forget; on short runs that transient
+/// biases the first epochs. During the warm-up the physics runs as usual
+/// but the epoch clock is held, and when it ends every score, per-node
+/// tally and metric is zeroed (ledgers restart from the charge then in
+/// the batteries), so the first epoch measures the settled field. Nodes
+/// that died during the warm-up stay dead with no fitness. Battery wear,
+/// soiling, the forecaster's learning and the calendar carry over.
+use bevy::prelude::*;
+
+use crate::catastrophe::Catastrophes;
+use crate::components::{Battery, EnergyLedger, EpochCount, SimulationMetrics, SurvivalScore};
+use crate::config::SimConfig;
+use crate::event_log::EventLog;
+use crate::sensing::Sensing;
+use crate::systems::SubsystemMetrics;
+use crate::units::{SECONDS_PER_HOUR, WattHours};
+
+/// Real seconds of warm-up left
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct WarmUp {
+    pub remaining_s: f32,
+}
+
+impl WarmUp {
+    pub fn new(config: &SimConfig) -> Self {
+        Self {
+            remaining_s: config.warm_up_h * SECONDS_PER_HOUR / config.time_scale,
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining_s <= 0.0
+    }
+}
+
+/// Run condition: true while the warm-up lasts
+pub fn warming_up(warm_up: Res<WarmUp>) -> bool {
+    !warm_up.is_over()
+}
+
+/// Warm-up system - count the warm-up down and start measuring when it ends
+#[allow(clippy::too_many_arguments)]
+pub fn warm_up_system(
+    time: Res<Time>,
+    epoch_count: Res<EpochCount>,
+    catastrophes: Res<Catastrophes>,
+    mut warm_up: ResMut<WarmUp>,
+    mut metrics: ResMut<SimulationMetrics>,
+    mut subsystems: SubsystemMetrics,
+    mut event_log: ResMut<EventLog>,
+    mut nodes: Query<(
+        &mut SurvivalScore,
+        &mut Sensing,
+        &mut EnergyLedger,
+        &Battery,
+    )>,
+) {
+    warm_up.remaining_s -= time.delta_secs();
+    if !warm_up.is_over() {
+        return;
+    }
+
+    *metrics = SimulationMetrics {
+        current_hour: metrics.current_hour,
+        generation: metrics.generation,
+        ..default()
+    };
+    subsystems.reset();
+    for (mut score, mut sensing, mut ledger, battery) in nodes.iter_mut() {
+        score.0 = 0.0;
+        // The object in view (if any) stays; only the tallies restart
+        *sensing = Sensing {
+            event_remaining_s: sensing.event_remaining_s,
+            event_resolved: sensing.event_resolved,
+            event_age_s: sensing.event_age_s,
+            ..default()
+        };
+        *ledger = EnergyLedger {
+            initial_wh: WattHours::charge_of(battery),
+            ..default()
+        };
+    }
+    info!(
+        "⏳ Warm-up over after {:.1} simulated hours, measuring from now",
+        catastrophes.elapsed_h
+    );
+    event_log.push(
+        epoch_count.0,
+        catastrophes.elapsed_h,
+        "Warm-up over: metrics reset",
+    );
+}
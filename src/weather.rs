@@ -0,0 +1,210 @@
+/// Stochastic weather (flag: `--weather <clear|markov|trace:<path>>`)
+/// Cloud cover changes hour by hour and scales the solar output of the
+/// whole field, so a run is no longer the same clear day repeated. The
+/// Markov model walks a clear / partly cloudy / overcast chain whose
+/// states persist for hours (an overcast sky stays overcast ~7 h on
+/// average), giving correlated multi-hour solar droughts; a trace replays
+/// a recorded hourly cloud-cover series (CSV `hour,cloud_cover`, looped).
+/// Cover becomes a solar factor by the Kasten-Czeplak relation
+/// `1 - 0.75 × cover^3.4`: scattered cloud costs little, a full overcast
+/// leaves a quarter of the light. Catastrophes, microclimates and dust act
+/// on top of the weather.
+use bevy::prelude::*;
+use rand::Rng;
+use std::str::FromStr;
+
+use crate::components::EnvRng;
+use crate::config::SimConfig;
+use crate::data_loader::CloudObservation;
+use crate::speed::SimTime;
+
+/// Solar factor below which an hour counts towards a solar drought
+pub const DROUGHT_SOLAR_FACTOR: f32 = 0.5;
+
+/// Where the cloud cover comes from
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum WeatherModel {
+    /// Cloudless sky every hour
+    #[default]
+    Clear,
+    /// Clear / partly cloudy / overcast Markov chain
+    Markov,
+    /// Recorded hourly cloud cover (CSV path)
+    Trace(String),
+}
+
+impl FromStr for WeatherModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("trace", path)) => Ok(WeatherModel::Trace(path.to_string())),
+            _ => match s.to_ascii_lowercase().as_str() {
+                "clear" | "off" => Ok(WeatherModel::Clear),
+                "markov" => Ok(WeatherModel::Markov),
+                other => Err(format!("unknown weather model {:?}", other)),
+            },
+        }
+    }
+}
+
+/// State of the Markov sky
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sky {
+    #[default]
+    Clear,
+    PartlyCloudy,
+    Overcast,
+}
+
+impl Sky {
+    pub const ALL: [Sky; 3] = [Sky::Clear, Sky::PartlyCloudy, Sky::Overcast];
+
+    /// Cloud cover of the state (fraction of the sky)
+    pub fn cloud_cover(&self) -> f32 {
+        match self {
+            Sky::Clear => 0.1,
+            Sky::PartlyCloudy => 0.6,
+            Sky::Overcast => 1.0,
+        }
+    }
+
+    /// Hourly transition probabilities, in `Sky::ALL` order
+    fn transitions(&self) -> [f32; 3] {
+        match self {
+            Sky::Clear => [0.90, 0.08, 0.02],
+            Sky::PartlyCloudy => [0.15, 0.70, 0.15],
+            Sky::Overcast => [0.03, 0.12, 0.85],
+        }
+    }
+
+    /// Sky of the next hour
+    pub fn next(&self, rng: &mut impl Rng) -> Sky {
+        let mut pick = rng.random_range(0.0..1.0f32);
+        Sky::ALL
+            .into_iter()
+            .zip(self.transitions())
+            .find(|(_, p)| {
+                pick -= p;
+                pick < 0.0
+            })
+            .map_or(*self, |(sky, _)| sky)
+    }
+}
+
+/// Solar factor of a cloud cover (Kasten-Czeplak)
+pub fn cloud_solar_factor(cloud_cover: f32) -> f32 {
+    1.0 - 0.75 * cloud_cover.clamp(0.0, 1.0).powf(3.4)
+}
+
+/// Current weather of the field
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Weather {
+    pub sky: Sky,
+    /// Cloud cover now (fraction of the sky)
+    pub cloud_cover: f32,
+    /// Recorded cover per hour (trace model)
+    trace: Vec<f32>,
+    /// Hours since the run started, for indexing the trace
+    hour_index: usize,
+    /// Fraction of the current hour elapsed
+    hour_progress: f32,
+    /// Length of the solar drought in progress (hours)
+    pub drought_h: f32,
+}
+
+impl Weather {
+    /// Weather replaying `observations` from the first row
+    pub fn from_trace(observations: &[CloudObservation]) -> Self {
+        let trace: Vec<f32> = observations.iter().map(|o| o.cloud_cover).collect();
+        Self {
+            cloud_cover: trace.first().copied().unwrap_or(0.0),
+            trace,
+            ..default()
+        }
+    }
+
+    /// Multiplier on the solar output now
+    pub fn solar_factor(&self) -> f32 {
+        cloud_solar_factor(self.cloud_cover)
+    }
+
+    /// Move on to the next hour's sky
+    fn next_hour(&mut self, model: &WeatherModel, rng: &mut impl Rng) {
+        self.hour_index += 1;
+        self.cloud_cover = match model {
+            WeatherModel::Clear => 0.0,
+            WeatherModel::Markov => {
+                self.sky = self.sky.next(rng);
+                self.sky.cloud_cover()
+            }
+            WeatherModel::Trace(_) if self.trace.is_empty() => 0.0,
+            WeatherModel::Trace(_) => self.trace[self.hour_index % self.trace.len()],
+        };
+    }
+}
+
+/// Per-epoch weather statistics
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct WeatherMetrics {
+    pub hours: f32,
+    /// Hours below `DROUGHT_SOLAR_FACTOR`
+    pub drought_hours: f32,
+    /// Longest solar drought seen (hours, spells from earlier epochs
+    /// included)
+    pub longest_drought_h: f32,
+    /// Solar factor summed over hours, for the mean
+    factor_hours: f32,
+}
+
+impl WeatherMetrics {
+    /// Mean solar factor of the epoch
+    pub fn mean_solar_factor(&self) -> f32 {
+        if self.hours > 0.0 {
+            self.factor_hours / self.hours
+        } else {
+            1.0
+        }
+    }
+
+    pub fn drought_fraction(&self) -> f32 {
+        if self.hours > 0.0 {
+            self.drought_hours / self.hours
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Run condition: true when the sky is not permanently clear
+pub fn weather_enabled(config: Res<SimConfig>) -> bool {
+    config.weather != WeatherModel::Clear
+}
+
+/// Weather system - advance the sky hour by hour
+pub fn weather_system(
+    time: SimTime,
+    config: Res<SimConfig>,
+    mut weather: ResMut<Weather>,
+    mut env_rng: ResMut<EnvRng>,
+    mut weather_metrics: ResMut<WeatherMetrics>,
+) {
+    let hours = time.window_h();
+    weather.hour_progress += hours;
+    while weather.hour_progress >= 1.0 {
+        weather.hour_progress -= 1.0;
+        weather.next_hour(&config.weather, &mut env_rng.0);
+    }
+
+    let factor = weather.solar_factor();
+    weather_metrics.hours += hours;
+    weather_metrics.factor_hours += factor * hours;
+    if factor < DROUGHT_SOLAR_FACTOR {
+        weather.drought_h += hours;
+        weather_metrics.drought_hours += hours;
+        weather_metrics.longest_drought_h =
+            weather_metrics.longest_drought_h.max(weather.drought_h);
+    } else {
+        weather.drought_h = 0.0;
+    }
+}
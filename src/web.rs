@@ -0,0 +1,227 @@
+/// Live web dashboard (flag: `--dashboard-port <port>`)
+/// A background thread serves one static HTML page and `/metrics.json`
+/// (latest epoch record plus the fitness/survival history), which the page
+/// polls every few seconds, and takes `POST /policy` interventions (see
+/// intervention.rs), waiting for the ECS to apply them. Plain `std::net`
+/// with one request per connection (each on its own thread, so a pending
+/// intervention never stalls the page) keeps it dependency-free. There is
+/// no authentication, so it listens on loopback unless `--dashboard-bind`
+/// opts into another interface.
+use bevy::prelude::*;
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{IpAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::Duration;
+
+use crate::export::EpochRecord;
+use crate::intervention::{PolicySwap, SwapRequest};
+
+/// Seconds between browser polls
+const POLL_SECONDS: u32 = 5;
+
+/// How long an intervention request waits for the simulation
+const SWAP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One point of the history charts
+#[derive(Debug, Clone, Copy, Serialize)]
+struct HistoryPoint {
+    epoch: u32,
+    survival_rate: f32,
+    fitness_mean: f32,
+    fitness_max: f32,
+}
+
+/// Body of `/metrics.json`
+#[derive(Debug, Default, Serialize)]
+struct DashboardState {
+    latest: Option<EpochRecord>,
+    history: Vec<HistoryPoint>,
+}
+
+/// Handle to the dashboard server (inert when disabled)
+#[derive(Resource, Default)]
+pub struct WebDashboard {
+    state: Option<Arc<Mutex<DashboardState>>>,
+    /// Interventions posted by clients, not yet applied
+    swaps: Option<Mutex<mpsc::Receiver<SwapRequest>>>,
+}
+
+impl WebDashboard {
+    /// Start serving on `address:port`; the dashboard is disabled (with a
+    /// warning) when the port cannot be bound
+    pub fn start(address: IpAddr, port: Option<u16>) -> Self {
+        let Some(port) = port else {
+            return Self::default();
+        };
+        let listener = match TcpListener::bind((address, port)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!(target: "edgegenesis::web",
+                    "⚠️ Cannot start dashboard on port {}: {}", port, e
+                );
+                return Self::default();
+            }
+        };
+        info!(target: "edgegenesis::web", "🖥️ Live dashboard on http://{}:{}/", address, port);
+
+        let state = Arc::new(Mutex::new(DashboardState::default()));
+        let shared = Arc::clone(&state);
+        let (swap_sender, swaps) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let shared = Arc::clone(&shared);
+                let swap_sender = swap_sender.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = respond(stream, &shared, &swap_sender) {
+                        warn!(target: "edgegenesis::web", "⚠️ Dashboard request failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        Self {
+            state: Some(state),
+            swaps: Some(Mutex::new(swaps)),
+        }
+    }
+
+    /// Interventions posted since the last call
+    pub fn swap_requests(&self) -> Vec<SwapRequest> {
+        self.swaps
+            .as_ref()
+            .and_then(|swaps| swaps.lock().ok())
+            .map_or_else(Vec::new, |swaps| swaps.try_iter().collect())
+    }
+
+    /// Publish one epoch (no-op when the dashboard is disabled)
+    pub fn publish(&self, record: &EpochRecord) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let Ok(mut state) = state.lock() else {
+            return;
+        };
+        state.history.push(HistoryPoint {
+            epoch: record.epoch,
+            survival_rate: record.survivors as f32 / record.population.max(1) as f32,
+            fitness_mean: record.fitness_mean,
+            fitness_max: record.fitness_max,
+        });
+        state.latest = Some(record.clone());
+    }
+}
+
+/// Answer one HTTP request and close the connection
+fn respond(
+    stream: TcpStream,
+    state: &Mutex<DashboardState>,
+    swaps: &mpsc::Sender<SwapRequest>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("GET");
+    let target = parts.next().unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let (status, content_type, body) = match path {
+        "/policy" if method != "POST" => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "POST /policy?set=<policy>[&hardware=..][&model=..][&from=..]".to_string(),
+        ),
+        "/policy" => match query.parse::<PolicySwap>() {
+            Ok(swap) => {
+                let (reply, switched) = mpsc::channel();
+                swaps.send(SwapRequest { swap, reply })?;
+                match switched.recv_timeout(SWAP_TIMEOUT) {
+                    Ok(nodes) => (
+                        "200 OK",
+                        "application/json",
+                        serde_json::json!({ "swap": swap.to_string(), "nodes": nodes }).to_string(),
+                    ),
+                    Err(_) => (
+                        "503 Service Unavailable",
+                        "text/plain",
+                        "simulation did not respond".to_string(),
+                    ),
+                }
+            }
+            Err(e) => ("400 Bad Request", "text/plain", e),
+        },
+        "/" | "/index.html" => (
+            "200 OK",
+            "text/html; charset=utf-8",
+            DASHBOARD_HTML.replace("{POLL_MS}", &(POLL_SECONDS * 1000).to_string()),
+        ),
+        "/metrics.json" => {
+            let body = match state.lock() {
+                Ok(state) => serde_json::to_string(&*state)?,
+                Err(_) => "{}".to_string(),
+            };
+            ("200 OK", "application/json", body)
+        }
+        _ => ("404 Not Found", "text/plain", "not found".to_string()),
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+const DASHBOARD_HTML: &str = r##"<!DOCTYPE html>
+<html><head><meta charset="utf-8"><title>EdgeGenesis live</title>
+<style>
+body { font-family: sans-serif; margin: 2em; background: #101418; color: #d8dee4; }
+table { border-collapse: collapse; }
+td { padding: 2px 10px; border-bottom: 1px solid #2a3038; }
+td:last-child { text-align: right; font-family: monospace; }
+svg { background: #181e24; margin: 0 1em 1em 0; }
+</style></head><body>
+<h1>EdgeGenesis live</h1>
+<p id="status">Waiting for the first epoch...</p>
+<svg id="fitness" width="460" height="200"></svg>
+<svg id="survival" width="460" height="200"></svg>
+<table id="latest"></table>
+<script>
+function chart(id, title, points, keys, colors) {
+  const svg = document.getElementById(id), w = 460, h = 200, pad = 30;
+  const values = points.flatMap(p => keys.map(k => p[k]));
+  const maxY = Math.max(1e-6, ...values), maxX = Math.max(1, ...points.map(p => p.epoch));
+  let out = `<text x="${pad}" y="18" fill="#d8dee4">${title} (max ${maxY.toFixed(2)})</text>`;
+  keys.forEach((k, i) => {
+    const line = points.map(p =>
+      `${pad + (w - 2 * pad) * p.epoch / maxX},${h - pad - (h - 2 * pad) * p[k] / maxY}`).join(" ");
+    out += `<polyline fill="none" stroke="${colors[i]}" stroke-width="2" points="${line}"/>`;
+  });
+  svg.innerHTML = out;
+}
+async function poll() {
+  try {
+    const state = await (await fetch("/metrics.json")).json();
+    if (state.latest) {
+      document.getElementById("status").textContent =
+        `Epoch ${state.latest.epoch}: ${state.latest.survivors}/${state.latest.population} survived`;
+      document.getElementById("latest").innerHTML = Object.entries(state.latest)
+        .map(([k, v]) => `<tr><td>${k}</td><td>${typeof v === "number" ? +v.toFixed(3) : v ?? "-"}</td></tr>`)
+        .join("");
+      chart("fitness", "Fitness: mean / max", state.history, ["fitness_mean", "fitness_max"], ["#4fc3f7", "#ffb74d"]);
+      chart("survival", "Survival rate", state.history, ["survival_rate"], ["#81c784"]);
+    }
+  } catch (e) {
+    document.getElementById("status").textContent = "Simulation not reachable";
+  }
+}
+poll();
+setInterval(poll, {POLL_MS});
+</script></body></html>
+"##;
@@ -0,0 +1,50 @@
+//! The engine stays Bevy-free: the modules lib.rs builds without the `ecs`
+//! feature (engine.rs and the data types it steps) may only name Bevy in
+//! `ecs`-gated derives, so `cargo build --no-default-features` keeps
+//! building the engine alone.
+
+use std::fs;
+
+/// Modules lib.rs declares without a `#[cfg(feature = "ecs")]` gate
+fn core_modules() -> Vec<String> {
+    let lib = fs::read_to_string("src/lib.rs").expect("lib.rs");
+    let mut gated = false;
+    let mut modules = Vec::new();
+    for line in lib.lines().map(str::trim) {
+        if line == "#[cfg(feature = \"ecs\")]" {
+            gated = true;
+            continue;
+        }
+        if let Some(module) = line
+            .strip_prefix("pub mod ")
+            .and_then(|rest| rest.strip_suffix(';'))
+            && !gated
+        {
+            modules.push(module.to_string());
+        }
+        gated = false;
+    }
+    modules
+}
+
+#[test]
+fn core_modules_do_not_import_bevy() {
+    let modules = core_modules();
+    assert!(modules.iter().any(|module| module == "engine"));
+
+    for module in modules {
+        let path = format!("src/{}.rs", module);
+        let source = fs::read_to_string(&path).expect("core module source");
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            let gated_derive = line.starts_with("#[cfg_attr(feature = \"ecs\", derive(bevy::");
+            assert!(
+                !line.contains("bevy::") || gated_derive,
+                "{}:{} uses Bevy outside an `ecs`-gated derive: {}",
+                path,
+                number + 1,
+                line
+            );
+        }
+    }
+}
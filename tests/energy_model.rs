@@ -0,0 +1,221 @@
+//! Property tests of the node physics step (`engine::step_node`) over
+//! random genomes, hardware, harvest and step lengths
+
+use proptest::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use edgegenesis::aging::BatteryHealth;
+use edgegenesis::compatibility::Compatibility;
+use edgegenesis::engine::{
+    NodeMut, SOLAR_EFFICIENCY_PENALTY, StepEnv, StepOutcome, random_gene, step_node,
+};
+use edgegenesis::hardware::{HardwareSpec, HardwareType, SolarPanel};
+use edgegenesis::node::{Battery, EnergyLedger, Gene, ModelCache, Status, Telemetry, TunedPolicy};
+use edgegenesis::policies::PolicyState;
+use edgegenesis::sensor::Sensor;
+use edgegenesis::units::WattHours;
+
+/// Steps per generated case
+const STEPS: usize = 40;
+
+/// One node's state, owned
+struct TestNode {
+    battery: Battery,
+    health: BatteryHealth,
+    status: Status,
+    telemetry: Telemetry,
+    tuned: TunedPolicy,
+    policy_state: PolicyState,
+    model_cache: ModelCache,
+    ledger: EnergyLedger,
+    sensor: Sensor,
+    gene: Gene,
+    hardware: HardwareSpec,
+    panel: SolarPanel,
+}
+
+impl TestNode {
+    fn new(gene_seed: u64, hw_type: HardwareType, charge: f32) -> Self {
+        let gene = random_gene(&mut StdRng::seed_from_u64(gene_seed));
+        let hardware = HardwareSpec::new(hw_type);
+        let initial_wh = hardware.usable_capacity_wh() * charge;
+        Self {
+            battery: Battery::new(initial_wh),
+            health: BatteryHealth::new(&hardware, charge),
+            status: Status::Alive,
+            telemetry: Telemetry::default(),
+            tuned: TunedPolicy(gene.policy_params),
+            policy_state: PolicyState::default(),
+            model_cache: ModelCache::default(),
+            ledger: EnergyLedger {
+                initial_wh: WattHours::from_wh(initial_wh),
+                ..Default::default()
+            },
+            sensor: Sensor::for_node(gene.model_type, hw_type),
+            gene,
+            hardware,
+            panel: SolarPanel::default(),
+        }
+    }
+
+    fn step(&mut self, env: &StepEnv, rng: &mut StdRng) -> StepOutcome {
+        step_node(
+            NodeMut {
+                battery: &mut self.battery,
+                health: &mut self.health,
+                status: &mut self.status,
+                telemetry: &mut self.telemetry,
+                tuned: &mut self.tuned,
+                policy_state: &mut self.policy_state,
+                model_cache: &mut self.model_cache,
+                ledger: &mut self.ledger,
+                sensor: &mut self.sensor,
+                gene: &self.gene,
+                hardware: &self.hardware,
+                panel: &self.panel,
+                exposure: 1.0,
+                temperature_c: 25.0,
+            },
+            env,
+            rng,
+        )
+    }
+}
+
+fn hardware_type() -> impl Strategy<Value = HardwareType> {
+    prop::sample::select(HardwareType::ALL.to_vec())
+}
+
+/// Run `STEPS` steps at a constant hour and panel output, checking
+/// `check(before_wh, node, outcome)` after each one while the node lives
+fn simulate(
+    gene_seed: u64,
+    hw_type: HardwareType,
+    charge: f32,
+    solar_w: f32,
+    dt: f32,
+    hour: usize,
+    mut check: impl FnMut(f32, &TestNode, &StepOutcome) -> Result<(), TestCaseError>,
+) -> Result<(), TestCaseError> {
+    let mut node = TestNode::new(gene_seed, hw_type, charge);
+    let mut rng = StdRng::seed_from_u64(gene_seed.rotate_left(17));
+    let env = StepEnv {
+        window_s: dt * edgegenesis::engine::DEFAULT_TIME_SCALE,
+        hour,
+        solar_w,
+        expected_solar_w: &|_| solar_w,
+        power_overrides: None,
+        // Aged hard, so capacity fades within a case
+        battery_aging: 1000.0,
+        compatibility: Compatibility::Off,
+    };
+    for _ in 0..STEPS {
+        if node.status == Status::Dead {
+            break;
+        }
+        let before_wh = node.battery.wh();
+        let outcome = node.step(&env, &mut rng);
+        check(before_wh, &node, &outcome)?;
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn battery_stays_within_capacity(
+        gene_seed in any::<u64>(),
+        hw_type in hardware_type(),
+        charge in 0.0f32..=1.0,
+        solar_w in 0.0f32..50.0,
+        dt in 0.0f32..2.0,
+        hour in 0usize..24,
+    ) {
+        simulate(gene_seed, hw_type, charge, solar_w, dt, hour, |_, node, _| {
+            prop_assert!(node.battery.wh() >= 0.0);
+            prop_assert!(node.battery.wh() <= node.health.capacity_wh);
+            prop_assert_eq!(node.status == Status::Alive, node.battery.wh() > 0.0);
+            Ok(())
+        })?;
+    }
+
+    #[test]
+    fn energy_accounting_is_non_negative(
+        gene_seed in any::<u64>(),
+        hw_type in hardware_type(),
+        charge in 0.0f32..=1.0,
+        solar_w in 0.0f32..50.0,
+        dt in 0.0f32..2.0,
+        hour in 0usize..24,
+    ) {
+        simulate(gene_seed, hw_type, charge, solar_w, dt, hour, |_, node, outcome| {
+            for (name, value) in [
+                ("recharge_w", outcome.recharge_w),
+                ("recharge_wh", outcome.recharge_wh.wh()),
+                ("power_w", outcome.power_w),
+                ("drain_wh", outcome.drain_wh.wh()),
+                ("load_energy_wh", outcome.load_energy_wh.wh()),
+                ("sensor_energy_wh", outcome.sensor_energy_wh.wh()),
+                ("harvested_wh", node.ledger.harvested_wh.wh()),
+                ("clipped_wh", node.ledger.clipped_wh.wh()),
+                ("consumed_wh", node.ledger.consumed_wh(&node.battery).wh()),
+            ] {
+                prop_assert!(value >= 0.0, "{} = {}", name, value);
+            }
+            prop_assert!(node.ledger.clipped_wh.wh() <= node.ledger.harvested_wh.wh() + 1e-4);
+            Ok(())
+        })?;
+    }
+
+    #[test]
+    fn battery_capacity_only_fades(
+        gene_seed in any::<u64>(),
+        hw_type in hardware_type(),
+        charge in 0.0f32..=1.0,
+        solar_w in 0.0f32..50.0,
+        dt in 0.0f32..2.0,
+        hour in 0usize..24,
+    ) {
+        let mut last_wh = HardwareSpec::new(hw_type).usable_capacity_wh();
+        simulate(gene_seed, hw_type, charge, solar_w, dt, hour, |_, node, _| {
+            prop_assert!(node.health.capacity_wh >= 0.0);
+            prop_assert!(node.health.capacity_wh <= last_wh);
+            prop_assert!(node.health.state_of_health() <= 1.0);
+            last_wh = node.health.capacity_wh;
+            Ok(())
+        })?;
+    }
+
+    #[test]
+    fn harvest_stays_within_charge_input(
+        gene_seed in any::<u64>(),
+        hw_type in hardware_type(),
+        charge in 0.0f32..=1.0,
+        solar_w in 0.0f32..200.0,
+        dt in 0.0f32..2.0,
+        hour in 0usize..24,
+    ) {
+        simulate(gene_seed, hw_type, charge, solar_w, dt, hour, |_, node, outcome| {
+            let input_w = node.hardware.max_solar_input_w
+                * node.gene.solar_efficiency_factor
+                * SOLAR_EFFICIENCY_PENALTY;
+            prop_assert!(outcome.recharge_w <= input_w + 1e-4);
+            Ok(())
+        })?;
+    }
+
+    #[test]
+    fn zero_irradiance_never_charges(
+        gene_seed in any::<u64>(),
+        hw_type in hardware_type(),
+        charge in 0.0f32..=1.0,
+        dt in 0.0f32..2.0,
+        hour in 0usize..24,
+    ) {
+        simulate(gene_seed, hw_type, charge, 0.0, dt, hour, |before_wh, node, outcome| {
+            prop_assert_eq!(outcome.recharge_wh, WattHours::ZERO);
+            prop_assert!(node.battery.wh() <= before_wh);
+            Ok(())
+        })?;
+    }
+}
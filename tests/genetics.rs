@@ -0,0 +1,577 @@
+//! Selection and mutation invariants of the genetic epoch, driven on a
+//! minimal headless `App` (no window, no scheduler: systems run once)
+
+use bevy::ecs::message::Messages;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, TaskPoolBuilder};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use edgegenesis::catastrophe::SolarExposure;
+use edgegenesis::compatibility::Compatibility;
+use edgegenesis::components::{
+    Battery, Dead, EpochCount, Gene, Lineage, SimulationMetrics, Status, SurvivalScore,
+};
+use edgegenesis::data_loader::load_hardware_profiles;
+use edgegenesis::engine::{Breeding, Selection, elite_count, random_gene, random_hardware_type};
+use edgegenesis::export::{EpochRecord, SnapshotRow, add_metric_collector};
+use edgegenesis::genome::SavedGenome;
+use edgegenesis::hardware::{HardwareCatalog, HardwareSpec};
+use edgegenesis::milestones::{DeathCause, ExtinctionOccurred, NodeDied};
+use edgegenesis::policies::{MAX_BATTERY_THRESHOLD, MAX_PID_GAIN, MIN_BATTERY_THRESHOLD};
+use edgegenesis::policy_stats::PolicyMetrics;
+use edgegenesis::radio::{MAX_TX_POWER_DBM, MIN_TX_POWER_DBM, Radio, RadioMetrics, radio_system};
+use edgegenesis::systems::{
+    genetic_epoch_system, mark_dead_system, resource_physics_system, setup_grid,
+};
+use edgegenesis::topology::GRID_SIZE;
+use edgegenesis::units::WattHours;
+use edgegenesis::{EdgeGenesisPlugin, SimConfig};
+
+const POPULATION: usize = (GRID_SIZE * GRID_SIZE) as usize;
+
+/// App with every simulation resource and a founding population
+fn founded_app(seed: u64) -> App {
+    founded_app_with(SimConfig { seed, ..default() })
+}
+
+fn founded_app_with(config: SimConfig) -> App {
+    let mut app = App::new();
+    EdgeGenesisPlugin::new(config).insert_resources(&mut app);
+    app.init_resource::<Time>();
+    app.world_mut().run_system_once(setup_grid).unwrap();
+    app
+}
+
+/// Give every node a fitness equal to its lineage id and kill the nodes
+/// whose id is a multiple of `dead_every` (none for 0)
+fn score_population(app: &mut App, dead_every: u64) {
+    let mut nodes = app
+        .world_mut()
+        .query::<(&Lineage, &mut SurvivalScore, &mut Status)>();
+    for (lineage, mut score, mut status) in nodes.iter_mut(app.world_mut()) {
+        score.0 = lineage.id as f32;
+        if dead_every > 0 && lineage.id % dead_every == 0 {
+            *status = Status::Dead;
+        }
+    }
+}
+
+fn lineages(app: &mut App) -> Vec<Lineage> {
+    let mut nodes = app.world_mut().query::<&Lineage>();
+    nodes.iter(app.world()).copied().collect()
+}
+
+fn run_epoch(app: &mut App) {
+    app.world_mut()
+        .run_system_once(genetic_epoch_system)
+        .unwrap();
+}
+
+#[test]
+fn founders_fill_the_grid() {
+    let mut app = founded_app(1);
+    let founders = lineages(&mut app);
+    assert_eq!(founders.len(), POPULATION);
+    assert!(founders.iter().all(|lineage| lineage.parent.is_none()));
+}
+
+#[test]
+fn population_size_is_constant_across_epochs() {
+    let mut app = founded_app(2);
+    for epoch in 0..5 {
+        score_population(&mut app, 3);
+        run_epoch(&mut app);
+        assert_eq!(lineages(&mut app).len(), POPULATION, "epoch {}", epoch);
+    }
+    assert_eq!(app.world().resource::<EpochCount>().0, 5);
+}
+
+#[test]
+fn offspring_take_over_the_previous_entities() {
+    let mut app = founded_app(7);
+    let entities = |app: &mut App| -> HashSet<Entity> {
+        let mut nodes = app.world_mut().query_filtered::<Entity, With<Lineage>>();
+        nodes.iter(app.world()).collect()
+    };
+    let before = entities(&mut app);
+    score_population(&mut app, 3);
+    let corpse = {
+        let mut nodes = app.world_mut().query::<(Entity, &Lineage)>();
+        let (entity, lineage) = nodes.iter(app.world()).next().unwrap();
+        (entity, lineage.id)
+    };
+    app.world_mut().write_message(NodeDied {
+        entity: corpse.0,
+        node_id: corpse.1,
+        cause: DeathCause::ManualKill,
+    });
+    app.world_mut().run_system_once(mark_dead_system).unwrap();
+    assert!(app.world().get::<Dead>(corpse.0).is_some());
+
+    run_epoch(&mut app);
+
+    assert_eq!(entities(&mut app), before);
+    assert!(app.world().get::<Dead>(corpse.0).is_none());
+    let lineage = app.world().get::<Lineage>(corpse.0).unwrap();
+    assert_ne!(lineage.id, corpse.1);
+    assert!(lineage.parent.is_some());
+}
+
+#[test]
+fn unaffordable_births_leave_only_the_elite_share() {
+    let mut app = founded_app_with(SimConfig {
+        seed: 8,
+        birth_cost: Some(1e6),
+        ..default()
+    });
+    score_population(&mut app, 3);
+    let survivors = lineages(&mut app)
+        .iter()
+        .filter(|lineage| lineage.id % 3 != 0)
+        .count();
+
+    run_epoch(&mut app);
+
+    assert_eq!(lineages(&mut app).len(), elite_count(survivors));
+}
+
+/// Hardware spend (USD) of the current population
+fn fleet_spend(app: &mut App) -> f32 {
+    let mut nodes = app.world_mut().query::<&HardwareSpec>();
+    nodes.iter(app.world()).map(HardwareSpec::cost_usd).sum()
+}
+
+#[test]
+fn skipped_births_leave_the_budget_untouched() {
+    let config = SimConfig {
+        seed: 3,
+        birth_cost: Some(0.6),
+        ..default()
+    };
+    let mut energy_bound = founded_app_with(config.clone());
+    let founders_usd = fleet_spend(&mut energy_bound);
+    score_population(&mut energy_bound, 3);
+    run_epoch(&mut energy_bound);
+    let births = lineages(&mut energy_bound).len();
+    let births_usd = fleet_spend(&mut energy_bound);
+    assert!(births < POPULATION, "the banked energy must skip births");
+
+    // A budget just above what was actually bought must not change the
+    // generation: skipped births cost nothing
+    let budget_usd = founders_usd.max(births_usd) + 1.0;
+    let mut both = founded_app_with(SimConfig {
+        budget_usd: Some(budget_usd),
+        ..config
+    });
+    score_population(&mut both, 3);
+    run_epoch(&mut both);
+    assert_eq!(lineages(&mut both).len(), births);
+    let spend = fleet_spend(&mut both);
+    assert!(
+        (spend - births_usd).abs() < 1e-3,
+        "{} vs {}",
+        spend,
+        births_usd
+    );
+    assert!(spend <= budget_usd);
+}
+
+#[test]
+fn only_elite_survivors_breed() {
+    let mut app = founded_app(3);
+    score_population(&mut app, 4);
+
+    // Fitness equals the id, so the elites are the highest surviving ids
+    let mut survivors: Vec<u64> = lineages(&mut app)
+        .iter()
+        .map(|lineage| lineage.id)
+        .filter(|id| id % 4 != 0)
+        .collect();
+    survivors.sort_unstable_by(|a, b| b.cmp(a));
+    let elites: HashSet<u64> = survivors[..elite_count(survivors.len())]
+        .iter()
+        .copied()
+        .collect();
+
+    run_epoch(&mut app);
+
+    let offspring = lineages(&mut app);
+    assert_eq!(offspring.len(), POPULATION);
+    for child in &offspring {
+        let parent = child.parent.expect("offspring records its parent");
+        assert!(elites.contains(&parent), "parent {} is not elite", parent);
+    }
+}
+
+#[test]
+fn engine_breeds_the_fittest_survivors() {
+    let mut rng = StdRng::seed_from_u64(5);
+    // Fitness equals the index, so the elites are the last survivors
+    let survivors: Vec<(f32, Gene, HardwareSpec)> = (0..20)
+        .map(|i| {
+            let gene = random_gene(&mut rng);
+            (
+                i as f32,
+                gene,
+                HardwareSpec::new(random_hardware_type(&mut rng)),
+            )
+        })
+        .collect();
+    assert!(Selection::new(&[]).is_none());
+    let selection = Selection::new(&survivors).expect("survivors");
+    assert_eq!(selection.fittest().0, 19.0);
+
+    let catalog = HardwareCatalog::default();
+    let births = Breeding::new(&catalog, Compatibility::Off)
+        .offspring(&selection, POPULATION, 0.0, &mut rng);
+    assert_eq!(births.len(), POPULATION);
+    let first_elite = survivors.len() - elite_count(survivors.len());
+    for (slot, birth) in births.iter().enumerate() {
+        assert_eq!(birth.slot, slot);
+        let parent = birth.parent.expect("offspring records its parent");
+        assert!(parent >= first_elite, "parent {} is not elite", parent);
+    }
+}
+
+#[test]
+fn metric_collectors_see_each_epochs_survivors() {
+    let path = std::env::temp_dir().join("edgegenesis_collector_metrics.csv");
+    let mut app = founded_app_with(SimConfig {
+        seed: 8,
+        metrics_out: Some(path.to_string_lossy().into_owned()),
+        ..default()
+    });
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = seen.clone();
+    add_metric_collector(
+        &mut app,
+        move |record: &EpochRecord, survivors: &[SnapshotRow]| {
+            log.lock().unwrap().push((record.epoch, survivors.len()));
+            vec![("survivor_rows".to_string(), survivors.len() as f32)]
+        },
+    );
+
+    for _ in 0..2 {
+        score_population(&mut app, 4);
+        run_epoch(&mut app);
+    }
+    let survivors = POPULATION - POPULATION / 4;
+    assert_eq!(*seen.lock().unwrap(), vec![(0, survivors), (1, survivors)]);
+
+    // Collector values are extra columns of the metrics CSV
+    let mut csv = csv::Reader::from_path(&path).unwrap();
+    assert_eq!(
+        csv.headers().unwrap().iter().next_back(),
+        Some("survivor_rows")
+    );
+    let rows: Vec<_> = csv.records().map(Result::unwrap).collect();
+    assert_eq!(rows.len(), 2);
+    let expected = format!("{:.1}", survivors as f32);
+    assert!(
+        rows.iter()
+            .all(|row| row.iter().next_back() == Some(expected.as_str()))
+    );
+}
+
+#[test]
+fn mutation_keeps_genes_in_range() {
+    let mut app = founded_app(4);
+    for _ in 0..10 {
+        score_population(&mut app, 2);
+        run_epoch(&mut app);
+    }
+
+    let mut genes = app.world_mut().query::<&Gene>();
+    for gene in genes.iter(app.world()) {
+        let params = &gene.policy_params;
+        assert!((0.1..=1.0).contains(&gene.inference_frequency));
+        assert!((0.7..=1.3).contains(&gene.solar_efficiency_factor));
+        assert!((MIN_TX_POWER_DBM..=MAX_TX_POWER_DBM).contains(&gene.tx_power_dbm));
+        assert!(
+            (MIN_BATTERY_THRESHOLD..=MAX_BATTERY_THRESHOLD).contains(&params.battery_threshold)
+        );
+        assert!((0.0..=MAX_BATTERY_THRESHOLD).contains(&params.tx_battery_threshold));
+        for gain in [params.pid_kp, params.pid_ki, params.pid_kd] {
+            assert!((0.0..=MAX_PID_GAIN).contains(&gain));
+        }
+    }
+}
+
+#[test]
+fn forbidden_combinations_are_never_born() {
+    let mut app = founded_app_with(SimConfig {
+        seed: 8,
+        compatibility: Compatibility::Forbid,
+        ..default()
+    });
+    for _ in 0..5 {
+        let mut nodes = app.world_mut().query::<(&Gene, &HardwareSpec)>();
+        for (gene, hardware) in nodes.iter(app.world()) {
+            assert!(hardware.runs(gene.model_type));
+        }
+        score_population(&mut app, 2);
+        run_epoch(&mut app);
+    }
+}
+
+#[test]
+fn births_draw_from_the_hardware_catalog() {
+    let mut app = founded_app_with(SimConfig {
+        seed: 10,
+        hardware_csv: Some("data/hardware_profiles/catalog.csv".to_string()),
+        ..default()
+    });
+    for _ in 0..3 {
+        let mut nodes = app.world_mut().query::<&HardwareSpec>();
+        let names: HashSet<&str> = nodes.iter(app.world()).map(|spec| spec.name()).collect();
+        assert!(names.contains("PiZero2W") || names.contains("OrinNano"));
+        assert!(
+            names
+                .iter()
+                .all(|name| { ["ESP32", "RPi4", "Jetson", "PiZero2W", "OrinNano"].contains(name) })
+        );
+        score_population(&mut app, 2);
+        run_epoch(&mut app);
+    }
+}
+
+#[test]
+fn budget_substitutes_stay_in_the_hardware_catalog() {
+    let path = std::env::temp_dir().join("edgegenesis_budget_catalog.csv");
+    std::fs::write(
+        &path,
+        "name,battery_wh,idle_w,peak_w,ram_mb,gflops,max_solar_w,class\n\
+         PiZero2W,7.4,0.6,3,512,12,5,rpi4\n\
+         OrinNano,37,7,15,8192,1280,60,jetson\n",
+    )
+    .unwrap();
+    let hardware_csv = path.to_string_lossy().into_owned();
+    let catalog = HardwareCatalog::from_profiles(&load_hardware_profiles(&hardware_csv).unwrap())
+        .unwrap()
+        .platforms();
+    let cost = |name: &str| {
+        catalog
+            .iter()
+            .find(|spec| spec.name() == name)
+            .unwrap()
+            .cost_usd()
+    };
+
+    // Once an Orin no longer fits, the fleet must fall back to the catalog's
+    // cheaper platform, not a built-in board
+    let mut app = founded_app_with(SimConfig {
+        seed: 12,
+        hardware_csv: Some(hardware_csv),
+        budget_usd: Some(cost("OrinNano") + 10.0 * cost("PiZero2W")),
+        ..default()
+    });
+    let mut nodes = app.world_mut().query::<&HardwareSpec>();
+    let names: Vec<&str> = nodes.iter(app.world()).map(|spec| spec.name()).collect();
+    assert!(names.contains(&"PiZero2W"));
+    assert!(
+        names
+            .iter()
+            .all(|name| ["PiZero2W", "OrinNano"].contains(name)),
+        "{:?}",
+        names
+    );
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn seed_genomes_keep_their_catalog_platform() {
+    let catalog = "data/hardware_profiles/catalog.csv".to_string();
+    let orin = HardwareCatalog::from_profiles(&load_hardware_profiles(&catalog).unwrap())
+        .unwrap()
+        .platforms()
+        .into_iter()
+        .find(|spec| spec.name() == "OrinNano")
+        .unwrap();
+    let genome = SavedGenome::new(random_gene(&mut StdRng::seed_from_u64(11)), &orin);
+    let path = std::env::temp_dir().join("edgegenesis_catalog_seed.jsonl");
+    std::fs::write(&path, serde_json::to_string(&genome).unwrap()).unwrap();
+
+    let platforms = |hardware_csv: Option<String>| {
+        let mut app = founded_app_with(SimConfig {
+            seed: 11,
+            hardware_csv,
+            seed_population: Some(path.to_string_lossy().into_owned()),
+            ..default()
+        });
+        let mut nodes = app.world_mut().query::<&HardwareSpec>();
+        nodes
+            .iter(app.world())
+            .map(|spec| spec.name().to_string())
+            .collect::<HashSet<_>>()
+    };
+    assert_eq!(
+        platforms(Some(catalog)),
+        HashSet::from(["OrinNano".to_string()])
+    );
+    // Without the catalog the genome falls back to its class
+    assert_eq!(platforms(None), HashSet::from(["Jetson".to_string()]));
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn incompatible_models_crash_on_load() {
+    let mut app = founded_app_with(SimConfig {
+        seed: 9,
+        compatibility: Compatibility::Crash,
+        ..default()
+    });
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(1));
+    app.world_mut()
+        .run_system_once(resource_physics_system)
+        .unwrap();
+
+    let crashed: HashSet<Entity> = app
+        .world()
+        .resource::<Messages<NodeDied>>()
+        .iter_current_update_messages()
+        .filter(|death| death.cause == DeathCause::IncompatibleModel)
+        .map(|death| death.entity)
+        .collect();
+    assert!(!crashed.is_empty());
+    let mut nodes = app
+        .world_mut()
+        .query::<(Entity, &Gene, &HardwareSpec, &Status)>();
+    for (entity, gene, hardware, status) in nodes.iter(app.world()) {
+        assert_eq!(crashed.contains(&entity), !hardware.runs(gene.model_type));
+        if crashed.contains(&entity) {
+            assert_eq!(*status, Status::Dead);
+        }
+    }
+}
+
+#[test]
+fn energy_per_useful_bit_adds_up_over_nodes_and_radios() {
+    let mut app = founded_app(11);
+    let mut radios = app.world_mut().query::<&mut Radio>();
+    for mut radio in radios.iter_mut(app.world_mut()) {
+        radio.backlog = 1;
+    }
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(1));
+    app.world_mut().run_system_once(radio_system).unwrap();
+
+    let metrics = *app.world().resource::<RadioMetrics>();
+    let total = metrics.useful_bits();
+    assert!(metrics.delivered > 0);
+    assert!((total.energy_wh / metrics.energy_wh - 1.0).abs() <= 1e-6);
+    assert!(total.uj_per_bit().is_some_and(|uj| uj > 0.0));
+
+    let mut radios = app.world_mut().query::<&Radio>();
+    let (node_wh, node_bytes) =
+        radios
+            .iter(app.world())
+            .fold((WattHours::ZERO, 0.0), |(wh, bytes), radio| {
+                (
+                    wh + radio.useful.energy_wh,
+                    bytes + radio.useful.delivered_bytes,
+                )
+            });
+    assert!((node_wh / total.energy_wh - 1.0).abs() <= 1e-6);
+    assert_eq!(node_bytes, total.delivered_bytes);
+}
+
+#[test]
+fn extinction_reseeds_random_founders() {
+    let mut app = founded_app(5);
+    score_population(&mut app, 1);
+    let dead_ids: HashSet<u64> = lineages(&mut app).iter().map(|l| l.id).collect();
+
+    run_epoch(&mut app);
+
+    let founders = lineages(&mut app);
+    assert_eq!(founders.len(), POPULATION);
+    for lineage in &founders {
+        assert!(lineage.parent.is_none());
+        assert!(!dead_ids.contains(&lineage.id));
+    }
+    let extinctions = app.world().resource::<Messages<ExtinctionOccurred>>();
+    assert_eq!(extinctions.len(), 1);
+}
+
+/// Fleet totals and death order of `epochs` epochs of `ticks` physics
+/// ticks, floats printed exactly
+fn pipeline_fingerprint(seed: u64, epochs: usize, ticks: usize) -> Vec<String> {
+    let mut app = founded_app(seed);
+    let mut fingerprint = Vec::new();
+    for _ in 0..epochs {
+        for _ in 0..ticks {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs(1));
+            app.world_mut()
+                .run_system_once(resource_physics_system)
+                .unwrap();
+            fingerprint.extend(
+                app.world()
+                    .resource::<Messages<NodeDied>>()
+                    .iter_current_update_messages()
+                    .map(|death| format!("died {}", death.node_id)),
+            );
+            app.world_mut().run_system_once(mark_dead_system).unwrap();
+        }
+        let metrics = app.world().resource::<SimulationMetrics>();
+        fingerprint.push(format!(
+            "{:?} {:?} {:?} {}",
+            metrics.total_energy_consumed.wh(),
+            metrics.total_energy_harvested.wh(),
+            metrics.sensor_energy_wh.wh(),
+            metrics.total_inferences
+        ));
+        let policy = app.world().resource::<PolicyMetrics>();
+        fingerprint.push(format!("{:?} {:?}", policy.inferences, policy.node_hours));
+        run_epoch(&mut app);
+    }
+    fingerprint
+}
+
+#[test]
+fn same_seed_reproduces_the_fleet_metrics() {
+    // Several workers even on a single core, so threads finish out of order
+    ComputeTaskPool::get_or_init(|| TaskPoolBuilder::new().num_threads(4).build());
+    assert_eq!(
+        pipeline_fingerprint(12, 2, 30),
+        pipeline_fingerprint(12, 2, 30)
+    );
+}
+
+#[test]
+fn depleted_node_dies_when_time_advances() {
+    let mut app = founded_app(6);
+    let victim = {
+        let mut nodes = app
+            .world_mut()
+            .query::<(Entity, &mut Battery, &mut SolarExposure)>();
+        let (entity, mut battery, mut exposure) = nodes.iter_mut(app.world_mut()).next().unwrap();
+        battery.0 = 1e-6;
+        exposure.0 = 0.0;
+        entity
+    };
+
+    app.world_mut()
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs(1));
+    app.world_mut()
+        .run_system_once(resource_physics_system)
+        .unwrap();
+
+    assert_eq!(*app.world().get::<Status>(victim).unwrap(), Status::Dead);
+    let deaths = app.world().resource::<Messages<NodeDied>>();
+    assert!(
+        deaths
+            .iter_current_update_messages()
+            .any(|death| { death.entity == victim && death.cause == DeathCause::BatteryDepleted })
+    );
+}
@@ -0,0 +1,115 @@
+//! Golden-run regression: 5 headless epochs with a fixed seed and canned
+//! solar and power profiles must reproduce the stored per-epoch summary.
+//! After an intended behavior change, regenerate the file with
+//! `UPDATE_GOLDEN=1 cargo test --test golden_run` and review its diff
+//! (add `--features f64-energy` for the double-precision file).
+//! The same run with night fast-forward must stay close to it.
+
+use std::fmt::Write as _;
+
+use edgegenesis::SimConfig;
+use edgegenesis::data_loader::{load_power_profiles, load_solar_profiles};
+use edgegenesis::engine::{Engine, EngineEpoch, HEADLESS_DT, hourly_solar_w};
+use edgegenesis::topology::GRID_SIZE;
+
+const SEED: u64 = 42;
+const EPOCHS: u32 = 5;
+const SOLAR_FIXTURE: &str = "tests/fixtures/solar_profile.csv";
+const POWER_FIXTURE: &str = "tests/fixtures/power_profile.csv";
+/// The summary depends on the energy precision, so each has its own file
+const GOLDEN: &str = if cfg!(feature = "f64-energy") {
+    "tests/fixtures/golden_run_f64.txt"
+} else {
+    "tests/fixtures/golden_run.txt"
+};
+
+/// The golden run's engine
+fn engine() -> Engine {
+    let profiles = load_solar_profiles(SOLAR_FIXTURE).expect("solar fixture");
+    let power_profiles = load_power_profiles(POWER_FIXTURE).expect("power fixture");
+    let config = SimConfig {
+        seed: SEED,
+        ..Default::default()
+    };
+    Engine::new(
+        config.genetic_seed(),
+        config.env_seed(),
+        (GRID_SIZE * GRID_SIZE) as usize,
+        hourly_solar_w(&profiles),
+    )
+    .with_power_overrides(power_profiles)
+}
+
+/// Per-epoch summary lines, rounded so the file stays readable
+fn run_summary() -> String {
+    let mut engine = engine();
+    let mut summary = String::from(
+        "epoch population survivors fitness_mean fitness_max energy_consumed_wh energy_harvested_wh\n",
+    );
+    while engine.epoch() < EPOCHS {
+        if let Some(epoch) = engine.step(HEADLESS_DT) {
+            writeln!(
+                summary,
+                "{} {} {} {:.3} {:.3} {:.3} {:.3}",
+                epoch.epoch,
+                epoch.population,
+                epoch.survivors,
+                epoch.fitness_mean,
+                epoch.fitness_max,
+                epoch.energy_consumed_wh.wh(),
+                epoch.energy_harvested_wh.wh()
+            )
+            .unwrap();
+        }
+    }
+    summary
+}
+
+#[test]
+fn five_epochs_match_the_golden_file() {
+    let actual = run_summary();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(GOLDEN, &actual).expect("write golden file");
+        return;
+    }
+    let golden = std::fs::read_to_string(GOLDEN).expect("golden file (UPDATE_GOLDEN=1 creates it)");
+    assert_eq!(
+        actual, golden,
+        "simulation behavior changed; if intended, rerun with UPDATE_GOLDEN=1"
+    );
+}
+
+/// Night fast-forward through the first dusk (epoch 8): the generations
+/// before it are identical, and the dusk epoch stays within 1% of the
+/// tick-by-tick run
+#[test]
+fn fast_night_tracks_the_tick_by_tick_run() {
+    const DUSK_EPOCH: u32 = 8;
+    let run = |mut engine: Engine| -> Vec<EngineEpoch> {
+        let mut epochs = Vec::new();
+        while engine.epoch() <= DUSK_EPOCH {
+            epochs.extend(engine.step(HEADLESS_DT));
+        }
+        epochs
+    };
+    let ticked = run(engine());
+    let fast = run(engine().with_fast_night(true));
+
+    let close = |a: f32, b: f32| (a - b).abs() <= 0.01 * b.abs().max(1e-3);
+    for (a, b) in ticked.iter().zip(&fast) {
+        assert_eq!((a.epoch, a.population), (b.epoch, b.population));
+        assert!(close(b.fitness_mean, a.fitness_mean), "{:?} vs {:?}", b, a);
+        assert!(
+            close(b.energy_consumed_wh.wh(), a.energy_consumed_wh.wh()),
+            "{:?} vs {:?}",
+            b,
+            a
+        );
+        assert!(
+            close(b.energy_harvested_wh.wh(), a.energy_harvested_wh.wh()),
+            "{:?} vs {:?}",
+            b,
+            a
+        );
+    }
+}